@@ -0,0 +1,550 @@
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use shared::{database::Database, models::backup_configurations::BackupConfigs};
+use sqlx::Row;
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+};
+
+/// Every archive starts with this, followed by the encrypted zip file.
+const MAGIC: &[u8] = b"CALAGOPUS-EXPORT\n";
+/// Bumped whenever the layout of the archive itself changes, schema changes are covered by `migration`.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Tables that are owned by the migrator and must never be copied.
+const EXCLUDED_TABLES: &[&str] = &["_sqlx_migrations"];
+/// `bytea` columns encrypted with [`Database::encrypt`].
+const ENCRYPTED_COLUMNS: &[(&str, &str)] = &[
+    ("database_hosts", "password"),
+    ("egg_repositories", "credentials"),
+    ("nodes", "token"),
+    ("oauth_providers", "client_secret"),
+    ("server_databases", "password"),
+    ("webhooks", "secret"),
+];
+/// Settings whose value is base32 encoded and encrypted with [`Database::encrypt`].
+const ENCRYPTED_SETTINGS: &[&str] = &[
+    "::storage_s3_access_key",
+    "::storage_s3_secret_key",
+    "::mail_smtp_username",
+    "::mail_smtp_password",
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    pub panel_version: compact_str::CompactString,
+    pub migration: i64,
+    pub tables: Vec<ManifestTable>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ManifestTable {
+    pub name: compact_str::CompactString,
+    pub rows: u64,
+}
+
+#[inline]
+fn table_file(table: &str) -> String {
+    format!("tables/{table}.jsonl")
+}
+
+async fn latest_migration(database: &Database) -> Result<i64, anyhow::Error> {
+    Ok(sqlx::query_scalar(
+        "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success",
+    )
+    .fetch_one(database.read())
+    .await?)
+}
+
+async fn tables(database: &Database) -> Result<Vec<compact_str::CompactString>, anyhow::Error> {
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT tablename::text FROM pg_tables WHERE schemaname = 'public' ORDER BY tablename",
+    )
+    .fetch_all(database.read())
+    .await?;
+
+    Ok(tables
+        .into_iter()
+        .filter(|table| !EXCLUDED_TABLES.contains(&table.as_str()))
+        .map(Into::into)
+        .collect())
+}
+
+fn encode_bytea(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(2 + data.len() * 2);
+    encoded.push_str("\\x");
+    for byte in data {
+        encoded.push_str(&format!("{byte:02x}"));
+    }
+
+    encoded
+}
+
+fn decode_bytea(data: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let data = data
+        .strip_prefix("\\x")
+        .ok_or_else(|| anyhow::anyhow!("bytea value is not hex encoded"))?;
+
+    (0..data.len())
+        .step_by(2)
+        .map(|i| {
+            data.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| anyhow::anyhow!("invalid hex in bytea value"))
+        })
+        .collect()
+}
+
+/// Replaces every secret in an exported row by its plaintext, so the archive does not depend on the source key.
+async fn decrypt_row(
+    database: &Database,
+    table: &str,
+    row: &mut serde_json::Map<String, serde_json::Value>,
+) -> Result<(), anyhow::Error> {
+    for (_, column) in ENCRYPTED_COLUMNS.iter().filter(|(t, _)| *t == table) {
+        if let Some(serde_json::Value::String(value)) = row.get_mut(*column) {
+            *value = database.decrypt(decode_bytea(value)?).await?.into();
+        }
+    }
+
+    match table {
+        "settings" => {
+            let encrypted = row
+                .get("key")
+                .and_then(|key| key.as_str())
+                .is_some_and(|key| ENCRYPTED_SETTINGS.contains(&key));
+
+            if encrypted
+                && let Some(serde_json::Value::String(value)) = row.get_mut("value")
+                && !value.is_empty()
+            {
+                let data = base32::decode(base32::Alphabet::Z, value)
+                    .ok_or_else(|| anyhow::anyhow!("invalid base32 in encrypted setting"))?;
+                *value = database.decrypt(data).await?.into();
+            }
+        }
+        "backup_configurations" => {
+            if let Some(value) = row.get_mut("backup_configs") {
+                let mut backup_configs: BackupConfigs = serde_json::from_value(value.take())?;
+                backup_configs.decrypt(database).await?;
+                *value = serde_json::to_value(backup_configs)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Encrypts every secret of an archived row with the key of the destination panel.
+async fn encrypt_row(
+    database: &Database,
+    table: &str,
+    row: &mut serde_json::Map<String, serde_json::Value>,
+) -> Result<(), anyhow::Error> {
+    for (_, column) in ENCRYPTED_COLUMNS.iter().filter(|(t, _)| *t == table) {
+        if let Some(serde_json::Value::String(value)) = row.get_mut(*column) {
+            *value = encode_bytea(&database.encrypt(value.clone()).await?);
+        }
+    }
+
+    match table {
+        "settings" => {
+            let encrypted = row
+                .get("key")
+                .and_then(|key| key.as_str())
+                .is_some_and(|key| ENCRYPTED_SETTINGS.contains(&key));
+
+            if encrypted
+                && let Some(serde_json::Value::String(value)) = row.get_mut("value")
+                && !value.is_empty()
+            {
+                *value =
+                    base32::encode(base32::Alphabet::Z, &database.encrypt(value.clone()).await?);
+            }
+        }
+        "backup_configurations" => {
+            if let Some(value) = row.get_mut("backup_configs") {
+                let mut backup_configs: BackupConfigs = serde_json::from_value(value.take())?;
+                backup_configs.encrypt(database).await?;
+                *value = serde_json::to_value(backup_configs)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Dumps every panel table into an archive encrypted with `passphrase`.
+pub async fn export(database: &Database, passphrase: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+
+    let mut manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        panel_version: shared::VERSION.into(),
+        migration: latest_migration(database).await?,
+        tables: Vec::new(),
+        created: chrono::Utc::now(),
+    };
+
+    for table in tables(database).await? {
+        tracing::info!(table = %table, "exporting table");
+
+        writer.start_file(table_file(&table), options)?;
+
+        let query = format!(r#"SELECT row_to_json(t) FROM "{table}" t"#);
+        let mut rows =
+            sqlx::query_scalar::<_, serde_json::Value>(&query).fetch(database.read());
+        let mut count = 0;
+
+        while let Some(row) = rows.try_next().await? {
+            let mut row = match row {
+                serde_json::Value::Object(row) => row,
+                _ => return Err(anyhow::anyhow!("unexpected row in table {table}")),
+            };
+            decrypt_row(database, &table, &mut row).await?;
+
+            serde_json::to_writer(&mut writer, &row)?;
+            writer.write_all(b"\n")?;
+            count += 1;
+        }
+
+        manifest.tables.push(ManifestTable {
+            name: table,
+            rows: count,
+        });
+    }
+
+    writer.start_file(MANIFEST_FILE, options)?;
+    serde_json::to_writer_pretty(&mut writer, &manifest)?;
+
+    let archive = writer.finish()?.into_inner();
+    let passphrase = passphrase.to_string();
+    let encrypted = tokio::task::spawn_blocking(move || {
+        simple_crypt::encrypt(&archive, passphrase.as_bytes())
+    })
+    .await??;
+
+    let mut data = Vec::with_capacity(MAGIC.len() + encrypted.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&encrypted);
+
+    Ok(data)
+}
+
+struct ForeignKey {
+    table: compact_str::CompactString,
+    column: compact_str::CompactString,
+    referenced: compact_str::CompactString,
+    nullable: bool,
+}
+
+/// Orders the tables so every non-nullable foreign key points to an earlier table.
+///
+/// Nullable foreign keys that point to the same or a later table are returned per table,
+/// those columns are inserted as `NULL` and filled in once every row exists.
+fn plan_restore(
+    tables: &[compact_str::CompactString],
+    foreign_keys: &[ForeignKey],
+) -> Result<
+    (
+        Vec<compact_str::CompactString>,
+        HashMap<compact_str::CompactString, Vec<compact_str::CompactString>>,
+    ),
+    anyhow::Error,
+> {
+    let mut order = Vec::with_capacity(tables.len());
+    let mut placed = HashSet::new();
+
+    while order.len() < tables.len() {
+        let next = tables.iter().find(|table| {
+            !placed.contains(*table)
+                && foreign_keys.iter().all(|foreign_key| {
+                    foreign_key.table != **table
+                        || foreign_key.nullable
+                        || foreign_key.referenced == **table
+                        || placed.contains(&foreign_key.referenced)
+                        || !tables.contains(&foreign_key.referenced)
+                })
+        });
+
+        match next {
+            Some(table) => {
+                placed.insert(table.clone());
+                order.push(table.clone());
+            }
+            None => {
+                return Err(anyhow::anyhow!(
+                    "unable to order tables, non-nullable foreign keys form a cycle"
+                ));
+            }
+        }
+    }
+
+    let mut deferred: HashMap<_, Vec<_>> = HashMap::new();
+    for foreign_key in foreign_keys.iter().filter(|foreign_key| foreign_key.nullable) {
+        let table_index = order.iter().position(|t| *t == foreign_key.table);
+        let referenced_index = order.iter().position(|t| *t == foreign_key.referenced);
+
+        if let (Some(table_index), Some(referenced_index)) = (table_index, referenced_index)
+            && referenced_index >= table_index
+        {
+            let columns = deferred.entry(foreign_key.table.clone()).or_default();
+            if !columns.contains(&foreign_key.column) {
+                columns.push(foreign_key.column.clone());
+            }
+        }
+    }
+
+    Ok((order, deferred))
+}
+
+fn read_table(
+    archive: &mut zip::ZipArchive<std::io::Cursor<Vec<u8>>>,
+    table: &str,
+) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, anyhow::Error> {
+    let mut content = String::new();
+    archive
+        .by_name(&table_file(table))?
+        .read_to_string(&mut content)?;
+
+    content
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+async fn open(
+    data: Vec<u8>,
+    passphrase: &str,
+) -> Result<(Manifest, zip::ZipArchive<std::io::Cursor<Vec<u8>>>), anyhow::Error> {
+    let encrypted = data
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| anyhow::anyhow!("file is not a panel export archive"))?
+        .to_vec();
+    let passphrase = passphrase.to_string();
+    let archive = tokio::task::spawn_blocking(move || {
+        simple_crypt::decrypt(&encrypted, passphrase.as_bytes())
+    })
+    .await?
+    .map_err(|_| anyhow::anyhow!("unable to decrypt archive, is the passphrase correct?"))?;
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(archive))?;
+    let manifest: Manifest = serde_json::from_reader(archive.by_name(MANIFEST_FILE)?)?;
+
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(anyhow::anyhow!(
+            "archive format version {} is newer than the supported version {}, update the panel first",
+            manifest.format_version,
+            FORMAT_VERSION
+        ));
+    }
+
+    Ok((manifest, archive))
+}
+
+/// Decrypts an archive and returns its manifest without restoring anything.
+#[inline]
+pub async fn inspect(data: Vec<u8>, passphrase: &str) -> Result<Manifest, anyhow::Error> {
+    Ok(open(data, passphrase).await?.0)
+}
+
+/// Restores an archive created by [`export`] into an empty, fully migrated database.
+pub async fn restore(
+    database: &Database,
+    data: Vec<u8>,
+    passphrase: &str,
+) -> Result<Manifest, anyhow::Error> {
+    let (manifest, mut archive) = open(data, passphrase).await?;
+
+    let migration = latest_migration(database).await?;
+    if manifest.migration != migration {
+        return Err(anyhow::anyhow!(
+            "archive was created at migration {} but the database is at migration {}, both panels must run the same version",
+            manifest.migration,
+            migration
+        ));
+    }
+
+    let has_users: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users)")
+        .fetch_one(database.read())
+        .await?;
+    if has_users {
+        return Err(anyhow::anyhow!(
+            "the database already contains users, archives can only be imported into a fresh panel"
+        ));
+    }
+
+    let destination_tables = tables(database).await?;
+    let archive_tables = manifest
+        .tables
+        .iter()
+        .map(|table| table.name.clone())
+        .collect::<Vec<_>>();
+    if let Some(table) = archive_tables
+        .iter()
+        .find(|table| !destination_tables.contains(table))
+    {
+        return Err(anyhow::anyhow!(
+            "archive contains table {table} which does not exist in the database"
+        ));
+    }
+
+    let foreign_keys = sqlx::query(
+        r#"
+        SELECT
+            pg_constraint.conrelid::regclass::text AS table,
+            pg_constraint.confrelid::regclass::text AS referenced,
+            pg_attribute.attname::text AS column,
+            NOT pg_attribute.attnotnull AS nullable
+        FROM pg_constraint
+        JOIN pg_attribute ON pg_attribute.attrelid = pg_constraint.conrelid AND pg_attribute.attnum = ANY(pg_constraint.conkey)
+        WHERE pg_constraint.contype = 'f' AND pg_constraint.connamespace = 'public'::regnamespace
+        "#,
+    )
+    .fetch_all(database.read())
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ForeignKey {
+            table: row.try_get::<String, _>("table")?.trim_matches('"').into(),
+            column: row.try_get::<String, _>("column")?.into(),
+            referenced: row.try_get::<String, _>("referenced")?.trim_matches('"').into(),
+            nullable: row.try_get("nullable")?,
+        })
+    })
+    .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+    let (order, deferred) = plan_restore(&archive_tables, &foreign_keys)?;
+
+    let mut transaction = database.write().begin().await?;
+
+    for table in &order {
+        let deferred_columns = deferred.get(table).map_or(&[][..], |c| c.as_slice());
+        let rows = read_table(&mut archive, table)?;
+
+        tracing::info!(table = %table, rows = rows.len(), "importing table");
+
+        for chunk in rows.chunks(IMPORT_BATCH_SIZE) {
+            let mut batch = Vec::with_capacity(chunk.len());
+            for row in chunk {
+                let mut row = row.clone();
+                encrypt_row(database, table, &mut row).await?;
+                for column in deferred_columns {
+                    row.insert(column.to_string(), serde_json::Value::Null);
+                }
+
+                batch.push(serde_json::Value::Object(row));
+            }
+
+            sqlx::query(&format!(
+                r#"
+                INSERT INTO "{table}"
+                SELECT * FROM json_populate_recordset(NULL::"{table}", $1)
+                ON CONFLICT DO NOTHING
+                "#
+            ))
+            .bind(serde_json::Value::Array(batch))
+            .execute(&mut *transaction)
+            .await?;
+        }
+    }
+
+    for (table, columns) in &deferred {
+        let primary_key: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT pg_attribute.attname::text
+            FROM pg_index
+            JOIN pg_attribute ON pg_attribute.attrelid = pg_index.indrelid AND pg_attribute.attnum = ANY(pg_index.indkey)
+            WHERE pg_index.indrelid = $1::regclass AND pg_index.indisprimary
+            "#,
+        )
+        .bind(format!(r#""{table}""#))
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        if primary_key.is_empty() {
+            return Err(anyhow::anyhow!(
+                "table {table} has deferred foreign keys but no primary key"
+            ));
+        }
+
+        let set = columns
+            .iter()
+            .map(|column| format!(r#""{column}" = source."{column}""#))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let condition = primary_key
+            .iter()
+            .map(|column| format!(r#""{table}"."{column}" = source."{column}""#))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let rows = read_table(&mut archive, table)?;
+        for chunk in rows.chunks(IMPORT_BATCH_SIZE) {
+            let batch = chunk
+                .iter()
+                .filter(|row| {
+                    columns
+                        .iter()
+                        .any(|column| row.get(column.as_str()).is_some_and(|v| !v.is_null()))
+                })
+                .cloned()
+                .map(serde_json::Value::Object)
+                .collect::<Vec<_>>();
+            if batch.is_empty() {
+                continue;
+            }
+
+            sqlx::query(&format!(
+                r#"
+                UPDATE "{table}"
+                SET {set}
+                FROM json_populate_recordset(NULL::"{table}", $1) source
+                WHERE {condition}
+                "#
+            ))
+            .bind(serde_json::Value::Array(batch))
+            .execute(&mut *transaction)
+            .await?;
+        }
+    }
+
+    let sequences = sqlx::query(
+        r#"
+        SELECT table_name::text, column_name::text
+        FROM information_schema.columns
+        WHERE table_schema = 'public' AND column_default LIKE 'nextval(%'
+        "#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    for sequence in sequences {
+        let table: String = sequence.try_get("table_name")?;
+        let column: String = sequence.try_get("column_name")?;
+
+        sqlx::query(&format!(
+            r#"
+            SELECT setval(pg_get_serial_sequence('"{table}"', '{column}'), COALESCE(MAX("{column}"), 0) + 1, false)
+            FROM "{table}"
+            "#
+        ))
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    transaction.commit().await?;
+
+    Ok(manifest)
+}
@@ -0,0 +1,73 @@
+use clap::{Args, FromArgMatches};
+use colored::Colorize;
+use dialoguer::{Password, theme::ColorfulTheme};
+use std::sync::Arc;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    #[arg(
+        short = 'o',
+        long = "output",
+        help = "the file to write the export archive to",
+        default_value = "calagopus-export.bin"
+    )]
+    output: String,
+    #[arg(
+        short = 'p',
+        long = "passphrase",
+        help = "the passphrase to encrypt the archive with, prompted for if not set"
+    )]
+    passphrase: Option<String>,
+}
+
+pub struct ExportCommand;
+
+impl shared::extensions::commands::CliCommand<ExportArgs> for ExportCommand {
+    fn get_command(&self, command: clap::Command) -> clap::Command {
+        command
+    }
+
+    fn get_executor(self) -> Box<shared::extensions::commands::ExecutorFunc> {
+        Box::new(|env, arg_matches| {
+            Box::pin(async move {
+                let args = ExportArgs::from_arg_matches(&arg_matches)?;
+
+                let env = match env {
+                    Some(env) => env,
+                    None => {
+                        eprintln!("{}", "no env found".red());
+                        std::process::exit(1);
+                    }
+                };
+
+                let passphrase = match args.passphrase {
+                    Some(passphrase) => passphrase,
+                    None => Password::with_theme(&ColorfulTheme::default())
+                        .with_prompt("archive passphrase")
+                        .with_confirmation("repeat passphrase", "the passphrases do not match")
+                        .interact()?,
+                };
+
+                let cache = Arc::new(shared::cache::Cache::new(&env).await);
+                let database = Arc::new(shared::database::Database::new(&env, cache).await);
+
+                let data = match super::archive::export(&database, &passphrase).await {
+                    Ok(data) => data,
+                    Err(err) => {
+                        eprintln!("{}: {:#?}", "failed to export panel".red(), err);
+                        std::process::exit(1);
+                    }
+                };
+
+                tokio::fs::write(&args.output, data).await?;
+
+                println!(
+                    "exported panel to {}, keep the passphrase safe, it is required to import the archive.",
+                    args.output.bright_green()
+                );
+
+                Ok(())
+            })
+        })
+    }
+}
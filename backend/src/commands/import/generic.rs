@@ -0,0 +1,557 @@
+use super::{ImportArgs, ImportFormat, progress::ImportProgress, users};
+use colored::Colorize;
+use rand::distr::SampleString;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use shared::models::{
+    ByUuid,
+    nest_egg::NestEgg,
+    nest_egg_variable::NestEggVariable,
+    node::Node,
+    server::{ApiServerFeatureLimits, ApiServerLimits, Server},
+};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+/// CSV columns that are parsed as numbers, every other column is a string.
+const NUMERIC_COLUMNS: &[&str] = &[
+    "memory",
+    "swap",
+    "disk",
+    "cpu",
+    "io_weight",
+    "allocation_limit",
+    "database_limit",
+    "backup_limit",
+    "schedule_limit",
+];
+const BOOLEAN_COLUMNS: &[&str] = &["admin", "skip_installer"];
+/// CSV columns starting with this prefix are collected into the `variables` of a server.
+const VARIABLE_COLUMN_PREFIX: &str = "variables.";
+
+#[derive(Deserialize)]
+struct UserRecord {
+    external_id: Option<compact_str::CompactString>,
+    username: compact_str::CompactString,
+    email: compact_str::CompactString,
+    name_first: Option<compact_str::CompactString>,
+    name_last: Option<compact_str::CompactString>,
+    password: Option<String>,
+    /// A bcrypt hash, takes priority over `password`.
+    password_hash: Option<String>,
+    #[serde(default)]
+    admin: bool,
+}
+
+#[derive(Deserialize)]
+struct ServerRecord {
+    external_id: Option<compact_str::CompactString>,
+    name: compact_str::CompactString,
+    description: Option<compact_str::CompactString>,
+    /// The username or email of the owner.
+    owner: compact_str::CompactString,
+    /// The uuid or name of the node.
+    node: compact_str::CompactString,
+    /// The uuid or name of the egg.
+    egg: compact_str::CompactString,
+    /// The primary allocation as `ip:port`, created on the node if it does not exist yet.
+    allocation: Option<compact_str::CompactString>,
+
+    memory: i64,
+    #[serde(default)]
+    swap: i64,
+    disk: i64,
+    cpu: i32,
+    io_weight: Option<i16>,
+
+    #[serde(default)]
+    allocation_limit: i32,
+    #[serde(default)]
+    database_limit: i32,
+    #[serde(default)]
+    backup_limit: i32,
+    #[serde(default)]
+    schedule_limit: i32,
+
+    startup: Option<compact_str::CompactString>,
+    image: Option<compact_str::CompactString>,
+    #[serde(default)]
+    variables: HashMap<compact_str::CompactString, String>,
+    #[serde(default)]
+    skip_installer: bool,
+}
+
+/// Imported users by their original username and email, so servers can reference renamed users.
+#[derive(Serialize, Deserialize)]
+struct ImportedUserMapping {
+    username: compact_str::CompactString,
+    email: compact_str::CompactString,
+    uuid: uuid::Uuid,
+}
+
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+fn parse_csv(content: &str) -> Result<Vec<serde_json::Value>, anyhow::Error> {
+    let mut lines = content
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty());
+    let header = match lines.next() {
+        Some(header) => parse_csv_line(header),
+        None => return Ok(Vec::new()),
+    };
+
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            let fields = parse_csv_line(line);
+            if fields.len() != header.len() {
+                return Err(anyhow::anyhow!(
+                    "line {} has {} columns, expected {}",
+                    i + 2,
+                    fields.len(),
+                    header.len()
+                ));
+            }
+
+            let mut record = serde_json::Map::new();
+            let mut variables = serde_json::Map::new();
+
+            for (column, value) in header.iter().zip(fields) {
+                if let Some(variable) = column.strip_prefix(VARIABLE_COLUMN_PREFIX) {
+                    variables.insert(variable.to_string(), value.into());
+                    continue;
+                }
+
+                if value.is_empty() {
+                    continue;
+                }
+
+                let value = if NUMERIC_COLUMNS.contains(&column.as_str()) {
+                    serde_json::Value::Number(value.parse::<i64>()?.into())
+                } else if BOOLEAN_COLUMNS.contains(&column.as_str()) {
+                    serde_json::Value::Bool(matches!(value.as_str(), "true" | "1" | "yes"))
+                } else {
+                    value.into()
+                };
+
+                record.insert(column.clone(), value);
+            }
+
+            if !variables.is_empty() {
+                record.insert("variables".into(), variables.into());
+            }
+
+            Ok(serde_json::Value::Object(record))
+        })
+        .collect()
+}
+
+async fn read_records<T: DeserializeOwned>(
+    path: &str,
+    format: ImportFormat,
+) -> Result<Vec<T>, anyhow::Error> {
+    let content = tokio::fs::read_to_string(path).await?;
+
+    let values = match format {
+        ImportFormat::Csv => parse_csv(&content)?,
+        _ => serde_json::from_str(&content)?,
+    };
+
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            serde_json::from_value(value)
+                .map_err(|err| anyhow::anyhow!("invalid record {} in {path}: {err}", i + 1))
+        })
+        .collect()
+}
+
+async fn resolve_owner(
+    database: &shared::database::Database,
+    mappings: &[ImportedUserMapping],
+    owner: &str,
+) -> Result<Option<uuid::Uuid>, anyhow::Error> {
+    if let Some(mapping) = mappings.iter().find(|mapping| {
+        mapping.username.eq_ignore_ascii_case(owner) || mapping.email.eq_ignore_ascii_case(owner)
+    }) {
+        return Ok(Some(mapping.uuid));
+    }
+
+    Ok(sqlx::query_scalar(
+        r#"
+        SELECT users.uuid
+        FROM users
+        WHERE lower(users.username) = lower($1) OR lower(users.email) = lower($1)
+        LIMIT 1
+        "#,
+    )
+    .bind(owner)
+    .fetch_optional(database.read())
+    .await?)
+}
+
+async fn resolve_unique(
+    database: &shared::database::Database,
+    table: &str,
+    value: &str,
+) -> Result<uuid::Uuid, anyhow::Error> {
+    let uuids: Vec<uuid::Uuid> = sqlx::query_scalar(&format!(
+        "SELECT {table}.uuid FROM {table} WHERE {table}.uuid::text = $1 OR {table}.name = $1 LIMIT 2"
+    ))
+    .bind(value)
+    .fetch_all(database.read())
+    .await?;
+
+    match uuids.as_slice() {
+        [uuid] => Ok(*uuid),
+        [] => Err(anyhow::anyhow!("{table} {value} not found")),
+        _ => Err(anyhow::anyhow!(
+            "{table} name {value} is ambiguous, use the uuid instead"
+        )),
+    }
+}
+
+async fn resolve_allocation(
+    database: &shared::database::Database,
+    node_uuid: uuid::Uuid,
+    allocation: &str,
+    dry_run: bool,
+) -> Result<Option<uuid::Uuid>, anyhow::Error> {
+    let (ip, port) = allocation
+        .rsplit_once(':')
+        .ok_or_else(|| anyhow::anyhow!("allocation {allocation} is not in the ip:port format"))?;
+    let ip = sqlx::types::ipnetwork::IpNetwork::from_str(ip.trim_matches(['[', ']']))?;
+    let port: i32 = port.parse()?;
+
+    if dry_run {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO node_allocations (node_uuid, ip, port)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (node_uuid, host(ip), port) DO UPDATE SET port = EXCLUDED.port
+            RETURNING uuid
+            "#,
+        )
+        .bind(node_uuid)
+        .bind(ip)
+        .bind(port)
+        .fetch_one(database.write())
+        .await?,
+    ))
+}
+
+async fn import_server(
+    database: &shared::database::Database,
+    mappings: &[ImportedUserMapping],
+    record: &ServerRecord,
+    dry_run: bool,
+) -> Result<Option<uuid::Uuid>, anyhow::Error> {
+    if let Some(external_id) = &record.external_id
+        && Server::by_external_id(database, external_id).await?.is_some()
+    {
+        return Ok(None);
+    }
+
+    let owner_uuid = match resolve_owner(database, mappings, &record.owner).await? {
+        Some(owner_uuid) => Some(owner_uuid),
+        None if dry_run => None,
+        None => return Err(anyhow::anyhow!("owner {} not found", record.owner)),
+    };
+    let node = Node::by_uuid(database, resolve_unique(database, "nodes", &record.node).await?).await?;
+    let egg =
+        NestEgg::by_uuid(database, resolve_unique(database, "nest_eggs", &record.egg).await?)
+            .await?;
+
+    let egg_variables = NestEggVariable::all_by_egg_uuid(database, egg.uuid).await?;
+    let values = egg_variables
+        .iter()
+        .map(|variable| {
+            let value = record
+                .variables
+                .get(&variable.env_variable)
+                .or(variable.default_value.as_ref())
+                .map_or("", |value| value.as_str());
+
+            (variable, value)
+        })
+        .collect::<Vec<_>>();
+    rule_validator::Validator::new(
+        values
+            .iter()
+            .map(|(variable, value)| {
+                (
+                    variable.env_variable.as_str(),
+                    (variable.rules.as_slice(), *value),
+                )
+            })
+            .collect(),
+    )
+    .and_then(|validator| {
+        validator.validate().map_err(|errors| {
+            errors
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+                .into()
+        })
+    })
+    .map_err(|err| anyhow::anyhow!("invalid variables: {err}"))?;
+
+    let image = match &record.image {
+        Some(image) => image.clone(),
+        None => egg
+            .docker_images
+            .values()
+            .next()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("egg {} does not have any images", egg.name))?,
+    };
+
+    let allocation_uuid = match &record.allocation {
+        Some(allocation) => resolve_allocation(database, node.uuid, allocation, dry_run).await?,
+        None => None,
+    };
+
+    let owner_uuid = match owner_uuid {
+        Some(owner_uuid) if !dry_run => owner_uuid,
+        _ => return Ok(None),
+    };
+
+    let variables = values
+        .iter()
+        .filter(|(variable, _)| record.variables.contains_key(&variable.env_variable))
+        .map(|(variable, value)| (variable.uuid, *value))
+        .collect::<HashMap<_, _>>();
+
+    Ok(Some(
+        Server::create(
+            database,
+            &node,
+            owner_uuid,
+            egg.uuid,
+            None,
+            allocation_uuid,
+            &[],
+            record.external_id.as_deref(),
+            false,
+            record.skip_installer,
+            &record.name,
+            record.description.as_deref(),
+            &ApiServerLimits {
+                cpu: record.cpu,
+                memory: record.memory,
+                swap: record.swap,
+                disk: record.disk,
+                io_weight: record.io_weight,
+            },
+            &[],
+            record.startup.as_deref().unwrap_or(egg.startup.as_str()),
+            &image,
+            None,
+            &ApiServerFeatureLimits {
+                allocations: record.allocation_limit,
+                databases: record.database_limit,
+                backups: record.backup_limit,
+                schedules: record.schedule_limit,
+            },
+            &variables,
+        )
+        .await?,
+    ))
+}
+
+/// Imports users and servers from JSON arrays or CSV files with a header row.
+pub async fn import(env: &shared::env::Env, args: ImportArgs) -> Result<(), anyhow::Error> {
+    if args.users_file.is_none() && args.servers_file.is_none() {
+        eprintln!(
+            "{}",
+            "--users-file or --servers-file is required for the json and csv formats.".red()
+        );
+        std::process::exit(1);
+    }
+
+    let user_records: Vec<UserRecord> = match &args.users_file {
+        Some(path) => read_records(path, args.format).await?,
+        None => Vec::new(),
+    };
+    let server_records: Vec<ServerRecord> = match &args.servers_file {
+        Some(path) => read_records(path, args.format).await?,
+        None => Vec::new(),
+    };
+
+    let cache = Arc::new(shared::cache::Cache::new(env).await);
+    let database = Arc::new(shared::database::Database::new(env, cache).await);
+
+    if args.dry_run {
+        println!(
+            "{}",
+            "dry run of import, nothing will be changed.".bright_black()
+        );
+    }
+
+    let mut progress = ImportProgress::load(
+        &args.progress_file,
+        &format!(
+            "{}:{}",
+            args.users_file.as_deref().unwrap_or_default(),
+            args.servers_file.as_deref().unwrap_or_default()
+        ),
+        args.resume && !args.dry_run,
+    )
+    .await?;
+
+    let mut mappings = progress
+        .table("users")
+        .results
+        .iter()
+        .map(|mapping| serde_json::from_value(mapping.clone()))
+        .collect::<Result<Vec<ImportedUserMapping>, _>>()?;
+
+    let start = progress.table("users").offset;
+    for (i, record) in user_records.into_iter().enumerate().skip(start) {
+        let conflict = users::find_conflict(
+            &database,
+            None,
+            record.external_id.as_deref(),
+            &record.username,
+            &record.email,
+        )
+        .await?;
+
+        if args.dry_run {
+            match conflict {
+                Some(conflict) => println!(
+                    "user {} <{}> conflicts with {} <{}>, strategy {}",
+                    record.username.bright_yellow(),
+                    record.email,
+                    conflict.username.bright_yellow(),
+                    conflict.email,
+                    match args.conflict_strategy {
+                        users::ConflictStrategy::Skip => "skip",
+                        users::ConflictStrategy::Overwrite => "overwrite",
+                        users::ConflictStrategy::Rename => "rename",
+                    }
+                ),
+                None => println!(
+                    "user {} <{}> would be created",
+                    record.username.bright_green(),
+                    record.email
+                ),
+            }
+
+            continue;
+        }
+
+        let password = match (record.password_hash, record.password) {
+            (Some(hash), _) => users::ImportedPassword::Hash(hash),
+            (None, Some(password)) => users::ImportedPassword::Plain(password),
+            (None, None) => users::ImportedPassword::Plain(
+                rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 32),
+            ),
+        };
+
+        let user_uuid = users::import_user(
+            &database,
+            args.conflict_strategy,
+            users::ImportedUser {
+                uuid: None,
+                external_id: record.external_id,
+                username: record.username.clone(),
+                email: record.email.clone(),
+                name_first: record.name_first.unwrap_or_else(|| record.username.clone()),
+                name_last: record.name_last.unwrap_or_default(),
+                password,
+                admin: record.admin,
+                totp_secret: None,
+                created: None,
+            },
+        )
+        .await?;
+
+        if let Some(user_uuid) = user_uuid {
+            let mapping = ImportedUserMapping {
+                username: record.username,
+                email: record.email,
+                uuid: user_uuid,
+            };
+
+            let table = progress.table("users");
+            table.results.push(serde_json::to_value(&mapping)?);
+            mappings.push(mapping);
+        }
+
+        progress.table("users").offset = i + 1;
+        progress.save().await?;
+    }
+
+    let start = progress.table("servers").offset;
+    let mut failed = 0;
+    for (i, record) in server_records.iter().enumerate().skip(start) {
+        match import_server(&database, &mappings, record, args.dry_run).await {
+            Ok(Some(server_uuid)) => {
+                tracing::info!(server = %server_uuid, name = %record.name, "imported server")
+            }
+            Ok(None) if args.dry_run => println!(
+                "server {} would be created on node {} for {}",
+                record.name.bright_green(),
+                record.node,
+                record.owner
+            ),
+            Ok(None) => {
+                tracing::info!(name = %record.name, "server already imported, skipping")
+            }
+            Err(err) => {
+                failed += 1;
+                if args.dry_run {
+                    println!("server {} would fail: {}", record.name.bright_red(), err);
+                } else {
+                    tracing::error!(name = %record.name, "failed to import server: {:?}", err);
+                }
+            }
+        }
+
+        if !args.dry_run {
+            progress.table("servers").offset = i + 1;
+            progress.save().await?;
+        }
+    }
+
+    if !args.dry_run {
+        progress.finish().await?;
+    }
+
+    if failed > 0 {
+        eprintln!(
+            "{}",
+            format!("{failed} servers could not be imported.").red()
+        );
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use base64::Engine;
-use clap::{Args, FromArgMatches};
+use clap::{Args, FromArgMatches, ValueEnum};
 use colored::Colorize;
 use compact_str::ToCompactString;
 use openssl::symm::Cipher;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use sqlx::Row;
 use std::{
     collections::{HashMap, HashSet},
@@ -17,15 +17,79 @@ static BASE64_ENGINE: base64::engine::general_purpose::GeneralPurpose =
         base64::engine::general_purpose::GeneralPurposeConfig::new(),
     );
 
+mod generic;
+mod progress;
+mod users;
+
+#[derive(ValueEnum, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    Pterodactyl,
+    Pelican,
+    Calagopus,
+    Json,
+    Csv,
+}
+
 #[derive(Args)]
 pub struct ImportArgs {
+    #[arg(
+        long = "format",
+        help = "the kind of data to import",
+        default_value = "pterodactyl"
+    )]
+    format: ImportFormat,
     #[arg(
         short = 'e',
         long = "environment",
-        help = "the environment variable file location for the pterodactyl panel",
-        default_value = "/var/www/pterodactyl/.env"
+        help = "the environment variable file location for the pterodactyl or pelican panel, defaults to /var/www/<format>/.env"
+    )]
+    environment: Option<String>,
+    #[arg(
+        short = 'f',
+        long = "file",
+        help = "the export archive to import, required for the calagopus format"
+    )]
+    file: Option<String>,
+    #[arg(
+        short = 'p',
+        long = "passphrase",
+        help = "the passphrase of the export archive, prompted for if not set"
+    )]
+    passphrase: Option<String>,
+    #[arg(
+        long = "users-file",
+        help = "the file containing the users to import, for the json and csv formats"
+    )]
+    users_file: Option<String>,
+    #[arg(
+        long = "servers-file",
+        help = "the file containing the servers to import, for the json and csv formats"
+    )]
+    servers_file: Option<String>,
+    #[arg(
+        long = "dry-run",
+        help = "only print what would be imported without changing anything",
+        default_value = "false"
+    )]
+    dry_run: bool,
+    #[arg(
+        long = "conflict-strategy",
+        help = "what to do with users whose username or email already exists",
+        default_value = "skip"
+    )]
+    conflict_strategy: users::ConflictStrategy,
+    #[arg(
+        long = "resume",
+        help = "continue a previously interrupted import from the progress file",
+        default_value = "false"
     )]
-    environment: String,
+    resume: bool,
+    #[arg(
+        long = "progress-file",
+        help = "the file import progress is recorded in",
+        default_value = "import-progress.json"
+    )]
+    progress_file: String,
 }
 
 pub struct ImportCommand;
@@ -52,10 +116,24 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     }
                 };
 
-                if let Err(err) = dotenvy::from_path(args.environment) {
+                let source_name = match args.format {
+                    ImportFormat::Calagopus => return import_archive(&env, args).await,
+                    ImportFormat::Json | ImportFormat::Csv => {
+                        return generic::import(&env, args).await;
+                    }
+                    ImportFormat::Pterodactyl => "pterodactyl",
+                    ImportFormat::Pelican => "pelican",
+                };
+                let format = args.format;
+                let conflict_strategy = args.conflict_strategy;
+
+                let environment = args
+                    .environment
+                    .unwrap_or_else(|| format!("/var/www/{source_name}/.env"));
+                if let Err(err) = dotenvy::from_path(environment) {
                     eprintln!(
                         "{}: {:#?}",
-                        "failed to read pterodactyl environment file".red(),
+                        format!("failed to read {source_name} environment file").red(),
                         err
                     );
 
@@ -67,7 +145,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment APP_URL".red(),
+                            format!("failed to read {source_name} environment APP_URL").red(),
                             err
                         );
 
@@ -81,7 +159,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Ok(Err(err)) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment APP_KEY".red(),
+                            format!("failed to read {source_name} environment APP_KEY").red(),
                             err
                         );
 
@@ -90,19 +168,31 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment APP_KEY".red(),
+                            format!("failed to read {source_name} environment APP_KEY").red(),
                             err
                         );
 
                         std::process::exit(1);
                     }
                 };
+                if let Ok(connection) = std::env::var("DB_CONNECTION")
+                    && !matches!(connection.trim_matches('"'), "mysql" | "mariadb")
+                {
+                    eprintln!(
+                        "{} {}",
+                        format!("unsupported {source_name} database connection").red(),
+                        connection.bright_red()
+                    );
+
+                    std::process::exit(1);
+                }
+
                 let source_database_host = match std::env::var("DB_HOST") {
                     Ok(value) => value,
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment DB_HOST".red(),
+                            format!("failed to read {source_name} environment DB_HOST").red(),
                             err
                         );
 
@@ -115,7 +205,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Ok(Err(err)) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment DB_PORT".red(),
+                            format!("failed to read {source_name} environment DB_PORT").red(),
                             err
                         );
 
@@ -124,7 +214,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment DB_PORT".red(),
+                            format!("failed to read {source_name} environment DB_PORT").red(),
                             err
                         );
 
@@ -136,7 +226,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment DB_DATABASE".red(),
+                            format!("failed to read {source_name} environment DB_DATABASE").red(),
                             err
                         );
 
@@ -148,7 +238,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment DB_USERNAME".red(),
+                            format!("failed to read {source_name} environment DB_USERNAME").red(),
                             err
                         );
 
@@ -160,7 +250,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to read pterodactyl environment DB_PASSWORD".red(),
+                            format!("failed to read {source_name} environment DB_PASSWORD").red(),
                             err
                         );
 
@@ -182,7 +272,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     Err(err) => {
                         eprintln!(
                             "{}: {:#?}",
-                            "failed to connect to pterodactyl database".red(),
+                            format!("failed to connect to {source_name} database").red(),
                             err
                         );
 
@@ -192,10 +282,34 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 let cache = Arc::new(shared::cache::Cache::new(&env).await);
                 let database = Arc::new(shared::database::Database::new(&env, cache.clone()).await);
+
+                if args.dry_run {
+                    return dry_run(&source_database, &database, source_name, conflict_strategy)
+                        .await;
+                }
+
                 let settings = Arc::new(shared::settings::Settings::new(database.clone()).await);
+                let mut progress = match progress::ImportProgress::load(
+                    args.progress_file,
+                    &format!(
+                        "{source_name}:{}:{source_database_port}/{}",
+                        source_database_host.trim_matches('"'),
+                        source_database_database.trim_matches('"')
+                    ),
+                    args.resume,
+                )
+                .await
+                {
+                    Ok(progress) => progress,
+                    Err(err) => {
+                        eprintln!("{}: {:#?}", "failed to load import progress".red(), err);
+                        std::process::exit(1);
+                    }
+                };
 
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "settings",
                     None,
                     async |rows| {
@@ -248,62 +362,70 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     std::process::exit(1);
                 }
 
+                let pelican_admins = match format {
+                    ImportFormat::Pelican => match pelican_root_admins(&source_database).await {
+                        Ok(admins) => admins,
+                        Err(err) => {
+                            tracing::error!("failed to read pelican root admins: {:?}", err);
+                            std::process::exit(1);
+                        }
+                    },
+                    _ => HashSet::new(),
+                };
                 let user_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "users",
                     None,
                     async |rows| {
                         let mut mapping = HashMap::with_capacity(rows.len());
-                        let mut futures = Vec::with_capacity(rows.len());
 
                         for row in rows {
                             let id: u32 = row.try_get("id")?;
                             let uuid: uuid::fmt::Hyphenated = row.try_get("uuid")?;
+                            let external_id: Option<&str> = row.try_get("external_id")?;
+                            let username: &str = row.try_get("username")?;
+                            let email: &str = row.try_get("email")?;
+                            let name_first: &str = row.try_get("name_first").unwrap_or(username);
+                            let name_last: &str = row.try_get("name_last").unwrap_or("");
+                            let password: &str = row.try_get("password")?;
+                            let admin: bool = match format {
+                                ImportFormat::Pelican => pelican_admins.contains(&id),
+                                _ => row.try_get("root_admin")?,
+                            };
+                            let totp_enabled: bool = row.try_get("use_totp").unwrap_or(false);
+                            let totp_secret: Option<compact_str::CompactString> = row
+                                .try_get::<Option<&str>, _>("totp_secret")
+                                .ok()
+                                .flatten()
+                                .and_then(|s| decrypt_laravel_value(s, &source_app_key).ok());
+                            let created: chrono::DateTime<chrono::Utc> = row.try_get("created_at")?;
 
-                            mapping.insert(id, *uuid.as_uuid());
-
-                            let source_app_key = source_app_key.clone();
-                            let database = database.clone();
-                            futures.push(async move {
-                                let external_id: Option<&str> = row.try_get("external_id")?;
-                                let username: &str = row.try_get("username")?;
-                                let email: &str = row.try_get("email")?;
-                                let name_first: &str = row.try_get("name_first")?;
-                                let name_last: &str = row.try_get("name_last")?;
-                                let password: &str = row.try_get("password")?;
-                                let admin: bool = row.try_get("root_admin")?;
-                                let totp_enabled: bool = row.try_get("use_totp")?;
-                                let totp_secret: Option<compact_str::CompactString> = row.try_get::<Option<&str>, _>("totp_secret")?
-                                    .and_then(|s| decrypt_laravel_value(s, &source_app_key).ok());
-                                let created: chrono::DateTime<chrono::Utc> = row.try_get("created_at")?;
-
-                                sqlx::query(
-                                    r#"
-                                    INSERT INTO users (uuid, external_id, username, email, name_first, name_last, password, admin, totp_enabled, totp_secret, created)
-                                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-                                    ON CONFLICT DO NOTHING
-                                    "#
-                                )
-                                .bind(uuid.as_uuid())
-                                .bind(external_id)
-                                .bind(username)
-                                .bind(email)
-                                .bind(name_first)
-                                .bind(name_last)
-                                .bind(password.replace("$2y$", "$2a$"))
-                                .bind(admin)
-                                .bind(totp_enabled)
-                                .bind(totp_secret)
-                                .bind(created)
-                                .execute(database.write())
-                                .await?;
+                            let user_uuid = users::import_user(
+                                &database,
+                                conflict_strategy,
+                                users::ImportedUser {
+                                    uuid: Some(*uuid.as_uuid()),
+                                    external_id: external_id.map(Into::into),
+                                    username: username.into(),
+                                    email: email.into(),
+                                    name_first: name_first.into(),
+                                    name_last: name_last.into(),
+                                    password: users::ImportedPassword::Hash(
+                                        password.replace("$2y$", "$2a$"),
+                                    ),
+                                    admin,
+                                    totp_secret: totp_secret.filter(|_| totp_enabled),
+                                    created: Some(created),
+                                },
+                            )
+                            .await?;
 
-                                Ok::<(), anyhow::Error>(())
-                            });
+                            if let Some(user_uuid) = user_uuid {
+                                mapping.insert(id, user_uuid);
+                            }
                         }
 
-                        futures_util::future::try_join_all(futures).await?;
-
                         Ok(mapping)
                     },
                     64,
@@ -319,6 +441,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "user_ssh_keys",
                     Some("deleted_at IS NULL"),
                     async |rows| {
@@ -406,6 +529,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 }
                 /*if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "api_keys",
                     Some("user_id IS NOT NULL AND key_type = 1"),
                     async |rows| {
@@ -457,9 +581,20 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 let backup_configuration_uuid: uuid::Uuid = {
                     let row = sqlx::query(
                         r#"
-                        INSERT INTO backup_configurations (name, description, backup_disk, backup_configs)
-                        VALUES ($1, $2, $3, $4)
-                        RETURNING uuid
+                        WITH existing AS (
+                            SELECT backup_configurations.uuid
+                            FROM backup_configurations
+                            WHERE backup_configurations.name = $1 AND backup_configurations.description = $2
+                            LIMIT 1
+                        ), inserted AS (
+                            INSERT INTO backup_configurations (name, description, backup_disk, backup_configs)
+                            SELECT $1, $2, $3, $4
+                            WHERE NOT EXISTS (SELECT 1 FROM existing)
+                            RETURNING uuid
+                        )
+                        SELECT uuid FROM existing
+                        UNION ALL
+                        SELECT uuid FROM inserted
                         "#,
                     )
                     .bind("global")
@@ -480,6 +615,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 let location_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "locations",
                     None,
                     async |rows| {
@@ -523,8 +659,22 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                         std::process::exit(1);
                     }
                 };
+                // pelican dropped locations and nests, everything it owns is grouped under a single one of each
+                let (default_location_uuid, default_nest_uuid) = match format {
+                    ImportFormat::Pelican => {
+                        match pelican_defaults(&database, backup_configuration_uuid).await {
+                            Ok((location_uuid, nest_uuid)) => (Some(location_uuid), Some(nest_uuid)),
+                            Err(err) => {
+                                tracing::error!("failed to create pelican location and nest: {:?}", err);
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                    _ => (None, None),
+                };
                 let node_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "nodes",
                     None,
                     async |rows| {
@@ -544,20 +694,29 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                                 let public: bool = row.try_get("public")?;
                                 let name: &str = row.try_get("name")?;
                                 let description: Option<&str> = row.try_get("description")?;
-                                let location_id: u32 = row.try_get("location_id")?;
                                 let fqdn: &str = row.try_get("fqdn")?;
                                 let scheme: &str = row.try_get("scheme")?;
                                 let memory: u64 = row.try_get("memory")?;
                                 let disk: u64 = row.try_get("disk")?;
                                 let token_id: &str = row.try_get("daemon_token_id")?;
                                 let token: &str = row.try_get("daemon_token")?;
-                                let daemon_listen: u16 = row.try_get("daemonListen")?;
-                                let daemon_sftp: u16 = row.try_get("daemonSFTP")?;
+                                let daemon_listen: u16 = row
+                                    .try_get("daemonListen")
+                                    .or_else(|_| row.try_get("daemon_listen"))?;
+                                let daemon_sftp: u16 = row
+                                    .try_get("daemonSFTP")
+                                    .or_else(|_| row.try_get("daemon_sftp"))?;
                                 let created: chrono::DateTime<chrono::Utc> = row.try_get("created_at")?;
 
-                                let location_uuid = match location_mappings.iter().find(|m| m.contains_key(&location_id)) {
-                                    Some(location_uuid) => location_uuid.get(&location_id).unwrap(),
-                                    None => return Ok(()),
+                                let location_uuid = match row.try_get::<u32, _>("location_id") {
+                                    Ok(location_id) => match location_mappings.iter().find(|m| m.contains_key(&location_id)) {
+                                        Some(location_uuid) => *location_uuid.get(&location_id).unwrap(),
+                                        None => return Ok(()),
+                                    },
+                                    Err(_) => match default_location_uuid {
+                                        Some(location_uuid) => location_uuid,
+                                        None => return Ok(()),
+                                    },
                                 };
 
                                 let token = match decrypt_laravel_value(token, &source_app_key) {
@@ -614,6 +773,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 let nest_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "nests",
                     None,
                     async |rows| {
@@ -660,6 +820,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 };
                 let egg_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "eggs",
                     None,
                     async |rows| {
@@ -668,7 +829,6 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                         for row in rows {
                             let id: u32 = row.try_get("id")?;
                             let uuid: uuid::fmt::Hyphenated = row.try_get("uuid")?;
-                            let nest_id: u32 = row.try_get("nest_id")?;
                             let author: &str = row.try_get("author")?;
                             let name: &str = row.try_get("name")?;
                             let description: Option<&str> = row.try_get("description")?;
@@ -691,11 +851,18 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                             let created: chrono::DateTime<chrono::Utc> =
                                 row.try_get("created_at")?;
 
-                            let nest_uuid =
-                                match nest_mappings.iter().find(|m| m.contains_key(&nest_id)) {
-                                    Some(nest_uuid) => nest_uuid.get(&nest_id).unwrap(),
+                            let nest_uuid = match row.try_get::<u32, _>("nest_id") {
+                                Ok(nest_id) => {
+                                    match nest_mappings.iter().find(|m| m.contains_key(&nest_id)) {
+                                        Some(nest_uuid) => *nest_uuid.get(&nest_id).unwrap(),
+                                        None => continue,
+                                    }
+                                }
+                                Err(_) => match default_nest_uuid {
+                                    Some(nest_uuid) => nest_uuid,
                                     None => continue,
-                                };
+                                },
+                            };
 
                             let features: Vec<String> = serde_json::from_value(
                                 features.unwrap_or_else(|| serde_json::Value::Array(vec![])),
@@ -778,6 +945,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 drop(nest_mappings);
                 let egg_variable_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "egg_variables",
                     None,
                     async |rows| {
@@ -802,8 +970,14 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                             let rules = rules.split('|').map(compact_str::CompactString::from).collect::<Vec<_>>();
 
-                            if rule_validator::validate_rules(&rules).is_err() {
-                                continue;
+                            let (rules, unsupported_rules) = rule_validator::partition_rules(&rules);
+                            for unsupported in unsupported_rules {
+                                tracing::warn!(
+                                    "leaving out unsupported rule '{}' of egg variable {}: {}",
+                                    unsupported.rule,
+                                    env_variable,
+                                    unsupported.error
+                                );
                             }
 
                             let row = sqlx::query(
@@ -844,6 +1018,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 let database_host_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "database_hosts",
                     None,
                     async |rows| {
@@ -900,6 +1075,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 let server_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "servers",
                     None,
                     async |rows| {
@@ -1019,6 +1195,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 };
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "databases",
                     None,
                     async |rows| {
@@ -1085,6 +1262,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 }
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "server_variables",
                     None,
                     async |rows| {
@@ -1135,6 +1313,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 }
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "backups",
                     None,
                     async |rows| {
@@ -1217,6 +1396,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 }
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "subusers",
                     None,
                     async |rows| {
@@ -1329,6 +1509,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 let mount_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "mounts",
                     None,
                     async |rows| {
@@ -1378,6 +1559,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 };
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "egg_mount",
                     None,
                     async |rows| {
@@ -1421,6 +1603,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 }
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "mount_node",
                     None,
                     async |rows| {
@@ -1464,6 +1647,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 }
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "mount_server",
                     None,
                     async |rows| {
@@ -1509,6 +1693,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 let schedule_mappings = match process_table(
                     &source_database,
+                    &mut progress,
                     "schedules",
                     None,
                     async |rows| {
@@ -1591,6 +1776,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                 };
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "tasks",
                     None,
                     async |rows| {
@@ -1650,6 +1836,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                                             .map(compact_str::CompactString::from)
                                             .collect::<Vec<_>>(),
                                         foreground: true,
+                                        rotate_oldest: None,
                                         ignore_failure: continue_on_failure,
                                     })
                                 }
@@ -1686,6 +1873,7 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
 
                 if let Err(err) = process_table(
                     &source_database,
+                    &mut progress,
                     "allocations",
                     None,
                     async |rows| {
@@ -1779,12 +1967,223 @@ impl shared::extensions::commands::CliCommand<ImportArgs> for ImportCommand {
                     std::process::exit(1);
                 }
 
+                if let Err(err) = progress.finish().await {
+                    tracing::warn!("failed to remove import progress file: {:?}", err);
+                }
+
                 Ok(())
             })
         })
     }
 }
 
+/// The source tables read by the pterodactyl and pelican imports, in import order.
+const SOURCE_TABLES: &[&str] = &[
+    "settings",
+    "users",
+    "user_ssh_keys",
+    "locations",
+    "nodes",
+    "nests",
+    "eggs",
+    "egg_variables",
+    "database_hosts",
+    "servers",
+    "databases",
+    "server_variables",
+    "backups",
+    "subusers",
+    "mounts",
+    "egg_mount",
+    "mount_node",
+    "mount_server",
+    "schedules",
+    "tasks",
+    "allocations",
+];
+
+async fn dry_run(
+    source_database: &sqlx::Pool<sqlx::MySql>,
+    database: &shared::database::Database,
+    source_name: &str,
+    conflict_strategy: users::ConflictStrategy,
+) -> Result<(), anyhow::Error> {
+    println!(
+        "{}",
+        format!("dry run of {source_name} import, nothing will be changed.").bright_black()
+    );
+
+    for table in SOURCE_TABLES {
+        if !source_table_exists(source_database, table).await? {
+            println!("{:<20} {}", table, "not present, skipped".bright_black());
+            continue;
+        }
+
+        let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM `{table}`"))
+            .fetch_one(source_database)
+            .await?;
+        println!("{:<20} {} rows", table, count.to_string().bright_green());
+    }
+
+    let rows = sqlx::query("SELECT uuid, external_id, username, email FROM users")
+        .fetch_all(source_database)
+        .await?;
+    let mut conflicts = 0;
+
+    for row in rows {
+        let uuid: uuid::fmt::Hyphenated = row.try_get("uuid")?;
+        let external_id: Option<&str> = row.try_get("external_id")?;
+        let username: &str = row.try_get("username")?;
+        let email: &str = row.try_get("email")?;
+
+        let conflict = match users::find_conflict(
+            database,
+            Some(*uuid.as_uuid()),
+            external_id,
+            username,
+            email,
+        )
+        .await?
+        {
+            Some(conflict) if conflict.uuid != *uuid.as_uuid() => conflict,
+            _ => continue,
+        };
+
+        conflicts += 1;
+        println!(
+            "user {} <{}> conflicts with {} <{}>, {}",
+            username.bright_yellow(),
+            email,
+            conflict.username.bright_yellow(),
+            conflict.email,
+            match conflict_strategy {
+                users::ConflictStrategy::Skip => "it would be skipped with everything it owns",
+                users::ConflictStrategy::Overwrite => "the existing user would be overwritten",
+                users::ConflictStrategy::Rename => "it would be imported under a new name",
+            }
+        );
+    }
+
+    println!("{} conflicting users", conflicts.to_string().bright_yellow());
+
+    Ok(())
+}
+
+/// Pelican replaced the `root_admin` flag of users with roles.
+async fn pelican_root_admins(
+    source_database: &sqlx::Pool<sqlx::MySql>,
+) -> Result<HashSet<u32>, anyhow::Error> {
+    if !source_table_exists(source_database, "model_has_roles").await? {
+        return Ok(HashSet::new());
+    }
+
+    let ids: Vec<u64> = sqlx::query_scalar(
+        r#"
+        SELECT model_has_roles.model_id
+        FROM model_has_roles
+        JOIN roles ON roles.id = model_has_roles.role_id
+        WHERE roles.name = 'Root Admin' AND model_has_roles.model_type LIKE '%User'
+        "#,
+    )
+    .fetch_all(source_database)
+    .await?;
+
+    Ok(ids.into_iter().map(|id| id as u32).collect())
+}
+
+async fn pelican_defaults(
+    database: &shared::database::Database,
+    backup_configuration_uuid: uuid::Uuid,
+) -> Result<(uuid::Uuid, uuid::Uuid), anyhow::Error> {
+    let location_uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO locations (backup_configuration_uuid, name, description)
+        VALUES ($1, 'pelican', 'automatically generated by import')
+        ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+        RETURNING uuid
+        "#,
+    )
+    .bind(backup_configuration_uuid)
+    .fetch_one(database.write())
+    .await?;
+
+    let nest_uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO nests (author, name, description)
+        VALUES ('support@pelican.dev', 'Pelican', 'automatically generated by import')
+        ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+        RETURNING uuid
+        "#,
+    )
+    .fetch_one(database.write())
+    .await?;
+
+    Ok((location_uuid, nest_uuid))
+}
+
+async fn import_archive(env: &shared::env::Env, args: ImportArgs) -> Result<(), anyhow::Error> {
+    let file = match args.file {
+        Some(file) => file,
+        None => {
+            eprintln!("{}", "--file is required for the calagopus format.".red());
+            std::process::exit(1);
+        }
+    };
+
+    let data = match tokio::fs::read(&file).await {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{}: {:#?}", "failed to read export archive".red(), err);
+            std::process::exit(1);
+        }
+    };
+
+    let passphrase = match args.passphrase {
+        Some(passphrase) => passphrase,
+        None => dialoguer::Password::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .with_prompt("archive passphrase")
+            .interact()?,
+    };
+
+    if args.dry_run {
+        let manifest = super::archive::inspect(data, &passphrase).await?;
+
+        println!(
+            "{}",
+            format!(
+                "dry run of archive exported by panel {} at {}, nothing will be changed.",
+                manifest.panel_version, manifest.created
+            )
+            .bright_black()
+        );
+        for table in manifest.tables {
+            println!("{:<32} {} rows", table.name, table.rows.to_string().bright_green());
+        }
+
+        return Ok(());
+    }
+
+    let cache = Arc::new(shared::cache::Cache::new(env).await);
+    let database = Arc::new(shared::database::Database::new(env, cache).await);
+
+    match super::archive::restore(&database, data, &passphrase).await {
+        Ok(manifest) => {
+            println!(
+                "imported {} tables exported by panel {} at {}.",
+                manifest.tables.len(),
+                manifest.panel_version.bright_green(),
+                manifest.created
+            );
+
+            Ok(())
+        }
+        Err(err) => {
+            eprintln!("{}: {:#?}", "failed to import export archive".red(), err);
+            std::process::exit(1);
+        }
+    }
+}
+
 #[inline]
 fn extract_php_serialized_string(
     serialized_data: &str,
@@ -1859,17 +2258,37 @@ fn decrypt_laravel_value(
     extract_php_serialized_string(&result)
 }
 
-pub async fn process_table<T, Fut: Future<Output = Result<T, anyhow::Error>>>(
+pub async fn process_table<
+    T: Serialize + DeserializeOwned,
+    Fut: Future<Output = Result<T, anyhow::Error>>,
+>(
     source_database: &sqlx::Pool<sqlx::MySql>,
+    progress: &mut progress::ImportProgress,
     table: &str,
     sql_where: Option<&str>,
     compute: impl Fn(Vec<sqlx::mysql::MySqlRow>) -> Fut,
     page_size: usize,
 ) -> Result<Vec<T>, anyhow::Error> {
-    tracing::info!(table, "starting processing");
+    if !source_table_exists(source_database, table).await? {
+        tracing::info!(table, "table does not exist in source, skipping");
+        return Ok(Vec::new());
+    }
+
+    let table_progress = progress.table(table);
+    let mut results = table_progress
+        .results
+        .iter()
+        .map(|result| serde_json::from_value(result.clone()))
+        .collect::<Result<Vec<T>, _>>()?;
+
+    if table_progress.completed {
+        tracing::info!(table, "already processed, skipping");
+        return Ok(results);
+    }
+
+    let mut offset = table_progress.offset;
 
-    let mut offset: usize = 0;
-    let mut results = Vec::new();
+    tracing::info!(table, offset, "starting processing");
 
     loop {
         tracing::info!(table, offset, "processing");
@@ -1891,12 +2310,34 @@ pub async fn process_table<T, Fut: Future<Output = Result<T, anyhow::Error>>>(
 
         let result = compute(rows).await?;
 
+        offset += page_size;
+
+        let table_progress = progress.table(table);
+        table_progress.offset = offset;
         if std::mem::size_of_val(&result) > 0 {
+            table_progress.results.push(serde_json::to_value(&result)?);
             results.push(result);
         }
 
-        offset += page_size;
+        progress.save().await?;
     }
 
+    progress.table(table).completed = true;
+    progress.save().await?;
+
     Ok(results)
 }
+
+async fn source_table_exists(
+    source_database: &sqlx::Pool<sqlx::MySql>,
+    table: &str,
+) -> Result<bool, anyhow::Error> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?",
+    )
+    .bind(table)
+    .fetch_one(source_database)
+    .await?;
+
+    Ok(count > 0)
+}
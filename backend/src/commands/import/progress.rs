@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct TableProgress {
+    pub offset: usize,
+    pub completed: bool,
+    /// The results of every processed page, so mappings are available again when resuming.
+    pub results: Vec<serde_json::Value>,
+}
+
+/// Records how far an import got, after every processed page.
+#[derive(Serialize, Deserialize)]
+pub struct ImportProgress {
+    source: compact_str::CompactString,
+    tables: HashMap<compact_str::CompactString, TableProgress>,
+
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl ImportProgress {
+    /// Loads the progress for `source` when resuming, otherwise starts from scratch.
+    pub async fn load(
+        path: impl Into<PathBuf>,
+        source: &str,
+        resume: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let path = path.into();
+
+        if resume {
+            match tokio::fs::read(&path).await {
+                Ok(data) => {
+                    let mut progress: Self = serde_json::from_slice(&data)?;
+                    if progress.source != source {
+                        return Err(anyhow::anyhow!(
+                            "progress file {} belongs to a different source ({}), refusing to resume",
+                            path.display(),
+                            progress.source
+                        ));
+                    }
+
+                    progress.path = path;
+                    return Ok(progress);
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Self {
+            source: source.into(),
+            tables: HashMap::new(),
+            path,
+        })
+    }
+
+    #[inline]
+    pub fn table(&mut self, table: &str) -> &mut TableProgress {
+        self.tables.entry(table.into()).or_default()
+    }
+
+    pub async fn save(&self) -> Result<(), anyhow::Error> {
+        tokio::fs::write(&self.path, serde_json::to_vec(self)?).await?;
+
+        Ok(())
+    }
+
+    pub async fn finish(self) -> Result<(), anyhow::Error> {
+        match tokio::fs::remove_file(&self.path).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use clap::ValueEnum;
+use sqlx::Row;
+
+const USERNAME_MAX_LENGTH: usize = 15;
+
+#[derive(ValueEnum, Clone, Copy, PartialEq)]
+pub enum ConflictStrategy {
+    /// Keep the existing user, the imported user and everything owned by it is not imported.
+    Skip,
+    /// Replace the details of the existing user with the imported ones and import everything owned by it.
+    Overwrite,
+    /// Import the user under a new username and email.
+    Rename,
+}
+
+pub enum ImportedPassword {
+    /// A bcrypt hash that can be stored as is.
+    Hash(String),
+    Plain(String),
+}
+
+pub struct ImportedUser {
+    pub uuid: Option<uuid::Uuid>,
+    pub external_id: Option<compact_str::CompactString>,
+    pub username: compact_str::CompactString,
+    pub email: compact_str::CompactString,
+    pub name_first: compact_str::CompactString,
+    pub name_last: compact_str::CompactString,
+    pub password: ImportedPassword,
+    pub admin: bool,
+    pub totp_secret: Option<compact_str::CompactString>,
+    pub created: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub struct Conflict {
+    pub uuid: uuid::Uuid,
+    pub username: compact_str::CompactString,
+    pub email: compact_str::CompactString,
+}
+
+pub async fn find_conflict(
+    database: &shared::database::Database,
+    uuid: Option<uuid::Uuid>,
+    external_id: Option<&str>,
+    username: &str,
+    email: &str,
+) -> Result<Option<Conflict>, anyhow::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT users.uuid, users.username, users.email
+        FROM users
+        WHERE users.uuid = $1 OR users.external_id = $2 OR lower(users.username) = lower($3) OR lower(users.email) = lower($4)
+        ORDER BY users.uuid = $1 DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(uuid)
+    .bind(external_id)
+    .bind(username)
+    .bind(email)
+    .fetch_optional(database.read())
+    .await?;
+
+    Ok(match row {
+        Some(row) => Some(Conflict {
+            uuid: row.try_get("uuid")?,
+            username: row.try_get("username")?,
+            email: row.try_get("email")?,
+        }),
+        None => None,
+    })
+}
+
+async fn is_taken(
+    database: &shared::database::Database,
+    column: &str,
+    value: &str,
+) -> Result<bool, anyhow::Error> {
+    Ok(sqlx::query_scalar(&format!(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE lower(users.{column}) = lower($1))"
+    ))
+    .bind(value)
+    .fetch_one(database.read())
+    .await?)
+}
+
+async fn free_username(
+    database: &shared::database::Database,
+    username: &str,
+) -> Result<compact_str::CompactString, anyhow::Error> {
+    for i in 1..1000 {
+        let suffix = format!("_{i}");
+        let base = username
+            .chars()
+            .take(USERNAME_MAX_LENGTH - suffix.len())
+            .collect::<String>();
+        let candidate = compact_str::format_compact!("{base}{suffix}");
+
+        if !is_taken(database, "username", &candidate).await? {
+            return Ok(candidate);
+        }
+    }
+
+    Err(anyhow::anyhow!("unable to find a free username for {username}"))
+}
+
+async fn free_email(
+    database: &shared::database::Database,
+    email: &str,
+) -> Result<compact_str::CompactString, anyhow::Error> {
+    let (local, domain) = email.rsplit_once('@').unwrap_or((email, "imported.invalid"));
+
+    for i in 1..1000 {
+        let candidate = compact_str::format_compact!("{local}+imported{i}@{domain}");
+
+        if !is_taken(database, "email", &candidate).await? {
+            return Ok(candidate);
+        }
+    }
+
+    Err(anyhow::anyhow!("unable to find a free email for {email}"))
+}
+
+/// Imports a single user, returns the uuid everything owned by the user should be imported under.
+///
+/// A user that was already imported under the same uuid is never treated as a conflict,
+/// so interrupted imports can simply be run again.
+pub async fn import_user(
+    database: &shared::database::Database,
+    strategy: ConflictStrategy,
+    mut user: ImportedUser,
+) -> Result<Option<uuid::Uuid>, anyhow::Error> {
+    let conflict = find_conflict(
+        database,
+        user.uuid,
+        user.external_id.as_deref(),
+        &user.username,
+        &user.email,
+    )
+    .await?;
+
+    match conflict {
+        Some(conflict) if Some(conflict.uuid) == user.uuid => {
+            if strategy == ConflictStrategy::Overwrite {
+                update_user(database, conflict.uuid, &user).await?;
+            }
+
+            return Ok(Some(conflict.uuid));
+        }
+        Some(conflict) => match strategy {
+            ConflictStrategy::Skip => {
+                tracing::warn!(
+                    username = %user.username,
+                    email = %user.email,
+                    "skipping user, conflicts with existing user {} ({})",
+                    conflict.username,
+                    conflict.email
+                );
+
+                return Ok(None);
+            }
+            ConflictStrategy::Overwrite => {
+                update_user(database, conflict.uuid, &user).await?;
+
+                return Ok(Some(conflict.uuid));
+            }
+            ConflictStrategy::Rename => {
+                if is_taken(database, "username", &user.username).await? {
+                    user.username = free_username(database, &user.username).await?;
+                }
+                if is_taken(database, "email", &user.email).await? {
+                    user.email = free_email(database, &user.email).await?;
+                }
+                if let Some(external_id) = &user.external_id
+                    && is_taken(database, "external_id", external_id).await?
+                {
+                    user.external_id = None;
+                }
+
+                tracing::info!(
+                    username = %user.username,
+                    email = %user.email,
+                    "renamed user to avoid conflict with {} ({})",
+                    conflict.username,
+                    conflict.email
+                );
+            }
+        },
+        None => {}
+    }
+
+    let (password, hashed) = match &user.password {
+        ImportedPassword::Hash(hash) => (hash.as_str(), true),
+        ImportedPassword::Plain(password) => (password.as_str(), false),
+    };
+
+    let row = sqlx::query(
+        r#"
+        INSERT INTO users (uuid, external_id, username, email, name_first, name_last, password, admin, totp_enabled, totp_secret, created)
+        VALUES (
+            COALESCE($1, gen_random_uuid()), $2, $3, $4, $5, $6,
+            CASE WHEN $8 THEN $7 ELSE crypt($7, gen_salt('bf', 8)) END,
+            $9, $10 IS NOT NULL, $10, COALESCE($11, NOW())
+        )
+        RETURNING users.uuid
+        "#,
+    )
+    .bind(user.uuid)
+    .bind(user.external_id.as_deref())
+    .bind(user.username.as_str())
+    .bind(user.email.as_str())
+    .bind(user.name_first.as_str())
+    .bind(user.name_last.as_str())
+    .bind(password)
+    .bind(hashed)
+    .bind(user.admin)
+    .bind(user.totp_secret.as_deref())
+    .bind(user.created.map(|created| created.naive_utc()))
+    .fetch_one(database.write())
+    .await?;
+
+    Ok(Some(row.try_get("uuid")?))
+}
+
+async fn update_user(
+    database: &shared::database::Database,
+    uuid: uuid::Uuid,
+    user: &ImportedUser,
+) -> Result<(), anyhow::Error> {
+    let (password, hashed) = match &user.password {
+        ImportedPassword::Hash(hash) => (hash.as_str(), true),
+        ImportedPassword::Plain(password) => (password.as_str(), false),
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET
+            username = $2, email = $3, name_first = $4, name_last = $5,
+            password = CASE WHEN $7 THEN $6 ELSE crypt($6, gen_salt('bf', 8)) END,
+            admin = $8, totp_enabled = $9 IS NOT NULL, totp_secret = $9
+        WHERE users.uuid = $1
+        "#,
+    )
+    .bind(uuid)
+    .bind(user.username.as_str())
+    .bind(user.email.as_str())
+    .bind(user.name_first.as_str())
+    .bind(user.name_last.as_str())
+    .bind(password)
+    .bind(hashed)
+    .bind(user.admin)
+    .bind(user.totp_secret.as_deref())
+    .execute(database.write())
+    .await?;
+
+    Ok(())
+}
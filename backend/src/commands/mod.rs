@@ -1,6 +1,8 @@
 use shared::extensions::commands::CliCommandGroupBuilder;
 
+mod archive;
 mod diagnostics;
+mod export;
 mod extensions;
 mod import;
 mod service_install;
@@ -22,9 +24,14 @@ pub fn commands(cli: CliCommandGroupBuilder) -> CliCommandGroupBuilder {
         "Gets Diagnostic Data for the Panel.",
         diagnostics::DiagnosticsCommand,
     )
+    .add_command(
+        "export",
+        "Exports all Panel Data to an encrypted Archive.",
+        export::ExportCommand,
+    )
     .add_command(
         "import",
-        "Imports Data from a Pterodactyl Instance to the Panel.",
+        "Imports Data from a Pterodactyl Instance or an Export Archive to the Panel.",
         import::ImportCommand,
     )
     .add_group(
@@ -1,18 +1,20 @@
 use axum::{
     ServiceExt,
     body::Body,
-    extract::{ConnectInfo, Path, Request},
+    extract::{ConnectInfo, MatchedPath, Path, Request},
     http::StatusCode,
     middleware::Next,
     response::Response,
     routing::get,
 };
 use colored::Colorize;
+use futures_util::StreamExt;
 use rand::Rng;
 use sentry_tower::SentryHttpLayer;
 use sha2::Digest;
 use shared::{
-    ApiError, FRONTEND_ASSETS, GetState, extensions::commands::CliCommandGroupBuilder,
+    ApiError, FRONTEND_ASSETS, GetState,
+    extensions::{commands::CliCommandGroupBuilder, jobs::JobType},
     response::ApiResponse,
 };
 use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Instant};
@@ -50,6 +52,28 @@ async fn handle_request(
     Ok(next.run(req).await)
 }
 
+async fn handle_metrics(state: GetState, req: Request, next: Next) -> Response {
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| compact_str::CompactString::from(path.as_str()));
+
+    let start = Instant::now();
+    let response = next.run(req).await;
+
+    if let Some(route) = route {
+        state.metrics.record_http_request(
+            method.as_str(),
+            &route,
+            response.status().as_u16(),
+            start.elapsed(),
+        );
+    }
+
+    response
+}
+
 async fn handle_postprocessing(req: Request, next: Next) -> Result<Response, StatusCode> {
     let if_none_match = req
         .headers()
@@ -211,12 +235,17 @@ async fn main() {
 
     let background_tasks =
         Arc::new(shared::extensions::background_tasks::BackgroundTaskManager::default());
+    let jobs = Arc::new(shared::extensions::jobs::JobManager::default());
+    let instance = uuid::Uuid::new_v4();
+    let leader = Arc::new(shared::leader::LeaderElection::new(instance, &env));
+    leader.start(database.clone());
     let settings = Arc::new(shared::settings::Settings::new(database.clone()).await);
     let storage = Arc::new(shared::storage::Storage::new(settings.clone()));
     let captcha = Arc::new(shared::captcha::Captcha::new(settings.clone()));
-    let mail = Arc::new(shared::mail::Mail::new(settings.clone()));
+    let mail = Arc::new(shared::mail::Mail::new(settings.clone(), database.clone()));
 
     let state = Arc::new(shared::AppState {
+        instance,
         start_time: Instant::now(),
         container_type: match std::env::var("OCI_CONTAINER").as_deref() {
             Ok("official") => shared::AppContainerType::Official,
@@ -233,17 +262,20 @@ async fn main() {
 
         extensions: extensions.clone(),
         background_tasks: background_tasks.clone(),
+        jobs: jobs.clone(),
+        leader,
         settings: settings.clone(),
         jwt,
         storage,
         captcha,
         mail,
+        metrics: Arc::new(shared::metrics::Metrics::default()),
         database: database.clone(),
         cache: cache.clone(),
         env,
     });
 
-    let (routes, background_task_builder) = extensions.init(state.clone()).await;
+    let (routes, background_task_builder, jobs_builder) = extensions.init(state.clone()).await;
     let mut extension_router = OpenApiRouter::new().with_state(state.clone());
 
     if let Some(global) = routes.global {
@@ -313,74 +345,55 @@ async fn main() {
         );
     }
 
+    let telemetry_cron_schedule = {
+        fn generate_randomized_cron_schedule() -> cron::Schedule {
+            let mut rng = rand::rng();
+            let seconds: u32 = rng.random_range(0..60);
+            let minutes: u32 = rng.random_range(0..60);
+            let hours: u32 = rng.random_range(0..24);
+
+            format!("{} {} {} * * *", seconds, minutes, hours)
+                .parse()
+                .unwrap()
+        }
+
+        let mut settings = state.settings.get_mut().await;
+        match settings.telemetry_cron_schedule.clone() {
+            Some(cron_schedule) => cron_schedule,
+            None => {
+                let cron_schedule = generate_randomized_cron_schedule();
+                settings.telemetry_cron_schedule = Some(cron_schedule.clone());
+
+                if let Err(err) = settings.save().await {
+                    tracing::error!("failed to save telemetry cron schedule: {:?}", err);
+                }
+
+                cron_schedule
+            }
+        }
+    };
+
     background_task_builder
-        .add_task(
+        .add_cron_task(
             "collect_telemetry",
+            telemetry_cron_schedule,
+            std::time::Duration::ZERO,
             Box::new(|state| {
                 Box::pin(async move {
-                    fn generate_randomized_cron_schedule() -> cron::Schedule {
-                        let mut rng = rand::rng();
-                        let seconds: u32 = rng.random_range(0..60);
-                        let minutes: u32 = rng.random_range(0..60);
-                        let hours: u32 = rng.random_range(0..24);
-
-                        format!("{} {} {} * * *", seconds, minutes, hours)
-                            .parse()
-                            .unwrap()
-                    }
-
-                    let settings = state.settings.get().await;
-                    if !settings.app.telemetry_enabled {
+                    if !state.settings.get().await.app.telemetry_enabled {
                         return Ok(());
                     }
-                    let cron_schedule = settings
-                        .telemetry_cron_schedule
-                        .clone()
-                        .unwrap_or_else(generate_randomized_cron_schedule);
-                    if settings.telemetry_cron_schedule.is_none() {
-                        drop(settings);
-                        let mut new_settings = state.settings.get_mut().await;
-                        new_settings.telemetry_cron_schedule = Some(cron_schedule.clone());
-                        new_settings.save().await?;
-                    } else {
-                        drop(settings);
-                    }
 
-                    let schedule_iter = cron_schedule.upcoming(chrono::Utc);
+                    let telemetry_data = shared::telemetry::TelemetryData::collect(&state).await?;
 
-                    for target_datetime in schedule_iter {
-                        let target_timestamp = target_datetime.timestamp();
-                        let now_timestamp = chrono::Utc::now().timestamp();
-                        let sleep_duration = target_timestamp - now_timestamp;
-                        if sleep_duration <= 0 {
-                            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-                            continue;
-                        }
-
-                        tokio::time::sleep(std::time::Duration::from_secs(sleep_duration as u64))
-                            .await;
+                    state
+                        .client
+                        .post("https://calagopus.com/api/telemetry")
+                        .json(&telemetry_data)
+                        .send()
+                        .await?;
 
-                        let telemetry_data =
-                            match shared::telemetry::TelemetryData::collect(&state).await {
-                                Ok(data) => data,
-                                Err(err) => {
-                                    tracing::error!("failed to collect telemetry data: {:#?}", err);
-                                    continue;
-                                }
-                            };
-
-                        if let Err(err) = state
-                            .client
-                            .post("https://calagopus.com/api/telemetry")
-                            .json(&telemetry_data)
-                            .send()
-                            .await
-                        {
-                            tracing::error!("failed to send telemetry data: {:#?}", err);
-                        } else {
-                            tracing::info!("successfully sent telemetry data");
-                        }
-                    }
+                    tracing::info!("successfully sent telemetry data");
 
                     Ok(())
                 })
@@ -388,8 +401,10 @@ async fn main() {
         )
         .await;
     background_task_builder
-        .add_task(
+        .add_interval_task(
             "delete_expired_sessions",
+            std::time::Duration::from_mins(5),
+            std::time::Duration::from_secs(30),
             Box::new(|state| {
                 Box::pin(async move {
                     let deleted_sessions =
@@ -400,16 +415,16 @@ async fn main() {
                         tracing::info!("deleted {} expired user sessions", deleted_sessions);
                     }
 
-                    tokio::time::sleep(std::time::Duration::from_mins(5)).await;
-
                     Ok(())
                 })
             }),
         )
         .await;
     background_task_builder
-        .add_task(
+        .add_interval_task(
             "delete_expired_api_keys",
+            std::time::Duration::from_mins(30),
+            std::time::Duration::from_secs(30),
             Box::new(|state| {
                 Box::pin(async move {
                     let deleted_api_keys =
@@ -420,14 +435,375 @@ async fn main() {
                         tracing::info!("deleted {} expired user api keys", deleted_api_keys);
                     }
 
-                    tokio::time::sleep(std::time::Duration::from_mins(30)).await;
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_interval_task(
+            "delete_expired_magic_links",
+            std::time::Duration::from_mins(15),
+            std::time::Duration::from_secs(30),
+            Box::new(|state| {
+                Box::pin(async move {
+                    let deleted_magic_links =
+                        shared::models::user_magic_link::UserMagicLink::delete_expired(
+                            &state.database,
+                        )
+                        .await?;
+
+                    if deleted_magic_links > 0 {
+                        tracing::info!("deleted {} expired user magic links", deleted_magic_links);
+                    }
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_interval_task(
+            "check_node_health",
+            std::time::Duration::from_secs(60),
+            std::time::Duration::ZERO,
+            Box::new(|state| {
+                Box::pin(async move {
+                    let nodes = shared::models::node::Node::all(&state.database).await?;
+
+                    futures_util::stream::iter(nodes)
+                        .for_each_concurrent(8, |node| {
+                            let state = state.clone();
+
+                            async move {
+                                let (status, error) = match node.check_health(&state.database).await
+                                {
+                                    Ok(result) => result,
+                                    Err(err) => {
+                                        tracing::error!(
+                                            node = %node.uuid,
+                                            "failed to check node health: {:#?}",
+                                            err
+                                        );
+                                        return;
+                                    }
+                                };
+
+                                if status == node.health_status
+                                    || (node.health_status
+                                        == shared::models::node::NodeHealthStatus::Unknown
+                                        && status == shared::models::node::NodeHealthStatus::Online)
+                                {
+                                    return;
+                                }
+
+                                node.report_health_change(&state, status, error.as_deref())
+                                    .await;
+                            }
+                        })
+                        .await;
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_interval_task(
+            "delete_expired_backups",
+            std::time::Duration::from_mins(15),
+            std::time::Duration::from_secs(30),
+            Box::new(|state| {
+                Box::pin(async move {
+                    let deleted_backups =
+                        shared::models::server_backup::ServerBackup::delete_expired(
+                            &state.database,
+                        )
+                        .await?;
+
+                    if deleted_backups > 0 {
+                        tracing::info!("deleted {} expired server backups", deleted_backups);
+                    }
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_task(
+            "deliver_webhooks",
+            Box::new(|state| {
+                Box::pin(async move {
+                    let deliveries = shared::models::webhook_delivery::WebhookDelivery::claim_due(
+                        &state.database,
+                        50,
+                    )
+                    .await?;
+
+                    futures_util::stream::iter(deliveries)
+                        .for_each_concurrent(8, |delivery| {
+                            let state = state.clone();
+
+                            async move {
+                                if let Err(err) =
+                                    delivery.deliver(&state.database, &state.client).await
+                                {
+                                    tracing::error!(
+                                        delivery = %delivery.uuid,
+                                        "failed to deliver webhook: {:#?}",
+                                        err
+                                    );
+                                }
+                            }
+                        })
+                        .await;
+
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_interval_task(
+            "delete_old_webhook_deliveries",
+            std::time::Duration::from_mins(30),
+            std::time::Duration::from_secs(30),
+            Box::new(|state| {
+                Box::pin(async move {
+                    let deleted_deliveries =
+                        shared::models::webhook_delivery::WebhookDelivery::delete_old(
+                            &state.database,
+                        )
+                        .await?;
+
+                    if deleted_deliveries > 0 {
+                        tracing::info!("deleted {} old webhook deliveries", deleted_deliveries);
+                    }
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_task(
+            "deliver_user_notifications",
+            Box::new(|state| {
+                Box::pin(async move {
+                    let notifications =
+                        shared::models::user_notification::UserNotification::all_due(
+                            &state.database,
+                            50,
+                        )
+                        .await?;
+
+                    futures_util::stream::iter(notifications)
+                        .for_each_concurrent(8, |notification| {
+                            let state = state.clone();
+
+                            async move {
+                                if let Err(err) = notification.deliver(&state).await {
+                                    tracing::error!(
+                                        notification = %notification.uuid,
+                                        "failed to deliver user notification: {:#?}",
+                                        err
+                                    );
+                                }
+                            }
+                        })
+                        .await;
+
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_interval_task(
+            "delete_old_user_notifications",
+            std::time::Duration::from_mins(30),
+            std::time::Duration::from_secs(30),
+            Box::new(|state| {
+                Box::pin(async move {
+                    let deleted_notifications =
+                        shared::models::user_notification::UserNotification::delete_old(
+                            &state.database,
+                        )
+                        .await?;
+
+                    if deleted_notifications > 0 {
+                        tracing::info!("deleted {} old user notifications", deleted_notifications);
+                    }
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_task(
+            "process_node_evacuations",
+            Box::new(|state| {
+                Box::pin(async move {
+                    let evacuations = shared::models::node_evacuation::NodeEvacuation::all_active(
+                        &state.database,
+                    )
+                    .await?;
+
+                    for evacuation in evacuations {
+                        if let Err(err) = evacuation.process(&state).await {
+                            tracing::error!(
+                                evacuation = %evacuation.uuid,
+                                "failed to process node evacuation: {:#?}",
+                                err
+                            );
+                        }
+                    }
+
+                    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_task(
+            "process_server_clones",
+            Box::new(|state| {
+                Box::pin(async move {
+                    let clones =
+                        shared::models::server_clone::ServerClone::all_active(&state.database)
+                            .await?;
+
+                    for clone in clones {
+                        if let Err(err) = clone.process(&state.database).await {
+                            tracing::error!(
+                                clone = %clone.uuid,
+                                "failed to process server clone: {:#?}",
+                                err
+                            );
+                        }
+                    }
+
+                    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
 
                     Ok(())
                 })
             }),
         )
         .await;
+    background_task_builder
+        .add_interval_task(
+            "process_server_suspensions",
+            std::time::Duration::from_mins(1),
+            std::time::Duration::from_secs(5),
+            Box::new(|state| {
+                Box::pin(async move {
+                    let changed_servers =
+                        shared::models::server_suspension::ServerSuspension::reconcile(
+                            &state.database,
+                            None,
+                        )
+                        .await?;
 
+                    if !changed_servers.is_empty() {
+                        tracing::info!(
+                            "updated suspension state of {} servers",
+                            changed_servers.len()
+                        );
+
+                        shared::models::server_suspension::ServerSuspension::sync_servers(
+                            &state.database,
+                            changed_servers,
+                        )
+                        .await;
+                    }
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+
+    background_task_builder
+        .add_task(
+            "process_jobs",
+            Box::new(|state| {
+                Box::pin(async move {
+                    state.jobs.process(&state).await?;
+
+                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_interval_task(
+            "delete_finished_jobs",
+            std::time::Duration::from_hours(1),
+            std::time::Duration::from_secs(30),
+            Box::new(|state| {
+                Box::pin(async move {
+                    let deleted_jobs =
+                        shared::models::job::Job::delete_finished(&state.database).await?;
+
+                    if deleted_jobs > 0 {
+                        tracing::info!("deleted {} finished jobs", deleted_jobs);
+                    }
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+    background_task_builder
+        .add_interval_task(
+            "schedule_egg_repository_syncs",
+            std::time::Duration::from_mins(1),
+            std::time::Duration::ZERO,
+            Box::new(|state| {
+                Box::pin(async move {
+                    let egg_repositories =
+                        shared::models::egg_repository::EggRepository::all_with_sync_schedule(
+                            &state.database,
+                        )
+                        .await?;
+
+                    for egg_repository in egg_repositories {
+                        let Some(next_sync) = egg_repository
+                            .sync_schedule
+                            .as_ref()
+                            .and_then(|schedule| schedule.upcoming(chrono::Utc).next())
+                        else {
+                            continue;
+                        };
+
+                        let job = shared::models::egg_repository::EggRepositorySyncJob {
+                            egg_repository_uuid: egg_repository.uuid,
+                        };
+                        if job.is_queued(&state.database).await? {
+                            continue;
+                        }
+
+                        job.enqueue_at(&state.database, Some(next_sync.naive_utc()))
+                            .await?;
+                    }
+
+                    Ok(())
+                })
+            }),
+        )
+        .await;
+
+    jobs.merge_builder(jobs_builder).await;
     background_tasks
         .merge_builder(background_task_builder)
         .await;
@@ -486,6 +862,36 @@ async fn main() {
                 },
             ),
         )
+        .route(
+            "/metrics",
+            get(
+                |state: GetState, headers: axum::http::HeaderMap| async move {
+                    let settings = state.settings.get().await;
+                    if !settings.metrics.enabled {
+                        return ApiResponse::error("route not found")
+                            .with_status(StatusCode::NOT_FOUND)
+                            .ok();
+                    }
+
+                    let token = headers
+                        .get("Authorization")
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.strip_prefix("Bearer "))
+                        .unwrap_or_default();
+                    if !settings.metrics.verify_token(token) {
+                        return ApiResponse::error("invalid authorization token")
+                            .with_status(StatusCode::UNAUTHORIZED)
+                            .ok();
+                    }
+
+                    drop(settings);
+
+                    ApiResponse::new(Body::from(state.metrics.render(&state).await?))
+                        .with_header("Content-Type", "text/plain; version=0.0.4")
+                        .ok()
+                },
+            ),
+        )
         .fallback(|state: GetState, req: Request<Body>| async move {
             if !req.uri().path().starts_with("/api") {
                 let path = &req.uri().path()[1..];
@@ -587,6 +993,10 @@ async fn main() {
         .layer(axum::middleware::from_fn(handle_request))
         .layer(CookieManagerLayer::new())
         .route_layer(axum::middleware::from_fn(handle_postprocessing))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            handle_metrics,
+        ))
         .route_layer(SentryHttpLayer::new().enable_transaction())
         .with_state(state.clone());
 
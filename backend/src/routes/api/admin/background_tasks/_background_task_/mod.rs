@@ -0,0 +1,61 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod run;
+
+mod get {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        extensions::background_tasks::AdminApiBackgroundTask,
+        models::user::GetPermissionManager,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        background_task: AdminApiBackgroundTask,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "background_task" = String,
+            description = "The background task name",
+            example = "delete_expired_sessions",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        Path(background_task): Path<String>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("background-tasks.read")?;
+
+        let background_tasks = state.background_tasks.get_tasks().await;
+        let background_task = match background_tasks.get(background_task.as_str()) {
+            Some(background_task) => background_task,
+            None => {
+                return ApiResponse::error("background task not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        ApiResponse::json(Response {
+            background_task: background_task.to_admin_api_object(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/run", run::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,77 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{admin_activity::GetAdminActivityLogger, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = ACCEPTED, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "background_task" = String,
+            description = "The background task name",
+            example = "delete_expired_sessions",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        Path(background_task): Path<String>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("background-tasks.run")?;
+
+        let background_tasks = state.background_tasks.get_tasks().await;
+        let background_task = match background_tasks.get(background_task.as_str()) {
+            Some(background_task) => background_task,
+            None => {
+                return ApiResponse::error("background task not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        if background_task.running {
+            return ApiResponse::error("background task is already running")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        if !background_task.trigger() {
+            return ApiResponse::error("only scheduled background tasks can be run manually")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        activity_logger
+            .log(
+                "background-task:run",
+                serde_json::json!({
+                    "name": background_task.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {})
+            .with_status(StatusCode::ACCEPTED)
+            .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
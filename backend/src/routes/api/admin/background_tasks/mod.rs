@@ -0,0 +1,45 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _background_task_;
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        GetState,
+        extensions::background_tasks::AdminApiBackgroundTask,
+        models::user::GetPermissionManager,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        background_tasks: Vec<AdminApiBackgroundTask>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ))]
+    pub async fn route(state: GetState, permissions: GetPermissionManager) -> ApiResponseResult {
+        permissions.has_admin_permission("background-tasks.read")?;
+
+        let mut background_tasks = state
+            .background_tasks
+            .get_tasks()
+            .await
+            .values()
+            .map(|task| task.to_admin_api_object())
+            .collect::<Vec<_>>();
+        background_tasks.sort_by_key(|task| task.name);
+
+        ApiResponse::json(Response { background_tasks }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/{background_task}", _background_task_::router(state))
+        .with_state(state.clone())
+}
@@ -175,6 +175,8 @@ mod patch {
 
         backup_disk: Option<shared::models::server_backup::BackupDisk>,
         backup_configs: Option<shared::models::backup_configurations::BackupConfigs>,
+        #[validate(nested)]
+        retention_policy: Option<shared::models::backup_configurations::BackupRetentionPolicy>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -227,17 +229,23 @@ mod patch {
                 .encrypt(&state.database)
                 .await?;
         }
+        if let Some(retention_policy) = data.retention_policy {
+            backup_configuration.retention_policy = retention_policy;
+        }
 
-        match sqlx::query!(
-            "UPDATE backup_configurations
-            SET name = $2, description = $3, backup_disk = $4, backup_configs = $5
-            WHERE backup_configurations.uuid = $1",
-            backup_configuration.uuid,
-            &backup_configuration.name,
-            backup_configuration.description.as_deref(),
-            backup_configuration.backup_disk as shared::models::server_backup::BackupDisk,
-            serde_json::to_value(&backup_configuration.backup_configs)?,
+        match sqlx::query(
+            r#"
+            UPDATE backup_configurations
+            SET name = $2, description = $3, backup_disk = $4, backup_configs = $5, retention_policy = $6
+            WHERE backup_configurations.uuid = $1
+            "#,
         )
+        .bind(backup_configuration.uuid)
+        .bind(&backup_configuration.name)
+        .bind(backup_configuration.description.as_deref())
+        .bind(backup_configuration.backup_disk)
+        .bind(serde_json::to_value(&backup_configuration.backup_configs)?)
+        .bind(serde_json::to_value(backup_configuration.retention_policy)?)
         .execute(state.database.write())
         .await
         {
@@ -263,6 +271,7 @@ mod patch {
                     "uuid": backup_configuration.uuid,
                     "name": backup_configuration.name,
                     "description": backup_configuration.description,
+                    "retention_policy": backup_configuration.retention_policy,
                 }),
             )
             .await;
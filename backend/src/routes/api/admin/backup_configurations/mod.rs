@@ -99,6 +99,9 @@ mod post {
         backup_disk: shared::models::server_backup::BackupDisk,
         #[serde(default)]
         backup_configs: shared::models::backup_configurations::BackupConfigs,
+        #[validate(nested)]
+        #[serde(default)]
+        retention_policy: shared::models::backup_configurations::BackupRetentionPolicy,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -131,6 +134,7 @@ mod post {
             data.description.as_deref(),
             data.backup_disk,
             data.backup_configs,
+            data.retention_policy,
         )
         .await
         {
@@ -156,6 +160,7 @@ mod post {
                     "uuid": backup_configuration.uuid,
                     "name": backup_configuration.name,
                     "description": backup_configuration.description,
+                    "retention_policy": backup_configuration.retention_policy,
                 }),
             )
             .await;
@@ -23,6 +23,7 @@ mod post {
     #[derive(ToSchema, Serialize)]
     struct Response {
         egg: shared::models::nest_egg::AdminApiNestEgg,
+        compatibility: shared::models::nest_egg::NestEggCompatibilityReport,
     }
 
     #[utoipa::path(post, path = "/", responses(
@@ -74,6 +75,7 @@ mod post {
             }
         };
 
+        let compatibility = egg_repository_egg.exported_egg.compatibility_report();
         let egg = match NestEgg::import(
             &state.database,
             nest.uuid,
@@ -128,6 +130,7 @@ mod post {
 
         ApiResponse::json(Response {
             egg: egg.into_admin_api_object(&state.database).await?,
+            compatibility,
         })
         .ok()
     }
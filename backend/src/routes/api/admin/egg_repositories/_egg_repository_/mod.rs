@@ -148,10 +148,14 @@ mod patch {
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
-        models::{admin_activity::GetAdminActivityLogger, user::GetPermissionManager},
+        models::{
+            admin_activity::GetAdminActivityLogger, egg_repository::EggRepositoryCredentials,
+            user::GetPermissionManager,
+        },
         prelude::SqlxErrorExtension,
         response::{ApiResponse, ApiResponseResult},
     };
+    use std::str::FromStr;
     use utoipa::ToSchema;
     use validator::Validate;
 
@@ -167,6 +171,23 @@ mod patch {
         #[validate(url)]
         #[schema(example = "https://github.com/example/repo.git", format = "uri")]
         git_repository: Option<compact_str::CompactString>,
+        #[validate(length(max = 255))]
+        #[schema(max_length = 255, example = "main")]
+        git_reference: Option<compact_str::CompactString>,
+        #[validate(length(max = 255))]
+        #[schema(max_length = 255, example = "eggs")]
+        git_subdirectory: Option<compact_str::CompactString>,
+
+        /// `null` removes the credentials, leaving it out keeps the current ones.
+        #[serde(
+            default,
+            deserialize_with = "shared::deserialize::deserialize_explicit_option"
+        )]
+        #[schema(value_type = Option<EggRepositoryCredentials>)]
+        credentials: Option<Option<EggRepositoryCredentials>>,
+        #[validate(length(max = 255))]
+        #[schema(max_length = 255, example = "0 0 4 * * *")]
+        sync_schedule: Option<compact_str::CompactString>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -212,16 +233,74 @@ mod patch {
         if let Some(git_repository) = data.git_repository {
             egg_repository.git_repository = git_repository;
         }
+        if let Some(git_reference) = data.git_reference {
+            if git_reference.is_empty() {
+                egg_repository.git_reference = None;
+            } else {
+                egg_repository.git_reference = Some(git_reference);
+            }
+        }
+        if let Some(git_subdirectory) = data.git_subdirectory {
+            if git_subdirectory.is_empty() {
+                egg_repository.git_subdirectory = None;
+            } else {
+                egg_repository.git_subdirectory = Some(git_subdirectory);
+            }
+        }
+        if let Some(credentials) = &data.credentials {
+            match credentials {
+                Some(credentials) => {
+                    egg_repository.credentials_type = Some(credentials.credentials_type());
+                    egg_repository.credentials = Some(
+                        state
+                            .database
+                            .encrypt(serde_json::to_string(credentials)?)
+                            .await?,
+                    );
+                }
+                None => {
+                    egg_repository.credentials_type = None;
+                    egg_repository.credentials = None;
+                }
+            }
+        }
+        if let Some(sync_schedule) = data.sync_schedule {
+            if sync_schedule.is_empty() {
+                egg_repository.sync_schedule = None;
+            } else {
+                match cron::Schedule::from_str(&sync_schedule) {
+                    Ok(sync_schedule) => egg_repository.sync_schedule = Some(sync_schedule),
+                    Err(_) => {
+                        return ApiResponse::error("invalid sync schedule cron expression")
+                            .with_status(StatusCode::BAD_REQUEST)
+                            .ok();
+                    }
+                }
+            }
+        }
 
-        match sqlx::query!(
-            "UPDATE egg_repositories
-            SET name = $1, description = $2, git_repository = $3
-            WHERE egg_repositories.uuid = $4",
-            &egg_repository.name,
-            egg_repository.description.as_deref(),
-            &egg_repository.git_repository,
-            egg_repository.uuid,
+        match sqlx::query(
+            r#"
+            UPDATE egg_repositories
+            SET name = $1, description = $2, git_repository = $3, git_reference = $4,
+                git_subdirectory = $5, credentials_type = $6, credentials = $7, sync_schedule = $8
+            WHERE egg_repositories.uuid = $9
+            "#,
+        )
+        .bind(&egg_repository.name)
+        .bind(egg_repository.description.as_deref())
+        .bind(&egg_repository.git_repository)
+        .bind(egg_repository.git_reference.as_deref())
+        .bind(egg_repository.git_subdirectory.as_deref())
+        .bind(egg_repository.credentials_type)
+        .bind(egg_repository.credentials.as_deref())
+        .bind(
+            egg_repository
+                .sync_schedule
+                .as_ref()
+                .map(|schedule| schedule.to_string()),
         )
+        .bind(egg_repository.uuid)
         .execute(state.database.write())
         .await
         {
@@ -250,6 +329,10 @@ mod patch {
                     "name": egg_repository.name,
                     "description": egg_repository.description,
                     "git_repository": egg_repository.git_repository,
+                    "git_reference": egg_repository.git_reference,
+                    "git_subdirectory": egg_repository.git_subdirectory,
+                    "credentials_type": egg_repository.credentials_type,
+                    "sync_schedule": egg_repository.sync_schedule.as_ref().map(|schedule| schedule.to_string()),
                 }),
             )
             .await;
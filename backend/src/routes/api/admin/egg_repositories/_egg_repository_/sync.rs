@@ -14,6 +14,7 @@ mod post {
     #[derive(ToSchema, Serialize)]
     struct Response {
         found: usize,
+        report: shared::models::egg_repository::EggRepositorySyncReport,
     }
 
     #[utoipa::path(post, path = "/", responses(
@@ -33,9 +34,13 @@ mod post {
     ) -> ApiResponseResult {
         permissions.has_admin_permission("egg-repositories.sync")?;
 
-        let found = egg_repository.sync(&state.database).await?;
+        let report = egg_repository.sync(&state.database).await?;
 
-        ApiResponse::json(Response { found }).ok()
+        ApiResponse::json(Response {
+            found: report.found,
+            report,
+        })
+        .ok()
     }
 }
 
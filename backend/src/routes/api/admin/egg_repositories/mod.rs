@@ -2,6 +2,7 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod _egg_repository_;
+mod updates;
 
 mod get {
     use axum::{extract::Query, http::StatusCode};
@@ -82,12 +83,15 @@ mod post {
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
+        extensions::jobs::JobType,
         models::{
-            admin_activity::GetAdminActivityLogger, egg_repository::EggRepository,
+            admin_activity::GetAdminActivityLogger,
+            egg_repository::{EggRepository, EggRepositoryCredentials, EggRepositorySyncJob},
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
     };
+    use std::str::FromStr;
     use utoipa::ToSchema;
     use validator::Validate;
 
@@ -103,6 +107,17 @@ mod post {
         #[validate(url)]
         #[schema(example = "https://github.com/example/repo.git", format = "uri")]
         git_repository: compact_str::CompactString,
+        #[validate(length(min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255, example = "main")]
+        git_reference: Option<compact_str::CompactString>,
+        #[validate(length(min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255, example = "eggs")]
+        git_subdirectory: Option<compact_str::CompactString>,
+
+        credentials: Option<EggRepositoryCredentials>,
+        #[validate(length(min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255, example = "0 0 4 * * *")]
+        sync_schedule: Option<compact_str::CompactString>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -129,11 +144,29 @@ mod post {
 
         permissions.has_admin_permission("egg-repositories.create")?;
 
+        let sync_schedule = match data
+            .sync_schedule
+            .as_deref()
+            .map(cron::Schedule::from_str)
+            .transpose()
+        {
+            Ok(sync_schedule) => sync_schedule,
+            Err(_) => {
+                return ApiResponse::error("invalid sync schedule cron expression")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
         let egg_repository = match EggRepository::create(
             &state.database,
             &data.name,
             data.description.as_deref(),
             &data.git_repository,
+            data.git_reference.as_deref(),
+            data.git_subdirectory.as_deref(),
+            data.credentials.as_ref(),
+            sync_schedule.as_ref(),
         )
         .await
         {
@@ -154,6 +187,15 @@ mod post {
             }
         };
 
+        if let Err(err) = EggRepositorySyncJob {
+            egg_repository_uuid: egg_repository.uuid,
+        }
+        .enqueue(&state.database)
+        .await
+        {
+            tracing::warn!(egg_repository = %egg_repository.uuid, "failed to queue egg repository sync: {:?}", err);
+        }
+
         activity_logger
             .log(
                 "egg-repository:create",
@@ -162,6 +204,10 @@ mod post {
                     "name": egg_repository.name,
                     "description": egg_repository.description,
                     "git_repository": egg_repository.git_repository,
+                    "git_reference": egg_repository.git_reference,
+                    "git_subdirectory": egg_repository.git_subdirectory,
+                    "credentials_type": egg_repository.credentials_type,
+                    "sync_schedule": data.sync_schedule,
                 }),
             )
             .await;
@@ -177,6 +223,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(get::route))
         .routes(routes!(post::route))
+        .nest("/updates", updates::router(state))
         .nest("/{egg_repository}", _egg_repository_::router(state))
         .with_state(state.clone())
 }
@@ -0,0 +1,52 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        GetState,
+        models::{nest_egg::NestEgg, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct ResponseEgg {
+        nest_uuid: uuid::Uuid,
+        egg: shared::models::nest_egg::AdminApiNestEgg,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        eggs: Vec<ResponseEgg>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ))]
+    pub async fn route(state: GetState, permissions: GetPermissionManager) -> ApiResponseResult {
+        permissions.has_admin_permission("eggs.read")?;
+
+        let eggs = NestEgg::all_with_repository_updates(&state.database, None).await?;
+
+        let mut response_eggs = Vec::with_capacity(eggs.len());
+        for egg in eggs {
+            response_eggs.push(ResponseEgg {
+                nest_uuid: egg.nest.uuid,
+                egg: egg.into_admin_api_object(&state.database).await?,
+            });
+        }
+
+        ApiResponse::json(Response {
+            eggs: response_eggs,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,61 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::admin::jobs::_job_::GetJob;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{admin_activity::GetAdminActivityLogger, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "job" = uuid::Uuid,
+            description = "The job ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        job: GetJob,
+        activity_logger: GetAdminActivityLogger,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("jobs.retry")?;
+
+        if !job.retry(&state.database).await? {
+            return ApiResponse::error("job is not failed or cancelled")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        activity_logger
+            .log(
+                "job:retry",
+                serde_json::json!({
+                    "uuid": job.uuid,
+                    "name": job.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
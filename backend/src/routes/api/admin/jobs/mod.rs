@@ -0,0 +1,110 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _job_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination,
+            job::{Job, JobStatus},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Params {
+        #[validate(range(min = 1))]
+        #[serde(default = "Pagination::default_page")]
+        pub page: i64,
+        #[validate(range(min = 1, max = 100))]
+        #[serde(default = "Pagination::default_per_page")]
+        pub per_page: i64,
+        #[validate(length(min = 1, max = 128))]
+        #[serde(
+            default,
+            deserialize_with = "shared::deserialize::deserialize_string_option"
+        )]
+        pub search: Option<compact_str::CompactString>,
+
+        status: Option<JobStatus>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        jobs: Pagination<shared::models::job::AdminApiJob>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for job names",
+        ),
+        (
+            "status" = Option<JobStatus>, Query,
+            description = "Only show jobs with this status",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("jobs.read")?;
+
+        let jobs = Job::all_with_pagination(
+            &state.database,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+            params.status,
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            jobs: Pagination {
+                total: jobs.total,
+                per_page: jobs.per_page,
+                page: jobs.page,
+                data: jobs
+                    .data
+                    .into_iter()
+                    .map(|job| job.into_admin_api_object())
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/{job}", _job_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,77 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod delete {
+    use crate::routes::api::admin::locations::_location_::GetLocation;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel, admin_activity::GetAdminActivityLogger,
+            location_nest_egg::LocationNestEgg, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "location" = uuid::Uuid,
+            description = "The location ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "egg" = uuid::Uuid,
+            description = "The egg ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        location: GetLocation,
+        activity_logger: GetAdminActivityLogger,
+        Path((_location, egg)): Path<(uuid::Uuid, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("locations.eggs")?;
+
+        let location_egg =
+            match LocationNestEgg::by_location_uuid_egg_uuid(&state.database, location.uuid, egg)
+                .await?
+            {
+                Some(location_egg) => location_egg,
+                None => {
+                    return ApiResponse::error("egg not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        location_egg.delete(&state.database, ()).await?;
+
+        activity_logger
+            .log(
+                "location:egg.delete",
+                serde_json::json!({
+                    "location_uuid": location.uuid,
+                    "egg_uuid": location_egg.egg.uuid,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,172 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _egg_;
+
+mod get {
+    use crate::routes::api::admin::locations::_location_::GetLocation;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParamsWithSearch, location_nest_egg::LocationNestEgg,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        eggs: Pagination<shared::models::location_nest_egg::AdminApiLocationNestEgg>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "location" = uuid::Uuid,
+            description = "The location ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        location: GetLocation,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("locations.eggs")?;
+
+        let eggs = LocationNestEgg::by_location_uuid_with_pagination(
+            &state.database,
+            location.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            eggs: Pagination {
+                total: eggs.total,
+                per_page: eggs.per_page,
+                page: eggs.page,
+                data: eggs
+                    .data
+                    .into_iter()
+                    .map(|egg| egg.into_admin_api_object())
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use crate::routes::api::admin::locations::_location_::GetLocation;
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger, location_nest_egg::LocationNestEgg,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Deserialize)]
+    pub struct Payload {
+        egg_uuid: uuid::Uuid,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "location" = uuid::Uuid,
+            description = "The location ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        location: GetLocation,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("locations.eggs")?;
+
+        match LocationNestEgg::create(&state.database, location.uuid, data.egg_uuid).await {
+            Ok(_) => {}
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("egg already exists in this location")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) if err.is_foreign_key_violation() => {
+                return ApiResponse::error("egg not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+            Err(err) => {
+                tracing::error!("failed to create location egg: {:?}", err);
+
+                return ApiResponse::error("failed to create location egg")
+                    .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .ok();
+            }
+        }
+
+        activity_logger
+            .log(
+                "location:egg.create",
+                serde_json::json!({
+                    "location_uuid": location.uuid,
+                    "egg_uuid": data.egg_uuid,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{egg}", _egg_::router(state))
+        .with_state(state.clone())
+}
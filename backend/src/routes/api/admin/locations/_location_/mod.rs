@@ -13,6 +13,7 @@ use shared::{
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod database_hosts;
+mod eggs;
 mod nodes;
 
 pub type GetLocation = shared::extract::ConsumingExtension<Location>;
@@ -293,6 +294,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(patch::route))
         .nest("/nodes", nodes::router(state))
         .nest("/database-hosts", database_hosts::router(state))
+        .nest("/eggs", eggs::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
 }
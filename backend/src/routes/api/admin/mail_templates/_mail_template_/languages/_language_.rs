@@ -0,0 +1,191 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod put {
+    use crate::routes::api::admin::mail_templates::_mail_template_::get_mail_template;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger, mail_template::MailTemplate,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(length(min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        subject: compact_str::CompactString,
+        #[validate(length(min = 1, max = 65535))]
+        #[schema(min_length = 1, max_length = 65535)]
+        body: String,
+        #[validate(length(min = 1, max = 65535))]
+        #[schema(min_length = 1, max_length = 65535)]
+        body_plain: Option<String>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        mail_template: shared::models::mail_template::AdminApiMailTemplate,
+    }
+
+    #[utoipa::path(put, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "mail_template" = String,
+            description = "The mail template name",
+            example = "password_reset",
+        ),
+        (
+            "language" = String,
+            description = "The language of the override",
+            example = "en-US",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        Path((mail_template, language)): Path<(String, compact_str::CompactString)>,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("mail-templates.update")?;
+
+        let (name, _) = get_mail_template(&mail_template)?;
+
+        if shared::validate_language(&language).is_err() {
+            return ApiResponse::error("invalid language")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        for (template_name, source) in [
+            ("subject.txt", Some(data.subject.as_str())),
+            ("body.html", Some(data.body.as_str())),
+            ("body.txt", data.body_plain.as_deref()),
+        ] {
+            if let Some(source) = source
+                && let Err(err) = shared::mail::validate_template(template_name, source)
+            {
+                return ApiResponse::error(&format!("invalid template syntax: {err}"))
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        }
+
+        let mail_template = MailTemplate::upsert(
+            &state.database,
+            name,
+            &language,
+            &data.subject,
+            &data.body,
+            data.body_plain.as_deref(),
+        )
+        .await?;
+
+        activity_logger
+            .log(
+                "mail-template:update",
+                serde_json::json!({
+                    "name": mail_template.name,
+                    "language": mail_template.language,
+                    "subject": mail_template.subject,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            mail_template: mail_template.into_admin_api_object(),
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::admin::mail_templates::_mail_template_::get_mail_template;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel, admin_activity::GetAdminActivityLogger,
+            mail_template::MailTemplate, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "mail_template" = String,
+            description = "The mail template name",
+            example = "password_reset",
+        ),
+        (
+            "language" = String,
+            description = "The language of the override",
+            example = "en-US",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        Path((mail_template, language)): Path<(String, String)>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("mail-templates.update")?;
+
+        let (name, _) = get_mail_template(&mail_template)?;
+
+        let mail_template =
+            match MailTemplate::by_name_language(&state.database, name, &language).await? {
+                Some(mail_template) => mail_template,
+                None => {
+                    return ApiResponse::error("mail template override not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        mail_template.delete(&state.database, ()).await?;
+
+        activity_logger
+            .log(
+                "mail-template:delete",
+                serde_json::json!({
+                    "name": mail_template.name,
+                    "language": mail_template.language,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(put::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
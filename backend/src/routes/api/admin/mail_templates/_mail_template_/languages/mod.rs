@@ -0,0 +1,10 @@
+use super::State;
+use utoipa_axum::router::OpenApiRouter;
+
+mod _language_;
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .nest("/{language}", _language_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,70 @@
+use super::State;
+use axum::http::StatusCode;
+use shared::{mail::BundledMailTemplate, response::ApiResponse};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod languages;
+mod test;
+
+pub fn get_mail_template(name: &str) -> Result<(&'static str, BundledMailTemplate), ApiResponse> {
+    match shared::mail::get_mail_templates().get_key_value(name) {
+        Some((name, template)) => Ok((*name, *template)),
+        None => Err(ApiResponse::error("mail template not found").with_status(StatusCode::NOT_FOUND)),
+    }
+}
+
+mod get {
+    use axum::extract::Path;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{mail_template::MailTemplate, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        mail_template: shared::mail::AdminApiBundledMailTemplate,
+        overrides: Vec<shared::models::mail_template::AdminApiMailTemplate>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "mail_template" = String,
+            description = "The mail template name",
+            example = "password_reset",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        Path(mail_template): Path<String>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("mail-templates.read")?;
+
+        let (name, template) = super::get_mail_template(&mail_template)?;
+
+        let overrides = MailTemplate::all_by_name(&state.database, name).await?;
+
+        ApiResponse::json(Response {
+            mail_template: template.into_admin_api_object(name),
+            overrides: overrides
+                .into_iter()
+                .map(|template_override| template_override.into_admin_api_object())
+                .collect(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/test", test::router(state))
+        .nest("/languages", languages::router(state))
+        .with_state(state.clone())
+}
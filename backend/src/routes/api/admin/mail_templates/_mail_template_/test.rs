@@ -0,0 +1,107 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::admin::mail_templates::_mail_template_::get_mail_template;
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(email)]
+        #[schema(format = "email")]
+        email: Option<String>,
+        #[validate(
+            length(min = 5, max = 15),
+            custom(function = "shared::validate_language")
+        )]
+        #[schema(min_length = 5, max_length = 15)]
+        language: Option<compact_str::CompactString>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "mail_template" = String,
+            description = "The mail template name",
+            example = "password_reset",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetAdminActivityLogger,
+        Path(mail_template): Path<String>,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("mail-templates.test")?;
+
+        let (name, template) = get_mail_template(&mail_template)?;
+
+        let mut recipient = user.0.clone();
+        if let Some(language) = data.language {
+            recipient.language = language;
+        }
+        let destination = data
+            .email
+            .map(compact_str::CompactString::from)
+            .unwrap_or_else(|| recipient.email.clone());
+
+        let mail = match state
+            .mail
+            .render(name, Some(&recipient), template.example_context())
+            .await
+        {
+            Ok(mail) => mail,
+            Err(err) => {
+                return ApiResponse::error(&format!("failed to render mail template: {err}"))
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
+        state.mail.send(destination.clone(), mail).await;
+
+        activity_logger
+            .log(
+                "mail-template:test",
+                serde_json::json!({
+                    "name": name,
+                    "language": recipient.language,
+                    "email": destination,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,59 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _mail_template_;
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        GetState,
+        models::{mail_template::MailTemplate, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct ResponseMailTemplate {
+        #[serde(flatten)]
+        template: shared::mail::AdminApiBundledMailTemplate,
+        languages: Vec<compact_str::CompactString>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        mail_templates: Vec<ResponseMailTemplate>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ))]
+    pub async fn route(state: GetState, permissions: GetPermissionManager) -> ApiResponseResult {
+        permissions.has_admin_permission("mail-templates.read")?;
+
+        let overrides = MailTemplate::all(&state.database).await?;
+        let templates = shared::mail::get_mail_templates().clone();
+
+        ApiResponse::json(Response {
+            mail_templates: templates
+                .into_iter()
+                .map(|(name, template)| ResponseMailTemplate {
+                    template: template.into_admin_api_object(name),
+                    languages: overrides
+                        .iter()
+                        .filter(|template_override| template_override.name == name)
+                        .map(|template_override| template_override.language.clone())
+                        .collect(),
+                })
+                .collect(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/{mail_template}", _mail_template_::router(state))
+        .with_state(state.clone())
+}
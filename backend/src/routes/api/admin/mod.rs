@@ -17,21 +17,26 @@ use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
 
 mod activity;
+mod background_tasks;
 mod backup_configurations;
 mod database_hosts;
 mod egg_repositories;
 mod extensions;
+mod jobs;
 mod locations;
+mod mail_templates;
 mod mounts;
 mod nests;
 mod nodes;
 mod oauth_providers;
 mod roles;
+mod server_templates;
 mod servers;
 mod settings;
 mod stats;
 mod telemetry;
 mod users;
+mod webhooks;
 
 pub async fn auth(
     state: GetState,
@@ -74,6 +79,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/settings", settings::router(state))
         .nest("/locations", locations::router(state))
         .nest("/servers", servers::router(state))
+        .nest("/server-templates", server_templates::router(state))
         .nest("/nodes", nodes::router(state))
         .nest("/nests", nests::router(state))
         .nest("/egg-repositories", egg_repositories::router(state))
@@ -86,7 +92,11 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/mounts", mounts::router(state))
         .nest("/users", users::router(state))
         .nest("/roles", roles::router(state))
+        .nest("/webhooks", webhooks::router(state))
+        .nest("/mail-templates", mail_templates::router(state))
         .nest("/extensions", extensions::router(state))
+        .nest("/background-tasks", background_tasks::router(state))
+        .nest("/jobs", jobs::router(state))
         .nest("/activity", activity::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .route_layer(axum::middleware::from_fn_with_state(
@@ -224,6 +224,8 @@ mod patch {
         #[validate(custom(function = "shared::models::nest_egg::validate_docker_images"))]
         docker_images: Option<IndexMap<compact_str::CompactString, compact_str::CompactString>>,
         file_denylist: Option<Vec<compact_str::CompactString>>,
+        user_selectable: Option<bool>,
+        update_policy: Option<shared::models::nest_egg::NestEggUpdatePolicy>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -262,7 +264,12 @@ mod patch {
 
         permissions.has_admin_permission("eggs.update")?;
 
-        if let Some(egg_repository_egg_uuid) = data.egg_repository_egg_uuid {
+        if let Some(egg_repository_egg_uuid) = data.egg_repository_egg_uuid
+            && egg.egg_repository_egg.as_ref().map(|e| e.uuid) != Some(egg_repository_egg_uuid)
+        {
+            // a newly linked repository egg has never been applied to this egg
+            egg.egg_repository_egg_applied = None;
+
             if egg_repository_egg_uuid.is_nil() {
                 egg.egg_repository_egg = None;
             } else {
@@ -336,33 +343,43 @@ mod patch {
         if let Some(file_denylist) = data.file_denylist {
             egg.file_denylist = file_denylist;
         }
+        if let Some(user_selectable) = data.user_selectable {
+            egg.user_selectable = user_selectable;
+        }
+        if let Some(update_policy) = data.update_policy {
+            egg.update_policy = update_policy;
+        }
 
-        match sqlx::query!(
+        match sqlx::query(
             "UPDATE nest_eggs
             SET
                 egg_repository_egg_uuid = $2, author = $3, name = $4, description = $5,
                 config_files = $6, config_startup = $7, config_stop = $8,
                 config_script = $9, config_allocations = $10, startup = $11,
                 force_outgoing_ip = $12, separate_port = $13, features = $14,
-                docker_images = $15, file_denylist = $16
+                docker_images = $15, file_denylist = $16, user_selectable = $17,
+                update_policy = $18, egg_repository_egg_applied = $19
             WHERE nest_eggs.uuid = $1",
-            egg.uuid,
-            egg.egg_repository_egg.as_ref().map(|e| e.uuid),
-            &egg.author,
-            &egg.name,
-            egg.description.as_deref(),
-            serde_json::to_value(&egg.config_files)?,
-            serde_json::to_value(&egg.config_startup)?,
-            serde_json::to_value(&egg.config_stop)?,
-            serde_json::to_value(&egg.config_script)?,
-            serde_json::to_value(&egg.config_allocations)?,
-            &egg.startup,
-            egg.force_outgoing_ip,
-            egg.separate_port,
-            &egg.features as &[compact_str::CompactString],
-            serde_json::to_string(&egg.docker_images)?,
-            &egg.file_denylist as &[compact_str::CompactString],
         )
+        .bind(egg.uuid)
+        .bind(egg.egg_repository_egg.as_ref().map(|e| e.uuid))
+        .bind(&egg.author)
+        .bind(&egg.name)
+        .bind(egg.description.as_deref())
+        .bind(serde_json::to_value(&egg.config_files)?)
+        .bind(serde_json::to_value(&egg.config_startup)?)
+        .bind(serde_json::to_value(&egg.config_stop)?)
+        .bind(serde_json::to_value(&egg.config_script)?)
+        .bind(serde_json::to_value(&egg.config_allocations)?)
+        .bind(&egg.startup)
+        .bind(egg.force_outgoing_ip)
+        .bind(egg.separate_port)
+        .bind(&egg.features)
+        .bind(serde_json::to_string(&egg.docker_images)?)
+        .bind(&egg.file_denylist)
+        .bind(egg.user_selectable)
+        .bind(egg.update_policy)
+        .bind(egg.egg_repository_egg_applied)
         .execute(state.database.write())
         .await
         {
@@ -406,6 +423,8 @@ mod patch {
                     "features": egg.features,
                     "docker_images": egg.docker_images,
                     "file_denylist": egg.file_denylist,
+                    "user_selectable": egg.user_selectable,
+                    "update_policy": egg.update_policy,
                 }),
             )
             .await;
@@ -16,7 +16,9 @@ mod post {
     use utoipa::ToSchema;
 
     #[derive(ToSchema, Serialize)]
-    struct Response {}
+    struct Response {
+        compatibility: shared::models::nest_egg::NestEggCompatibilityReport,
+    }
 
     #[utoipa::path(post, path = "/", responses(
         (status = OK, body = inline(Response)),
@@ -49,6 +51,7 @@ mod post {
 
         permissions.has_admin_permission("eggs.update")?;
 
+        let compatibility = data.compatibility_report();
         match egg.import_update(&state.database, data).await {
             Ok(_) => {}
             Err(err) if err.is_unique_violation() => {
@@ -94,7 +97,7 @@ mod post {
             )
             .await;
 
-        ApiResponse::json(Response {}).ok()
+        ApiResponse::json(Response { compatibility }).ok()
     }
 }
 
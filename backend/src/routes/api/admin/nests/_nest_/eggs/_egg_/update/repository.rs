@@ -1,6 +1,65 @@
 use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+mod get {
+    use crate::routes::api::admin::nests::_nest_::eggs::_egg_::GetNestEgg;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::user::GetPermissionManager,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        update_available: bool,
+        diff: shared::models::nest_egg::NestEggDiff,
+        compatibility: shared::models::nest_egg::NestEggCompatibilityReport,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = BAD_REQUEST, body = ApiError),
+    ), params(
+        (
+            "nest" = uuid::Uuid,
+            description = "The nest ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "egg" = uuid::Uuid,
+            description = "The egg ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        egg: GetNestEgg,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("eggs.read")?;
+
+        let egg_repository_egg = match &egg.egg_repository_egg {
+            Some(egg_repository_egg) => egg_repository_egg.fetch(&state.database).await?,
+            None => {
+                return ApiResponse::error("egg is not linked to a repository egg")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
+        ApiResponse::json(Response {
+            update_available: egg.update_available(&egg_repository_egg),
+            diff: egg.repository_diff(&state.database, &egg_repository_egg).await?,
+            compatibility: egg_repository_egg.exported_egg.compatibility_report(),
+        })
+        .ok()
+    }
+}
+
 mod post {
     use crate::routes::api::admin::nests::_nest_::{GetNest, eggs::_egg_::GetNestEgg};
     use axum::http::StatusCode;
@@ -13,7 +72,10 @@ mod post {
     use utoipa::ToSchema;
 
     #[derive(ToSchema, Serialize)]
-    struct Response {}
+    struct Response {
+        diff: shared::models::nest_egg::NestEggDiff,
+        compatibility: shared::models::nest_egg::NestEggCompatibilityReport,
+    }
 
     #[utoipa::path(post, path = "/", responses(
         (status = OK, body = inline(Response)),
@@ -49,8 +111,12 @@ mod post {
             }
         };
 
+        let diff = egg
+            .repository_diff(&state.database, &egg_repository_egg)
+            .await?;
+        let compatibility = egg_repository_egg.exported_egg.compatibility_report();
         match egg
-            .import_update(&state.database, egg_repository_egg.exported_egg)
+            .import_repository_update(&state.database, egg_repository_egg)
             .await
         {
             Ok(_) => {}
@@ -97,12 +163,17 @@ mod post {
             )
             .await;
 
-        ApiResponse::json(Response {}).ok()
+        ApiResponse::json(Response {
+            diff,
+            compatibility,
+        })
+        .ok()
     }
 }
 
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
+        .routes(routes!(get::route))
         .routes(routes!(post::route))
         .with_state(state.clone())
 }
@@ -19,6 +19,7 @@ mod post {
     #[derive(ToSchema, Serialize)]
     struct Response {
         egg: shared::models::nest_egg::AdminApiNestEgg,
+        compatibility: shared::models::nest_egg::NestEggCompatibilityReport,
     }
 
     #[utoipa::path(post, path = "/", responses(
@@ -48,6 +49,7 @@ mod post {
 
         permissions.has_admin_permission("eggs.create")?;
 
+        let compatibility = data.compatibility_report();
         let egg = match NestEgg::import(&state.database, nest.uuid, None, data).await {
             Ok(egg) => egg,
             Err(err) if err.is_unique_violation() => {
@@ -94,6 +96,7 @@ mod post {
 
         ApiResponse::json(Response {
             egg: egg.into_admin_api_object(&state.database).await?,
+            compatibility,
         })
         .ok()
     }
@@ -0,0 +1,74 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::admin::nodes::_node_::evacuations::_evacuation_::GetNodeEvacuation;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger, node_evacuation::NodeEvacuationStatus,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "node" = uuid::Uuid,
+            description = "The node ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "evacuation" = uuid::Uuid,
+            description = "The evacuation ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut evacuation: GetNodeEvacuation,
+        activity_logger: GetAdminActivityLogger,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("nodes.evacuations")?;
+
+        if !matches!(
+            evacuation.status,
+            NodeEvacuationStatus::Running | NodeEvacuationStatus::Paused
+        ) {
+            return ApiResponse::error("evacuation is already finished")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        evacuation.cancel(&state.database).await?;
+
+        activity_logger
+            .log(
+                "node:evacuation.cancel",
+                serde_json::json!({
+                    "uuid": evacuation.uuid,
+                    "node_uuid": evacuation.node.uuid,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,210 @@
+use super::State;
+use axum::{
+    extract::{Path, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{
+    GetState,
+    models::{node::GetNode, node_evacuation::NodeEvacuation, user::GetPermissionManager},
+    response::ApiResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod cancel;
+mod servers;
+
+pub type GetNodeEvacuation = shared::extract::ConsumingExtension<NodeEvacuation>;
+
+pub async fn auth(
+    state: GetState,
+    permissions: GetPermissionManager,
+    node: GetNode,
+    Path(evacuation): Path<Vec<String>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let evacuation = match evacuation.get(1).map(|s| s.parse::<uuid::Uuid>()) {
+        Some(Ok(id)) => id,
+        _ => {
+            return Ok(ApiResponse::error("invalid evacuation uuid")
+                .with_status(StatusCode::BAD_REQUEST)
+                .into_response());
+        }
+    };
+
+    if let Err(err) = permissions.has_admin_permission("nodes.evacuations") {
+        return Ok(err.into_response());
+    }
+
+    let evacuation =
+        NodeEvacuation::by_node_uuid_uuid(&state.database, node.uuid, evacuation).await;
+    let evacuation = match evacuation {
+        Ok(Some(evacuation)) => evacuation,
+        Ok(None) => {
+            return Ok(ApiResponse::error("evacuation not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .into_response());
+        }
+        Err(err) => return Ok(ApiResponse::from(err).into_response()),
+    };
+
+    req.extensions_mut().insert(node.0);
+    req.extensions_mut().insert(evacuation);
+
+    Ok(next.run(req).await)
+}
+
+mod get {
+    use crate::routes::api::admin::nodes::_node_::evacuations::_evacuation_::GetNodeEvacuation;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::user::GetPermissionManager,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        evacuation: shared::models::node_evacuation::AdminApiNodeEvacuation,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "node" = uuid::Uuid,
+            description = "The node ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "evacuation" = uuid::Uuid,
+            description = "The evacuation ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        evacuation: GetNodeEvacuation,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("nodes.evacuations")?;
+
+        ApiResponse::json(Response {
+            evacuation: evacuation.0.into_admin_api_object(&state.database).await?,
+        })
+        .ok()
+    }
+}
+
+mod patch {
+    use crate::routes::api::admin::nodes::_node_::evacuations::_evacuation_::GetNodeEvacuation;
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger, node_evacuation::NodeEvacuationStatus,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        paused: Option<bool>,
+        #[validate(range(min = 1, max = 50))]
+        #[schema(minimum = 1, maximum = 50)]
+        concurrency: Option<i16>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        evacuation: shared::models::node_evacuation::AdminApiNodeEvacuation,
+    }
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "node" = uuid::Uuid,
+            description = "The node ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "evacuation" = uuid::Uuid,
+            description = "The evacuation ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        mut evacuation: GetNodeEvacuation,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("nodes.evacuations")?;
+
+        if !matches!(
+            evacuation.status,
+            NodeEvacuationStatus::Running | NodeEvacuationStatus::Paused
+        ) {
+            return ApiResponse::error("evacuation is already finished")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let status = match data.paused {
+            Some(true) => NodeEvacuationStatus::Paused,
+            Some(false) => NodeEvacuationStatus::Running,
+            None => evacuation.status,
+        };
+        let concurrency = data.concurrency.unwrap_or(evacuation.concurrency);
+
+        evacuation
+            .update(&state.database, status, concurrency)
+            .await?;
+
+        activity_logger
+            .log(
+                "node:evacuation.update",
+                serde_json::json!({
+                    "uuid": evacuation.uuid,
+                    "node_uuid": evacuation.node.uuid,
+                    "status": evacuation.status,
+                    "concurrency": evacuation.concurrency,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            evacuation: evacuation.0.into_admin_api_object(&state.database).await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(patch::route))
+        .nest("/servers", servers::router(state))
+        .nest("/cancel", cancel::router(state))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
+        .with_state(state.clone())
+}
@@ -0,0 +1,110 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::routes::api::admin::nodes::_node_::evacuations::_evacuation_::GetNodeEvacuation;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination,
+            node_evacuation_server::{NodeEvacuationServer, NodeEvacuationServerStatus},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Params {
+        #[validate(range(min = 1))]
+        #[serde(default = "Pagination::default_page")]
+        pub page: i64,
+        #[validate(range(min = 1, max = 100))]
+        #[serde(default = "Pagination::default_per_page")]
+        pub per_page: i64,
+
+        status: Option<NodeEvacuationServerStatus>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        servers: Pagination<shared::models::node_evacuation_server::AdminApiNodeEvacuationServer>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "node" = uuid::Uuid,
+            description = "The node ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "evacuation" = uuid::Uuid,
+            description = "The evacuation ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "status" = Option<NodeEvacuationServerStatus>, Query,
+            description = "Only show servers with this evacuation status",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        evacuation: GetNodeEvacuation,
+        Query(params): Query<Params>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("nodes.evacuations")?;
+
+        let servers = NodeEvacuationServer::by_evacuation_uuid_with_pagination(
+            &state.database,
+            evacuation.uuid,
+            params.page,
+            params.per_page,
+            params.status,
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            servers: Pagination {
+                total: servers.total,
+                per_page: servers.per_page,
+                page: servers.page,
+                data: servers
+                    .data
+                    .into_iter()
+                    .map(|server| server.into_admin_api_object())
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
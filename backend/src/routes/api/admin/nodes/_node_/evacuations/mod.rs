@@ -0,0 +1,225 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _evacuation_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParams, node::GetNode, node_evacuation::NodeEvacuation,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        evacuations: Pagination<shared::models::node_evacuation::AdminApiNodeEvacuation>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "node" = uuid::Uuid,
+            description = "The node ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        node: GetNode,
+        Query(params): Query<PaginationParams>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("nodes.evacuations")?;
+
+        let evacuations = NodeEvacuation::by_node_uuid_with_pagination(
+            &state.database,
+            node.uuid,
+            params.page,
+            params.per_page,
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            evacuations: evacuations
+                .try_async_map(|evacuation| evacuation.into_admin_api_object(&state.database))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid,
+            admin_activity::GetAdminActivityLogger,
+            location::Location,
+            node::{GetNode, Node},
+            node_evacuation::{NodeEvacuation, NodeEvacuationOptions},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    fn default_concurrency() -> i16 {
+        2
+    }
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        destination_location_uuid: Option<uuid::Uuid>,
+        #[validate(length(max = 50))]
+        #[schema(max_items = 50)]
+        #[serde(default)]
+        destination_node_uuids: Vec<uuid::Uuid>,
+
+        #[validate(range(min = 1, max = 50))]
+        #[schema(minimum = 1, maximum = 50)]
+        #[serde(default = "default_concurrency")]
+        concurrency: i16,
+        #[serde(flatten)]
+        options: NodeEvacuationOptions,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        evacuation: shared::models::node_evacuation::AdminApiNodeEvacuation,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "node" = uuid::Uuid,
+            description = "The node ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        node: GetNode,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("nodes.evacuations")?;
+        permissions.has_admin_permission("servers.transfer")?;
+
+        if data.destination_location_uuid.is_none() && data.destination_node_uuids.is_empty() {
+            return ApiResponse::error("a destination location or destination nodes are required")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        if data.destination_node_uuids.contains(&node.uuid) {
+            return ApiResponse::error("cannot evacuate a node onto itself")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        if let Some(location_uuid) = data.destination_location_uuid
+            && Location::by_uuid_optional(&state.database, location_uuid)
+                .await?
+                .is_none()
+        {
+            return ApiResponse::error("location not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .ok();
+        }
+
+        for node_uuid in &data.destination_node_uuids {
+            if Node::by_uuid_optional(&state.database, *node_uuid)
+                .await?
+                .is_none()
+            {
+                return ApiResponse::error(&format!("node {node_uuid} not found"))
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        }
+
+        if NodeEvacuation::active_by_node_uuid(&state.database, node.uuid)
+            .await?
+            .is_some()
+        {
+            return ApiResponse::error("node is already being evacuated")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        let evacuation = NodeEvacuation::create(
+            &state.database,
+            node.uuid,
+            data.destination_location_uuid,
+            &data.destination_node_uuids,
+            data.concurrency,
+            &data.options,
+        )
+        .await?;
+
+        activity_logger
+            .log(
+                "node:evacuation.create",
+                serde_json::json!({
+                    "uuid": evacuation.uuid,
+                    "node_uuid": node.uuid,
+                    "destination_location_uuid": data.destination_location_uuid,
+                    "destination_node_uuids": data.destination_node_uuids,
+                    "concurrency": data.concurrency,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            evacuation: evacuation.into_admin_api_object(&state.database).await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{evacuation}", _evacuation_::router(state))
+        .with_state(state.clone())
+}
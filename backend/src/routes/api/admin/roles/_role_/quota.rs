@@ -0,0 +1,177 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::routes::api::admin::roles::_role_::GetRole;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{resource_quota::ResourceQuota, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        quota: Option<shared::models::resource_quota::ApiResourceQuota>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "role" = uuid::Uuid,
+            description = "The role ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        role: GetRole,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("roles.quota")?;
+
+        let quota = ResourceQuota::by_role_uuid(&state.database, role.uuid).await?;
+
+        ApiResponse::json(Response {
+            quota: quota.map(|quota| quota.into_api_object()),
+        })
+        .ok()
+    }
+}
+
+mod put {
+    use crate::routes::api::admin::roles::_role_::GetRole;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger,
+            resource_quota::{ApiResourceQuota, ResourceQuota},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        quota: ApiResourceQuota,
+    }
+
+    #[utoipa::path(put, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = BAD_REQUEST, body = ApiError),
+    ), params(
+        (
+            "role" = uuid::Uuid,
+            description = "The role ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = ApiResourceQuota)]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        role: GetRole,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<ApiResourceQuota>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("roles.quota")?;
+
+        let quota = ResourceQuota::upsert_for_role(&state.database, role.uuid, &data).await?;
+
+        activity_logger
+            .log(
+                "role:quota.update",
+                serde_json::json!({
+                    "uuid": role.uuid,
+                    "name": role.name,
+                    "quota": data,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            quota: quota.into_api_object(),
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::admin::roles::_role_::GetRole;
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel, admin_activity::GetAdminActivityLogger, resource_quota::ResourceQuota,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "role" = uuid::Uuid,
+            description = "The role ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        role: GetRole,
+        activity_logger: GetAdminActivityLogger,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("roles.quota")?;
+
+        let quota = match ResourceQuota::by_role_uuid(&state.database, role.uuid).await? {
+            Some(quota) => quota,
+            None => {
+                return ApiResponse::error("quota not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        quota.delete(&state.database, ()).await?;
+
+        activity_logger
+            .log(
+                "role:quota.delete",
+                serde_json::json!({
+                    "uuid": role.uuid,
+                    "name": role.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(put::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
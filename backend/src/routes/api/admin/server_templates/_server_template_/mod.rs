@@ -0,0 +1,403 @@
+use super::State;
+use axum::{
+    extract::{Path, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{
+    GetState,
+    models::{ByUuid, server_template::ServerTemplate, user::GetPermissionManager},
+    response::ApiResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub type GetServerTemplate = shared::extract::ConsumingExtension<ServerTemplate>;
+
+pub async fn auth(
+    state: GetState,
+    permissions: GetPermissionManager,
+    Path(server_template): Path<uuid::Uuid>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if let Err(err) = permissions.has_admin_permission("server-templates.read") {
+        return Ok(err.into_response());
+    }
+
+    let server_template = ServerTemplate::by_uuid_optional(&state.database, server_template).await;
+    let server_template = match server_template {
+        Ok(Some(server_template)) => server_template,
+        Ok(None) => {
+            return Ok(ApiResponse::error("server template not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .into_response());
+        }
+        Err(err) => return Ok(ApiResponse::from(err).into_response()),
+    };
+
+    req.extensions_mut().insert(server_template);
+
+    Ok(next.run(req).await)
+}
+
+mod get {
+    use crate::routes::api::admin::server_templates::_server_template_::GetServerTemplate;
+    use serde::Serialize;
+    use shared::{
+        ApiError,
+        models::user::GetPermissionManager,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        server_template: shared::models::server_template::AdminApiServerTemplate,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server_template" = uuid::Uuid,
+            description = "The server template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        permissions: GetPermissionManager,
+        server_template: GetServerTemplate,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("server-templates.read")?;
+
+        ApiResponse::json(Response {
+            server_template: server_template.0.into_admin_api_object(),
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use crate::routes::api::admin::server_templates::_server_template_::GetServerTemplate;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel, admin_activity::GetAdminActivityLogger, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server_template" = uuid::Uuid,
+            description = "The server template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        server_template: GetServerTemplate,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("server-templates.delete")?;
+
+        server_template.delete(&state.database, ()).await?;
+
+        activity_logger
+            .log(
+                "server-template:delete",
+                serde_json::json!({
+                    "uuid": server_template.uuid,
+                    "name": server_template.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+mod patch {
+    use crate::routes::api::admin::server_templates::_server_template_::GetServerTemplate;
+    use axum::http::StatusCode;
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid, admin_activity::GetAdminActivityLogger,
+            backup_configurations::BackupConfiguration, mount::Mount, nest_egg::NestEgg,
+            nest_egg_variable::NestEggVariable, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        egg_uuid: Option<uuid::Uuid>,
+        backup_configuration_uuid: Option<uuid::Uuid>,
+
+        #[validate(length(min = 3, max = 255))]
+        #[schema(min_length = 3, max_length = 255)]
+        name: Option<compact_str::CompactString>,
+        #[validate(length(max = 1024))]
+        #[schema(max_length = 1024)]
+        description: Option<compact_str::CompactString>,
+
+        #[validate(nested)]
+        limits: Option<shared::models::server::ApiServerLimits>,
+        pinned_cpus: Option<Vec<i16>>,
+
+        #[validate(length(max = 8192))]
+        #[schema(max_length = 8192)]
+        startup: Option<compact_str::CompactString>,
+        #[validate(length(max = 255))]
+        #[schema(max_length = 255)]
+        image: Option<compact_str::CompactString>,
+        #[validate(length(max = 255))]
+        #[schema(max_length = 255)]
+        timezone: Option<compact_str::CompactString>,
+
+        #[validate(nested)]
+        feature_limits: Option<shared::models::server::ApiServerFeatureLimits>,
+        variables: Option<IndexMap<compact_str::CompactString, String>>,
+        mount_uuids: Option<Vec<uuid::Uuid>>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        server_template: shared::models::server_template::AdminApiServerTemplate,
+    }
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server_template" = uuid::Uuid,
+            description = "The server template ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        mut server_template: GetServerTemplate,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("server-templates.update")?;
+
+        if let Some(egg_uuid) = data.egg_uuid {
+            let egg = match NestEgg::by_uuid_optional(&state.database, egg_uuid).await? {
+                Some(egg) => egg,
+                None => {
+                    return ApiResponse::error("egg not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+            server_template.egg = NestEgg::get_fetchable(egg.uuid);
+        }
+        if let Some(backup_configuration_uuid) = data.backup_configuration_uuid {
+            if backup_configuration_uuid.is_nil() {
+                server_template.backup_configuration = None;
+            } else {
+                let backup_configuration = match BackupConfiguration::by_uuid_optional(
+                    &state.database,
+                    backup_configuration_uuid,
+                )
+                .await?
+                {
+                    Some(backup_configuration) => backup_configuration,
+                    None => {
+                        return ApiResponse::error("backup configuration not found")
+                            .with_status(StatusCode::NOT_FOUND)
+                            .ok();
+                    }
+                };
+
+                server_template.backup_configuration = Some(BackupConfiguration::get_fetchable(
+                    backup_configuration.uuid,
+                ));
+            }
+        }
+        if let Some(name) = data.name {
+            server_template.name = name;
+        }
+        if let Some(description) = data.description {
+            if description.is_empty() {
+                server_template.description = None;
+            } else {
+                server_template.description = Some(description);
+            }
+        }
+        if let Some(limits) = &data.limits {
+            server_template.cpu = limits.cpu;
+            server_template.memory = limits.memory;
+            server_template.swap = limits.swap;
+            server_template.disk = limits.disk;
+            server_template.io_weight = limits.io_weight;
+        }
+        if let Some(pinned_cpus) = data.pinned_cpus {
+            server_template.pinned_cpus = pinned_cpus;
+        }
+        if let Some(startup) = data.startup {
+            if startup.is_empty() {
+                server_template.startup = None;
+            } else {
+                server_template.startup = Some(startup);
+            }
+        }
+        if let Some(image) = data.image {
+            if image.is_empty() {
+                server_template.image = None;
+            } else {
+                server_template.image = Some(image);
+            }
+        }
+        if let Some(timezone) = data.timezone {
+            if timezone.is_empty() {
+                server_template.timezone = None;
+            } else {
+                if timezone.parse::<chrono_tz::Tz>().is_err() {
+                    return ApiResponse::error("invalid timezone")
+                        .with_status(StatusCode::BAD_REQUEST)
+                        .ok();
+                }
+
+                server_template.timezone = Some(timezone);
+            }
+        }
+        if let Some(feature_limits) = &data.feature_limits {
+            server_template.allocation_limit = feature_limits.allocations;
+            server_template.backup_limit = feature_limits.backups;
+            server_template.database_limit = feature_limits.databases;
+            server_template.schedule_limit = feature_limits.schedules;
+        }
+        if let Some(mount_uuids) = data.mount_uuids {
+            for mount_uuid in &mount_uuids {
+                if Mount::by_uuid_optional(&state.database, *mount_uuid)
+                    .await?
+                    .is_none()
+                {
+                    return ApiResponse::error("mount not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            }
+
+            server_template.mount_uuids = mount_uuids;
+        }
+
+        if data.egg_uuid.is_some() || data.variables.is_some() {
+            let variables =
+                NestEggVariable::all_by_egg_uuid(&state.database, server_template.egg.uuid)
+                    .await?;
+
+            if let Some(data_variables) = &data.variables {
+                for (env_variable, value) in data_variables {
+                    if !variables.iter().any(|v| v.env_variable == *env_variable) {
+                        return ApiResponse::error(&format!(
+                            "variables: egg has no variable named {env_variable}"
+                        ))
+                        .with_status(StatusCode::BAD_REQUEST)
+                        .ok();
+                    }
+
+                    if value.len() > 4096 {
+                        return ApiResponse::error(&format!(
+                            "variables: value of {env_variable} is longer than 4096 characters"
+                        ))
+                        .with_status(StatusCode::BAD_REQUEST)
+                        .ok();
+                    }
+                }
+
+                server_template.variables = data_variables.clone();
+            } else {
+                // overrides for variables the new egg does not have are dropped
+                server_template.variables.retain(|env_variable, _| {
+                    variables.iter().any(|v| v.env_variable == *env_variable)
+                });
+            }
+        }
+
+        match server_template.save(&state.database).await {
+            Ok(_) => {}
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("server template with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => {
+                tracing::error!("failed to update server template: {:?}", err);
+
+                return ApiResponse::error("failed to update server template")
+                    .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .ok();
+            }
+        }
+
+        activity_logger
+            .log(
+                "server-template:update",
+                serde_json::json!({
+                    "uuid": server_template.uuid,
+                    "egg_uuid": server_template.egg.uuid,
+                    "backup_configuration_uuid": server_template
+                        .backup_configuration
+                        .as_ref()
+                        .map(|backup_configuration| backup_configuration.uuid),
+
+                    "name": server_template.name,
+                    "description": server_template.description,
+                    "limits": data.limits,
+                    "pinned_cpus": server_template.pinned_cpus,
+                    "startup": server_template.startup,
+                    "image": server_template.image,
+                    "timezone": server_template.timezone,
+                    "feature_limits": data.feature_limits,
+                    "variables": server_template.variables,
+                    "mount_uuids": server_template.mount_uuids,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            server_template: server_template.0.into_admin_api_object(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(delete::route))
+        .routes(routes!(patch::route))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
+        .with_state(state.clone())
+}
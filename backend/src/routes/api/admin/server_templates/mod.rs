@@ -0,0 +1,282 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _server_template_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParamsWithSearch, server_template::ServerTemplate,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        server_templates: Pagination<shared::models::server_template::AdminApiServerTemplate>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("server-templates.read")?;
+
+        let server_templates = ServerTemplate::all_with_pagination(
+            &state.database,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            server_templates: Pagination {
+                total: server_templates.total,
+                per_page: server_templates.per_page,
+                page: server_templates.page,
+                data: server_templates
+                    .data
+                    .into_iter()
+                    .map(|server_template| server_template.into_admin_api_object())
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid, admin_activity::GetAdminActivityLogger,
+            backup_configurations::BackupConfiguration, mount::Mount, nest_egg::NestEgg,
+            nest_egg_variable::NestEggVariable, server_template::ServerTemplate,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        egg_uuid: uuid::Uuid,
+        backup_configuration_uuid: Option<uuid::Uuid>,
+
+        #[validate(length(min = 3, max = 255))]
+        #[schema(min_length = 3, max_length = 255)]
+        name: compact_str::CompactString,
+        #[validate(length(max = 1024))]
+        #[schema(max_length = 1024)]
+        description: Option<compact_str::CompactString>,
+
+        #[validate(nested)]
+        limits: shared::models::server::ApiServerLimits,
+        #[serde(default)]
+        pinned_cpus: Vec<i16>,
+
+        #[validate(length(min = 1, max = 8192))]
+        #[schema(min_length = 1, max_length = 8192)]
+        startup: Option<compact_str::CompactString>,
+        #[validate(length(min = 2, max = 255))]
+        #[schema(min_length = 2, max_length = 255)]
+        image: Option<compact_str::CompactString>,
+        #[schema(min_length = 3, max_length = 255, value_type = Option<String>)]
+        timezone: Option<chrono_tz::Tz>,
+
+        #[validate(nested)]
+        feature_limits: shared::models::server::ApiServerFeatureLimits,
+        #[serde(default)]
+        variables: IndexMap<compact_str::CompactString, String>,
+        #[serde(default)]
+        mount_uuids: Vec<uuid::Uuid>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        server_template: shared::models::server_template::AdminApiServerTemplate,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("server-templates.create")?;
+
+        let egg = match NestEgg::by_uuid_optional(&state.database, data.egg_uuid).await? {
+            Some(egg) => egg,
+            None => {
+                return ApiResponse::error("egg not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        let backup_configuration_uuid = match data.backup_configuration_uuid {
+            Some(backup_configuration_uuid) if !backup_configuration_uuid.is_nil() => {
+                match BackupConfiguration::by_uuid_optional(
+                    &state.database,
+                    backup_configuration_uuid,
+                )
+                .await?
+                {
+                    Some(backup_configuration) => Some(backup_configuration.uuid),
+                    None => {
+                        return ApiResponse::error("backup configuration not found")
+                            .with_status(StatusCode::NOT_FOUND)
+                            .ok();
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let variables = NestEggVariable::all_by_egg_uuid(&state.database, egg.uuid).await?;
+
+        for (env_variable, value) in &data.variables {
+            if !variables.iter().any(|v| v.env_variable == *env_variable) {
+                return ApiResponse::error(&format!(
+                    "variables: egg has no variable named {env_variable}"
+                ))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+            }
+
+            if value.len() > 4096 {
+                return ApiResponse::error(&format!(
+                    "variables: value of {env_variable} is longer than 4096 characters"
+                ))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+            }
+        }
+
+        for mount_uuid in &data.mount_uuids {
+            if Mount::by_uuid_optional(&state.database, *mount_uuid)
+                .await?
+                .is_none()
+            {
+                return ApiResponse::error("mount not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        }
+
+        let server_template = match ServerTemplate::create(
+            &state.database,
+            egg.uuid,
+            backup_configuration_uuid,
+            &data.name,
+            data.description.as_deref(),
+            &data.limits,
+            &data.pinned_cpus,
+            data.startup.as_deref(),
+            data.image.as_deref(),
+            data.timezone.as_ref().map(|tz| tz.name()),
+            &data.feature_limits,
+            &data.variables,
+            &data.mount_uuids,
+        )
+        .await
+        {
+            Ok(server_template) => server_template,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("server template with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => {
+                tracing::error!("failed to create server template: {:?}", err);
+
+                return ApiResponse::error("failed to create server template")
+                    .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .ok();
+            }
+        };
+
+        activity_logger
+            .log(
+                "server-template:create",
+                serde_json::json!({
+                    "uuid": server_template.uuid,
+                    "egg_uuid": server_template.egg.uuid,
+                    "backup_configuration_uuid": backup_configuration_uuid,
+
+                    "name": server_template.name,
+                    "description": server_template.description,
+                    "limits": data.limits,
+                    "pinned_cpus": server_template.pinned_cpus,
+                    "startup": server_template.startup,
+                    "image": server_template.image,
+                    "timezone": server_template.timezone,
+                    "feature_limits": data.feature_limits,
+                    "variables": server_template.variables,
+                    "mount_uuids": server_template.mount_uuids,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            server_template: server_template.into_admin_api_object(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{server_template}", _server_template_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,126 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use axum::http::StatusCode;
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            ByUuid,
+            admin_activity::GetAdminActivityLogger,
+            nest_egg::NestEgg,
+            server::{GetServer, ServerChangeEggOptions},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        egg_uuid: uuid::Uuid,
+
+        #[validate(length(min = 1, max = 8192))]
+        #[schema(min_length = 1, max_length = 8192)]
+        startup: Option<compact_str::CompactString>,
+        #[validate(length(min = 2, max = 255))]
+        #[schema(min_length = 2, max_length = 255)]
+        image: Option<compact_str::CompactString>,
+        #[serde(default)]
+        variables: IndexMap<compact_str::CompactString, String>,
+
+        truncate_directory: bool,
+        backup: bool,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        backup_uuid: Option<uuid::Uuid>,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = ACCEPTED, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("servers.change-egg")?;
+
+        let egg = match NestEgg::by_uuid_optional(&state.database, data.egg_uuid).await? {
+            Some(egg) => egg,
+            None => {
+                return ApiResponse::error("egg not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        let server_uuid = server.uuid;
+        let old_egg_uuid = server.egg.uuid;
+
+        let backup_uuid = server
+            .0
+            .change_egg(
+                &state.database,
+                egg,
+                ServerChangeEggOptions {
+                    startup: data.startup.clone(),
+                    image: data.image.clone(),
+                    variables: data.variables.clone(),
+                    restricted: false,
+                    truncate_directory: data.truncate_directory,
+                    backup: data.backup,
+                },
+            )
+            .await?;
+
+        activity_logger
+            .log(
+                "server:egg.change",
+                serde_json::json!({
+                    "uuid": server_uuid,
+                    "old_egg_uuid": old_egg_uuid,
+                    "egg_uuid": data.egg_uuid,
+                    "backup_uuid": backup_uuid,
+
+                    "startup": data.startup,
+                    "image": data.image,
+                    "variables": data.variables,
+                    "truncate_directory": data.truncate_directory,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response { backup_uuid })
+            .with_status(StatusCode::ACCEPTED)
+            .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -14,7 +14,10 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod allocations;
 mod clear_state;
+mod clones;
+mod egg;
 mod mounts;
+mod suspensions;
 mod transfer;
 mod variables;
 
@@ -189,6 +192,7 @@ mod patch {
             backup_configurations::BackupConfiguration,
             nest_egg::NestEgg,
             server::GetServer,
+            server_suspension::ServerSuspension,
             user::{GetPermissionManager, User},
         },
         response::{ApiResponse, ApiResponseResult},
@@ -238,6 +242,7 @@ mod patch {
         (status = OK, body = inline(Response)),
         (status = NOT_FOUND, body = ApiError),
         (status = BAD_REQUEST, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
         (status = CONFLICT, body = ApiError),
     ), params(
         (
@@ -261,6 +266,8 @@ mod patch {
 
         permissions.has_admin_permission("servers.update")?;
 
+        let previous_server = server.0.clone();
+
         if let Some(owner_uuid) = data.owner_uuid {
             let owner = match User::by_uuid_optional(&state.database, owner_uuid).await? {
                 Some(owner) => owner,
@@ -308,6 +315,7 @@ mod patch {
                 ));
             }
         }
+        let was_suspended = server.suspended;
         if let Some(suspended) = data.suspended {
             server.suspended = suspended;
         }
@@ -364,6 +372,12 @@ mod patch {
             server.schedule_limit = feature_limits.schedules;
         }
 
+        let mut transaction = state.database.write().begin().await?;
+
+        server
+            .enforce_resource_quota(&mut transaction, Some(&previous_server))
+            .await?;
+
         match sqlx::query!(
             "UPDATE servers
             SET
@@ -399,10 +413,10 @@ mod patch {
             server.schedule_limit,
             server.uuid,
         )
-        .execute(state.database.write())
+        .execute(&mut *transaction)
         .await
         {
-            Ok(_) => {}
+            Ok(_) => transaction.commit().await?,
             Err(err) => {
                 tracing::error!("failed to update server: {:?}", err);
 
@@ -412,6 +426,26 @@ mod patch {
             }
         }
 
+        let suspension_changed = server.suspended != was_suspended;
+        if suspension_changed {
+            if server.suspended {
+                ServerSuspension::create(
+                    &state.database,
+                    server.uuid,
+                    Some(activity_logger.user_uuid),
+                    "suspended through the server settings",
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
+            } else {
+                ServerSuspension::lift_active_by_server_uuid(&state.database, server.uuid).await?;
+            }
+
+            ServerSuspension::reconcile(&state.database, Some(server.uuid)).await?;
+        }
+
         activity_logger
             .log(
                 "server:update",
@@ -434,7 +468,9 @@ mod patch {
             .await;
 
         tokio::spawn(async move {
-            if let Err(err) = server.0.sync(&state.database).await {
+            if suspension_changed {
+                ServerSuspension::sync_servers(&state.database, vec![server.uuid]).await;
+            } else if let Err(err) = server.0.sync(&state.database).await {
                 tracing::error!("failed to sync server on node: {:?}", err);
             }
         });
@@ -450,7 +486,10 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(patch::route))
         .nest("/variables", variables::router(state))
         .nest("/mounts", mounts::router(state))
+        .nest("/suspensions", suspensions::router(state))
         .nest("/transfer", transfer::router(state))
+        .nest("/clones", clones::router(state))
+        .nest("/egg", egg::router(state))
         .nest("/allocations", allocations::router(state))
         .nest("/clear-state", clear_state::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
@@ -0,0 +1,153 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            server::GetServer, server_suspension::ServerSuspension, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        suspension: shared::models::server_suspension::AdminApiServerSuspension,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "suspension" = uuid::Uuid,
+            description = "The suspension ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Path((_server, suspension)): Path<(String, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("servers.suspensions")?;
+
+        let suspension =
+            match ServerSuspension::by_server_uuid_uuid(&state.database, server.uuid, suspension)
+                .await?
+            {
+                Some(suspension) => suspension,
+                None => {
+                    return ApiResponse::error("suspension not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        ApiResponse::json(Response {
+            suspension: suspension.into_admin_api_object(&state.storage.retrieve_urls().await),
+        })
+        .ok()
+    }
+}
+
+mod delete {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger, server::GetServer,
+            server_suspension::ServerSuspension, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "suspension" = uuid::Uuid,
+            description = "The suspension ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetAdminActivityLogger,
+        Path((_server, suspension)): Path<(String, uuid::Uuid)>,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("servers.suspensions")?;
+
+        let mut suspension =
+            match ServerSuspension::by_server_uuid_uuid(&state.database, server.uuid, suspension)
+                .await?
+            {
+                Some(suspension) => suspension,
+                None => {
+                    return ApiResponse::error("suspension not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        if suspension.lifted.is_some()
+            || suspension
+                .expires
+                .is_some_and(|expires| expires <= chrono::Utc::now().naive_utc())
+        {
+            return ApiResponse::error("suspension has already ended")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        suspension.lift(&state.database).await?;
+
+        let changed_servers = ServerSuspension::reconcile(&state.database, Some(server.uuid)).await?;
+
+        activity_logger
+            .log(
+                "server:suspension.lift",
+                serde_json::json!({
+                    "uuid": suspension.uuid,
+                    "server_uuid": server.uuid,
+                }),
+            )
+            .await;
+
+        tokio::spawn(async move {
+            ServerSuspension::sync_servers(&state.database, changed_servers).await;
+        });
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,213 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _suspension_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParams, server::GetServer,
+            server_suspension::ServerSuspension, user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        suspensions: Pagination<shared::models::server_suspension::AdminApiServerSuspension>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(params): Query<PaginationParams>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("servers.suspensions")?;
+
+        let suspensions = ServerSuspension::by_server_uuid_with_pagination(
+            &state.database,
+            server.uuid,
+            params.page,
+            params.per_page,
+        )
+        .await?;
+
+        let storage_url_retriever = state.storage.retrieve_urls().await;
+
+        ApiResponse::json(Response {
+            suspensions: Pagination {
+                total: suspensions.total,
+                per_page: suspensions.per_page,
+                page: suspensions.page,
+                data: suspensions
+                    .data
+                    .into_iter()
+                    .map(|suspension| suspension.into_admin_api_object(&storage_url_retriever))
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger,
+            server::GetServer,
+            server_suspension::ServerSuspension,
+            user::{GetPermissionManager, GetUser},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(length(min = 1, max = 1024))]
+        #[schema(min_length = 1, max_length = 1024)]
+        reason: String,
+        #[validate(length(max = 1024))]
+        #[schema(max_length = 1024)]
+        message: Option<String>,
+
+        starts: Option<chrono::DateTime<chrono::Utc>>,
+        expires: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        suspension: shared::models::server_suspension::AdminApiServerSuspension,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        server: GetServer,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("servers.suspensions")?;
+
+        if let Some(expires) = data.expires
+            && expires <= data.starts.unwrap_or_else(chrono::Utc::now)
+        {
+            return ApiResponse::error("expires: must be after the suspension starts")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let message = data.message.as_deref().filter(|message| !message.is_empty());
+
+        let suspension_uuid = ServerSuspension::create(
+            &state.database,
+            server.uuid,
+            Some(user.uuid),
+            &data.reason,
+            message,
+            data.starts.map(|dt| dt.naive_utc()),
+            data.expires.map(|dt| dt.naive_utc()),
+        )
+        .await?;
+
+        let changed_servers = ServerSuspension::reconcile(&state.database, Some(server.uuid)).await?;
+
+        let suspension =
+            match ServerSuspension::by_server_uuid_uuid(&state.database, server.uuid, suspension_uuid)
+                .await?
+            {
+                Some(suspension) => suspension,
+                None => {
+                    return ApiResponse::error("suspension not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            };
+
+        activity_logger
+            .log(
+                "server:suspension.create",
+                serde_json::json!({
+                    "uuid": suspension.uuid,
+                    "server_uuid": server.uuid,
+
+                    "reason": suspension.reason,
+                    "message": suspension.message,
+                    "starts": suspension.starts.and_utc(),
+                    "expires": suspension.expires.map(|dt| dt.and_utc()),
+                }),
+            )
+            .await;
+
+        let database = state.database.clone();
+        tokio::spawn(async move {
+            ServerSuspension::sync_servers(&database, changed_servers).await;
+        });
+
+        ApiResponse::json(Response {
+            suspension: suspension.into_admin_api_object(&state.storage.retrieve_urls().await),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{suspension}", _suspension_::router(state))
+        .with_state(state.clone())
+}
@@ -3,13 +3,14 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod post {
     use axum::http::StatusCode;
-    use compact_str::ToCompactString;
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
-        jwt::BasePayload,
         models::{
-            ByUuid, admin_activity::GetAdminActivityLogger, node::Node, server::GetServer,
+            ByUuid,
+            admin_activity::GetAdminActivityLogger,
+            node::Node,
+            server::{GetServer, ServerTransferOptions},
             user::GetPermissionManager,
         },
         response::{ApiResponse, ApiResponseResult},
@@ -36,6 +37,7 @@ mod post {
     #[utoipa::path(post, path = "/", responses(
         (status = OK, body = inline(Response)),
         (status = CONFLICT, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
         (status = NOT_FOUND, body = ApiError),
     ), params(
         (
@@ -75,97 +77,30 @@ mod post {
             }
         };
 
+        // transfers move the full limits of the server, so servers that are over the quota of
+        // their owner have to be scaled down before they can be moved
         let mut transaction = state.database.write().begin().await?;
-
-        let destination_allocation_uuid = if let Some(allocation_uuid) = data.allocation_uuid {
-            Some(
-                sqlx::query!(
-                    "INSERT INTO server_allocations (server_uuid, allocation_uuid)
-                    VALUES ($1, $2)
-                    ON CONFLICT DO NOTHING
-                    RETURNING uuid",
-                    server.uuid,
-                    allocation_uuid
-                )
-                .fetch_one(&mut *transaction)
-                .await?
-                .uuid,
-            )
-        } else {
-            None
-        };
-
-        sqlx::query!(
-            r#"
-            UPDATE servers
-            SET destination_node_uuid = $2, destination_allocation_uuid = $3
-            WHERE servers.uuid = $1
-            "#,
-            server.uuid,
-            destination_node.uuid,
-            destination_allocation_uuid
-        )
-        .execute(&mut *transaction)
-        .await?;
-
-        if !data.allocation_uuids.is_empty() {
-            sqlx::query!(
-                r#"
-                INSERT INTO server_allocations (server_uuid, allocation_uuid)
-                SELECT $1, UNNEST($2::uuid[])
-                ON CONFLICT DO NOTHING
-                "#,
-                server.uuid,
-                &data.allocation_uuids
-            )
-            .execute(&mut *transaction)
+        server
+            .enforce_resource_quota(&mut transaction, None)
             .await?;
-        }
-
-        let token = destination_node.create_jwt(
-            &state.database,
-            &state.jwt,
-            &BasePayload {
-                issuer: "panel".into(),
-                subject: Some(server.uuid.to_string()),
-                audience: Vec::new(),
-                expiration_time: Some(chrono::Utc::now().timestamp() + 600),
-                not_before: None,
-                issued_at: Some(chrono::Utc::now().timestamp()),
-                jwt_id: server.node.uuid.to_string(),
-            },
-        )?;
-
         transaction.commit().await?;
 
-        let mut url = destination_node.url;
-        url.set_path("/api/transfers");
-
-        if let Err(err) = server
-            .node
-            .fetch_cached(&state.database)
-            .await?
-            .api_client(&state.database)
-            .post_servers_server_transfer(
-                server.uuid,
-                &wings_api::servers_server_transfer::post::RequestBody {
-                    url: url.to_compact_string(),
-                    token: format!("Bearer {token}").into(),
+        let destination_allocation_uuid = server
+            .transfer(
+                &state.database,
+                &state.jwt,
+                &destination_node,
+                data.allocation_uuid,
+                &data.allocation_uuids,
+                ServerTransferOptions {
                     backups: data.backups,
-                    delete_backups: data.delete_source_backups,
+                    delete_source_backups: data.delete_source_backups,
                     archive_format: data.archive_format,
                     compression_level: data.compression_level,
-                    multiplex_streams: data.multiplex_channels,
+                    multiplex_channels: data.multiplex_channels,
                 },
             )
-            .await
-        {
-            tracing::error!("failed to transfer server to node: {:?}", err);
-
-            return ApiResponse::error("failed to transfer server to node")
-                .with_status(StatusCode::INTERNAL_SERVER_ERROR)
-                .ok();
-        }
+            .await?;
 
         activity_logger
             .log(
@@ -145,8 +145,8 @@ mod put {
             }
         };
 
-        if let Err(error) = validator.validate() {
-            return ApiResponse::error(&error)
+        if let Err(errors) = validator.validate() {
+            return ApiResponse::json(ApiError::new_fields_value(&errors))
                 .with_status(StatusCode::BAD_REQUEST)
                 .ok();
         }
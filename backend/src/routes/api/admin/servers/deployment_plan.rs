@@ -0,0 +1,69 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        deployment::{self, DeploymentRequest},
+        models::{ByUuid, nest_egg::NestEgg, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        egg_uuid: uuid::Uuid,
+
+        #[validate(nested)]
+        deploy: DeploymentRequest,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        plan: shared::deployment::ApiDeploymentPlan,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("servers.create")?;
+
+        let egg = match NestEgg::by_uuid_optional(&state.database, data.egg_uuid).await? {
+            Some(egg) => egg,
+            None => {
+                return ApiResponse::error("egg not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        let plan = deployment::plan(&state.database, &egg, &data.deploy).await?;
+
+        ApiResponse::json(Response {
+            plan: plan.into_api_object(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
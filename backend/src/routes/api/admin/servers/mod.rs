@@ -2,6 +2,7 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod _server_;
+mod deployment_plan;
 mod external;
 
 mod get {
@@ -79,18 +80,23 @@ mod post {
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
+        deployment::{self, DeploymentRequest},
         models::{
             ByUuid,
             admin_activity::GetAdminActivityLogger,
             backup_configurations::BackupConfiguration,
+            mount::Mount,
             nest_egg::NestEgg,
             nest_egg_variable::NestEggVariable,
             node::Node,
             server::Server,
+            server_mount::ServerMount,
+            server_template::ServerTemplate,
             user::{GetPermissionManager, User},
         },
         response::{ApiResponse, ApiResponseResult},
     };
+    use indexmap::IndexMap;
     use std::collections::HashMap;
     use utoipa::ToSchema;
     use validator::Validate;
@@ -107,13 +113,18 @@ mod post {
 
     #[derive(ToSchema, Validate, Deserialize)]
     pub struct Payload {
-        node_uuid: uuid::Uuid,
+        template_uuid: Option<uuid::Uuid>,
+
+        node_uuid: Option<uuid::Uuid>,
         owner_uuid: uuid::Uuid,
-        egg_uuid: uuid::Uuid,
+        egg_uuid: Option<uuid::Uuid>,
         backup_configuration_uuid: Option<uuid::Uuid>,
 
         allocation_uuid: Option<uuid::Uuid>,
+        #[serde(default)]
         allocation_uuids: Vec<uuid::Uuid>,
+        #[validate(nested)]
+        deploy: Option<DeploymentRequest>,
 
         start_on_completion: bool,
         skip_installer: bool,
@@ -128,19 +139,20 @@ mod post {
         #[schema(max_length = 1024)]
         description: Option<compact_str::CompactString>,
 
-        limits: shared::models::server::ApiServerLimits,
-        pinned_cpus: Vec<i16>,
+        limits: Option<shared::models::server::ApiServerLimits>,
+        pinned_cpus: Option<Vec<i16>>,
 
         #[validate(length(min = 1, max = 8192))]
         #[schema(min_length = 1, max_length = 8192)]
-        startup: compact_str::CompactString,
+        startup: Option<compact_str::CompactString>,
         #[validate(length(min = 2, max = 255))]
         #[schema(min_length = 2, max_length = 255)]
-        image: compact_str::CompactString,
+        image: Option<compact_str::CompactString>,
         #[schema(min_length = 3, max_length = 255, value_type = String)]
         timezone: Option<chrono_tz::Tz>,
 
-        feature_limits: shared::models::server::ApiServerFeatureLimits,
+        feature_limits: Option<shared::models::server::ApiServerFeatureLimits>,
+        #[serde(default)]
         #[schema(inline)]
         variables: Vec<PayloadVariable>,
     }
@@ -154,6 +166,8 @@ mod post {
         (status = OK, body = inline(Response)),
         (status = BAD_REQUEST, body = ApiError),
         (status = NOT_FOUND, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
         (status = CONFLICT, body = ApiError),
     ), request_body = inline(Payload))]
     pub async fn route(
@@ -170,25 +184,40 @@ mod post {
 
         permissions.has_admin_permission("servers.create")?;
 
-        let node = match Node::by_uuid_optional(&state.database, data.node_uuid).await? {
-            Some(node) => node,
+        let owner = match User::by_uuid_optional(&state.database, data.owner_uuid).await? {
+            Some(user) => user,
             None => {
-                return ApiResponse::error("node not found")
+                return ApiResponse::error("owner not found")
                     .with_status(StatusCode::NOT_FOUND)
                     .ok();
             }
         };
 
-        let owner = match User::by_uuid_optional(&state.database, data.owner_uuid).await? {
-            Some(user) => user,
-            None => {
-                return ApiResponse::error("owner not found")
-                    .with_status(StatusCode::NOT_FOUND)
+        let template = match data.template_uuid {
+            Some(template_uuid) => {
+                match ServerTemplate::by_uuid_optional(&state.database, template_uuid).await? {
+                    Some(template) => Some(template),
+                    None => {
+                        return ApiResponse::error("server template not found")
+                            .with_status(StatusCode::NOT_FOUND)
+                            .ok();
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let egg_uuid = match (data.egg_uuid, &template) {
+            (Some(egg_uuid), _) => egg_uuid,
+            (None, Some(template)) => template.egg.uuid,
+            (None, None) => {
+                return ApiResponse::error("egg_uuid: required when not using a template")
+                    .with_status(StatusCode::BAD_REQUEST)
                     .ok();
             }
         };
 
-        let egg = match NestEgg::by_uuid_optional(&state.database, data.egg_uuid).await? {
+        let egg = match NestEgg::by_uuid_optional(&state.database, egg_uuid).await? {
             Some(egg) => egg,
             None => {
                 return ApiResponse::error("egg not found")
@@ -197,8 +226,98 @@ mod post {
             }
         };
 
-        let backup_configuration = if let Some(backup_configuration_uuid) =
-            data.backup_configuration_uuid
+        let limits = match (data.limits, &template) {
+            (Some(limits), _) => limits,
+            (None, Some(template)) => template.limits(),
+            (None, None) => {
+                return ApiResponse::error("limits: required when not using a template")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+        let feature_limits = match (data.feature_limits, &template) {
+            (Some(feature_limits), _) => feature_limits,
+            (None, Some(template)) => template.feature_limits(),
+            (None, None) => {
+                return ApiResponse::error("feature_limits: required when not using a template")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+        let pinned_cpus = data
+            .pinned_cpus
+            .or_else(|| template.as_ref().map(|template| template.pinned_cpus.clone()))
+            .unwrap_or_default();
+        let startup = data
+            .startup
+            .or_else(|| template.as_ref().and_then(|template| template.startup.clone()))
+            .unwrap_or_else(|| egg.startup.clone());
+        let image = match data
+            .image
+            .or_else(|| template.as_ref().and_then(|template| template.image.clone()))
+            .or_else(|| egg.docker_images.values().next().cloned())
+        {
+            Some(image) => image,
+            None => {
+                return ApiResponse::error("image: required, the egg has no docker images")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+        let timezone = match (data.timezone, &template) {
+            (Some(timezone), _) => Some(compact_str::CompactString::from(timezone.name())),
+            (None, Some(template)) => template.timezone.clone(),
+            (None, None) => None,
+        };
+
+        let mut variable_values: IndexMap<compact_str::CompactString, String> = IndexMap::new();
+        if let Some(template) = &template
+            && template.egg.uuid == egg.uuid
+        {
+            variable_values.extend(template.variables.clone());
+        }
+        for variable in &data.variables {
+            variable_values.insert(variable.env_variable.as_str().into(), variable.value.clone());
+        }
+
+        let (node, allocation_uuid, allocation_uuids) = if let Some(deploy) = &data.deploy {
+            let plan = deployment::plan(&state.database, &egg, deploy).await?;
+
+            let allocation_uuid = plan.primary_allocation_uuid();
+            let allocation_uuids = plan.additional_allocation_uuids();
+            match plan.node {
+                Some(node) => (node, allocation_uuid, allocation_uuids),
+                None => {
+                    return ApiResponse::error("no node matches the deployment requirements")
+                        .with_status(StatusCode::EXPECTATION_FAILED)
+                        .ok();
+                }
+            }
+        } else if let Some(node_uuid) = data.node_uuid {
+            match Node::by_uuid_optional(&state.database, node_uuid).await? {
+                Some(node) => (node, data.allocation_uuid, data.allocation_uuids.clone()),
+                None => {
+                    return ApiResponse::error("node not found")
+                        .with_status(StatusCode::NOT_FOUND)
+                        .ok();
+                }
+            }
+        } else {
+            return ApiResponse::error("either node_uuid or deploy is required")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        };
+
+        let backup_configuration = if let Some(backup_configuration_uuid) = data
+            .backup_configuration_uuid
+            .or_else(|| {
+                template.as_ref().and_then(|template| {
+                    template
+                        .backup_configuration
+                        .as_ref()
+                        .map(|backup_configuration| backup_configuration.uuid)
+                })
+            })
             && !backup_configuration_uuid.is_nil()
         {
             match BackupConfiguration::by_uuid_optional(&state.database, backup_configuration_uuid)
@@ -225,12 +344,8 @@ mod post {
                 variable.env_variable.as_str(),
                 (
                     variable.rules.as_slice(),
-                    if let Some(value) = data
-                        .variables
-                        .iter()
-                        .find(|v| v.env_variable == variable.env_variable)
-                    {
-                        value.value.as_str()
+                    if let Some(value) = variable_values.get(variable.env_variable.as_str()) {
+                        value.as_str()
                     } else {
                         variable.default_value.as_ref().map_or("", |v| v.as_str())
                     },
@@ -247,8 +362,8 @@ mod post {
             }
         };
 
-        if let Err(error) = validator.validate() {
-            return ApiResponse::error(&error)
+        if let Err(errors) = validator.validate() {
+            return ApiResponse::json(ApiError::new_fields_value(&errors))
                 .with_status(StatusCode::BAD_REQUEST)
                 .ok();
         }
@@ -256,16 +371,16 @@ mod post {
         let mut server_variables = HashMap::new();
         server_variables.reserve(variables.len());
 
-        for data_variable in &data.variables {
+        for (env_variable, value) in &variable_values {
             let variable_uuid = match variables
                 .iter()
-                .find(|v| v.env_variable == data_variable.env_variable)
+                .find(|v| v.env_variable == *env_variable)
             {
                 Some(variable) => variable.uuid,
                 None => continue,
             };
 
-            server_variables.insert(variable_uuid, data_variable.value.as_str());
+            server_variables.insert(variable_uuid, value.as_str());
         }
 
         let server = match Server::create(
@@ -274,19 +389,19 @@ mod post {
             owner.uuid,
             egg.uuid,
             backup_configuration.map(|backup_configuration| backup_configuration.uuid),
-            data.allocation_uuid,
-            &data.allocation_uuids,
+            allocation_uuid,
+            &allocation_uuids,
             data.external_id.as_deref(),
             data.start_on_completion,
             data.skip_installer,
             &data.name,
             data.description.as_deref(),
-            &data.limits,
-            &data.pinned_cpus,
-            &data.startup,
-            &data.image,
-            data.timezone.as_ref().map(|tz| tz.name()),
-            &data.feature_limits,
+            &limits,
+            &pinned_cpus,
+            &startup,
+            &image,
+            timezone.as_deref(),
+            &feature_limits,
             &server_variables,
         )
         .await
@@ -297,6 +412,11 @@ mod post {
                     .with_status(StatusCode::CONFLICT)
                     .ok();
             }
+            Err(shared::database::DatabaseError::Any(err))
+                if err.is::<shared::response::DisplayError<'static>>() =>
+            {
+                return Err(err.into());
+            }
             Err(err) => {
                 tracing::error!("failed to create server: {:?}", err);
 
@@ -306,6 +426,34 @@ mod post {
             }
         };
 
+        if let Some(template) = &template
+            && !template.mount_uuids.is_empty()
+        {
+            for mount_uuid in &template.mount_uuids {
+                let mount = match Mount::by_node_uuid_egg_uuid_uuid(
+                    &state.database,
+                    node.uuid,
+                    egg.uuid,
+                    *mount_uuid,
+                )
+                .await?
+                {
+                    Some(mount) => mount,
+                    None => continue,
+                };
+
+                ServerMount::create(&state.database, server.uuid, mount.uuid).await?;
+            }
+
+            let server = server.clone();
+            let database = state.database.clone();
+            tokio::spawn(async move {
+                if let Err(err) = server.sync(&database).await {
+                    tracing::error!("failed to sync server on node: {:?}", err);
+                }
+            });
+        }
+
         activity_logger
             .log(
                 "server:create",
@@ -314,9 +462,10 @@ mod post {
                     "node_uuid": node.uuid,
                     "owner_uuid": owner.uuid,
                     "egg_uuid": egg.uuid,
+                    "template_uuid": template.as_ref().map(|template| template.uuid),
 
-                    "allocation_uuid": data.allocation_uuid,
-                    "allocation_uuids": data.allocation_uuids,
+                    "allocation_uuid": allocation_uuid,
+                    "allocation_uuids": allocation_uuids,
                     "external_id": data.external_id,
 
                     "start_on_completion": data.start_on_completion,
@@ -324,13 +473,13 @@ mod post {
 
                     "name": data.name,
                     "description": data.description,
-                    "limits": data.limits,
-                    "pinned_cpus": data.pinned_cpus,
-                    "startup": data.startup,
-                    "image": data.image,
-                    "timezone": data.timezone,
-                    "feature_limits": data.feature_limits,
-                    "variables": data.variables,
+                    "limits": limits,
+                    "pinned_cpus": pinned_cpus,
+                    "startup": startup,
+                    "image": image,
+                    "timezone": timezone,
+                    "feature_limits": feature_limits,
+                    "variables": variable_values,
                 }),
             )
             .await;
@@ -350,5 +499,6 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(post::route))
         .nest("/{server}", _server_::router(state))
         .nest("/external", external::router(state))
+        .nest("/deployment-plan", deployment_plan::router(state))
         .with_state(state.clone())
 }
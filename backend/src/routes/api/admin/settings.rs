@@ -56,6 +56,8 @@ mod put {
         two_factor_requirement: Option<shared::settings::TwoFactorRequirement>,
         telemetry_enabled: Option<bool>,
         registration_enabled: Option<bool>,
+        magic_link_login_enabled: Option<bool>,
+        node_status_mails_enabled: Option<bool>,
     }
 
     #[derive(ToSchema, Validate, Deserialize)]
@@ -77,6 +79,13 @@ mod put {
         allow_editing_startup_command: Option<bool>,
     }
 
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct PayloadMetrics {
+        enabled: Option<bool>,
+        #[validate(length(min = 16, max = 255))]
+        token: Option<compact_str::CompactString>,
+    }
+
     #[derive(ToSchema, Validate, Deserialize)]
     pub struct Payload {
         oobe_step: Option<compact_str::CompactString>,
@@ -91,6 +100,9 @@ mod put {
         webauthn: Option<PayloadWebauthn>,
         #[schema(inline)]
         server: Option<PayloadServer>,
+        #[validate(nested)]
+        #[schema(inline)]
+        metrics: Option<PayloadMetrics>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -152,6 +164,12 @@ mod put {
             if let Some(registration_enabled) = app.registration_enabled {
                 settings.app.registration_enabled = registration_enabled;
             }
+            if let Some(magic_link_login_enabled) = app.magic_link_login_enabled {
+                settings.app.magic_link_login_enabled = magic_link_login_enabled;
+            }
+            if let Some(node_status_mails_enabled) = app.node_status_mails_enabled {
+                settings.app.node_status_mails_enabled = node_status_mails_enabled;
+            }
         }
         if let Some(webauthn) = data.webauthn {
             if let Some(rp_id) = webauthn.rp_id {
@@ -187,6 +205,14 @@ mod put {
                 settings.server.allow_editing_startup_command = allow_editing_startup_command;
             }
         }
+        if let Some(metrics) = data.metrics {
+            if let Some(enabled) = metrics.enabled {
+                settings.metrics.enabled = enabled;
+            }
+            if let Some(token) = metrics.token {
+                settings.metrics.token = token;
+            }
+        }
 
         let settings_json = settings.censored();
         settings.save().await?;
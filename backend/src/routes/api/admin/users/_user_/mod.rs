@@ -18,6 +18,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod activity;
 mod oauth_links;
+mod quota;
 mod servers;
 mod two_factor;
 
@@ -378,6 +379,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/servers", servers::router(state))
         .nest("/activity", activity::router(state))
         .nest("/oauth-links", oauth_links::router(state))
+        .nest("/quota", quota::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
 }
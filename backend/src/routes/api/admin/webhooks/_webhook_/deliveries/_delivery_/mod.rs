@@ -0,0 +1,108 @@
+use super::State;
+use crate::routes::api::admin::webhooks::_webhook_::GetWebhook;
+use axum::{
+    extract::{Path, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{
+    GetState,
+    models::{user::GetPermissionManager, webhook_delivery::WebhookDelivery},
+    response::ApiResponse,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod retry;
+
+pub type GetWebhookDelivery = shared::extract::ConsumingExtension<WebhookDelivery>;
+
+pub async fn auth(
+    state: GetState,
+    permissions: GetPermissionManager,
+    webhook: GetWebhook,
+    Path(delivery): Path<Vec<String>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let delivery = match delivery.get(1).map(|s| s.parse::<uuid::Uuid>()) {
+        Some(Ok(id)) => id,
+        _ => {
+            return Ok(ApiResponse::error("invalid delivery uuid")
+                .with_status(StatusCode::BAD_REQUEST)
+                .into_response());
+        }
+    };
+
+    if let Err(err) = permissions.has_admin_permission("webhooks.read") {
+        return Ok(err.into_response());
+    }
+
+    let delivery =
+        WebhookDelivery::by_webhook_uuid_uuid(&state.database, webhook.uuid, delivery).await;
+    let delivery = match delivery {
+        Ok(Some(delivery)) => delivery,
+        Ok(None) => {
+            return Ok(ApiResponse::error("delivery not found")
+                .with_status(StatusCode::NOT_FOUND)
+                .into_response());
+        }
+        Err(err) => return Ok(ApiResponse::from(err).into_response()),
+    };
+
+    req.extensions_mut().insert(webhook.0);
+    req.extensions_mut().insert(delivery);
+
+    Ok(next.run(req).await)
+}
+
+mod get {
+    use crate::routes::api::admin::webhooks::_webhook_::deliveries::_delivery_::GetWebhookDelivery;
+    use serde::Serialize;
+    use shared::{
+        ApiError,
+        models::user::GetPermissionManager,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        delivery: shared::models::webhook_delivery::AdminApiWebhookDelivery,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "webhook" = uuid::Uuid,
+            description = "The webhook ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "delivery" = uuid::Uuid,
+            description = "The delivery ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        permissions: GetPermissionManager,
+        delivery: GetWebhookDelivery,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("webhooks.read")?;
+
+        ApiResponse::json(Response {
+            delivery: delivery.0.into_admin_api_object(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/retry", retry::router(state))
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
+        .with_state(state.clone())
+}
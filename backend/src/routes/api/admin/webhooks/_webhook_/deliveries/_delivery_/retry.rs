@@ -0,0 +1,74 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::admin::webhooks::_webhook_::{
+        GetWebhook, deliveries::_delivery_::GetWebhookDelivery,
+    };
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger, user::GetPermissionManager,
+            webhook_delivery::WebhookDeliveryStatus,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "webhook" = uuid::Uuid,
+            description = "The webhook ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "delivery" = uuid::Uuid,
+            description = "The delivery ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        webhook: GetWebhook,
+        delivery: GetWebhookDelivery,
+        activity_logger: GetAdminActivityLogger,
+    ) -> ApiResponseResult {
+        permissions.has_admin_permission("webhooks.update")?;
+
+        if delivery.status == WebhookDeliveryStatus::Pending {
+            return ApiResponse::error("delivery is already pending")
+                .with_status(StatusCode::CONFLICT)
+                .ok();
+        }
+
+        delivery.retry(&state.database).await?;
+
+        activity_logger
+            .log(
+                "webhook:delivery.retry",
+                serde_json::json!({
+                    "uuid": webhook.uuid,
+                    "delivery_uuid": delivery.uuid,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,94 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _delivery_;
+
+mod get {
+    use crate::routes::api::admin::webhooks::_webhook_::GetWebhook;
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParamsWithSearch, user::GetPermissionManager,
+            webhook_delivery::WebhookDelivery,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        deliveries: Pagination<shared::models::webhook_delivery::AdminApiWebhookDelivery>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "webhook" = uuid::Uuid,
+            description = "The webhook ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        webhook: GetWebhook,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("webhooks.read")?;
+
+        let deliveries = WebhookDelivery::by_webhook_uuid_with_pagination(
+            &state.database,
+            webhook.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            deliveries: Pagination {
+                total: deliveries.total,
+                per_page: deliveries.per_page,
+                page: deliveries.page,
+                data: deliveries
+                    .data
+                    .into_iter()
+                    .map(|delivery| delivery.into_admin_api_object())
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .nest("/{delivery}", _delivery_::router(state))
+        .with_state(state.clone())
+}
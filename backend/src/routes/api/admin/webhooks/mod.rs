@@ -0,0 +1,192 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _webhook_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParamsWithSearch, user::GetPermissionManager, webhook::Webhook,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        webhooks: Pagination<shared::models::webhook::AdminApiWebhook>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("webhooks.read")?;
+
+        let webhooks = Webhook::all_with_pagination(
+            &state.database,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            webhooks: webhooks
+                .try_async_map(|webhook| webhook.into_admin_api_object(&state.database))
+                .await?,
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use rand::distr::SampleString;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            admin_activity::GetAdminActivityLogger, user::GetPermissionManager, webhook::Webhook,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(length(min = 3, max = 255))]
+        #[schema(min_length = 3, max_length = 255)]
+        name: compact_str::CompactString,
+        #[validate(length(max = 1024))]
+        #[schema(max_length = 1024)]
+        description: Option<compact_str::CompactString>,
+        enabled: bool,
+
+        #[validate(url, length(max = 255))]
+        #[schema(format = "uri", max_length = 255)]
+        url: String,
+        #[validate(length(min = 16, max = 255))]
+        #[schema(min_length = 16, max_length = 255)]
+        secret: Option<compact_str::CompactString>,
+        #[validate(length(max = 255))]
+        #[schema(max_length = 255)]
+        events: Vec<compact_str::CompactString>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        webhook: shared::models::webhook::AdminApiWebhook,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        activity_logger: GetAdminActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_admin_permission("webhooks.create")?;
+
+        let secret = match data.secret {
+            Some(secret) => secret,
+            None => rand::distr::Alphanumeric
+                .sample_string(&mut rand::rng(), 48)
+                .into(),
+        };
+
+        let webhook = match Webhook::create(
+            &state.database,
+            &data.name,
+            data.description.as_deref(),
+            &data.url,
+            &secret,
+            &data.events,
+            data.enabled,
+        )
+        .await
+        {
+            Ok(webhook) => webhook,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("webhook with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => {
+                tracing::error!("failed to create webhook: {:?}", err);
+
+                return ApiResponse::error("failed to create webhook")
+                    .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .ok();
+            }
+        };
+
+        activity_logger
+            .log(
+                "webhook:create",
+                serde_json::json!({
+                    "uuid": webhook.uuid,
+                    "name": webhook.name,
+                    "description": webhook.description,
+                    "enabled": webhook.enabled,
+
+                    "url": webhook.url,
+                    "events": webhook.events,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            webhook: webhook.into_admin_api_object(&state.database).await?,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{webhook}", _webhook_::router(state))
+        .with_state(state.clone())
+}
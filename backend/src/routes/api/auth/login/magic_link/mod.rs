@@ -0,0 +1,128 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod redeem;
+
+mod post {
+    use axum::http::{HeaderMap, StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{user::User, user_activity::UserActivity, user_magic_link::UserMagicLink},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(email)]
+        #[schema(format = "email")]
+        email: String,
+
+        captcha: Option<String>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = TOO_MANY_REQUESTS, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        ip: shared::GetIp,
+        headers: HeaderMap,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        if !state.settings.get().await.app.magic_link_login_enabled {
+            return ApiResponse::error("magic link login is disabled")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        state
+            .cache
+            .ratelimit("auth/login/magic-link", 5, 300, ip.to_string())
+            .await?;
+
+        if let Err(error) = state.captcha.verify(ip, data.captcha).await {
+            return ApiResponse::error(&error)
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let user = match User::by_email(&state.database, &data.email).await? {
+            Some(user) => user,
+            None => return ApiResponse::json(Response {}).ok(),
+        };
+
+        tokio::spawn(async move {
+            let token = match UserMagicLink::create(&state.database, user.uuid).await {
+                Ok(token) => token,
+                Err(err) => {
+                    tracing::warn!(
+                        user = %user.uuid,
+                        "failed to create magic link token: {:#?}",
+                        err
+                    );
+                    return;
+                }
+            };
+
+            let settings = state.settings.get().await;
+            let login_link = format!(
+                "{}/auth/magic-link?token={}",
+                settings.app.url,
+                urlencoding::encode(&token),
+            );
+            drop(settings);
+
+            UserActivity::log(
+                &state.database,
+                user.uuid,
+                None,
+                "email:magic-link",
+                Some(ip.0.into()),
+                serde_json::json!({
+                    "user_agent": headers
+                        .get("User-Agent")
+                        .map(|ua| shared::utils::slice_up_to(ua.to_str().unwrap_or("unknown"), 255))
+                        .unwrap_or("unknown"),
+                }),
+            )
+            .await
+            .ok();
+
+            state
+                .mail
+                .send_template(
+                    user.email.clone(),
+                    "magic_link",
+                    Some(&user),
+                    serde_json::json!({
+                        "login_link": login_link,
+                        "expires_minutes": UserMagicLink::VALIDITY_MINUTES,
+                    }),
+                )
+                .await;
+        });
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .nest("/redeem", redeem::router(state))
+        .with_state(state.clone())
+}
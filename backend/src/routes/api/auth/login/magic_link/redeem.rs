@@ -0,0 +1,163 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod post {
+    use crate::routes::api::auth::login::checkpoint::TwoFactorRequiredJwt;
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        jwt::BasePayload,
+        models::{
+            user_activity::UserActivity, user_magic_link::UserMagicLink, user_session::UserSession,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use tower_cookies::{Cookie, Cookies};
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(length(equal = 96))]
+        #[schema(min_length = 96, max_length = 96)]
+        token: String,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    enum Response {
+        Completed {
+            user: Box<shared::models::user::ApiFullUser>,
+        },
+        TwoFactorRequired {
+            token: String,
+        },
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = TOO_MANY_REQUESTS, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        ip: shared::GetIp,
+        headers: axum::http::HeaderMap,
+        cookies: Cookies,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        if !state.settings.get().await.app.magic_link_login_enabled {
+            return ApiResponse::error("magic link login is disabled")
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        state
+            .cache
+            .ratelimit("auth/login/magic-link/redeem", 10, 300, ip.to_string())
+            .await?;
+
+        let user = match UserMagicLink::delete_by_token(&state.database, &data.token).await? {
+            Some(magic_link) => magic_link.user,
+            None => {
+                return ApiResponse::error("invalid or expired magic link")
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
+        if user.totp_enabled
+            && let Some(secret) = &user.totp_secret
+        {
+            let token = state.jwt.create(&TwoFactorRequiredJwt {
+                base: BasePayload {
+                    issuer: "panel".into(),
+                    subject: None,
+                    audience: Vec::new(),
+                    expiration_time: Some(chrono::Utc::now().timestamp() + 300),
+                    not_before: None,
+                    issued_at: Some(chrono::Utc::now().timestamp()),
+                    jwt_id: user.uuid.to_string(),
+                },
+                user_uuid: user.uuid,
+                user_totp_secret: secret.clone(),
+            })?;
+
+            if let Err(err) = UserActivity::log(
+                &state.database,
+                user.uuid,
+                None,
+                "auth:checkpoint",
+                Some(ip.0.into()),
+                serde_json::json!({
+                    "using": "magic_link",
+                }),
+            )
+            .await
+            {
+                tracing::warn!(user = %user.uuid, "failed to log user activity: {:?}", err);
+            }
+
+            ApiResponse::json(Response::TwoFactorRequired { token }).ok()
+        } else {
+            let key = UserSession::create(
+                &state.database,
+                user.uuid,
+                ip.0.into(),
+                headers
+                    .get("User-Agent")
+                    .map(|ua| shared::utils::slice_up_to(ua.to_str().unwrap_or("unknown"), 255))
+                    .unwrap_or("unknown"),
+            )
+            .await?;
+
+            let settings = state.settings.get().await;
+
+            cookies.add(
+                Cookie::build(("session", key))
+                    .http_only(true)
+                    .same_site(tower_cookies::cookie::SameSite::Strict)
+                    .secure(settings.app.url.starts_with("https://"))
+                    .path("/")
+                    .expires(
+                        tower_cookies::cookie::time::OffsetDateTime::now_utc()
+                            + tower_cookies::cookie::time::Duration::days(30),
+                    )
+                    .build(),
+            );
+
+            if let Err(err) = UserActivity::log(
+                &state.database,
+                user.uuid,
+                None,
+                "auth:success",
+                Some(ip.0.into()),
+                serde_json::json!({
+                    "using": "magic_link",
+                }),
+            )
+            .await
+            {
+                tracing::warn!(user = %user.uuid, "failed to log user activity: {:?}", err);
+            }
+
+            ApiResponse::json(Response::Completed {
+                user: Box::new(user.into_api_full_object(&state.storage.retrieve_urls().await)),
+            })
+            .ok()
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -2,6 +2,7 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod checkpoint;
+mod magic_link;
 mod security_key;
 
 mod post {
@@ -168,5 +169,6 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(post::route))
         .nest("/security-key", security_key::router(state))
         .nest("/checkpoint", checkpoint::router(state))
+        .nest("/magic-link", magic_link::router(state))
         .with_state(state.clone())
 }
@@ -65,18 +65,12 @@ mod post {
             };
 
             let settings = state.settings.get().await;
-
-            let mail = shared::mail::MAIL_PASSWORD_RESET
-                .replace("{{app_name}}", &settings.app.name)
-                .replace("{{user_username}}", &user.username)
-                .replace(
-                    "{{reset_link}}",
-                    &format!(
-                        "{}/auth/reset-password?token={}",
-                        settings.app.url,
-                        urlencoding::encode(&token),
-                    ),
-                );
+            let reset_link = format!(
+                "{}/auth/reset-password?token={}",
+                settings.app.url,
+                urlencoding::encode(&token),
+            );
+            drop(settings);
 
             UserActivity::log(
                 &state.database,
@@ -96,10 +90,13 @@ mod post {
 
             state
                 .mail
-                .send(
-                    user.email,
-                    format!("{} - Password Reset", settings.app.name).into(),
-                    mail,
+                .send_template(
+                    user.email.clone(),
+                    "password_reset",
+                    Some(&user),
+                    serde_json::json!({
+                        "reset_link": reset_link,
+                    }),
                 )
                 .await;
         });
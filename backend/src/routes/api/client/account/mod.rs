@@ -6,8 +6,10 @@ mod api_keys;
 mod avatar;
 mod email;
 mod logout;
+mod notification_channels;
 mod oauth_links;
 mod password;
+mod quota;
 mod security_keys;
 mod sessions;
 mod ssh_keys;
@@ -163,7 +165,12 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/oauth-links", oauth_links::router(state))
         .nest("/api-keys", api_keys::router(state))
         .nest("/ssh-keys", ssh_keys::router(state))
+        .nest(
+            "/notification-channels",
+            notification_channels::router(state),
+        )
         .nest("/sessions", sessions::router(state))
         .nest("/activity", activity::router(state))
+        .nest("/quota", quota::router(state))
         .with_state(state.clone())
 }
@@ -0,0 +1,206 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod delete {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            DeletableModel,
+            user::{GetPermissionManager, GetUser},
+            user_activity::GetUserActivityLogger,
+            user_notification_channel::UserNotificationChannel,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+    ), params(
+        (
+            "notification_channel" = uuid::Uuid,
+            description = "The notification channel ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetUserActivityLogger,
+        Path(notification_channel): Path<uuid::Uuid>,
+    ) -> ApiResponseResult {
+        permissions.has_user_permission("notification-channels.delete")?;
+
+        let notification_channel = match UserNotificationChannel::by_user_uuid_uuid(
+            &state.database,
+            user.uuid,
+            notification_channel,
+        )
+        .await?
+        {
+            Some(notification_channel) => notification_channel,
+            None => {
+                return ApiResponse::error("notification channel not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        notification_channel.delete(&state.database, ()).await?;
+
+        activity_logger
+            .log(
+                "notification-channel:delete",
+                serde_json::json!({
+                    "uuid": notification_channel.uuid,
+                    "name": notification_channel.name,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+mod patch {
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            user::{GetPermissionManager, GetUser},
+            user_activity::GetUserActivityLogger,
+            user_notification_channel::{UserNotificationChannel, UserNotificationEvent},
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(length(min = 3, max = 31))]
+        #[schema(min_length = 3, max_length = 31)]
+        name: Option<compact_str::CompactString>,
+        #[validate(url, length(max = 255))]
+        #[schema(format = "uri", max_length = 255)]
+        url: Option<String>,
+        #[validate(length(max = 5))]
+        #[schema(max_items = 5)]
+        events: Option<Vec<UserNotificationEvent>>,
+        enabled: Option<bool>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {}
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = ApiError),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), params(
+        (
+            "notification_channel" = uuid::Uuid,
+            description = "The notification channel ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetUserActivityLogger,
+        Path(notification_channel): Path<uuid::Uuid>,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_user_permission("notification-channels.update")?;
+
+        let mut notification_channel = match UserNotificationChannel::by_user_uuid_uuid(
+            &state.database,
+            user.uuid,
+            notification_channel,
+        )
+        .await?
+        {
+            Some(notification_channel) => notification_channel,
+            None => {
+                return ApiResponse::error("notification channel not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        if let Some(name) = data.name {
+            notification_channel.name = name;
+        }
+        if let Some(url) = data.url {
+            if let Err(err) = notification_channel.r#type.validate_url(Some(&url)) {
+                return ApiResponse::error(err)
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+
+            notification_channel.url = Some(url.into());
+        }
+        if let Some(mut events) = data.events {
+            events.sort_by_key(|event| event.as_str());
+            events.dedup();
+
+            notification_channel.events = events;
+        }
+        if let Some(enabled) = data.enabled {
+            notification_channel.enabled = enabled;
+        }
+
+        match notification_channel.update(&state.database).await {
+            Ok(_) => {}
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("notification channel with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => {
+                tracing::error!("failed to update notification channel: {:?}", err);
+
+                return ApiResponse::error("failed to update notification channel")
+                    .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .ok();
+            }
+        }
+
+        activity_logger
+            .log(
+                "notification-channel:update",
+                serde_json::json!({
+                    "uuid": notification_channel.uuid,
+                    "name": notification_channel.name,
+                    "events": notification_channel.events,
+                    "enabled": notification_channel.enabled,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {}).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(delete::route))
+        .routes(routes!(patch::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,206 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _notification_channel_;
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParamsWithSearch,
+            user::{GetPermissionManager, GetUser},
+            user_notification_channel::UserNotificationChannel,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        notification_channels:
+            Pagination<shared::models::user_notification_channel::ApiUserNotificationChannel>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+        (
+            "search" = Option<String>, Query,
+            description = "Search term for items",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        Query(params): Query<PaginationParamsWithSearch>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_user_permission("notification-channels.read")?;
+
+        let notification_channels = UserNotificationChannel::by_user_uuid_with_pagination(
+            &state.database,
+            user.uuid,
+            params.page,
+            params.per_page,
+            params.search.as_deref(),
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            notification_channels: Pagination {
+                total: notification_channels.total,
+                per_page: notification_channels.per_page,
+                page: notification_channels.page,
+                data: notification_channels
+                    .data
+                    .into_iter()
+                    .map(|notification_channel| notification_channel.into_api_object())
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            user::{GetPermissionManager, GetUser},
+            user_activity::GetUserActivityLogger,
+            user_notification_channel::{
+                UserNotificationChannel, UserNotificationChannelType, UserNotificationEvent,
+            },
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(length(min = 3, max = 31))]
+        #[schema(min_length = 3, max_length = 31)]
+        name: compact_str::CompactString,
+        r#type: UserNotificationChannelType,
+        #[validate(url, length(max = 255))]
+        #[schema(format = "uri", max_length = 255)]
+        url: Option<String>,
+        #[validate(length(max = 5))]
+        #[schema(max_items = 5)]
+        events: Vec<UserNotificationEvent>,
+        enabled: bool,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        notification_channel: shared::models::user_notification_channel::ApiUserNotificationChannel,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetUserActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_user_permission("notification-channels.create")?;
+
+        if let Err(err) = data.r#type.validate_url(data.url.as_deref()) {
+            return ApiResponse::error(err)
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let mut events = data.events;
+        events.sort_by_key(|event| event.as_str());
+        events.dedup();
+
+        let notification_channel = match UserNotificationChannel::create(
+            &state.database,
+            user.uuid,
+            &data.name,
+            data.r#type,
+            data.url.as_deref(),
+            &events,
+            data.enabled,
+        )
+        .await
+        {
+            Ok(notification_channel) => notification_channel,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("notification channel with name already exists")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => {
+                tracing::error!("failed to create notification channel: {:?}", err);
+
+                return ApiResponse::error("failed to create notification channel")
+                    .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .ok();
+            }
+        };
+
+        activity_logger
+            .log(
+                "notification-channel:create",
+                serde_json::json!({
+                    "uuid": notification_channel.uuid,
+                    "name": notification_channel.name,
+                    "type": notification_channel.r#type,
+                    "events": notification_channel.events,
+                    "enabled": notification_channel.enabled,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            notification_channel: notification_channel.into_api_object(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest(
+            "/{notification_channel}",
+            _notification_channel_::router(state),
+        )
+        .with_state(state.clone())
+}
@@ -0,0 +1,38 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        GetState,
+        models::{resource_quota::ResourceQuota, user::GetUser},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        quota: Option<shared::models::resource_quota::ApiResourceQuota>,
+        usage: shared::models::resource_quota::ApiResourceUsage,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ))]
+    pub async fn route(state: GetState, user: GetUser) -> ApiResponseResult {
+        let quota = ResourceQuota::effective_by_user_uuid(&state.database, user.uuid).await?;
+        let usage = ResourceQuota::usage_by_user_uuid(&state.database, user.uuid, None).await?;
+
+        ApiResponse::json(Response {
+            quota: quota.map(|quota| quota.into_api_object()),
+            usage,
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,235 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::{extract::Query, http::StatusCode};
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{
+            Pagination, PaginationParams, server::GetServer, server_clone::ServerClone,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        clones: Pagination<shared::models::server_clone::ApiServerClone>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+        (
+            "page" = i64, Query,
+            description = "The page number",
+            example = "1",
+        ),
+        (
+            "per_page" = i64, Query,
+            description = "The number of items per page",
+            example = "10",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        Query(params): Query<PaginationParams>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&params) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("clones.read")?;
+
+        let clones = ServerClone::by_server_uuid_with_pagination(
+            &state.database,
+            server.uuid,
+            params.page,
+            params.per_page,
+        )
+        .await?;
+
+        ApiResponse::json(Response {
+            clones: Pagination {
+                total: clones.total,
+                per_page: clones.per_page,
+                page: clones.page,
+                data: clones
+                    .data
+                    .into_iter()
+                    .map(|clone| clone.into_api_object())
+                    .collect(),
+            },
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        deployment::DeploymentRequest,
+        models::{
+            resource_quota::ResourceQuota,
+            server::{GetServer, GetServerActivityLogger},
+            server_allocation::ServerAllocation,
+            server_clone::ServerClone,
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        #[validate(length(min = 3, max = 255))]
+        #[schema(min_length = 3, max_length = 255)]
+        name: compact_str::CompactString,
+
+        copy_files: bool,
+        copy_databases: bool,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        clone: shared::models::server_clone::ApiServerClone,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("clones.create")?;
+
+        if ResourceQuota::effective_by_user_uuid(&state.database, server.owner.uuid)
+            .await?
+            .is_none()
+        {
+            return ApiResponse::error(
+                "the server owner does not have a resource quota to clone servers with",
+            )
+            .with_status(StatusCode::FORBIDDEN)
+            .ok();
+        }
+
+        let plan = shared::deployment::plan_restricted(
+            &state.database,
+            &server.egg,
+            &DeploymentRequest {
+                location_uuids: Vec::new(),
+                memory: server.memory,
+                disk: server.disk,
+                allocations: ServerAllocation::count_by_server_uuid(&state.database, server.uuid)
+                    .await
+                    .max(1),
+                port_start: None,
+                port_end: None,
+            },
+            &[server.node.uuid],
+            &[],
+        )
+        .await?;
+
+        let allocation_uuid = plan.primary_allocation_uuid();
+        let allocation_uuids = plan.additional_allocation_uuids();
+        let node = match plan.node {
+            Some(node) => node,
+            None => {
+                return ApiResponse::error("the node of this server does not have enough free resources")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
+        };
+
+        let clone = match ServerClone::create(
+            &state.database,
+            &server,
+            &node,
+            allocation_uuid,
+            &allocation_uuids,
+            &data.name,
+            data.copy_files,
+            data.copy_databases,
+        )
+        .await
+        {
+            Ok(clone) => clone,
+            Err(err)
+                if err
+                    .downcast_ref::<sqlx::Error>()
+                    .and_then(|err| err.as_database_error())
+                    .is_some_and(|err| err.is_unique_violation()) =>
+            {
+                return ApiResponse::error("the selected allocation was taken, please try again")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        activity_logger
+            .log(
+                "server:clone",
+                serde_json::json!({
+                    "uuid": clone.uuid,
+                    "clone_server_uuid": clone.clone_server.uuid,
+
+                    "name": data.name,
+                    "copy_files": data.copy_files,
+                    "copy_databases": data.copy_databases,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            clone: clone.into_api_object(),
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
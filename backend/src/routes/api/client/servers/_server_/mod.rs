@@ -19,6 +19,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 mod activity;
 mod allocations;
 mod backups;
+mod clones;
 mod command;
 mod databases;
 mod files;
@@ -63,9 +64,21 @@ pub async fn auth(
     if !IGNORED_STATUS_PATHS.contains(&matched_path.as_str()) {
         if server.suspended {
             if !user.admin {
-                return Ok(ApiResponse::error("server is suspended")
-                    .with_status(StatusCode::CONFLICT)
-                    .into_response());
+                let message = match &server.suspension {
+                    Some(suspension) => suspension
+                        .fetch_cached(&state.database)
+                        .await
+                        .ok()
+                        .and_then(|suspension| suspension.message),
+                    None => None,
+                };
+
+                return Ok(ApiResponse::error(&match message {
+                    Some(message) => format!("server is suspended: {message}"),
+                    None => "server is suspended".to_string(),
+                })
+                .with_status(StatusCode::CONFLICT)
+                .into_response());
             }
         } else if server.destination_node.is_some() {
             return Ok(ApiResponse::error("server is being transferred")
@@ -149,6 +162,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/startup", startup::router(state))
         .nest("/subusers", subusers::router(state))
         .nest("/backups", backups::router(state))
+        .nest("/clones", clones::router(state))
         .nest("/allocations", allocations::router(state))
         .nest("/databases", databases::router(state))
         .nest("/mounts", mounts::router(state))
@@ -0,0 +1,167 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        ApiError, GetState,
+        models::{nest_egg::NestEgg, server::GetServer, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        eggs: Vec<shared::models::nest_egg::ApiNestEgg>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = UNAUTHORIZED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+    ) -> ApiResponseResult {
+        permissions.has_server_permission("settings.change-egg")?;
+
+        let eggs =
+            NestEgg::all_user_selectable_by_nest_uuid(&state.database, server.nest.uuid).await?;
+
+        ApiResponse::json(Response {
+            eggs: eggs
+                .into_iter()
+                .filter(|egg| egg.uuid != server.egg.uuid)
+                .map(|egg| egg.into_api_object())
+                .collect(),
+        })
+        .ok()
+    }
+}
+
+mod post {
+    use axum::http::StatusCode;
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        models::{
+            nest_egg::NestEgg,
+            server::{GetServer, GetServerActivityLogger, ServerChangeEggOptions},
+            user::GetPermissionManager,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        egg_uuid: uuid::Uuid,
+
+        #[validate(length(min = 2, max = 255))]
+        #[schema(min_length = 2, max_length = 255)]
+        image: Option<compact_str::CompactString>,
+        #[serde(default)]
+        variables: IndexMap<compact_str::CompactString, String>,
+
+        truncate_directory: bool,
+        backup: bool,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        backup_uuid: Option<uuid::Uuid>,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = ACCEPTED, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = UNAUTHORIZED, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+    ), params(
+        (
+            "server" = uuid::Uuid,
+            description = "The server ID",
+            example = "123e4567-e89b-12d3-a456-426614174000",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        server: GetServer,
+        activity_logger: GetServerActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_server_permission("settings.change-egg")?;
+
+        let egg = match NestEgg::by_nest_uuid_uuid(&state.database, server.nest.uuid, data.egg_uuid)
+            .await?
+        {
+            Some(egg) if egg.user_selectable && egg.uuid != server.egg.uuid => egg,
+            _ => {
+                return ApiResponse::error("egg not found")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        let old_egg_uuid = server.egg.uuid;
+
+        let backup_uuid = server
+            .0
+            .change_egg(
+                &state.database,
+                egg,
+                ServerChangeEggOptions {
+                    startup: None,
+                    image: data.image.clone(),
+                    variables: data.variables.clone(),
+                    restricted: true,
+                    truncate_directory: data.truncate_directory,
+                    backup: data.backup,
+                },
+            )
+            .await?;
+
+        activity_logger
+            .log(
+                "server:settings.egg",
+                serde_json::json!({
+                    "old_egg_uuid": old_egg_uuid,
+                    "egg_uuid": data.egg_uuid,
+                    "backup_uuid": backup_uuid,
+
+                    "image": data.image,
+                    "variables": data.variables,
+                    "truncate_directory": data.truncate_directory,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response { backup_uuid })
+            .with_status(StatusCode::ACCEPTED)
+            .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .with_state(state.clone())
+}
@@ -3,6 +3,7 @@ use utoipa_axum::router::OpenApiRouter;
 
 mod auto_kill;
 mod auto_start;
+mod egg;
 mod install;
 mod rename;
 mod timezone;
@@ -14,5 +15,6 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/timezone", timezone::router(state))
         .nest("/auto-kill", auto_kill::router(state))
         .nest("/auto-start", auto_start::router(state))
+        .nest("/egg", egg::router(state))
         .with_state(state.clone())
 }
@@ -0,0 +1,54 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use serde::Serialize;
+    use shared::{
+        GetState,
+        models::{location_nest_egg::LocationNestEgg, user::GetPermissionManager},
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct ResponseLocation {
+        location: shared::models::location::ApiLocation,
+        eggs: Vec<shared::models::nest_egg::ApiNestEgg>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        #[schema(inline)]
+        locations: Vec<ResponseLocation>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ))]
+    pub async fn route(state: GetState, permissions: GetPermissionManager) -> ApiResponseResult {
+        permissions.has_user_permission("servers.deploy")?;
+
+        let mut locations: Vec<ResponseLocation> = Vec::new();
+
+        for location_egg in LocationNestEgg::all(&state.database).await? {
+            match locations
+                .iter_mut()
+                .find(|l| l.location.uuid == location_egg.location.uuid)
+            {
+                Some(location) => location.eggs.push(location_egg.egg.into_api_object()),
+                None => locations.push(ResponseLocation {
+                    location: location_egg.location.into_api_object(),
+                    eggs: vec![location_egg.egg.into_api_object()],
+                }),
+            }
+        }
+
+        ApiResponse::json(Response { locations }).ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -2,6 +2,7 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 pub mod _server_;
+mod deployable;
 mod groups;
 
 mod get {
@@ -110,9 +111,286 @@ mod get {
     }
 }
 
+mod post {
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use shared::{
+        ApiError, GetState,
+        deployment::DeploymentRequest,
+        models::{
+            ByUuid,
+            location_nest_egg::LocationNestEgg,
+            nest_egg_variable::NestEggVariable,
+            resource_quota::ResourceQuota,
+            server::{ApiServerFeatureLimits, ApiServerLimits, Server},
+            user::{GetPermissionManager, GetUser},
+            user_activity::GetUserActivityLogger,
+        },
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use std::collections::HashMap;
+    use utoipa::ToSchema;
+    use validator::Validate;
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct PayloadLimits {
+        #[validate(range(min = 0))]
+        #[schema(minimum = 0)]
+        cpu: i32,
+        #[validate(range(min = 1))]
+        #[schema(minimum = 1)]
+        memory: i64,
+        #[validate(range(min = 1))]
+        #[schema(minimum = 1)]
+        disk: i64,
+    }
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct PayloadVariable {
+        #[validate(length(min = 1, max = 255))]
+        #[schema(min_length = 1, max_length = 255)]
+        env_variable: String,
+        #[validate(length(max = 4096))]
+        #[schema(max_length = 4096)]
+        value: String,
+    }
+
+    #[derive(ToSchema, Validate, Deserialize)]
+    pub struct Payload {
+        location_uuid: uuid::Uuid,
+        egg_uuid: uuid::Uuid,
+
+        #[validate(length(min = 3, max = 255))]
+        #[schema(min_length = 3, max_length = 255)]
+        name: compact_str::CompactString,
+        #[validate(length(max = 1024))]
+        #[schema(max_length = 1024)]
+        description: Option<compact_str::CompactString>,
+
+        #[validate(nested)]
+        #[schema(inline)]
+        limits: PayloadLimits,
+        #[validate(nested)]
+        feature_limits: ApiServerFeatureLimits,
+        #[validate(nested)]
+        #[schema(inline)]
+        variables: Vec<PayloadVariable>,
+    }
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        server: shared::models::server::ApiServer,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = ApiError),
+        (status = FORBIDDEN, body = ApiError),
+        (status = NOT_FOUND, body = ApiError),
+        (status = EXPECTATION_FAILED, body = ApiError),
+        (status = CONFLICT, body = ApiError),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        permissions: GetPermissionManager,
+        user: GetUser,
+        activity_logger: GetUserActivityLogger,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> ApiResponseResult {
+        if let Err(errors) = shared::utils::validate_data(&data) {
+            return ApiResponse::json(ApiError::new_strings_value(errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        permissions.has_user_permission("servers.deploy")?;
+
+        if ResourceQuota::effective_by_user_uuid(&state.database, user.uuid)
+            .await?
+            .is_none()
+        {
+            return ApiResponse::error(
+                "your account does not have a resource quota to deploy servers with",
+            )
+            .with_status(StatusCode::FORBIDDEN)
+            .ok();
+        }
+
+        let egg = match LocationNestEgg::by_location_uuid_egg_uuid(
+            &state.database,
+            data.location_uuid,
+            data.egg_uuid,
+        )
+        .await?
+        {
+            Some(location_egg) => location_egg.egg,
+            None => {
+                return ApiResponse::error("egg not available in this location")
+                    .with_status(StatusCode::NOT_FOUND)
+                    .ok();
+            }
+        };
+
+        let image = match egg.docker_images.values().next() {
+            Some(image) => image.clone(),
+            None => {
+                return ApiResponse::error("egg does not have any docker images")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
+        };
+
+        let variables = NestEggVariable::all_by_egg_uuid(&state.database, egg.uuid).await?;
+
+        let mut validator_variables = HashMap::new();
+        validator_variables.reserve(variables.len());
+
+        for variable in variables.iter() {
+            validator_variables.insert(
+                variable.env_variable.as_str(),
+                (
+                    variable.rules.as_slice(),
+                    if variable.user_editable
+                        && let Some(value) = data
+                            .variables
+                            .iter()
+                            .find(|v| v.env_variable == variable.env_variable)
+                    {
+                        value.value.as_str()
+                    } else {
+                        variable.default_value.as_ref().map_or("", |v| v.as_str())
+                    },
+                ),
+            );
+        }
+
+        let validator = match rule_validator::Validator::new(validator_variables) {
+            Ok(validator) => validator,
+            Err(error) => {
+                return ApiResponse::error(&error)
+                    .with_status(StatusCode::BAD_REQUEST)
+                    .ok();
+            }
+        };
+
+        if let Err(errors) = validator.validate() {
+            return ApiResponse::json(ApiError::new_fields_value(&errors))
+                .with_status(StatusCode::BAD_REQUEST)
+                .ok();
+        }
+
+        let mut server_variables = HashMap::new();
+        server_variables.reserve(variables.len());
+
+        for data_variable in &data.variables {
+            let variable_uuid = match variables
+                .iter()
+                .find(|v| v.user_editable && v.env_variable == data_variable.env_variable)
+            {
+                Some(variable) => variable.uuid,
+                None => continue,
+            };
+
+            server_variables.insert(variable_uuid, data_variable.value.as_str());
+        }
+
+        let plan = shared::deployment::plan(
+            &state.database,
+            &egg,
+            &DeploymentRequest {
+                location_uuids: vec![data.location_uuid],
+                memory: data.limits.memory,
+                disk: data.limits.disk,
+                allocations: 1,
+                port_start: None,
+                port_end: None,
+            },
+        )
+        .await?;
+
+        let allocation_uuid = plan.primary_allocation_uuid();
+        let node = match plan.node {
+            Some(node) => node,
+            None => {
+                return ApiResponse::error("no node in this location has enough free resources")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
+        };
+
+        let server = match Server::create(
+            &state.database,
+            &node,
+            user.uuid,
+            egg.uuid,
+            None,
+            allocation_uuid,
+            &[],
+            None,
+            true,
+            false,
+            &data.name,
+            data.description.as_deref(),
+            &ApiServerLimits {
+                cpu: data.limits.cpu,
+                memory: data.limits.memory,
+                swap: 0,
+                disk: data.limits.disk,
+                io_weight: None,
+            },
+            &[],
+            &egg.startup,
+            &image,
+            None,
+            &data.feature_limits,
+            &server_variables,
+        )
+        .await
+        {
+            Ok(server_uuid) => Server::by_uuid(&state.database, server_uuid).await?,
+            Err(err) if err.is_unique_violation() => {
+                return ApiResponse::error("the selected allocation was taken, please try again")
+                    .with_status(StatusCode::CONFLICT)
+                    .ok();
+            }
+            Err(shared::database::DatabaseError::Any(err))
+                if err.is::<shared::response::DisplayError<'static>>() =>
+            {
+                return Err(err.into());
+            }
+            Err(err) => {
+                tracing::error!("failed to deploy server: {:?}", err);
+
+                return ApiResponse::error("failed to deploy server")
+                    .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .ok();
+            }
+        };
+
+        activity_logger
+            .log(
+                "server:deploy",
+                serde_json::json!({
+                    "uuid": server.uuid,
+                    "name": server.name,
+                    "location_uuid": data.location_uuid,
+                    "egg_uuid": egg.uuid,
+                }),
+            )
+            .await;
+
+        ApiResponse::json(Response {
+            server: server.into_api_object(&state.database, &user).await?,
+        })
+        .ok()
+    }
+}
+
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/deployable", deployable::router(state))
         .nest("/groups", groups::router(state))
         .nest("/{server}", _server_::router(state))
         .with_state(state.clone())
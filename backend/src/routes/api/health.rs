@@ -0,0 +1,68 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use axum::http::StatusCode;
+    use serde::Serialize;
+    use shared::{
+        GetState,
+        response::{ApiResponse, ApiResponseResult},
+    };
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize)]
+    struct Response {
+        healthy: bool,
+        database: bool,
+        cache: bool,
+
+        /// The instance that handled this request.
+        instance: uuid::Uuid,
+        /// Whether the instance that handled this request is the leader.
+        leader: bool,
+        leader_instance: Option<uuid::Uuid>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = SERVICE_UNAVAILABLE, body = inline(Response)),
+    ))]
+    pub async fn route(state: GetState) -> ApiResponseResult {
+        let leader_instance = state.leader.leader(&state.database).await;
+        if let Err(err) = &leader_instance {
+            tracing::error!("failed to check database health: {:?}", err);
+        }
+
+        let cache = match state.cache.version().await {
+            Ok(_) => true,
+            Err(err) => {
+                tracing::error!("failed to check cache health: {:?}", err);
+                false
+            }
+        };
+
+        let database = leader_instance.is_ok();
+        let healthy = database && cache;
+
+        ApiResponse::json(Response {
+            healthy,
+            database,
+            cache,
+            instance: state.instance,
+            leader: state.leader.is_leader(),
+            leader_instance: leader_instance.ok().flatten(),
+        })
+        .with_status(if healthy {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        })
+        .ok()
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
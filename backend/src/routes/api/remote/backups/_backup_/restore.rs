@@ -7,7 +7,7 @@ mod post {
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
-        models::{node::GetNode, server_activity::ServerActivity},
+        models::{node::GetNode, server_activity::ServerActivity, server_clone::ServerClone},
         response::{ApiResponse, ApiResponseResult},
     };
     use utoipa::ToSchema;
@@ -68,6 +68,8 @@ mod post {
                 .ok();
         }
 
+        ServerClone::finish_restore(&state.database, server_uuid, data.successful).await?;
+
         if let Err(err) = ServerActivity::log(
             &state.database,
             server_uuid,
@@ -5,7 +5,7 @@ mod post {
     use serde::{Deserialize, Serialize};
     use shared::{
         ApiError, GetState,
-        models::server_schedule::ServerSchedule,
+        models::{server_activity::ServerActivity, server_schedule::ServerSchedule},
         response::{ApiResponse, ApiResponseResult},
     };
     use std::collections::HashMap;
@@ -82,6 +82,8 @@ mod post {
                 .execute(state.database.write()),
             );
 
+            let failed_steps = schedule_status.errors.len();
+
             for (step_uuid, error_message) in schedule_status.errors {
                 futures.push(
                     sqlx::query!(
@@ -96,6 +98,41 @@ mod post {
             }
 
             futures_util::future::try_join_all(futures).await?;
+
+            if !schedule_status.successful {
+                let server_uuid: uuid::Uuid = sqlx::query_scalar(
+                    r#"
+                    SELECT server_schedules.server_uuid
+                    FROM server_schedules
+                    WHERE server_schedules.uuid = $1
+                    "#,
+                )
+                .bind(schedule.uuid)
+                .fetch_one(state.database.read())
+                .await?;
+
+                if let Err(err) = ServerActivity::log(
+                    &state.database,
+                    server_uuid,
+                    None,
+                    None,
+                    "server:schedule.fail",
+                    None,
+                    serde_json::json!({
+                        "uuid": schedule.uuid,
+                        "name": schedule.name,
+                        "failed_steps": failed_steps,
+                    }),
+                )
+                .await
+                {
+                    tracing::warn!(
+                        schedule = %schedule.uuid,
+                        "failed to log server activity: {:#?}",
+                        err
+                    );
+                }
+            }
         }
 
         ApiResponse::json(Response {}).ok()
@@ -24,6 +24,10 @@ mod post {
         name: Option<String>,
 
         ignored_files: Vec<String>,
+        /// Whether to rotate out the oldest unlocked backup when the backup limit is reached,
+        /// `None` rotates, matching `ScheduleActionInner::CreateBackup` and older wings versions that do not send this.
+        #[serde(default)]
+        rotate_oldest: Option<bool>,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -53,15 +57,22 @@ mod post {
         }
 
         let backups = ServerBackup::count_by_server_uuid(&state.database, server.uuid).await;
-        if backups >= server.backup_limit as i64
-            && let Err(err) =
+        if backups >= server.backup_limit as i64 {
+            if !data.rotate_oldest.unwrap_or(true) {
+                return ApiResponse::error("maximum number of backups reached")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
+
+            if let Err(err) =
                 ServerBackup::delete_oldest_by_server_uuid(&state.database, &server).await
-        {
-            tracing::error!(server = %server.uuid, "failed to delete old backup: {:?}", err);
+            {
+                tracing::error!(server = %server.uuid, "failed to delete old backup: {:?}", err);
 
-            return ApiResponse::error("maximum number of backups reached")
-                .with_status(StatusCode::EXPECTATION_FAILED)
-                .ok();
+                return ApiResponse::error("maximum number of backups reached")
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .ok();
+            }
         }
 
         state
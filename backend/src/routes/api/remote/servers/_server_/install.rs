@@ -31,7 +31,10 @@ mod post {
     use serde::{Deserialize, Serialize};
     use shared::{
         GetState,
-        models::server::{GetServer, ServerStatus},
+        models::{
+            server::{GetServer, ServerStatus},
+            server_activity::ServerActivity,
+        },
         response::{ApiResponse, ApiResponseResult},
     };
     use utoipa::ToSchema;
@@ -74,6 +77,30 @@ mod post {
         .execute(state.database.write())
         .await?;
 
+        if let Err(err) = ServerActivity::log(
+            &state.database,
+            server.0.uuid,
+            None,
+            None,
+            if data.successful {
+                "server:install.complete"
+            } else {
+                "server:install.fail"
+            },
+            None,
+            serde_json::json!({
+                "successful": data.successful,
+            }),
+        )
+        .await
+        {
+            tracing::warn!(
+                server = %server.0.uuid,
+                "failed to log server activity: {:#?}",
+                err
+            );
+        }
+
         ApiResponse::json(Response {}).ok()
     }
 }
@@ -15,6 +15,7 @@ mod get {
         name: &'a str,
         language: &'a str,
         registration_enabled: bool,
+        magic_link_login_enabled: bool,
     }
 
     #[derive(ToSchema, Serialize)]
@@ -56,6 +57,7 @@ mod get {
                 name: &settings.app.name,
                 language: &settings.app.language,
                 registration_enabled: settings.app.registration_enabled,
+                magic_link_login_enabled: settings.app.magic_link_login_enabled,
             },
             server: ResponseServer {
                 max_file_manager_view_size: settings.server.max_file_manager_view_size,
@@ -0,0 +1,31 @@
+CREATE TYPE "public"."webhook_delivery_status" AS ENUM('PENDING', 'DELIVERED', 'FAILED');
+
+CREATE TABLE "webhooks" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"name" varchar(1020) NOT NULL,
+	"description" text,
+	"url" varchar(255) NOT NULL,
+	"secret" bytea NOT NULL,
+	"events" varchar(255)[] DEFAULT '{}' NOT NULL,
+	"enabled" boolean DEFAULT true NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "webhook_deliveries" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"webhook_uuid" uuid NOT NULL,
+	"event" varchar(255) NOT NULL,
+	"payload" jsonb NOT NULL,
+	"status" "webhook_delivery_status" DEFAULT 'PENDING' NOT NULL,
+	"attempts" smallint DEFAULT 0 NOT NULL,
+	"response_status" smallint,
+	"error" text,
+	"next_attempt" timestamp DEFAULT now() NOT NULL,
+	"last_attempt" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "webhook_deliveries" ADD CONSTRAINT "webhook_deliveries_webhook_uuid_webhooks_uuid_fk" FOREIGN KEY ("webhook_uuid") REFERENCES "public"."webhooks"("uuid") ON DELETE cascade ON UPDATE no action;
+CREATE UNIQUE INDEX "webhooks_name_idx" ON "webhooks" USING btree ("name");
+CREATE INDEX "webhook_deliveries_webhook_uuid_idx" ON "webhook_deliveries" USING btree ("webhook_uuid");
+CREATE INDEX "webhook_deliveries_status_next_attempt_idx" ON "webhook_deliveries" USING btree ("status","next_attempt");
\ No newline at end of file
@@ -0,0 +1,17 @@
+CREATE TABLE "resource_quotas" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"user_uuid" uuid,
+	"role_uuid" uuid,
+	"servers" integer,
+	"memory" bigint,
+	"disk" bigint,
+	"cpu" integer,
+	"databases" integer,
+	"backups" integer,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "resource_quotas" ADD CONSTRAINT "resource_quotas_user_uuid_users_uuid_fk" FOREIGN KEY ("user_uuid") REFERENCES "public"."users"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "resource_quotas" ADD CONSTRAINT "resource_quotas_role_uuid_roles_uuid_fk" FOREIGN KEY ("role_uuid") REFERENCES "public"."roles"("uuid") ON DELETE cascade ON UPDATE no action;
+CREATE UNIQUE INDEX "resource_quotas_user_uuid_idx" ON "resource_quotas" USING btree ("user_uuid");
+CREATE UNIQUE INDEX "resource_quotas_role_uuid_idx" ON "resource_quotas" USING btree ("role_uuid");
\ No newline at end of file
@@ -0,0 +1,11 @@
+CREATE TABLE "location_nest_eggs" (
+	"location_uuid" uuid NOT NULL,
+	"egg_uuid" uuid NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL,
+	CONSTRAINT "location_nest_eggs_pk" PRIMARY KEY("location_uuid","egg_uuid")
+);
+
+ALTER TABLE "location_nest_eggs" ADD CONSTRAINT "location_nest_eggs_location_uuid_locations_uuid_fk" FOREIGN KEY ("location_uuid") REFERENCES "public"."locations"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "location_nest_eggs" ADD CONSTRAINT "location_nest_eggs_egg_uuid_nest_eggs_uuid_fk" FOREIGN KEY ("egg_uuid") REFERENCES "public"."nest_eggs"("uuid") ON DELETE cascade ON UPDATE no action;
+CREATE INDEX "location_nest_eggs_location_uuid_idx" ON "location_nest_eggs" USING btree ("location_uuid");
+CREATE INDEX "location_nest_eggs_egg_uuid_idx" ON "location_nest_eggs" USING btree ("egg_uuid");
\ No newline at end of file
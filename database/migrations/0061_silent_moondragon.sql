@@ -0,0 +1 @@
+ALTER TABLE "backup_configurations" ADD COLUMN "retention_policy" jsonb DEFAULT '{}'::jsonb NOT NULL;
\ No newline at end of file
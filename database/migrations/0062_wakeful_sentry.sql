@@ -0,0 +1,7 @@
+CREATE TYPE "public"."node_health_status" AS ENUM('UNKNOWN', 'ONLINE', 'OFFLINE');
+
+ALTER TABLE "nodes" ADD COLUMN "health_status" "node_health_status" DEFAULT 'UNKNOWN' NOT NULL;
+ALTER TABLE "nodes" ADD COLUMN "health_latency" integer;
+ALTER TABLE "nodes" ADD COLUMN "wings_version" varchar(255);
+ALTER TABLE "nodes" ADD COLUMN "last_seen" timestamp;
+ALTER TABLE "nodes" ADD COLUMN "health_checked" timestamp;
\ No newline at end of file
@@ -0,0 +1,33 @@
+CREATE TYPE "public"."user_notification_channel_type" AS ENUM('EMAIL', 'DISCORD', 'WEBHOOK');
+
+CREATE TABLE "user_notification_channels" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"user_uuid" uuid NOT NULL,
+	"name" varchar(124) NOT NULL,
+	"type" "user_notification_channel_type" NOT NULL,
+	"url" varchar(255),
+	"events" varchar(31)[] DEFAULT '{}' NOT NULL,
+	"enabled" boolean DEFAULT true NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "user_notifications" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"channel_uuid" uuid NOT NULL,
+	"server_uuid" uuid NOT NULL,
+	"event" varchar(31) NOT NULL,
+	"data" jsonb NOT NULL,
+	"attempts" smallint DEFAULT 0 NOT NULL,
+	"error" text,
+	"next_attempt" timestamp DEFAULT now() NOT NULL,
+	"sent" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "user_notification_channels" ADD CONSTRAINT "user_notification_channels_user_uuid_users_uuid_fk" FOREIGN KEY ("user_uuid") REFERENCES "public"."users"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "user_notifications" ADD CONSTRAINT "user_notifications_channel_uuid_user_notification_channels_uuid_fk" FOREIGN KEY ("channel_uuid") REFERENCES "public"."user_notification_channels"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "user_notifications" ADD CONSTRAINT "user_notifications_server_uuid_servers_uuid_fk" FOREIGN KEY ("server_uuid") REFERENCES "public"."servers"("uuid") ON DELETE cascade ON UPDATE no action;
+CREATE INDEX "user_notification_channels_user_uuid_idx" ON "user_notification_channels" USING btree ("user_uuid");
+CREATE UNIQUE INDEX "user_notification_channels_user_uuid_name_idx" ON "user_notification_channels" USING btree ("user_uuid","name");
+CREATE INDEX "user_notifications_channel_uuid_idx" ON "user_notifications" USING btree ("channel_uuid");
+CREATE INDEX "user_notifications_sent_next_attempt_idx" ON "user_notifications" USING btree ("sent","next_attempt");
\ No newline at end of file
@@ -0,0 +1,12 @@
+CREATE TABLE "mail_templates" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"name" varchar(255) NOT NULL,
+	"language" varchar(15) NOT NULL,
+	"subject" varchar(1020) NOT NULL,
+	"body" text NOT NULL,
+	"body_plain" text,
+	"updated" timestamp DEFAULT now() NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE UNIQUE INDEX "mail_templates_name_language_idx" ON "mail_templates" USING btree ("name","language");
\ No newline at end of file
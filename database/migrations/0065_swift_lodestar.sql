@@ -0,0 +1,10 @@
+CREATE TABLE "user_magic_links" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"user_uuid" uuid NOT NULL,
+	"token" text NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "user_magic_links" ADD CONSTRAINT "user_magic_links_user_uuid_users_uuid_fk" FOREIGN KEY ("user_uuid") REFERENCES "public"."users"("uuid") ON DELETE cascade ON UPDATE no action;
+CREATE INDEX "user_magic_links_user_uuid_idx" ON "user_magic_links" USING btree ("user_uuid");
+CREATE UNIQUE INDEX "user_magic_links_token_idx" ON "user_magic_links" USING btree ("token");
\ No newline at end of file
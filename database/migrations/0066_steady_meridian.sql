@@ -0,0 +1,36 @@
+CREATE TYPE "public"."node_evacuation_status" AS ENUM('RUNNING', 'PAUSED', 'CANCELLED', 'COMPLETED');
+CREATE TYPE "public"."node_evacuation_server_status" AS ENUM('PENDING', 'TRANSFERRING', 'COMPLETED', 'FAILED', 'CANCELLED');
+
+CREATE TABLE "node_evacuations" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"node_uuid" uuid NOT NULL,
+	"destination_location_uuid" uuid,
+	"destination_node_uuids" uuid[] DEFAULT '{}' NOT NULL,
+	"status" "node_evacuation_status" DEFAULT 'RUNNING' NOT NULL,
+	"concurrency" smallint DEFAULT 2 NOT NULL,
+	"options" jsonb NOT NULL,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE TABLE "node_evacuation_servers" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"evacuation_uuid" uuid NOT NULL,
+	"server_uuid" uuid NOT NULL,
+	"destination_node_uuid" uuid,
+	"status" "node_evacuation_server_status" DEFAULT 'PENDING' NOT NULL,
+	"error" text,
+	"started" timestamp,
+	"finished" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "node_evacuations" ADD CONSTRAINT "node_evacuations_node_uuid_nodes_uuid_fk" FOREIGN KEY ("node_uuid") REFERENCES "public"."nodes"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "node_evacuations" ADD CONSTRAINT "node_evacuations_destination_location_uuid_locations_uuid_fk" FOREIGN KEY ("destination_location_uuid") REFERENCES "public"."locations"("uuid") ON DELETE set null ON UPDATE no action;
+ALTER TABLE "node_evacuation_servers" ADD CONSTRAINT "node_evacuation_servers_evacuation_uuid_node_evacuations_uuid_fk" FOREIGN KEY ("evacuation_uuid") REFERENCES "public"."node_evacuations"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "node_evacuation_servers" ADD CONSTRAINT "node_evacuation_servers_server_uuid_servers_uuid_fk" FOREIGN KEY ("server_uuid") REFERENCES "public"."servers"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "node_evacuation_servers" ADD CONSTRAINT "node_evacuation_servers_destination_node_uuid_nodes_uuid_fk" FOREIGN KEY ("destination_node_uuid") REFERENCES "public"."nodes"("uuid") ON DELETE set null ON UPDATE no action;
+CREATE INDEX "node_evacuations_node_uuid_idx" ON "node_evacuations" USING btree ("node_uuid");
+CREATE INDEX "node_evacuations_status_idx" ON "node_evacuations" USING btree ("status");
+CREATE INDEX "node_evacuation_servers_evacuation_uuid_idx" ON "node_evacuation_servers" USING btree ("evacuation_uuid");
+CREATE UNIQUE INDEX "node_evacuation_servers_evacuation_uuid_server_uuid_idx" ON "node_evacuation_servers" USING btree ("evacuation_uuid","server_uuid");
\ No newline at end of file
@@ -0,0 +1,21 @@
+CREATE TABLE "server_suspensions" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"server_uuid" uuid NOT NULL,
+	"user_uuid" uuid,
+	"reason" text NOT NULL,
+	"message" text,
+	"starts" timestamp DEFAULT now() NOT NULL,
+	"expires" timestamp,
+	"lifted" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "servers" ADD COLUMN "suspension_uuid" uuid;
+INSERT INTO "server_suspensions" ("server_uuid", "reason") SELECT "uuid", 'suspended before suspension records were introduced' FROM "servers" WHERE "suspended" = true;
+UPDATE "servers" SET "suspension_uuid" = "server_suspensions"."uuid" FROM "server_suspensions" WHERE "server_suspensions"."server_uuid" = "servers"."uuid";
+ALTER TABLE "server_suspensions" ADD CONSTRAINT "server_suspensions_server_uuid_servers_uuid_fk" FOREIGN KEY ("server_uuid") REFERENCES "public"."servers"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "server_suspensions" ADD CONSTRAINT "server_suspensions_user_uuid_users_uuid_fk" FOREIGN KEY ("user_uuid") REFERENCES "public"."users"("uuid") ON DELETE set null ON UPDATE no action;
+ALTER TABLE "servers" ADD CONSTRAINT "servers_suspension_uuid_server_suspensions_uuid_fk" FOREIGN KEY ("suspension_uuid") REFERENCES "public"."server_suspensions"("uuid") ON DELETE set null ON UPDATE no action;
+CREATE INDEX "server_suspensions_server_uuid_idx" ON "server_suspensions" USING btree ("server_uuid");
+CREATE INDEX "server_suspensions_starts_idx" ON "server_suspensions" USING btree ("starts");
+CREATE INDEX "server_suspensions_expires_idx" ON "server_suspensions" USING btree ("expires");
\ No newline at end of file
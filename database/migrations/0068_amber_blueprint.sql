@@ -0,0 +1,28 @@
+CREATE TABLE "server_templates" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"egg_uuid" uuid NOT NULL,
+	"backup_configuration_uuid" uuid,
+	"name" varchar(255) NOT NULL,
+	"description" text,
+	"memory" bigint NOT NULL,
+	"swap" bigint NOT NULL,
+	"disk" bigint NOT NULL,
+	"io_weight" smallint,
+	"cpu" integer NOT NULL,
+	"pinned_cpus" smallint[] DEFAULT '{}' NOT NULL,
+	"startup" text,
+	"image" varchar(255),
+	"timezone" varchar(255),
+	"allocation_limit" integer DEFAULT 0 NOT NULL,
+	"database_limit" integer DEFAULT 0 NOT NULL,
+	"backup_limit" integer DEFAULT 0 NOT NULL,
+	"schedule_limit" integer DEFAULT 0 NOT NULL,
+	"variables" jsonb DEFAULT '{}'::jsonb NOT NULL,
+	"mount_uuids" uuid[] DEFAULT '{}' NOT NULL,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "server_templates" ADD CONSTRAINT "server_templates_egg_uuid_nest_eggs_uuid_fk" FOREIGN KEY ("egg_uuid") REFERENCES "public"."nest_eggs"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "server_templates" ADD CONSTRAINT "server_templates_backup_configuration_uuid_backup_configurations_uuid_fk" FOREIGN KEY ("backup_configuration_uuid") REFERENCES "public"."backup_configurations"("uuid") ON DELETE set null ON UPDATE no action;
+CREATE INDEX "server_templates_egg_uuid_idx" ON "server_templates" USING btree ("egg_uuid");
+CREATE UNIQUE INDEX "server_templates_name_idx" ON "server_templates" USING btree ("name");
\ No newline at end of file
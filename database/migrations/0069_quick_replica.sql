@@ -0,0 +1,21 @@
+CREATE TYPE "public"."server_clone_status" AS ENUM('BACKING_UP', 'RESTORING_FILES', 'COPYING_DATABASES', 'COMPLETED', 'FAILED');
+
+CREATE TABLE "server_clones" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"server_uuid" uuid,
+	"clone_server_uuid" uuid NOT NULL,
+	"backup_uuid" uuid,
+	"copy_files" boolean NOT NULL,
+	"copy_databases" boolean NOT NULL,
+	"status" "server_clone_status" DEFAULT 'BACKING_UP' NOT NULL,
+	"error" text,
+	"completed" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+ALTER TABLE "server_clones" ADD CONSTRAINT "server_clones_server_uuid_servers_uuid_fk" FOREIGN KEY ("server_uuid") REFERENCES "public"."servers"("uuid") ON DELETE set null ON UPDATE no action;
+ALTER TABLE "server_clones" ADD CONSTRAINT "server_clones_clone_server_uuid_servers_uuid_fk" FOREIGN KEY ("clone_server_uuid") REFERENCES "public"."servers"("uuid") ON DELETE cascade ON UPDATE no action;
+ALTER TABLE "server_clones" ADD CONSTRAINT "server_clones_backup_uuid_server_backups_uuid_fk" FOREIGN KEY ("backup_uuid") REFERENCES "public"."server_backups"("uuid") ON DELETE set null ON UPDATE no action;
+CREATE INDEX "server_clones_server_uuid_idx" ON "server_clones" USING btree ("server_uuid");
+CREATE UNIQUE INDEX "server_clones_clone_server_uuid_idx" ON "server_clones" USING btree ("clone_server_uuid");
+CREATE INDEX "server_clones_status_idx" ON "server_clones" USING btree ("status");
\ No newline at end of file
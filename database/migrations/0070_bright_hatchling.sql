@@ -0,0 +1 @@
+ALTER TABLE "nest_eggs" ADD COLUMN "user_selectable" boolean DEFAULT false NOT NULL;
\ No newline at end of file
@@ -0,0 +1,21 @@
+CREATE TYPE "public"."job_status" AS ENUM('PENDING', 'RUNNING', 'COMPLETED', 'FAILED', 'CANCELLED');
+
+CREATE TABLE "jobs" (
+	"uuid" uuid PRIMARY KEY DEFAULT gen_random_uuid() NOT NULL,
+	"name" varchar(255) NOT NULL,
+	"payload" jsonb DEFAULT '{}'::jsonb NOT NULL,
+	"status" "job_status" DEFAULT 'PENDING' NOT NULL,
+	"priority" smallint DEFAULT 0 NOT NULL,
+	"attempts" integer DEFAULT 0 NOT NULL,
+	"max_attempts" integer DEFAULT 3 NOT NULL,
+	"error" text,
+	"locked_by" uuid,
+	"locked_until" timestamp,
+	"run_at" timestamp DEFAULT now() NOT NULL,
+	"started" timestamp,
+	"finished" timestamp,
+	"created" timestamp DEFAULT now() NOT NULL
+);
+
+CREATE INDEX "jobs_name_idx" ON "jobs" USING btree ("name");
+CREATE INDEX "jobs_status_priority_run_at_idx" ON "jobs" USING btree ("status","priority","run_at");
\ No newline at end of file
@@ -0,0 +1,8 @@
+CREATE TYPE "public"."egg_repository_credentials_type" AS ENUM('TOKEN', 'SSH_KEY');
+
+ALTER TABLE "egg_repositories" ADD COLUMN "git_reference" varchar(255);
+ALTER TABLE "egg_repositories" ADD COLUMN "git_subdirectory" varchar(255);
+ALTER TABLE "egg_repositories" ADD COLUMN "credentials_type" "egg_repository_credentials_type";
+ALTER TABLE "egg_repositories" ADD COLUMN "credentials" bytea;
+ALTER TABLE "egg_repositories" ADD COLUMN "sync_schedule" varchar(255);
+ALTER TABLE "egg_repositories" ADD COLUMN "last_sync_report" jsonb;
\ No newline at end of file
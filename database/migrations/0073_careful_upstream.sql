@@ -0,0 +1,6 @@
+CREATE TYPE "public"."nest_egg_update_policy" AS ENUM('MANUAL', 'NON_BREAKING');
+
+ALTER TABLE "egg_repository_eggs" ADD COLUMN "updated" timestamp DEFAULT now() NOT NULL;
+ALTER TABLE "nest_eggs" ADD COLUMN "update_policy" "nest_egg_update_policy" DEFAULT 'MANUAL' NOT NULL;
+ALTER TABLE "nest_eggs" ADD COLUMN "egg_repository_egg_applied" timestamp;
+UPDATE "nest_eggs" SET "egg_repository_egg_applied" = now() WHERE "egg_repository_egg_uuid" IS NOT NULL;
\ No newline at end of file
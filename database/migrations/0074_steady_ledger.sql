@@ -0,0 +1,2 @@
+ALTER TABLE "resource_quotas" ADD COLUMN "allocations" integer;
+ALTER TABLE "resource_quotas" ADD COLUMN "schedules" integer;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE "nodes" ADD COLUMN "health_failures" smallint DEFAULT 0 NOT NULL;
\ No newline at end of file
@@ -34,6 +34,42 @@ export const userToastPositionEnum = new DatabaseEnum('user_toast_position', [
   'BOTTOM_CENTER',
   'BOTTOM_RIGHT',
 ]);
+export const webhookDeliveryStatusEnum = new DatabaseEnum('webhook_delivery_status', ['PENDING', 'DELIVERED', 'FAILED']);
+export const nodeHealthStatusEnum = new DatabaseEnum('node_health_status', ['UNKNOWN', 'ONLINE', 'OFFLINE']);
+export const userNotificationChannelTypeEnum = new DatabaseEnum('user_notification_channel_type', [
+  'EMAIL',
+  'DISCORD',
+  'WEBHOOK',
+]);
+export const nodeEvacuationStatusEnum = new DatabaseEnum('node_evacuation_status', [
+  'RUNNING',
+  'PAUSED',
+  'CANCELLED',
+  'COMPLETED',
+]);
+export const nodeEvacuationServerStatusEnum = new DatabaseEnum('node_evacuation_server_status', [
+  'PENDING',
+  'TRANSFERRING',
+  'COMPLETED',
+  'FAILED',
+  'CANCELLED',
+]);
+export const serverCloneStatusEnum = new DatabaseEnum('server_clone_status', [
+  'BACKING_UP',
+  'RESTORING_FILES',
+  'COPYING_DATABASES',
+  'COMPLETED',
+  'FAILED',
+]);
+export const jobStatusEnum = new DatabaseEnum('job_status', [
+  'PENDING',
+  'RUNNING',
+  'COMPLETED',
+  'FAILED',
+  'CANCELLED',
+]);
+export const eggRepositoryCredentialsTypeEnum = new DatabaseEnum('egg_repository_credentials_type', ['TOKEN', 'SSH_KEY']);
+export const nestEggUpdatePolicyEnum = new DatabaseEnum('nest_egg_update_policy', ['MANUAL', 'NON_BREAKING']);
 
 export const settingsTable = new DatabaseTable('settings')
   .addColumn('key', varchar({ length: 255 }).primaryKey().notNull())
@@ -153,6 +189,21 @@ export const userPasswordResetsTable = new DatabaseTable('user_password_resets')
     uniqueIndex('user_password_resets_token_idx').on(cols.token),
   ]);
 
+export const userMagicLinksTable = new DatabaseTable('user_magic_links')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'user_uuid',
+    uuid()
+      .references(() => usersTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn('token', text().notNull())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('user_magic_links_user_uuid_idx').on(cols.user_uuid),
+    uniqueIndex('user_magic_links_token_idx').on(cols.token),
+  ]);
+
 export const userSecurityKeysTable = new DatabaseTable('user_security_keys')
   .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
   .addColumn(
@@ -307,6 +358,7 @@ export const backupConfigurationsTable = new DatabaseTable('backup_configuration
   .addColumn('description', text())
   .addColumn('backup_disk', backupDiskEnum.intoDrizzleEnum()().default('LOCAL').notNull())
   .addColumn('backup_configs', jsonb().default({}).notNull())
+  .addColumn('retention_policy', jsonb().default({}).notNull())
   .addColumn('created', timestamp().defaultNow().notNull())
   .addConfigBuilder((cols) => [uniqueIndex('backup_configurations_name_idx').on(cols.name)]);
 
@@ -344,6 +396,26 @@ export const locationDatabaseHostsTable = new DatabaseTable('location_database_h
     index('location_database_hosts_database_host_uuid_idx').on(cols.database_host_uuid),
   ]);
 
+export const locationNestEggsTable = new DatabaseTable('location_nest_eggs')
+  .addColumn(
+    'location_uuid',
+    uuid()
+      .references(() => locationsTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'egg_uuid',
+    uuid()
+      .references(() => nestEggsTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    primaryKey({ name: 'location_nest_eggs_pk', columns: [cols.location_uuid, cols.egg_uuid] }),
+    index('location_nest_eggs_location_uuid_idx').on(cols.location_uuid),
+    index('location_nest_eggs_egg_uuid_idx').on(cols.egg_uuid),
+  ]);
+
 export const nodesTable = new DatabaseTable('nodes')
   .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
   .addColumn(
@@ -368,6 +440,12 @@ export const nodesTable = new DatabaseTable('nodes')
   .addColumn('disk', bigint({ mode: 'number' }).notNull())
   .addColumn('token_id', char({ length: 16 }).notNull())
   .addColumn('token', bytea().notNull())
+  .addColumn('health_status', nodeHealthStatusEnum.intoDrizzleEnum()().default('UNKNOWN').notNull())
+  .addColumn('health_latency', integer())
+  .addColumn('health_failures', smallint().default(0).notNull())
+  .addColumn('wings_version', varchar({ length: 255 }))
+  .addColumn('last_seen', timestamp())
+  .addColumn('health_checked', timestamp())
   .addColumn('created', timestamp().defaultNow().notNull())
   .addConfigBuilder((cols) => [
     index('nodes_location_uuid_idx').on(cols.location_uuid),
@@ -420,6 +498,12 @@ export const eggRepositoriesTable = new DatabaseTable('egg_repositories')
   .addColumn('name', varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull())
   .addColumn('description', text())
   .addColumn('git_repository', text().notNull())
+  .addColumn('git_reference', varchar({ length: 255 }))
+  .addColumn('git_subdirectory', varchar({ length: 255 }))
+  .addColumn('credentials_type', eggRepositoryCredentialsTypeEnum.intoDrizzleEnum()())
+  .addColumn('credentials', bytea())
+  .addColumn('sync_schedule', varchar({ length: 255 }))
+  .addColumn('last_sync_report', jsonb())
   .addColumn('last_synced', timestamp())
   .addColumn('created', timestamp().defaultNow().notNull())
   .addConfigBuilder((cols) => [
@@ -440,6 +524,7 @@ export const eggRepositoriesEggsTable = new DatabaseTable('egg_repository_eggs')
   .addColumn('description', text())
   .addColumn('author', varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull())
   .addColumn('exported_egg', jsonb().notNull())
+  .addColumn('updated', timestamp().defaultNow().notNull())
   .addConfigBuilder((cols) => [
     index('egg_repository_eggs_egg_repository_uuid_idx').on(cols.egg_repository_uuid),
     uniqueIndex('egg_repository_eggs_egg_repository_uuid_path_idx').on(cols.egg_repository_uuid, cols.path),
@@ -479,6 +564,9 @@ export const nestEggsTable = new DatabaseTable('nest_eggs')
   .addColumn('features', text().array().notNull())
   .addColumn('docker_images', text().notNull())
   .addColumn('file_denylist', text().array().notNull())
+  .addColumn('user_selectable', boolean().default(false).notNull())
+  .addColumn('update_policy', nestEggUpdatePolicyEnum.intoDrizzleEnum()().default('MANUAL').notNull())
+  .addColumn('egg_repository_egg_applied', timestamp())
   .addColumn('created', timestamp().defaultNow().notNull())
   .addConfigBuilder((cols) => [
     index('eggs_nest_uuid_idx').on(cols.nest_uuid),
@@ -589,6 +677,10 @@ export const serversTable = new DatabaseTable('servers')
   .addColumn('description', text())
   .addColumn('status', serverStatusEnum.intoDrizzleEnum()())
   .addColumn('suspended', boolean().default(false).notNull())
+  .addColumn(
+    'suspension_uuid',
+    uuid().references((): PgColumn => serverSuspensionsTable.join().uuid, { onDelete: 'set null' }),
+  )
   .addColumn('memory', bigint({ mode: 'number' }).notNull())
   .addColumn('swap', bigint({ mode: 'number' }).notNull())
   .addColumn('disk', bigint({ mode: 'number' }).notNull())
@@ -684,6 +776,30 @@ export const serverActivitiesTable = new DatabaseTable('server_activities')
     index('server_activities_user_uuid_event_idx').on(cols.user_uuid, cols.event),
   ]);
 
+export const serverSuspensionsTable = new DatabaseTable('server_suspensions')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'server_uuid',
+    uuid()
+      .references((): PgColumn => serversTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'user_uuid',
+    uuid().references(() => usersTable.join().uuid, { onDelete: 'set null' }),
+  )
+  .addColumn('reason', text().notNull())
+  .addColumn('message', text())
+  .addColumn('starts', timestamp().defaultNow().notNull())
+  .addColumn('expires', timestamp())
+  .addColumn('lifted', timestamp())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('server_suspensions_server_uuid_idx').on(cols.server_uuid),
+    index('server_suspensions_starts_idx').on(cols.starts),
+    index('server_suspensions_expires_idx').on(cols.expires),
+  ]);
+
 export const serverVariablesTable = new DatabaseTable('server_variables')
   .addColumn(
     'server_uuid',
@@ -764,6 +880,34 @@ export const serverBackupsTable = new DatabaseTable('server_backups')
     uniqueIndex('server_backups_uuid_idx').on(cols.uuid),
   ]);
 
+export const serverClonesTable = new DatabaseTable('server_clones')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'server_uuid',
+    uuid().references((): PgColumn => serversTable.join().uuid, { onDelete: 'set null' }),
+  )
+  .addColumn(
+    'clone_server_uuid',
+    uuid()
+      .references((): PgColumn => serversTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'backup_uuid',
+    uuid().references(() => serverBackupsTable.join().uuid, { onDelete: 'set null' }),
+  )
+  .addColumn('copy_files', boolean().notNull())
+  .addColumn('copy_databases', boolean().notNull())
+  .addColumn('status', serverCloneStatusEnum.intoDrizzleEnum()().default('BACKING_UP').notNull())
+  .addColumn('error', text())
+  .addColumn('completed', timestamp())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('server_clones_server_uuid_idx').on(cols.server_uuid),
+    uniqueIndex('server_clones_clone_server_uuid_idx').on(cols.clone_server_uuid),
+    index('server_clones_status_idx').on(cols.status),
+  ]);
+
 export const serverDatabasesTable = new DatabaseTable('server_databases')
   .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
   .addColumn(
@@ -823,3 +967,222 @@ export const serverScheduleStepsTable = new DatabaseTable('server_schedule_steps
   .addColumn('error', text())
   .addColumn('created', timestamp().defaultNow().notNull())
   .addConfigBuilder((cols) => [index('server_schedule_steps_schedule_uuid_idx').on(cols.schedule_uuid)]);
+
+export const serverTemplatesTable = new DatabaseTable('server_templates')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'egg_uuid',
+    uuid()
+      .references(() => nestEggsTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'backup_configuration_uuid',
+    uuid().references(() => backupConfigurationsTable.join().uuid, { onDelete: 'set null' }),
+  )
+  .addColumn('name', varchar({ length: 255 }).notNull())
+  .addColumn('description', text())
+  .addColumn('memory', bigint({ mode: 'number' }).notNull())
+  .addColumn('swap', bigint({ mode: 'number' }).notNull())
+  .addColumn('disk', bigint({ mode: 'number' }).notNull())
+  .addColumn('io_weight', smallint())
+  .addColumn('cpu', integer().notNull())
+  .addColumn('pinned_cpus', smallint().array().default([]).notNull())
+  .addColumn('startup', text())
+  .addColumn('image', varchar({ length: 255 }))
+  .addColumn('timezone', varchar({ length: 255 }))
+  .addColumn('allocation_limit', integer().default(0).notNull())
+  .addColumn('database_limit', integer().default(0).notNull())
+  .addColumn('backup_limit', integer().default(0).notNull())
+  .addColumn('schedule_limit', integer().default(0).notNull())
+  .addColumn('variables', jsonb().default({}).notNull())
+  .addColumn('mount_uuids', uuid().array().default([]).notNull())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('server_templates_egg_uuid_idx').on(cols.egg_uuid),
+    uniqueIndex('server_templates_name_idx').on(cols.name),
+  ]);
+
+export const nodeEvacuationsTable = new DatabaseTable('node_evacuations')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'node_uuid',
+    uuid()
+      .references(() => nodesTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'destination_location_uuid',
+    uuid().references(() => locationsTable.join().uuid, { onDelete: 'set null' }),
+  )
+  .addColumn('destination_node_uuids', uuid().array().default([]).notNull())
+  .addColumn('status', nodeEvacuationStatusEnum.intoDrizzleEnum()().default('RUNNING').notNull())
+  .addColumn('concurrency', smallint().default(2).notNull())
+  .addColumn('options', jsonb().notNull())
+  .addColumn('completed', timestamp())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('node_evacuations_node_uuid_idx').on(cols.node_uuid),
+    index('node_evacuations_status_idx').on(cols.status),
+  ]);
+
+export const nodeEvacuationServersTable = new DatabaseTable('node_evacuation_servers')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'evacuation_uuid',
+    uuid()
+      .references(() => nodeEvacuationsTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'server_uuid',
+    uuid()
+      .references(() => serversTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'destination_node_uuid',
+    uuid().references(() => nodesTable.join().uuid, { onDelete: 'set null' }),
+  )
+  .addColumn('status', nodeEvacuationServerStatusEnum.intoDrizzleEnum()().default('PENDING').notNull())
+  .addColumn('error', text())
+  .addColumn('started', timestamp())
+  .addColumn('finished', timestamp())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('node_evacuation_servers_evacuation_uuid_idx').on(cols.evacuation_uuid),
+    uniqueIndex('node_evacuation_servers_evacuation_uuid_server_uuid_idx').on(cols.evacuation_uuid, cols.server_uuid),
+  ]);
+
+export const webhooksTable = new DatabaseTable('webhooks')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn('name', varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull())
+  .addColumn('description', text())
+  .addColumn('url', varchar({ length: 255 }).notNull())
+  .addColumn('secret', bytea().notNull())
+  .addColumn('events', varchar({ length: 255 }).array().default([]).notNull())
+  .addColumn('enabled', boolean().default(true).notNull())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [uniqueIndex('webhooks_name_idx').on(cols.name)]);
+
+export const webhookDeliveriesTable = new DatabaseTable('webhook_deliveries')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'webhook_uuid',
+    uuid()
+      .references(() => webhooksTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn('event', varchar({ length: 255 }).notNull())
+  .addColumn('payload', jsonb().notNull())
+  .addColumn('status', webhookDeliveryStatusEnum.intoDrizzleEnum()().default('PENDING').notNull())
+  .addColumn('attempts', smallint().default(0).notNull())
+  .addColumn('response_status', smallint())
+  .addColumn('error', text())
+  .addColumn('next_attempt', timestamp().defaultNow().notNull())
+  .addColumn('last_attempt', timestamp())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('webhook_deliveries_webhook_uuid_idx').on(cols.webhook_uuid),
+    index('webhook_deliveries_status_next_attempt_idx').on(cols.status, cols.next_attempt),
+  ]);
+
+export const mailTemplatesTable = new DatabaseTable('mail_templates')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn('name', varchar({ length: 255 }).notNull())
+  .addColumn('language', varchar({ length: 15 }).notNull())
+  .addColumn('subject', varchar({ length: 255 * UTF8_MAX_SCALAR_SIZE }).notNull())
+  .addColumn('body', text().notNull())
+  .addColumn('body_plain', text())
+  .addColumn('updated', timestamp().defaultNow().notNull())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [uniqueIndex('mail_templates_name_language_idx').on(cols.name, cols.language)]);
+
+export const userNotificationChannelsTable = new DatabaseTable('user_notification_channels')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'user_uuid',
+    uuid()
+      .references(() => usersTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn('name', varchar({ length: 31 * UTF8_MAX_SCALAR_SIZE }).notNull())
+  .addColumn('type', userNotificationChannelTypeEnum.intoDrizzleEnum()().notNull())
+  .addColumn('url', varchar({ length: 255 }))
+  .addColumn('events', varchar({ length: 31 }).array().default([]).notNull())
+  .addColumn('enabled', boolean().default(true).notNull())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('user_notification_channels_user_uuid_idx').on(cols.user_uuid),
+    uniqueIndex('user_notification_channels_user_uuid_name_idx').on(cols.user_uuid, cols.name),
+  ]);
+
+export const userNotificationsTable = new DatabaseTable('user_notifications')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'channel_uuid',
+    uuid()
+      .references(() => userNotificationChannelsTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn(
+    'server_uuid',
+    uuid()
+      .references(() => serversTable.join().uuid, { onDelete: 'cascade' })
+      .notNull(),
+  )
+  .addColumn('event', varchar({ length: 31 }).notNull())
+  .addColumn('data', jsonb().notNull())
+  .addColumn('attempts', smallint().default(0).notNull())
+  .addColumn('error', text())
+  .addColumn('next_attempt', timestamp().defaultNow().notNull())
+  .addColumn('sent', timestamp())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('user_notifications_channel_uuid_idx').on(cols.channel_uuid),
+    index('user_notifications_sent_next_attempt_idx').on(cols.sent, cols.next_attempt),
+  ]);
+
+export const resourceQuotasTable = new DatabaseTable('resource_quotas')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn(
+    'user_uuid',
+    uuid().references(() => usersTable.join().uuid, { onDelete: 'cascade' }),
+  )
+  .addColumn(
+    'role_uuid',
+    uuid().references(() => rolesTable.join().uuid, { onDelete: 'cascade' }),
+  )
+  .addColumn('servers', integer())
+  .addColumn('memory', bigint({ mode: 'number' }))
+  .addColumn('disk', bigint({ mode: 'number' }))
+  .addColumn('cpu', integer())
+  .addColumn('databases', integer())
+  .addColumn('backups', integer())
+  .addColumn('allocations', integer())
+  .addColumn('schedules', integer())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    uniqueIndex('resource_quotas_user_uuid_idx').on(cols.user_uuid),
+    uniqueIndex('resource_quotas_role_uuid_idx').on(cols.role_uuid),
+  ]);
+
+export const jobsTable = new DatabaseTable('jobs')
+  .addColumn('uuid', uuid().default(sql`gen_random_uuid()`).primaryKey().notNull())
+  .addColumn('name', varchar({ length: 255 }).notNull())
+  .addColumn('payload', jsonb().default({}).notNull())
+  .addColumn('status', jobStatusEnum.intoDrizzleEnum()().default('PENDING').notNull())
+  .addColumn('priority', smallint().default(0).notNull())
+  .addColumn('attempts', integer().default(0).notNull())
+  .addColumn('max_attempts', integer().default(3).notNull())
+  .addColumn('error', text())
+  .addColumn('locked_by', uuid())
+  .addColumn('locked_until', timestamp())
+  .addColumn('run_at', timestamp().defaultNow().notNull())
+  .addColumn('started', timestamp())
+  .addColumn('finished', timestamp())
+  .addColumn('created', timestamp().defaultNow().notNull())
+  .addConfigBuilder((cols) => [
+    index('jobs_name_idx').on(cols.name),
+    index('jobs_status_priority_run_at_idx').on(cols.status, cols.priority, cols.run_at),
+  ]);
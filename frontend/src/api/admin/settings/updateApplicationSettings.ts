@@ -13,6 +13,7 @@ export default async (data: z.infer<typeof adminSettingsApplicationSchema>): Pro
           two_factor_requirement: data.twoFactorRequirement,
           telemetry_enabled: data.telemetryEnabled,
           registration_enabled: data.registrationEnabled,
+          magic_link_login_enabled: data.magicLinkLoginEnabled,
         },
       })
       .then(() => resolve())
@@ -0,0 +1,16 @@
+import { axiosInstance } from '@/api/axios.ts';
+
+interface Response {
+  user?: User;
+  token?: string;
+  type: 'completed' | 'two_factor_required';
+}
+
+export default async (token: string): Promise<Response> => {
+  return new Promise((resolve, reject) => {
+    axiosInstance
+      .post('/api/auth/login/magic-link/redeem', { token })
+      .then(({ data }) => resolve(data))
+      .catch(reject);
+  });
+};
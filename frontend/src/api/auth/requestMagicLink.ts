@@ -0,0 +1,12 @@
+import { z } from 'zod';
+import { axiosInstance } from '@/api/axios.ts';
+import { authMagicLinkSchema } from '@/lib/schemas/auth.ts';
+
+export default async (data: z.infer<typeof authMagicLinkSchema>, captcha: string | null): Promise<void> => {
+  return new Promise((resolve, reject) => {
+    axiosInstance
+      .post('/api/auth/login/magic-link', { ...data, captcha })
+      .then(() => resolve())
+      .catch(reject);
+  });
+};
@@ -7,6 +7,7 @@ export const adminSettingsApplicationSchema = z.object({
   twoFactorRequirement: z.enum(['admins', 'all_users', 'none']),
   telemetryEnabled: z.boolean(),
   registrationEnabled: z.boolean(),
+  magicLinkLoginEnabled: z.boolean(),
 });
 
 export const adminSettingsCaptchaProviderNoneSchema = z.object({
@@ -4,6 +4,10 @@ export const authForgotPasswordSchema = z.object({
   email: z.email(),
 });
 
+export const authMagicLinkSchema = z.object({
+  email: z.email(),
+});
+
 export const authRegisterSchema = z.object({
   username: z
     .string()
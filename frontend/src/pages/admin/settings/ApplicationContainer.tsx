@@ -35,6 +35,7 @@ export default function ApplicationContainer() {
       twoFactorRequirement: 'none',
       telemetryEnabled: true,
       registrationEnabled: true,
+      magicLinkLoginEnabled: false,
     },
     validateInputOnBlur: true,
     validate: zod4Resolver(adminSettingsApplicationSchema),
@@ -163,6 +164,15 @@ export default function ApplicationContainer() {
               }}
             />
           </Group>
+
+          <Group grow>
+            <Switch
+              label='Enable Magic Link Login'
+              description='Allow users to sign in using a one-time link sent to their email address.'
+              name='magicLinkLoginEnabled'
+              {...form.getInputProps('magicLinkLoginEnabled', { type: 'checkbox' })}
+            />
+          </Group>
         </Stack>
 
         <Group mt='md'>
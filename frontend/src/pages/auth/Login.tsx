@@ -235,6 +235,11 @@ export default function Login() {
                   Register
                 </Button>
               )}
+              {settings.app.magicLinkLoginEnabled && (
+                <Button variant='light' onClick={() => navigate('/auth/magic-link')} size='md' fullWidth>
+                  Email Login Link
+                </Button>
+              )}
               {oAuthProviders.length > 0 && (
                 <Button
                   variant='light'
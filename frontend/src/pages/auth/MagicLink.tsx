@@ -0,0 +1,205 @@
+import { faExclamationTriangle, faInfoCircle } from '@fortawesome/free-solid-svg-icons';
+import { FontAwesomeIcon } from '@fortawesome/react-fontawesome';
+import { Alert, Center, Divider, Stack, Text, Title } from '@mantine/core';
+import { useForm } from '@mantine/form';
+import { zod4Resolver } from 'mantine-form-zod-resolver';
+import { useEffect, useRef, useState } from 'react';
+import { useNavigate, useSearchParams } from 'react-router';
+import { z } from 'zod';
+import checkpointLogin from '@/api/auth/checkpointLogin.ts';
+import redeemMagicLink from '@/api/auth/redeemMagicLink.ts';
+import requestMagicLink from '@/api/auth/requestMagicLink.ts';
+import { httpErrorToHuman } from '@/api/axios.ts';
+import Button from '@/elements/Button.tsx';
+import Captcha, { CaptchaRef } from '@/elements/Captcha.tsx';
+import Card from '@/elements/Card.tsx';
+import PinInput from '@/elements/input/PinInput.tsx';
+import TextInput from '@/elements/input/TextInput.tsx';
+import { authMagicLinkSchema, authTotpSchema } from '@/lib/schemas/auth.ts';
+import { useAuth } from '@/providers/AuthProvider.tsx';
+import AuthWrapper from './AuthWrapper.tsx';
+
+export default function MagicLink() {
+  const { doLogin } = useAuth();
+  const navigate = useNavigate();
+  const [searchParams] = useSearchParams();
+  const token = searchParams.get('token');
+
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState('');
+  const [success, setSuccess] = useState('');
+  const [requested, setRequested] = useState(false);
+  const [step, setStep] = useState<'request' | 'redeem' | 'totp'>(token ? 'redeem' : 'request');
+  const [twoFactorToken, setTwoFactorToken] = useState('');
+  const captchaRef = useRef<CaptchaRef>(null);
+
+  const form = useForm<z.infer<typeof authMagicLinkSchema>>({
+    initialValues: {
+      email: '',
+    },
+    validateInputOnBlur: true,
+    validate: zod4Resolver(authMagicLinkSchema),
+  });
+
+  const totpForm = useForm({
+    initialValues: {
+      code: '',
+    },
+    validateInputOnBlur: true,
+    validate: zod4Resolver(authTotpSchema),
+  });
+
+  useEffect(() => {
+    if (!token) {
+      return;
+    }
+
+    setLoading(true);
+
+    redeemMagicLink(token)
+      .then((response) => {
+        if (response.type === 'two_factor_required') {
+          setTwoFactorToken(response.token!);
+          setStep('totp');
+          return;
+        }
+
+        doLogin(response.user!);
+      })
+      .catch((msg) => {
+        setError(httpErrorToHuman(msg));
+      })
+      .finally(() => setLoading(false));
+  }, [token]);
+
+  const doRequest = () => {
+    setLoading(true);
+
+    captchaRef.current?.getToken().then((captcha) => {
+      requestMagicLink(form.values, captcha)
+        .then(() => {
+          setSuccess('If an account with that email exists, a login link has been sent to it.');
+          setRequested(true);
+        })
+        .catch((msg) => {
+          setError(httpErrorToHuman(msg));
+        })
+        .finally(() => setLoading(false));
+    });
+  };
+
+  const doSubmitTotp = () => {
+    setLoading(true);
+
+    checkpointLogin({ code: totpForm.values.code, confirmation_token: twoFactorToken })
+      .then((response) => {
+        doLogin(response.user);
+      })
+      .catch((msg) => {
+        setError(httpErrorToHuman(msg));
+      })
+      .finally(() => setLoading(false));
+  };
+
+  return (
+    <AuthWrapper>
+      <Stack>
+        {error && (
+          <Alert
+            icon={<FontAwesomeIcon icon={faExclamationTriangle} />}
+            color='red'
+            title='Error'
+            onClose={() => setError('')}
+            withCloseButton
+          >
+            {error}
+          </Alert>
+        )}
+        {success && (
+          <Alert
+            icon={<FontAwesomeIcon icon={faInfoCircle} />}
+            color='green'
+            title='Success'
+            onClose={() => setSuccess('')}
+            withCloseButton
+          >
+            {success}
+          </Alert>
+        )}
+
+        <Card>
+          {step === 'request' ? (
+            <Stack>
+              <Title order={2} ta='center'>
+                Email Login Link
+              </Title>
+              <Text c='dimmed' ta='center'>
+                Enter your email to receive a link that signs you in
+              </Text>
+
+              <TextInput placeholder='Email' {...form.getInputProps('email')} />
+              <Captcha ref={captchaRef} />
+
+              <Button onClick={doRequest} loading={loading} disabled={requested || !form.isValid()} size='md' fullWidth>
+                Send Login Link
+              </Button>
+
+              <Divider label='OR' labelPosition='center' />
+
+              <Button variant='light' onClick={() => navigate('/auth/login')} size='md' fullWidth>
+                Login
+              </Button>
+            </Stack>
+          ) : step === 'redeem' ? (
+            <Stack>
+              <Title order={2} ta='center'>
+                Email Login Link
+              </Title>
+              <Text c='dimmed' ta='center'>
+                {loading ? 'Signing you in...' : 'This login link is invalid or has expired'}
+              </Text>
+
+              <Button
+                variant='light'
+                onClick={() => {
+                  setError('');
+                  setStep('request');
+                  navigate('/auth/magic-link');
+                }}
+                disabled={loading}
+                size='md'
+                fullWidth
+              >
+                Request New Link
+              </Button>
+            </Stack>
+          ) : (
+            <Stack>
+              <Title order={2} ta='center'>
+                Two-Factor Authentication
+              </Title>
+              <Text c='dimmed' ta='center'>
+                Enter the 6-digit code from your authenticator app
+              </Text>
+
+              <Center>
+                <PinInput
+                  length={6}
+                  placeholder='0'
+                  size='md'
+                  type='number'
+                  oneTimeCode
+                  {...totpForm.getInputProps('code')}
+                />
+              </Center>
+
+              <Button onClick={doSubmitTotp} loading={loading} disabled={!totpForm.isValid()} size='md' fullWidth>
+                Verify Code
+              </Button>
+            </Stack>
+          )}
+        </Card>
+      </Stack>
+    </AuthWrapper>
+  );
+}
@@ -63,6 +63,7 @@ export default function OobeConfiguration({ onNext }: OobeComponentProps) {
       twoFactorRequirement: 'none',
       telemetryEnabled: form.values.applicationTelemetry,
       registrationEnabled: form.values.applicationRegistration,
+      magicLinkLoginEnabled: false,
     })
       .then(() => {
         setLanguage(form.values.applicationLanguage);
@@ -2,6 +2,7 @@ import type { GlobalRouteDefinition } from 'shared';
 import ForgotPassword from '@/pages/auth/ForgotPassword.tsx';
 import Login from '@/pages/auth/Login.tsx';
 import LoginOAuth from '@/pages/auth/LoginSteps/LoginOAuth.tsx';
+import MagicLink from '@/pages/auth/MagicLink.tsx';
 import Register from '@/pages/auth/Register.tsx';
 import ResetPassword from '@/pages/auth/ResetPassword.tsx';
 
@@ -14,6 +15,10 @@ const routes: GlobalRouteDefinition[] = [
     path: '/login/oauth',
     element: LoginOAuth,
   },
+  {
+    path: '/magic-link',
+    element: MagicLink,
+  },
   {
     path: '/register',
     element: Register,
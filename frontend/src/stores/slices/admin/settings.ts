@@ -24,6 +24,7 @@ export const createSettingsSlice: StateCreator<AdminStore, [], [], SettingsSlice
     twoFactorRequirement: 'none',
     telemetryEnabled: true,
     registrationEnabled: true,
+    magicLinkLoginEnabled: false,
   },
   server: {
     maxFileManagerViewSize: 10 * 1024 * 1024 * 1024,
@@ -90,6 +90,72 @@ const baseTranslations = defineTranslations({
       no: 'No',
       system: 'System',
     },
+    validation: {
+      accepted: "must be 'true', '1', 'yes', or 'on'",
+      acceptedIf: "must be 'true', '1', 'yes', or 'on' when '{other}' is '{value}'",
+      after: 'must be a date after {date}',
+      afterOrEqual: 'must be a date after or equal to {date}',
+      alpha: 'must contain only alphabetic characters',
+      alphaDash: 'must contain only alphanumeric characters, dashes, or underscores',
+      alphaNum: 'must contain only alphanumeric characters',
+      array: 'must be a JSON array',
+      ascii: 'must contain only ASCII characters',
+      before: 'must be a date before {date}',
+      beforeOrEqual: 'must be a date before or equal to {date}',
+      between: 'must be between {min} and {max}',
+      boolean: 'must be a boolean (true/false, 1/0, yes/no, on/off)',
+      confirmed: "does not match confirmation field '{other}'",
+      date: 'must be a valid date',
+      dateFormat: "must match the format '{format}'",
+      declined: "must be 'false', '0', 'no', or 'off'",
+      declinedIf: "must be 'false', '0', 'no', or 'off' when '{other}' is '{value}'",
+      different: "must be different from '{other}'",
+      digits: 'must contain exactly {digits} digits',
+      digitsBetween: 'must contain between {min} and {max} digits',
+      distinct: 'must not contain duplicate values',
+      doesntStartWith: "must not start with '{value}'",
+      doesntEndWith: "must not end with '{value}'",
+      endsWith: 'must end with one of: {values}',
+      gt: 'must be greater than {value}',
+      gte: 'must be greater than or equal to {value}',
+      hexColor: 'must be a valid hex color code',
+      in: 'must be one of: {values}',
+      inArray: "must be one of the values of '{other}'",
+      integer: 'must be a valid integer',
+      ip: 'must be a valid IP address',
+      ipv4: 'must be a valid IPv4 address',
+      ipv6: 'must be a valid IPv6 address',
+      json: 'must be valid JSON',
+      lt: 'must be less than {value}',
+      lte: 'must be less than or equal to {value}',
+      lowercase: 'must be lowercase',
+      macAddress: 'must be a valid MAC address',
+      max: 'must be less than or equal to {max}',
+      maxDigits: 'must contain at most {max} digits',
+      min: 'must be greater than or equal to {min}',
+      minDigits: 'must contain at least {min} digits',
+      multipleOf: 'must be a multiple of {value}',
+      notIn: 'must not be one of: {values}',
+      notRegex: "must not match the regex pattern '{pattern}'",
+      numeric: 'must be a valid numeric value',
+      prohibited: 'must be empty',
+      prohibitedIf: "must be empty when '{other}' is '{value}'",
+      regex: "must match the regex pattern '{pattern}'",
+      required: 'is required and cannot be empty',
+      requiredIf: "is required when '{other}' is '{value}'",
+      requiredIfAccepted: "is required when '{other}' is accepted",
+      requiredIfDeclined: "is required when '{other}' is declined",
+      requiredUnless: "is required unless '{other}' is one of: {values}",
+      requiredWith: 'is required when any of {values} is present',
+      requiredWithout: 'is required when any of {values} is not present',
+      same: "must be the same as '{other}'",
+      size: 'must be equal to {size}',
+      startsWith: 'must start with one of: {values}',
+      timezone: 'must be a valid timezone',
+      uppercase: 'must be uppercase',
+      url: 'must be a valid URL with one of the following protocols: {protocols}',
+      uuid: 'must be a valid UUID',
+    },
     pages: {
       oobe: {
         welcome: {
@@ -99,8 +99,23 @@ declare global {
     gitRepository: string;
   }
 
+  interface AdminEggRepositorySyncReport {
+    found: number;
+    added: string[];
+    changed: string[];
+    removed: string[];
+    failed: { path: string; error: string }[];
+    updatedEggs: string[];
+  }
+
   interface AdminEggRepository extends AdminUpdateEggRepository {
     uuid: string;
+    gitReference: string | null;
+    gitSubdirectory: string | null;
+    credentialsType: 'token' | 'ssh_key' | null;
+    syncSchedule: string | null;
+    lastSyncReport: AdminEggRepositorySyncReport | null;
+    lastSynced: Date | null;
     created: Date;
   }
 
@@ -111,6 +126,7 @@ declare global {
     name: string;
     description: string | null;
     exportedEgg: object;
+    updated: Date;
   }
 
   interface AdminNest {
@@ -170,9 +186,29 @@ declare global {
   interface AdminNestEgg extends Omit<AdminUpdateNestEgg, 'eggRepositoryEggUuid'> {
     uuid: string;
     eggRepositoryEgg: (AdminEggRepositoryEgg & { eggRepository: AdminEggRepository }) | null;
+    updatePolicy: NestEggUpdatePolicy;
+    updateAvailable: boolean;
     created: Date;
   }
 
+  type NestEggUpdatePolicy = 'manual' | 'non_breaking';
+
+  interface AdminNestEggDiff {
+    fields: string[];
+    startup: { old: string; new: string } | null;
+    installScript: { old: AdminNestEgg['configScript']; new: AdminNestEgg['configScript'] } | null;
+    dockerImagesAdded: Record<string, string>;
+    dockerImagesRemoved: Record<string, string>;
+    dockerImagesChanged: Record<string, { old: string; new: string }>;
+    variablesAdded: string[];
+    variablesRemoved: string[];
+    variablesChanged: { envVariable: string; fields: string[] }[];
+    configFilesAdded: string[];
+    configFilesRemoved: string[];
+    configFilesChanged: string[];
+    breakingChanges: string[];
+  }
+
   interface NestEggVariable {
     uuid: string;
     name: string;
@@ -184,6 +220,7 @@ declare global {
     userEditable: boolean;
     isSecret: boolean;
     rules: string[];
+    rulesSchema: Record<string, unknown>;
     created: Date;
   }
 
@@ -469,6 +506,7 @@ declare global {
     isEditable: boolean;
     isSecret: boolean;
     rules: string[];
+    rulesSchema: Record<string, unknown>;
     created: Date;
   }
 
@@ -1099,6 +1137,7 @@ declare global {
       name: string;
       language: string;
       registrationEnabled: boolean;
+      magicLinkLoginEnabled: boolean;
     };
     server: {
       maxFileManagerViewSize: number;
@@ -1124,6 +1163,7 @@ declare global {
       twoFactorRequirement: TwoFactorRequirement;
       telemetryEnabled: boolean;
       registrationEnabled: boolean;
+      magicLinkLoginEnabled: boolean;
     };
     webauthn: {
       rpId: string;
@@ -1,7 +1,13 @@
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
 use validator::ValidationError;
 
+mod messages;
 mod rules;
+mod schema;
+
+pub use schema::JsonSchema;
 
 pub fn validate_rules(rules: &[compact_str::CompactString]) -> Result<(), ValidationError> {
     for rule in rules {
@@ -13,6 +19,89 @@ pub fn validate_rules(rules: &[compact_str::CompactString]) -> Result<(), Valida
     Ok(())
 }
 
+#[derive(ToSchema, Serialize, Debug, Clone)]
+pub struct UnsupportedRule {
+    pub rule: compact_str::CompactString,
+    pub error: compact_str::CompactString,
+}
+
+/// Splits rules into the supported ones and the ones that can not be used, along with the reason why.
+pub fn partition_rules(
+    rules: &[compact_str::CompactString],
+) -> (Vec<compact_str::CompactString>, Vec<UnsupportedRule>) {
+    let mut supported = Vec::new();
+    let mut unsupported = Vec::new();
+
+    for rule in rules {
+        match rules::parse_validation_rule(rule) {
+            Ok(_) => supported.push(rule.clone()),
+            Err(error) => unsupported.push(UnsupportedRule {
+                rule: rule.clone(),
+                error,
+            }),
+        }
+    }
+
+    (supported, unsupported)
+}
+
+/// Compiles the rules of a value into a JSON Schema, invalid rules are ignored.
+pub fn rules_json_schema(rules: &[compact_str::CompactString]) -> serde_json::Value {
+    let mut schema = JsonSchema::default();
+    for rule in rules {
+        if let Ok(rule) = rules::parse_validation_rule(rule) {
+            rule.json_schema(&mut schema);
+        }
+    }
+
+    schema.into_value()
+}
+
+/// A failed rule, `message` is a stable key that can be translated using `params`.
+#[derive(Debug, Clone)]
+pub struct RuleError {
+    pub message: &'static str,
+    pub params: BTreeMap<&'static str, compact_str::CompactString>,
+}
+
+impl RuleError {
+    #[inline]
+    pub fn new(message: &'static str) -> Self {
+        Self {
+            message,
+            params: BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn with_param(mut self, name: &'static str, value: impl std::fmt::Display) -> Self {
+        self.params
+            .insert(name, compact_str::format_compact!("{value}"));
+        self
+    }
+}
+
+#[derive(ToSchema, Serialize, Debug, Clone)]
+pub struct FieldError {
+    pub field: compact_str::CompactString,
+    pub rule: &'static str,
+    /// A stable message key, for example `validation.between`.
+    pub message: &'static str,
+    /// The values for the placeholders of the message, for example `min` and `max`.
+    pub params: BTreeMap<&'static str, compact_str::CompactString>,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut message = messages::english(self.message).to_string();
+        for (name, value) in &self.params {
+            message = message.replace(&format!("{{{name}}}"), value);
+        }
+
+        write!(f, "{}: {}", self.field, message)
+    }
+}
+
 pub struct Validator<'a> {
     pub rules: HashMap<&'a str, Vec<Box<dyn ValidateRule>>>,
     pub data: HashMap<&'a str, &'a str>,
@@ -59,21 +148,39 @@ impl<'a> Validator<'a> {
         false
     }
 
-    pub fn validate(&self) -> Result<(), String> {
-        for (key, rules) in &self.rules {
-            for rule in rules {
+    /// Runs the rules of every key and returns the failures, ordered by key.
+    /// Only the first failing rule of a key is reported.
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut keys = self.rules.keys().copied().collect::<Vec<_>>();
+        keys.sort_unstable();
+
+        let mut errors = Vec::new();
+        for key in keys {
+            for rule in &self.rules[key] {
                 match rule.validate(key, self) {
                     Ok(abort_early) => {
                         if abort_early {
                             break;
                         }
                     }
-                    Err(err) => return Err(format!("{key}: {err}")),
+                    Err(err) => {
+                        errors.push(FieldError {
+                            field: key.into(),
+                            rule: rule.label(),
+                            message: err.message,
+                            params: err.params,
+                        });
+                        break;
+                    }
                 }
             }
         }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
     pub fn into_data(self) -> HashMap<&'a str, &'a str> {
@@ -84,11 +191,11 @@ impl<'a> Validator<'a> {
 pub trait ValidateRule: Send + Sync {
     fn label(&self) -> &'static str;
 
-    fn validate(
-        &self,
-        key: &str,
-        validator: &Validator,
-    ) -> Result<bool, compact_str::CompactString>;
+    /// Returns whether the remaining rules of the key should be skipped.
+    fn validate(&self, key: &str, validator: &Validator) -> Result<bool, RuleError>;
+
+    /// Adds the constraints of this rule to `schema`, rules that can not be expressed leave it untouched.
+    fn json_schema(&self, _schema: &mut JsonSchema) {}
 }
 
 pub trait ParseValidationRule: Send + Sync {
@@ -96,3 +203,346 @@ pub trait ParseValidationRule: Send + Sync {
         rules: &[compact_str::CompactString],
     ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(rules: &[&str]) -> Vec<compact_str::CompactString> {
+        rules.iter().map(|rule| (*rule).into()).collect()
+    }
+
+    fn validate(
+        fields: &[(&str, Vec<compact_str::CompactString>, &str)],
+    ) -> Result<(), Vec<FieldError>> {
+        let data = fields
+            .iter()
+            .map(|(key, rules, value)| (*key, (rules.as_slice(), *value)))
+            .collect();
+
+        Validator::new(data).unwrap().validate()
+    }
+
+    fn failed_rules(fields: &[(&str, Vec<compact_str::CompactString>, &str)]) -> Vec<&'static str> {
+        match validate(fields) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|error| error.rule).collect(),
+        }
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let key_rules = rules(&["integer", "unknown_rule"]);
+        let data = HashMap::from([("count", (key_rules.as_slice(), "1"))]);
+
+        assert!(Validator::new(data).is_err());
+        assert!(validate_rules(&rules(&["between:1"])).is_err());
+        assert!(validate_rules(&rules(&["required", "between:1,10"])).is_ok());
+    }
+
+    #[test]
+    fn only_the_first_failure_of_a_key_is_reported() {
+        let errors =
+            validate(&[("count", rules(&["required", "integer", "min:5"]), "")]).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule, "required");
+        assert_eq!(errors[0].message, "validation.required");
+
+        assert_eq!(
+            failed_rules(&[("count", rules(&["required", "integer", "min:5"]), "abc")]),
+            ["integer"]
+        );
+        assert_eq!(
+            failed_rules(&[("count", rules(&["required", "integer", "min:5"]), "3")]),
+            ["min"]
+        );
+        assert!(validate(&[("count", rules(&["required", "integer", "min:5"]), "7")]).is_ok());
+    }
+
+    #[test]
+    fn failures_are_ordered_by_key() {
+        let errors = validate(&[
+            ("port", rules(&["integer"]), "abc"),
+            ("name", rules(&["required"]), ""),
+            ("memory", rules(&["integer"]), "1024"),
+        ])
+        .unwrap_err();
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| error.field.as_str())
+                .collect::<Vec<_>>(),
+            ["name", "port"]
+        );
+    }
+
+    #[test]
+    fn nullable_skips_the_remaining_rules() {
+        assert!(validate(&[("count", rules(&["nullable", "integer"]), "")]).is_ok());
+        assert!(validate(&[("count", rules(&["nullable", "integer"]), "null")]).is_ok());
+        assert_eq!(
+            failed_rules(&[("count", rules(&["nullable", "integer"]), "abc")]),
+            ["integer"]
+        );
+    }
+
+    #[test]
+    fn errors_are_displayed_with_their_params() {
+        let errors = validate(&[("count", rules(&["between:1,10"]), "20")]).unwrap_err();
+
+        assert_eq!(errors[0].params["min"], "1");
+        assert_eq!(errors[0].params["max"], "10");
+        assert_eq!(errors[0].to_string(), "count: must be between 1 and 10");
+    }
+
+    #[test]
+    fn json_schema_of_numbers() {
+        assert_eq!(
+            rules_json_schema(&rules(&["integer", "between:1,10"])),
+            serde_json::json!({ "type": "integer", "minimum": 1.0, "maximum": 10.0 })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["numeric", "gt:0", "lt:100", "multiple_of:5"])),
+            serde_json::json!({
+                "type": "number",
+                "exclusiveMinimum": 0.0,
+                "exclusiveMaximum": 100.0,
+                "multipleOf": 5.0,
+            })
+        );
+    }
+
+    #[test]
+    fn json_schema_of_strings() {
+        assert_eq!(
+            rules_json_schema(&rules(&["required"])),
+            serde_json::json!({ "type": "string", "minLength": 1 })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["required", "string", "between:3,20", "max:16"])),
+            serde_json::json!({ "type": "string", "minLength": 3, "maxLength": 16 })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["required", "min:10", "max:20"])),
+            serde_json::json!({ "type": "string", "minLength": 1 })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["between:100,200"])),
+            serde_json::json!({ "type": "string" })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["regex:/^[a-z]+$/", "not_in:root,admin"])),
+            serde_json::json!({
+                "type": "string",
+                "pattern": "^[a-z]+$",
+                "not": { "enum": ["root", "admin"] },
+            })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["ip"])),
+            serde_json::json!({
+                "type": "string",
+                "anyOf": [{ "format": "ipv4" }, { "format": "ipv6" }],
+            })
+        );
+    }
+
+    #[test]
+    fn json_schema_of_allowed_values() {
+        assert_eq!(
+            rules_json_schema(&rules(&["nullable", "in:a,b"])),
+            serde_json::json!({ "type": ["string", "null"], "enum": ["a", "b"] })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["integer", "in:1,2,3", "in:2,3,4"])),
+            serde_json::json!({ "type": "integer", "enum": [2, 3] })
+        );
+    }
+
+    #[test]
+    fn json_schema_ignores_invalid_and_dependent_rules() {
+        assert_eq!(
+            rules_json_schema(&rules(&["integer", "unknown_rule", "between:1"])),
+            serde_json::json!({ "type": "integer" })
+        );
+        assert_eq!(
+            rules_json_schema(&rules(&["required_if:mode,auto", "same:other"])),
+            serde_json::json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn unsupported_rules_are_partitioned() {
+        let (supported, unsupported) =
+            partition_rules(&rules(&["integer", "unknown_rule", "between:1,2"]));
+
+        assert_eq!(supported, rules(&["integer", "between:1,2"]));
+        assert_eq!(unsupported.len(), 1);
+        assert_eq!(unsupported[0].rule, "unknown_rule");
+        assert_eq!(
+            unsupported[0].error,
+            "unknown or unsupported validation rule: unknown_rule"
+        );
+    }
+
+    #[test]
+    fn date_rules() {
+        assert!(validate(&[("day", rules(&["date"]), "2024-02-30")]).is_err());
+        assert!(validate(&[("day", rules(&["date", "after:2024-01-01"]), "2024-02-01")]).is_ok());
+        assert_eq!(
+            failed_rules(&[("day", rules(&["date", "after:2024-01-01"]), "2023-12-31")]),
+            ["after"]
+        );
+        assert!(validate(&[("day", rules(&["before:2024-01-01"]), "2023-06-01")]).is_ok());
+        assert_eq!(
+            failed_rules(&[("day", rules(&["before:2024-01-01"]), "2024-01-01")]),
+            ["before"]
+        );
+
+        assert!(
+            validate(&[
+                ("start", rules(&[]), "2024-01-01 10:00:00"),
+                ("end", rules(&["after:start"]), "2024-01-01 12:00:00"),
+            ])
+            .is_ok()
+        );
+        assert_eq!(
+            failed_rules(&[
+                ("start", rules(&[]), "2024-01-01 10:00:00"),
+                ("end", rules(&["after:start"]), "2024-01-01 08:00:00"),
+            ]),
+            ["after"]
+        );
+    }
+
+    #[test]
+    fn array_rules() {
+        assert!(validate(&[("ports", rules(&["array", "distinct"]), "[1, 2, 3]")]).is_ok());
+        assert_eq!(
+            failed_rules(&[("ports", rules(&["array", "distinct"]), "1,2,3")]),
+            ["array"]
+        );
+        assert_eq!(
+            failed_rules(&[("ports", rules(&["array", "distinct"]), r#"[1, 2, 1]"#)]),
+            ["distinct"]
+        );
+
+        let options = [("options", rules(&[]), r#"["a", "b"]"#)];
+        assert!(
+            validate(&[
+                options[0].clone(),
+                ("choice", rules(&["in_array:options.*"]), "a")
+            ])
+            .is_ok()
+        );
+        assert_eq!(
+            failed_rules(&[
+                options[0].clone(),
+                ("choice", rules(&["in_array:options"]), "c")
+            ]),
+            ["in_array"]
+        );
+    }
+
+    #[test]
+    fn prohibited_rules() {
+        assert!(validate(&[("token", rules(&["prohibited"]), "")]).is_ok());
+        assert_eq!(
+            failed_rules(&[("token", rules(&["prohibited"]), "secret")]),
+            ["prohibited"]
+        );
+
+        assert_eq!(
+            failed_rules(&[
+                ("mode", rules(&[]), "auto"),
+                ("port", rules(&["prohibited_if:mode,auto"]), "25565"),
+            ]),
+            ["prohibited_if"]
+        );
+        assert!(
+            validate(&[
+                ("mode", rules(&[]), "manual"),
+                (
+                    "port",
+                    rules(&["prohibited_if:mode,auto", "integer"]),
+                    "25565"
+                ),
+            ])
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn exclude_if_skips_the_remaining_rules() {
+        assert!(
+            validate(&[
+                ("mode", rules(&[]), "auto"),
+                ("port", rules(&["exclude_if:mode,auto", "integer"]), "abc"),
+            ])
+            .is_ok()
+        );
+        assert_eq!(
+            failed_rules(&[
+                ("mode", rules(&[]), "manual"),
+                ("port", rules(&["exclude_if:mode,auto", "integer"]), "abc"),
+            ]),
+            ["integer"]
+        );
+    }
+
+    #[test]
+    fn required_with_rules_only_skip_empty_values() {
+        let port_rules = rules(&["required_with:ip", "integer"]);
+        assert!(validate(&[("ip", rules(&[]), ""), ("port", port_rules.clone(), "")]).is_ok());
+        assert_eq!(
+            failed_rules(&[("ip", rules(&[]), ""), ("port", port_rules.clone(), "abc")]),
+            ["integer"]
+        );
+        assert_eq!(
+            failed_rules(&[("ip", rules(&[]), "127.0.0.1"), ("port", port_rules, "")]),
+            ["required_with"]
+        );
+
+        let port_rules = rules(&["required_without:socket", "integer"]);
+        assert!(
+            validate(&[
+                ("socket", rules(&[]), "/run/app.sock"),
+                ("port", port_rules.clone(), "")
+            ])
+            .is_ok()
+        );
+        assert_eq!(
+            failed_rules(&[
+                ("socket", rules(&[]), "/run/app.sock"),
+                ("port", port_rules.clone(), "abc"),
+            ]),
+            ["integer"]
+        );
+        assert_eq!(
+            failed_rules(&[("socket", rules(&[]), ""), ("port", port_rules, "")]),
+            ["required_without"]
+        );
+
+        let port_rules = rules(&["required_unless:mode,auto", "integer"]);
+        assert!(
+            validate(&[
+                ("mode", rules(&[]), "auto"),
+                ("port", port_rules.clone(), "")
+            ])
+            .is_ok()
+        );
+        assert_eq!(
+            failed_rules(&[
+                ("mode", rules(&[]), "auto"),
+                ("port", port_rules.clone(), "abc")
+            ]),
+            ["integer"]
+        );
+        assert_eq!(
+            failed_rules(&[("mode", rules(&[]), "manual"), ("port", port_rules, "")]),
+            ["required_unless"]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+/// The english message for a rule error key, `{param}` placeholders are filled from the error parameters.
+pub fn english(message: &str) -> &'static str {
+    match message {
+        "validation.accepted" => "must be 'true', '1', 'yes', or 'on'",
+        "validation.acceptedIf" => {
+            "must be 'true', '1', 'yes', or 'on' when '{other}' is '{value}'"
+        }
+        "validation.after" => "must be a date after {date}",
+        "validation.afterOrEqual" => "must be a date after or equal to {date}",
+        "validation.alpha" => "must contain only alphabetic characters",
+        "validation.alphaDash" => {
+            "must contain only alphanumeric characters, dashes, or underscores"
+        }
+        "validation.alphaNum" => "must contain only alphanumeric characters",
+        "validation.array" => "must be a JSON array",
+        "validation.ascii" => "must contain only ASCII characters",
+        "validation.before" => "must be a date before {date}",
+        "validation.beforeOrEqual" => "must be a date before or equal to {date}",
+        "validation.between" => "must be between {min} and {max}",
+        "validation.boolean" => "must be a boolean (true/false, 1/0, yes/no, on/off)",
+        "validation.confirmed" => "does not match confirmation field '{other}'",
+        "validation.date" => "must be a valid date",
+        "validation.dateFormat" => "must match the format '{format}'",
+        "validation.declined" => "must be 'false', '0', 'no', or 'off'",
+        "validation.declinedIf" => {
+            "must be 'false', '0', 'no', or 'off' when '{other}' is '{value}'"
+        }
+        "validation.different" => "must be different from '{other}'",
+        "validation.digits" => "must contain exactly {digits} digits",
+        "validation.digitsBetween" => "must contain between {min} and {max} digits",
+        "validation.distinct" => "must not contain duplicate values",
+        "validation.doesntStartWith" => "must not start with '{value}'",
+        "validation.doesntEndWith" => "must not end with '{value}'",
+        "validation.endsWith" => "must end with one of: {values}",
+        "validation.gt" => "must be greater than {value}",
+        "validation.gte" => "must be greater than or equal to {value}",
+        "validation.hexColor" => "must be a valid hex color code",
+        "validation.in" => "must be one of: {values}",
+        "validation.inArray" => "must be one of the values of '{other}'",
+        "validation.integer" => "must be a valid integer",
+        "validation.ip" => "must be a valid IP address",
+        "validation.ipv4" => "must be a valid IPv4 address",
+        "validation.ipv6" => "must be a valid IPv6 address",
+        "validation.json" => "must be valid JSON",
+        "validation.lt" => "must be less than {value}",
+        "validation.lte" => "must be less than or equal to {value}",
+        "validation.lowercase" => "must be lowercase",
+        "validation.macAddress" => "must be a valid MAC address",
+        "validation.max" => "must be less than or equal to {max}",
+        "validation.maxDigits" => "must contain at most {max} digits",
+        "validation.min" => "must be greater than or equal to {min}",
+        "validation.minDigits" => "must contain at least {min} digits",
+        "validation.multipleOf" => "must be a multiple of {value}",
+        "validation.notIn" => "must not be one of: {values}",
+        "validation.notRegex" => "must not match the regex pattern '{pattern}'",
+        "validation.numeric" => "must be a valid numeric value",
+        "validation.prohibited" => "must be empty",
+        "validation.prohibitedIf" => "must be empty when '{other}' is '{value}'",
+        "validation.regex" => "must match the regex pattern '{pattern}'",
+        "validation.required" => "is required and cannot be empty",
+        "validation.requiredIf" => "is required when '{other}' is '{value}'",
+        "validation.requiredIfAccepted" => "is required when '{other}' is accepted",
+        "validation.requiredIfDeclined" => "is required when '{other}' is declined",
+        "validation.requiredUnless" => "is required unless '{other}' is one of: {values}",
+        "validation.requiredWith" => "is required when any of {values} is present",
+        "validation.requiredWithout" => "is required when any of {values} is not present",
+        "validation.same" => "must be the same as '{other}'",
+        "validation.size" => "must be equal to {size}",
+        "validation.startsWith" => "must start with one of: {values}",
+        "validation.timezone" => "must be a valid timezone",
+        "validation.uppercase" => "must be uppercase",
+        "validation.url" => "must be a valid URL with one of the following protocols: {protocols}",
+        "validation.uuid" => "must be a valid UUID",
+        _ => "is invalid",
+    }
+}
@@ -1,4 +1,4 @@
-use super::{ParseValidationRule, ValidateRule, Validator};
+use super::{JsonSchema, ParseValidationRule, RuleError, ValidateRule, Validator};
 
 pub fn parse_validation_rule(
     rule: &str,
@@ -17,10 +17,15 @@ pub fn parse_validation_rule(
     match rule_name {
         "accepted" => Accepted::parse_rule(&rule_args),
         "accepted_if" => AcceptedIf::parse_rule(&rule_args),
+        "after" => After::parse_rule(&rule_args),
+        "after_or_equal" => AfterOrEqual::parse_rule(&rule_args),
         "alpha" => Alpha::parse_rule(&rule_args),
         "alpha_dash" => AlphaDash::parse_rule(&rule_args),
         "alpha_num" => AlphaNum::parse_rule(&rule_args),
+        "array" => Array::parse_rule(&rule_args),
         "ascii" => Ascii::parse_rule(&rule_args),
+        "before" => Before::parse_rule(&rule_args),
+        "before_or_equal" => BeforeOrEqual::parse_rule(&rule_args),
         "between" => Between::parse_rule(&rule_args),
         "boolean" => Boolean::parse_rule(&rule_args),
         "confirmed" => Confirmed::parse_rule(&rule_args),
@@ -31,13 +36,16 @@ pub fn parse_validation_rule(
         "different" => Different::parse_rule(&rule_args),
         "digits" => Digits::parse_rule(&rule_args),
         "digits_between" => DigitsBetween::parse_rule(&rule_args),
+        "distinct" => Distinct::parse_rule(&rule_args),
         "doesnt_start_with" => DoesntStartWith::parse_rule(&rule_args),
         "doesnt_end_with" => DoesntEndWith::parse_rule(&rule_args),
         "ends_with" => EndsWith::parse_rule(&rule_args),
+        "exclude_if" => ExcludeIf::parse_rule(&rule_args),
         "gt" => Gt::parse_rule(&rule_args),
         "gte" => Gte::parse_rule(&rule_args),
         "hex_color" => HexColor::parse_rule(&rule_args),
         "in" => In::parse_rule(&rule_args),
+        "in_array" => InArray::parse_rule(&rule_args),
         "integer" | "int" => Integer::parse_rule(&rule_args),
         "ip" => Ip::parse_rule(&rule_args),
         "ipv4" => Ipv4::parse_rule(&rule_args),
@@ -56,11 +64,16 @@ pub fn parse_validation_rule(
         "not_regex" => NotRegex::parse_rule(&rule_args),
         "nullable" => Nullable::parse_rule(&rule_args),
         "numeric" | "num" => Numeric::parse_rule(&rule_args),
+        "prohibited" => Prohibited::parse_rule(&rule_args),
+        "prohibited_if" => ProhibitedIf::parse_rule(&rule_args),
         "regex" => Regex::parse_rule(&rule_args),
         "required" => Required::parse_rule(&rule_args),
         "required_if" => RequiredIf::parse_rule(&rule_args),
         "required_if_accepted" => RequiredIfAccepted::parse_rule(&rule_args),
         "required_if_declined" => RequiredIfDeclined::parse_rule(&rule_args),
+        "required_unless" => RequiredUnless::parse_rule(&rule_args),
+        "required_with" => RequiredWith::parse_rule(&rule_args),
+        "required_without" => RequiredWithout::parse_rule(&rule_args),
         "same" => Same::parse_rule(&rule_args),
         "size" => Size::parse_rule(&rule_args),
         "starts_with" => StartsWith::parse_rule(&rule_args),
@@ -90,12 +103,16 @@ impl ValidateRule for Accepted {
         "accepted"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         match data.data.get(key).copied() {
             Some("true") | Some("1") | Some("yes") | Some("on") => Ok(false),
-            _ => Err("value must be 'true', '1', 'yes', or 'on'".into()),
+            _ => Err(RuleError::new("validation.accepted")),
         }
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_values(&["true".into(), "1".into(), "yes".into(), "on".into()]);
+    }
 }
 
 pub struct AcceptedIf {
@@ -128,7 +145,7 @@ impl ValidateRule for AcceptedIf {
         "accepted_if"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         for (check_key, check_value) in &self.keys {
             if let Some(value) = data.data.get(check_key.as_str())
                 && value == check_value
@@ -136,7 +153,9 @@ impl ValidateRule for AcceptedIf {
                 match data.data.get(key).copied() {
                     Some("true") | Some("1") | Some("yes") | Some("on") => return Ok(false),
                     _ => {
-                        return Err("must be 'true', '1', 'yes', or 'on'".into());
+                        return Err(RuleError::new("validation.acceptedIf")
+                            .with_param("other", check_key)
+                            .with_param("value", check_value));
                     }
                 }
             }
@@ -146,6 +165,70 @@ impl ValidateRule for AcceptedIf {
     }
 }
 
+pub struct After {
+    date: compact_str::CompactString,
+}
+
+impl ParseValidationRule for After {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() != 1 {
+            return Err("after requires a date or key to compare to".into());
+        }
+
+        Ok(Box::new(After {
+            date: rule[0].clone(),
+        }))
+    }
+}
+
+impl ValidateRule for After {
+    fn label(&self) -> &'static str {
+        "after"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if compare_dates(key, &self.date, data).is_some_and(|ordering| ordering.is_gt()) {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.after").with_param("date", &self.date))
+    }
+}
+
+pub struct AfterOrEqual {
+    date: compact_str::CompactString,
+}
+
+impl ParseValidationRule for AfterOrEqual {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() != 1 {
+            return Err("after_or_equal requires a date or key to compare to".into());
+        }
+
+        Ok(Box::new(AfterOrEqual {
+            date: rule[0].clone(),
+        }))
+    }
+}
+
+impl ValidateRule for AfterOrEqual {
+    fn label(&self) -> &'static str {
+        "after_or_equal"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if compare_dates(key, &self.date, data).is_some_and(|ordering| ordering.is_ge()) {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.afterOrEqual").with_param("date", &self.date))
+    }
+}
+
 pub struct Alpha {
     only_ascii: bool,
 }
@@ -165,7 +248,7 @@ impl ValidateRule for Alpha {
         "alpha"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if self.only_ascii {
                 if value.chars().all(|c| c.is_ascii_alphabetic()) {
@@ -176,7 +259,13 @@ impl ValidateRule for Alpha {
             }
         }
 
-        Err("must contain only alphabetic characters".into())
+        Err(RuleError::new("validation.alpha"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        if self.only_ascii {
+            schema.patterns.push("^[a-zA-Z]*$".into());
+        }
     }
 }
 
@@ -199,7 +288,7 @@ impl ValidateRule for AlphaDash {
         "alpha_dash"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if self.only_ascii {
                 if value
@@ -216,7 +305,13 @@ impl ValidateRule for AlphaDash {
             }
         }
 
-        Err("must contain only alphanumeric characters, dashes, or underscores".into())
+        Err(RuleError::new("validation.alphaDash"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        if self.only_ascii {
+            schema.patterns.push("^[a-zA-Z0-9_-]*$".into());
+        }
     }
 }
 
@@ -239,7 +334,7 @@ impl ValidateRule for AlphaNum {
         "alpha_num"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if self.only_ascii {
                 if value.chars().all(|c| c.is_ascii_alphanumeric()) {
@@ -250,7 +345,46 @@ impl ValidateRule for AlphaNum {
             }
         }
 
-        Err("must contain only alphanumeric characters".into())
+        Err(RuleError::new("validation.alphaNum"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        if self.only_ascii {
+            schema.patterns.push("^[a-zA-Z0-9]*$".into());
+        }
+    }
+}
+
+pub struct Array;
+
+impl ParseValidationRule for Array {
+    fn parse_rule(
+        _rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        Ok(Box::new(Array))
+    }
+}
+
+impl ValidateRule for Array {
+    fn label(&self) -> &'static str {
+        "array"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if data
+            .data
+            .get(key)
+            .and_then(|value| parse_array(value))
+            .is_some()
+        {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.array"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.content_media_type = Some("application/json");
     }
 }
 
@@ -269,14 +403,82 @@ impl ValidateRule for Ascii {
         "ascii"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.is_ascii()
         {
             return Ok(false);
         }
 
-        Err("must contain only ASCII characters".into())
+        Err(RuleError::new("validation.ascii"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push("^[\\x00-\\x7F]*$".into());
+    }
+}
+
+pub struct Before {
+    date: compact_str::CompactString,
+}
+
+impl ParseValidationRule for Before {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() != 1 {
+            return Err("before requires a date or key to compare to".into());
+        }
+
+        Ok(Box::new(Before {
+            date: rule[0].clone(),
+        }))
+    }
+}
+
+impl ValidateRule for Before {
+    fn label(&self) -> &'static str {
+        "before"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if compare_dates(key, &self.date, data).is_some_and(|ordering| ordering.is_lt()) {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.before").with_param("date", &self.date))
+    }
+}
+
+pub struct BeforeOrEqual {
+    date: compact_str::CompactString,
+}
+
+impl ParseValidationRule for BeforeOrEqual {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() != 1 {
+            return Err("before_or_equal requires a date or key to compare to".into());
+        }
+
+        Ok(Box::new(BeforeOrEqual {
+            date: rule[0].clone(),
+        }))
+    }
+}
+
+impl ValidateRule for BeforeOrEqual {
+    fn label(&self) -> &'static str {
+        "before_or_equal"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if compare_dates(key, &self.date, data).is_some_and(|ordering| ordering.is_le()) {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.beforeOrEqual").with_param("date", &self.date))
     }
 }
 
@@ -309,7 +511,7 @@ impl ValidateRule for Between {
         "between"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if let Ok(num) = value.parse::<f64>() {
                 if num >= self.min && num <= self.max {
@@ -320,11 +522,14 @@ impl ValidateRule for Between {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be between {} and {}",
-            self.min,
-            self.max
-        ))
+        Err(RuleError::new("validation.between")
+            .with_param("min", self.min)
+            .with_param("max", self.max))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_minimum(self.min, false);
+        schema.set_maximum(self.max, false);
     }
 }
 
@@ -343,13 +548,17 @@ impl ValidateRule for Boolean {
         "boolean"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         match data.data.get(key).copied() {
             Some("true") | Some("1") | Some("yes") | Some("on") | Some("false") | Some("0")
             | Some("no") | Some("off") => Ok(false),
-            _ => Err("must be a boolean (true/false, 1/0, yes/no, on/off)".into()),
+            _ => Err(RuleError::new("validation.boolean")),
         }
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.r#type = Some("boolean");
+    }
 }
 
 pub struct Confirmed;
@@ -367,7 +576,7 @@ impl ValidateRule for Confirmed {
         "confirmed"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         let confirm_key = format!("{}{}", key, "_confirmation");
         if let Some(value) = data.data.get(key)
             && let Some(confirm_value) = data.data.get(confirm_key.as_str())
@@ -376,9 +585,7 @@ impl ValidateRule for Confirmed {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "does not match confirmation field '{confirm_key}'"
-        ))
+        Err(RuleError::new("validation.confirmed").with_param("other", confirm_key))
     }
 }
 
@@ -397,14 +604,18 @@ impl ValidateRule for Date {
         "date"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.parse::<chrono::NaiveDate>().is_ok()
         {
             return Ok(false);
         }
 
-        Err("must be a valid date".into())
+        Err(RuleError::new("validation.date"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.formats.push("date");
     }
 }
 
@@ -430,17 +641,14 @@ impl ValidateRule for DateFormat {
         "date_format"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && chrono::NaiveDate::parse_from_str(value, &self.format).is_ok()
         {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must match the format '{}'",
-            self.format
-        ))
+        Err(RuleError::new("validation.dateFormat").with_param("format", &self.format))
     }
 }
 
@@ -459,12 +667,16 @@ impl ValidateRule for Declined {
         "declined"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         match data.data.get(key).copied() {
             Some("false") | Some("0") | Some("no") | Some("off") => Ok(false),
-            _ => Err("value must be 'false', '0', 'no', or 'off'".into()),
+            _ => Err(RuleError::new("validation.declined")),
         }
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_values(&["false".into(), "0".into(), "no".into(), "off".into()]);
+    }
 }
 
 pub struct DeclinedIf {
@@ -497,7 +709,7 @@ impl ValidateRule for DeclinedIf {
         "declined_if"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         for (check_key, check_value) in &self.keys {
             if let Some(value) = data.data.get(check_key.as_str())
                 && value == check_value
@@ -505,7 +717,9 @@ impl ValidateRule for DeclinedIf {
                 match data.data.get(key).copied() {
                     Some("false") | Some("0") | Some("no") | Some("off") => return Ok(false),
                     _ => {
-                        return Err("must be 'false', '0', 'no', or 'off'".into());
+                        return Err(RuleError::new("validation.declinedIf")
+                            .with_param("other", check_key)
+                            .with_param("value", check_value));
                     }
                 }
             }
@@ -538,7 +752,7 @@ impl ValidateRule for Different {
         "different"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && let Some(other_value) = data.data.get(self.other_key.as_str())
             && value != other_value
@@ -546,10 +760,7 @@ impl ValidateRule for Different {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must be different from '{}'",
-            self.other_key
-        ))
+        Err(RuleError::new("validation.different").with_param("other", &self.other_key))
     }
 }
 
@@ -578,7 +789,7 @@ impl ValidateRule for Digits {
         "digits"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.chars().all(|c| c.is_ascii_digit())
             && value.len() == self.length
@@ -586,10 +797,11 @@ impl ValidateRule for Digits {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must contain exactly {} digits",
-            self.length
-        ))
+        Err(RuleError::new("validation.digits").with_param("digits", self.length))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push(format!("^[0-9]{{{}}}$", self.length));
     }
 }
 
@@ -622,7 +834,7 @@ impl ValidateRule for DigitsBetween {
         "digits_between"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.chars().all(|c| c.is_ascii_digit())
         {
@@ -632,11 +844,43 @@ impl ValidateRule for DigitsBetween {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must contain between {} and {} digits",
-            self.minimum,
-            self.maximum
-        ))
+        Err(RuleError::new("validation.digitsBetween")
+            .with_param("min", self.minimum)
+            .with_param("max", self.maximum))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema
+            .patterns
+            .push(format!("^[0-9]{{{},{}}}$", self.minimum, self.maximum));
+    }
+}
+
+pub struct Distinct;
+
+impl ParseValidationRule for Distinct {
+    fn parse_rule(
+        _rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        Ok(Box::new(Distinct))
+    }
+}
+
+impl ValidateRule for Distinct {
+    fn label(&self) -> &'static str {
+        "distinct"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if let Some(values) = data.data.get(key).and_then(|value| parse_array(value)) {
+            for (i, value) in values.iter().enumerate() {
+                if values[..i].contains(value) {
+                    return Err(RuleError::new("validation.distinct"));
+                }
+            }
+        }
+
+        Ok(false)
     }
 }
 
@@ -663,19 +907,30 @@ impl ValidateRule for DoesntStartWith {
         "doesnt_start_with"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             for prefix in &self.prefixes {
                 if value.starts_with(&**prefix) {
-                    return Err(compact_str::format_compact!(
-                        "must not start with '{prefix}'"
-                    ));
+                    return Err(
+                        RuleError::new("validation.doesntStartWith").with_param("value", prefix)
+                    );
                 }
             }
         }
 
         Ok(false)
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.not_patterns.push(format!(
+            "^({})",
+            self.prefixes
+                .iter()
+                .map(|prefix| regex::escape(prefix))
+                .collect::<Vec<_>>()
+                .join("|")
+        ));
+    }
 }
 
 pub struct DoesntEndWith {
@@ -701,17 +956,30 @@ impl ValidateRule for DoesntEndWith {
         "doesnt_end_with"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             for suffix in &self.suffixes {
                 if value.ends_with(&**suffix) {
-                    return Err(compact_str::format_compact!("must not end with '{suffix}'"));
+                    return Err(
+                        RuleError::new("validation.doesntEndWith").with_param("value", suffix)
+                    );
                 }
             }
         }
 
         Ok(false)
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.not_patterns.push(format!(
+            "({})$",
+            self.suffixes
+                .iter()
+                .map(|suffix| regex::escape(suffix))
+                .collect::<Vec<_>>()
+                .join("|")
+        ));
+    }
 }
 
 pub struct EndsWith {
@@ -737,15 +1005,69 @@ impl ValidateRule for EndsWith {
         "ends_with"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             for suffix in &self.suffixes {
                 if value.ends_with(&**suffix) {
-                    return Err(compact_str::format_compact!("must not end with '{suffix}'"));
+                    return Ok(false);
                 }
             }
         }
 
+        Err(RuleError::new("validation.endsWith").with_param("values", self.suffixes.join(", ")))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push(format!(
+            "({})$",
+            self.suffixes
+                .iter()
+                .map(|suffix| regex::escape(suffix))
+                .collect::<Vec<_>>()
+                .join("|")
+        ));
+    }
+}
+
+pub struct ExcludeIf {
+    keys: Vec<(compact_str::CompactString, compact_str::CompactString)>,
+}
+
+impl ParseValidationRule for ExcludeIf {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() < 2 {
+            return Err("exclude_if requires a key and value to check".into());
+        }
+
+        let mut keys = Vec::new();
+        for i in (0..rule.len()).step_by(2) {
+            if i + 1 < rule.len() {
+                keys.push((rule[i].clone(), rule[i + 1].clone()));
+            } else {
+                return Err("exclude_if requires an even number of arguments".into());
+            }
+        }
+
+        Ok(Box::new(ExcludeIf { keys }))
+    }
+}
+
+impl ValidateRule for ExcludeIf {
+    fn label(&self) -> &'static str {
+        "exclude_if"
+    }
+
+    fn validate(&self, _key: &str, data: &Validator) -> Result<bool, RuleError> {
+        for (check_key, check_value) in &self.keys {
+            if let Some(value) = data.data.get(check_key.as_str())
+                && value == check_value
+            {
+                return Ok(true);
+            }
+        }
+
         Ok(false)
     }
 }
@@ -775,7 +1097,7 @@ impl ValidateRule for Gt {
         "gt"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if let Ok(num) = value.parse::<f64>() {
                 if num > self.value {
@@ -786,10 +1108,11 @@ impl ValidateRule for Gt {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be greater than {}",
-            self.value
-        ))
+        Err(RuleError::new("validation.gt").with_param("value", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_minimum(self.value, true);
     }
 }
 
@@ -818,7 +1141,7 @@ impl ValidateRule for Gte {
         "gte"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if let Ok(num) = value.parse::<f64>() {
                 if num >= self.value {
@@ -829,10 +1152,11 @@ impl ValidateRule for Gte {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be greater than or equal to {}",
-            self.value
-        ))
+        Err(RuleError::new("validation.gte").with_param("value", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_minimum(self.value, false);
     }
 }
 
@@ -851,7 +1175,7 @@ impl ValidateRule for HexColor {
         "hex_color"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.starts_with('#')
             && value.len() == 7
@@ -860,7 +1184,11 @@ impl ValidateRule for HexColor {
             return Ok(false);
         }
 
-        Err("must be a valid hex color code".into())
+        Err(RuleError::new("validation.hexColor"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push("^#[0-9a-fA-F]{6}$".into());
     }
 }
 
@@ -887,17 +1215,56 @@ impl ValidateRule for In {
         "in"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key).copied()
             && self.options.iter().any(|option| option == value)
         {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must be one of: {}",
-            self.options.join(", ")
-        ))
+        Err(RuleError::new("validation.in").with_param("values", self.options.join(", ")))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_values(&self.options);
+    }
+}
+
+pub struct InArray {
+    other_key: compact_str::CompactString,
+}
+
+impl ParseValidationRule for InArray {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() != 1 {
+            return Err("in_array requires one key to check".into());
+        }
+
+        Ok(Box::new(InArray {
+            other_key: rule[0].trim_end_matches(".*").into(),
+        }))
+    }
+}
+
+impl ValidateRule for InArray {
+    fn label(&self) -> &'static str {
+        "in_array"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if let Some(value) = data.data.get(key)
+            && let Some(values) = data
+                .data
+                .get(self.other_key.as_str())
+                .and_then(|values| parse_array(values))
+            && values.iter().any(|v| v == value)
+        {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.inArray").with_param("other", &self.other_key))
     }
 }
 
@@ -916,14 +1283,18 @@ impl ValidateRule for Integer {
         "integer"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.parse::<i64>().is_ok()
         {
             return Ok(false);
         }
 
-        Err("must be a valid integer".into())
+        Err(RuleError::new("validation.integer"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.r#type = Some("integer");
     }
 }
 
@@ -942,14 +1313,18 @@ impl ValidateRule for Ip {
         "ip"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.parse::<std::net::IpAddr>().is_ok()
         {
             return Ok(false);
         }
 
-        Err("must be a valid IP address".into())
+        Err(RuleError::new("validation.ip"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.formats.extend(["ipv4", "ipv6"]);
     }
 }
 
@@ -968,14 +1343,18 @@ impl ValidateRule for Ipv4 {
         "ipv4"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.parse::<std::net::Ipv4Addr>().is_ok()
         {
             return Ok(false);
         }
 
-        Err("must be a valid IPv4 address".into())
+        Err(RuleError::new("validation.ipv4"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.formats.push("ipv4");
     }
 }
 
@@ -994,14 +1373,18 @@ impl ValidateRule for Ipv6 {
         "ipv6"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.parse::<std::net::Ipv6Addr>().is_ok()
         {
             return Ok(false);
         }
 
-        Err("must be a valid IPv6 address".into())
+        Err(RuleError::new("validation.ipv6"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.formats.push("ipv6");
     }
 }
 
@@ -1020,14 +1403,18 @@ impl ValidateRule for Json {
         "json"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && serde_json::from_str::<serde_json::Value>(value).is_ok()
         {
             return Ok(false);
         }
 
-        Err("must be valid JSON".into())
+        Err(RuleError::new("validation.json"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.content_media_type = Some("application/json");
     }
 }
 
@@ -1056,7 +1443,7 @@ impl ValidateRule for Lt {
         "lt"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if let Ok(num) = value.parse::<f64>() {
                 if num < self.value {
@@ -1067,10 +1454,11 @@ impl ValidateRule for Lt {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be less than {}",
-            self.value
-        ))
+        Err(RuleError::new("validation.lt").with_param("value", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_maximum(self.value, true);
     }
 }
 
@@ -1099,7 +1487,7 @@ impl ValidateRule for Lte {
         "lte"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if let Ok(num) = value.parse::<f64>() {
                 if num <= self.value {
@@ -1110,10 +1498,11 @@ impl ValidateRule for Lte {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be less than or equal to {}",
-            self.value
-        ))
+        Err(RuleError::new("validation.lte").with_param("value", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_maximum(self.value, false);
     }
 }
 
@@ -1132,14 +1521,18 @@ impl ValidateRule for Lowercase {
         "lowercase"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.chars().all(|c| c.is_lowercase())
         {
             return Ok(false);
         }
 
-        Err("must be lowercase".into())
+        Err(RuleError::new("validation.lowercase"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push("^[^A-Z]*$".into());
     }
 }
 
@@ -1158,7 +1551,7 @@ impl ValidateRule for MacAddress {
         "mac_address"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             let parts: Vec<&str> = value.split(':').collect();
             if parts.len() == 6
@@ -1170,7 +1563,13 @@ impl ValidateRule for MacAddress {
             }
         }
 
-        Err("must be a valid MAC address".into())
+        Err(RuleError::new("validation.macAddress"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema
+            .patterns
+            .push("^[0-9a-fA-F]{2}(:[0-9a-fA-F]{2}){5}$".into());
     }
 }
 
@@ -1199,7 +1598,7 @@ impl ValidateRule for Max {
         "max"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if !data.has_rule(key, "string")
                 && let Ok(num) = value.parse::<f64>()
@@ -1212,10 +1611,11 @@ impl ValidateRule for Max {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be less than or equal to {}",
-            self.value
-        ))
+        Err(RuleError::new("validation.max").with_param("max", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_maximum(self.value, false);
     }
 }
 
@@ -1244,7 +1644,7 @@ impl ValidateRule for MaxDigits {
         "max_digits"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.chars().all(|c| c.is_ascii_digit())
             && value.len() <= self.value
@@ -1252,10 +1652,11 @@ impl ValidateRule for MaxDigits {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must contain at most {} digits",
-            self.value
-        ))
+        Err(RuleError::new("validation.maxDigits").with_param("max", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push(format!("^[0-9]{{0,{}}}$", self.value));
     }
 }
 
@@ -1284,7 +1685,7 @@ impl ValidateRule for Min {
         "min"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if !data.has_rule(key, "string")
                 && let Ok(num) = value.parse::<f64>()
@@ -1297,10 +1698,11 @@ impl ValidateRule for Min {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be greater than or equal to {}",
-            self.value
-        ))
+        Err(RuleError::new("validation.min").with_param("min", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_minimum(self.value, false);
     }
 }
 
@@ -1329,7 +1731,7 @@ impl ValidateRule for MinDigits {
         "min_digits"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.chars().all(|c| c.is_ascii_digit())
             && value.len() >= self.value
@@ -1337,10 +1739,11 @@ impl ValidateRule for MinDigits {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must contain at least {} digits",
-            self.value
-        ))
+        Err(RuleError::new("validation.minDigits").with_param("min", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push(format!("^[0-9]{{{},}}$", self.value));
     }
 }
 
@@ -1369,7 +1772,7 @@ impl ValidateRule for MultipleOf {
         "multiple_of"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && let Ok(num) = value.parse::<f64>()
             && num % self.value == 0.0
@@ -1377,10 +1780,11 @@ impl ValidateRule for MultipleOf {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must be a multiple of {}",
-            self.value
-        ))
+        Err(RuleError::new("validation.multipleOf").with_param("value", self.value))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.multiple_of = Some(self.value);
     }
 }
 
@@ -1407,17 +1811,18 @@ impl ValidateRule for NotIn {
         "not_in"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key).copied()
             && !self.options.iter().any(|option| option == value)
         {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must not be one of: {}",
-            self.options.join(", ")
-        ))
+        Err(RuleError::new("validation.notIn").with_param("values", self.options.join(", ")))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.not_values.extend(self.options.iter().cloned());
     }
 }
 
@@ -1446,17 +1851,18 @@ impl ValidateRule for NotRegex {
         "not_regex"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && !self.pattern.is_match(value)
         {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must not match the regex pattern '{}'",
-            self.pattern
-        ))
+        Err(RuleError::new("validation.notRegex").with_param("pattern", &self.pattern))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.not_patterns.push(self.pattern.as_str().into());
     }
 }
 
@@ -1475,7 +1881,7 @@ impl ValidateRule for Nullable {
         "nullable"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key).copied()
             && (value.is_empty() || value == "null")
         {
@@ -1484,6 +1890,10 @@ impl ValidateRule for Nullable {
 
         Ok(false)
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.nullable = true;
+    }
 }
 
 pub struct Numeric;
@@ -1501,7 +1911,7 @@ impl ValidateRule for Numeric {
         "numeric"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value
                 .chars()
@@ -1510,7 +1920,90 @@ impl ValidateRule for Numeric {
             return Ok(false);
         }
 
-        Err("must be a valid numeric value".into())
+        Err(RuleError::new("validation.numeric"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        if schema.r#type != Some("integer") {
+            schema.r#type = Some("number");
+        }
+    }
+}
+
+pub struct Prohibited;
+
+impl ParseValidationRule for Prohibited {
+    fn parse_rule(
+        _rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        Ok(Box::new(Prohibited))
+    }
+}
+
+impl ValidateRule for Prohibited {
+    fn label(&self) -> &'static str {
+        "prohibited"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        if data.data.get(key).is_none_or(|value| value.is_empty()) {
+            return Ok(true);
+        }
+
+        Err(RuleError::new("validation.prohibited"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_values(&["".into()]);
+    }
+}
+
+pub struct ProhibitedIf {
+    keys: Vec<(compact_str::CompactString, compact_str::CompactString)>,
+}
+
+impl ParseValidationRule for ProhibitedIf {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() < 2 {
+            return Err("prohibited_if requires a key and value to check".into());
+        }
+
+        let mut keys = Vec::new();
+        for i in (0..rule.len()).step_by(2) {
+            if i + 1 < rule.len() {
+                keys.push((rule[i].clone(), rule[i + 1].clone()));
+            } else {
+                return Err("prohibited_if requires an even number of arguments".into());
+            }
+        }
+
+        Ok(Box::new(ProhibitedIf { keys }))
+    }
+}
+
+impl ValidateRule for ProhibitedIf {
+    fn label(&self) -> &'static str {
+        "prohibited_if"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        for (check_key, check_value) in &self.keys {
+            if let Some(value) = data.data.get(check_key.as_str())
+                && value == check_value
+            {
+                if data.data.get(key).is_none_or(|value| value.is_empty()) {
+                    return Ok(true);
+                }
+
+                return Err(RuleError::new("validation.prohibitedIf")
+                    .with_param("other", check_key)
+                    .with_param("value", check_value));
+            }
+        }
+
+        Ok(false)
     }
 }
 
@@ -1539,17 +2032,18 @@ impl ValidateRule for Regex {
         "regex"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && self.pattern.is_match(value)
         {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must match the regex pattern '{}'",
-            self.pattern
-        ))
+        Err(RuleError::new("validation.regex").with_param("pattern", &self.pattern))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push(self.pattern.as_str().into());
     }
 }
 
@@ -1568,14 +2062,18 @@ impl ValidateRule for Required {
         "required"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && !value.is_empty()
         {
             return Ok(false);
         }
 
-        Err("is required and cannot be empty".into())
+        Err(RuleError::new("validation.required"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.required = true;
     }
 }
 
@@ -1609,7 +2107,7 @@ impl ValidateRule for RequiredIf {
         "required_if"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         for (check_key, check_value) in &self.keys {
             if let Some(value) = data.data.get(check_key.as_str()).copied()
                 && value == check_value
@@ -1620,9 +2118,9 @@ impl ValidateRule for RequiredIf {
                     return Ok(false);
                 }
 
-                return Err(compact_str::format_compact!(
-                    "is required when '{check_key}' is '{check_value}'"
-                ));
+                return Err(RuleError::new("validation.requiredIf")
+                    .with_param("other", check_key)
+                    .with_param("value", check_value));
             }
         }
 
@@ -1653,7 +2151,7 @@ impl ValidateRule for RequiredIfAccepted {
         "required_if_accepted"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(self.other_key.as_str()).copied()
             && (value == "true" || value == "1" || value == "yes" || value == "on")
         {
@@ -1663,10 +2161,8 @@ impl ValidateRule for RequiredIfAccepted {
                 return Ok(false);
             }
 
-            return Err(compact_str::format_compact!(
-                "is required when '{}' is accepted",
-                self.other_key
-            ));
+            return Err(RuleError::new("validation.requiredIfAccepted")
+                .with_param("other", &self.other_key));
         }
 
         Ok(true)
@@ -1696,7 +2192,7 @@ impl ValidateRule for RequiredIfDeclined {
         "required_if_declined"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(self.other_key.as_str()).copied()
             && (value == "false" || value == "0" || value == "no" || value == "off")
         {
@@ -1706,16 +2202,144 @@ impl ValidateRule for RequiredIfDeclined {
                 return Ok(false);
             }
 
-            return Err(compact_str::format_compact!(
-                "is required when '{}' is declined",
-                self.other_key
-            ));
+            return Err(RuleError::new("validation.requiredIfDeclined")
+                .with_param("other", &self.other_key));
         }
 
         Ok(true)
     }
 }
 
+pub struct RequiredUnless {
+    other_key: compact_str::CompactString,
+    values: Vec<compact_str::CompactString>,
+}
+
+impl ParseValidationRule for RequiredUnless {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.len() < 2 {
+            return Err("required_unless requires a key and at least one value to check".into());
+        }
+
+        Ok(Box::new(RequiredUnless {
+            other_key: rule[0].clone(),
+            values: rule[1..].to_vec(),
+        }))
+    }
+}
+
+impl ValidateRule for RequiredUnless {
+    fn label(&self) -> &'static str {
+        "required_unless"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        let is_empty = data.data.get(key).is_none_or(|value| value.is_empty());
+
+        if let Some(value) = data.data.get(self.other_key.as_str())
+            && self.values.iter().any(|v| v == value)
+        {
+            return Ok(is_empty);
+        }
+
+        if !is_empty {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.requiredUnless")
+            .with_param("other", &self.other_key)
+            .with_param("values", self.values.join(", ")))
+    }
+}
+
+pub struct RequiredWith {
+    other_keys: Vec<compact_str::CompactString>,
+}
+
+impl ParseValidationRule for RequiredWith {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.is_empty() {
+            return Err("required_with requires at least one key to check".into());
+        }
+
+        Ok(Box::new(RequiredWith {
+            other_keys: rule.to_vec(),
+        }))
+    }
+}
+
+impl ValidateRule for RequiredWith {
+    fn label(&self) -> &'static str {
+        "required_with"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        let is_empty = data.data.get(key).is_none_or(|value| value.is_empty());
+
+        if !self.other_keys.iter().any(|other_key| {
+            data.data
+                .get(other_key.as_str())
+                .is_some_and(|value| !value.is_empty())
+        }) {
+            return Ok(is_empty);
+        }
+
+        if !is_empty {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.requiredWith")
+            .with_param("values", self.other_keys.join(", ")))
+    }
+}
+
+pub struct RequiredWithout {
+    other_keys: Vec<compact_str::CompactString>,
+}
+
+impl ParseValidationRule for RequiredWithout {
+    fn parse_rule(
+        rule: &[compact_str::CompactString],
+    ) -> Result<Box<dyn ValidateRule>, compact_str::CompactString> {
+        if rule.is_empty() {
+            return Err("required_without requires at least one key to check".into());
+        }
+
+        Ok(Box::new(RequiredWithout {
+            other_keys: rule.to_vec(),
+        }))
+    }
+}
+
+impl ValidateRule for RequiredWithout {
+    fn label(&self) -> &'static str {
+        "required_without"
+    }
+
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
+        let is_empty = data.data.get(key).is_none_or(|value| value.is_empty());
+
+        if self.other_keys.iter().all(|other_key| {
+            data.data
+                .get(other_key.as_str())
+                .is_some_and(|value| !value.is_empty())
+        }) {
+            return Ok(is_empty);
+        }
+
+        if !is_empty {
+            return Ok(false);
+        }
+
+        Err(RuleError::new("validation.requiredWithout")
+            .with_param("values", self.other_keys.join(", ")))
+    }
+}
+
 pub struct Same {
     other_key: compact_str::CompactString,
 }
@@ -1739,7 +2363,7 @@ impl ValidateRule for Same {
         "same"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && let Some(other_value) = data.data.get(self.other_key.as_str())
             && value == other_value
@@ -1747,10 +2371,7 @@ impl ValidateRule for Same {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must be the same as '{}'",
-            self.other_key
-        ))
+        Err(RuleError::new("validation.same").with_param("other", &self.other_key))
     }
 }
 
@@ -1779,7 +2400,7 @@ impl ValidateRule for Size {
         "size"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             if let Ok(num) = value.parse::<f64>() {
                 if num == self.size {
@@ -1790,10 +2411,12 @@ impl ValidateRule for Size {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must be equal to {}",
-            self.size
-        ))
+        Err(RuleError::new("validation.size").with_param("size", self.size))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.set_minimum(self.size, false);
+        schema.set_maximum(self.size, false);
     }
 }
 
@@ -1820,7 +2443,7 @@ impl ValidateRule for StartsWith {
         "starts_with"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key) {
             for prefix in &self.prefixes {
                 if value.starts_with(&**prefix) {
@@ -1829,10 +2452,18 @@ impl ValidateRule for StartsWith {
             }
         }
 
-        Err(compact_str::format_compact!(
-            "must start with one of: {}",
-            self.prefixes.join(", ")
-        ))
+        Err(RuleError::new("validation.startsWith").with_param("values", self.prefixes.join(", ")))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push(format!(
+            "^({})",
+            self.prefixes
+                .iter()
+                .map(|prefix| regex::escape(prefix))
+                .collect::<Vec<_>>()
+                .join("|")
+        ));
     }
 }
 
@@ -1851,9 +2482,13 @@ impl ValidateRule for StringRule {
         "string"
     }
 
-    fn validate(&self, _key: &str, _data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, _key: &str, _data: &Validator) -> Result<bool, RuleError> {
         Ok(false)
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.r#type = Some("string");
+    }
 }
 
 pub struct Timezone;
@@ -1871,14 +2506,14 @@ impl ValidateRule for Timezone {
         "timezone"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key).copied()
             && (value.parse::<chrono::FixedOffset>().is_ok() || value == "UTC")
         {
             return Ok(false);
         }
 
-        Err("must be a valid timezone".into())
+        Err(RuleError::new("validation.timezone"))
     }
 }
 
@@ -1897,14 +2532,18 @@ impl ValidateRule for Uppercase {
         "uppercase"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && value.chars().all(|c| c.is_uppercase())
         {
             return Ok(false);
         }
 
-        Err("must be uppercase".into())
+        Err(RuleError::new("validation.uppercase"))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.patterns.push("^[^a-z]*$".into());
     }
 }
 
@@ -1927,7 +2566,7 @@ impl ValidateRule for Url {
         "url"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && let Ok(url) = reqwest::Url::parse(value)
             && (self.protocols.is_empty() || self.protocols.contains(&url.scheme().into()))
@@ -1935,10 +2574,21 @@ impl ValidateRule for Url {
             return Ok(false);
         }
 
-        Err(compact_str::format_compact!(
-            "must be a valid URL with one of the following protocols: {}",
-            self.protocols.join(", ")
-        ))
+        Err(RuleError::new("validation.url").with_param("protocols", self.protocols.join(", ")))
+    }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.formats.push("uri");
+        if !self.protocols.is_empty() {
+            schema.patterns.push(format!(
+                "^({}):",
+                self.protocols
+                    .iter()
+                    .map(|protocol| regex::escape(protocol))
+                    .collect::<Vec<_>>()
+                    .join("|")
+            ));
+        }
     }
 }
 
@@ -1957,13 +2607,67 @@ impl ValidateRule for Uuid {
         "uuid"
     }
 
-    fn validate(&self, key: &str, data: &Validator) -> Result<bool, compact_str::CompactString> {
+    fn validate(&self, key: &str, data: &Validator) -> Result<bool, RuleError> {
         if let Some(value) = data.data.get(key)
             && uuid::Uuid::parse_str(value).is_ok()
         {
             return Ok(false);
         }
 
-        Err("must be a valid UUID".into())
+        Err(RuleError::new("validation.uuid"))
     }
+
+    fn json_schema(&self, schema: &mut JsonSchema) {
+        schema.formats.push("uuid");
+    }
+}
+
+/// Parses a date, a date with time or one of `now`, `today`, `tomorrow` and `yesterday`.
+fn parse_date(value: &str) -> Option<chrono::NaiveDateTime> {
+    let today = chrono::Utc::now().date_naive();
+
+    match value {
+        "now" => Some(chrono::Utc::now().naive_utc()),
+        "today" => today.and_hms_opt(0, 0, 0),
+        "tomorrow" => today.succ_opt()?.and_hms_opt(0, 0, 0),
+        "yesterday" => today.pred_opt()?.and_hms_opt(0, 0, 0),
+        value => value
+            .parse::<chrono::DateTime<chrono::FixedOffset>>()
+            .map(|date| date.naive_utc())
+            .or_else(|_| value.parse::<chrono::NaiveDateTime>())
+            .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+            .ok()
+            .or_else(|| {
+                value
+                    .parse::<chrono::NaiveDate>()
+                    .ok()?
+                    .and_hms_opt(0, 0, 0)
+            }),
+    }
+}
+
+/// Compares the date of `key` to `date`, which is either a date or the key of another date.
+fn compare_dates(key: &str, date: &str, data: &Validator) -> Option<std::cmp::Ordering> {
+    let value = parse_date(data.data.get(key)?)?;
+    let other = match data.data.get(date) {
+        Some(other) => parse_date(other)?,
+        None => parse_date(date)?,
+    };
+
+    Some(value.cmp(&other))
+}
+
+/// Parses a JSON array into its values, with strings unquoted so they can be compared to plain values.
+fn parse_array(value: &str) -> Option<Vec<compact_str::CompactString>> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(value).ok()?;
+
+    Some(
+        values
+            .into_iter()
+            .map(|value| match value {
+                serde_json::Value::String(value) => value.into(),
+                value => compact_str::format_compact!("{value}"),
+            })
+            .collect(),
+    )
 }
@@ -0,0 +1,207 @@
+use serde_json::{Map, Value, json};
+
+/// The constraints the rules of a single value put on it, collected by [`crate::ValidateRule::json_schema`].
+///
+/// Rules that depend on other values (like `required_if` or `same`) can not be expressed and are left out,
+/// so the resulting schema is only ever as strict as the backend and may accept values that it rejects.
+#[derive(Debug, Default)]
+pub struct JsonSchema {
+    pub r#type: Option<&'static str>,
+    pub nullable: bool,
+    pub required: bool,
+
+    /// The lower bound and whether it is exclusive, the length for strings and the value for numbers.
+    pub minimum: Option<(f64, bool)>,
+    /// The upper bound and whether it is exclusive, the length for strings and the value for numbers.
+    pub maximum: Option<(f64, bool)>,
+    pub multiple_of: Option<f64>,
+
+    pub patterns: Vec<String>,
+    pub not_patterns: Vec<String>,
+    pub values: Option<Vec<compact_str::CompactString>>,
+    pub not_values: Vec<compact_str::CompactString>,
+    pub formats: Vec<&'static str>,
+    pub content_media_type: Option<&'static str>,
+}
+
+impl JsonSchema {
+    pub fn set_minimum(&mut self, value: f64, exclusive: bool) {
+        if self.minimum.is_none_or(|(minimum, _)| value >= minimum) {
+            self.minimum = Some((value, exclusive));
+        }
+    }
+
+    pub fn set_maximum(&mut self, value: f64, exclusive: bool) {
+        if self.maximum.is_none_or(|(maximum, _)| value <= maximum) {
+            self.maximum = Some((value, exclusive));
+        }
+    }
+
+    /// Restricts the value to `values`, keeping only the values that were already allowed.
+    pub fn set_values(&mut self, values: &[compact_str::CompactString]) {
+        self.values = Some(match self.values.take() {
+            Some(existing) => existing
+                .into_iter()
+                .filter(|value| values.contains(value))
+                .collect(),
+            None => values.to_vec(),
+        });
+    }
+
+    fn value(&self, value: &str) -> Value {
+        match self.r#type {
+            Some("integer" | "number") => value
+                .parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| value.parse::<f64>().map(Value::from))
+                .unwrap_or_else(|_| value.into()),
+            Some("boolean") => match value {
+                "true" | "1" | "yes" | "on" => true.into(),
+                "false" | "0" | "no" | "off" => false.into(),
+                _ => value.into(),
+            },
+            _ => value.into(),
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        let r#type = self.r#type.unwrap_or("string");
+        let mut schema = Map::new();
+
+        schema.insert(
+            "type".into(),
+            if self.nullable {
+                json!([r#type, "null"])
+            } else {
+                json!(r#type)
+            },
+        );
+
+        match r#type {
+            "integer" | "number" => {
+                if let Some((minimum, exclusive)) = self.minimum {
+                    schema.insert(
+                        if exclusive {
+                            "exclusiveMinimum"
+                        } else {
+                            "minimum"
+                        }
+                        .into(),
+                        minimum.into(),
+                    );
+                }
+                if let Some((maximum, exclusive)) = self.maximum {
+                    schema.insert(
+                        if exclusive {
+                            "exclusiveMaximum"
+                        } else {
+                            "maximum"
+                        }
+                        .into(),
+                        maximum.into(),
+                    );
+                }
+                if let Some(multiple_of) = self.multiple_of {
+                    schema.insert("multipleOf".into(), multiple_of.into());
+                }
+            }
+            "string" => {
+                // without the `string` rule the backend compares numeric values by their value
+                // instead of their length, so the bounds can only be expressed as lengths with it
+                let (minimum, maximum) = if self.r#type == Some("string") {
+                    (self.minimum, self.maximum)
+                } else {
+                    (None, None)
+                };
+
+                let min_length = match minimum {
+                    Some((minimum, true)) => Some(minimum.floor() as u64 + 1),
+                    Some((minimum, false)) => Some(minimum.ceil() as u64),
+                    None => None,
+                };
+                let min_length = match min_length {
+                    Some(0) | None if self.required => Some(1),
+                    min_length => min_length,
+                };
+                if let Some(min_length) = min_length {
+                    schema.insert("minLength".into(), min_length.into());
+                }
+
+                let max_length = match maximum {
+                    Some((maximum, true)) => Some((maximum.ceil() as u64).saturating_sub(1)),
+                    Some((maximum, false)) => Some(maximum.floor() as u64),
+                    None => None,
+                };
+                if let Some(max_length) = max_length {
+                    schema.insert("maxLength".into(), max_length.into());
+                }
+            }
+            _ => {}
+        }
+
+        match self.patterns.len() {
+            0 => {}
+            1 => {
+                schema.insert("pattern".into(), self.patterns[0].clone().into());
+            }
+            _ => {
+                schema.insert(
+                    "allOf".into(),
+                    self.patterns
+                        .iter()
+                        .map(|pattern| json!({ "pattern": pattern }))
+                        .collect(),
+                );
+            }
+        }
+
+        if let Some(values) = &self.values {
+            schema.insert(
+                "enum".into(),
+                values.iter().map(|value| self.value(value)).collect(),
+            );
+        }
+
+        let mut forbidden = self
+            .not_patterns
+            .iter()
+            .map(|pattern| json!({ "pattern": pattern }))
+            .collect::<Vec<_>>();
+        if !self.not_values.is_empty() {
+            forbidden.push(json!({
+                "enum": self.not_values.iter().map(|value| self.value(value)).collect::<Vec<_>>(),
+            }));
+        }
+        match forbidden.len() {
+            0 => {}
+            1 => {
+                schema.insert("not".into(), forbidden.remove(0));
+            }
+            _ => {
+                schema.insert("not".into(), json!({ "anyOf": forbidden }));
+            }
+        }
+
+        match self.formats.as_slice() {
+            [] => {}
+            [format] => {
+                schema.insert("format".into(), (*format).into());
+            }
+            formats => {
+                schema.insert(
+                    "anyOf".into(),
+                    formats
+                        .iter()
+                        .map(|format| json!({ "format": format }))
+                        .collect(),
+                );
+            }
+        }
+
+        if let Some(content_media_type) = self.content_media_type {
+            schema.insert("contentMediaType".into(), content_media_type.into());
+        }
+
+        Value::Object(schema)
+    }
+}
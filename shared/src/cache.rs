@@ -9,7 +9,15 @@ use rustis::{
     resp::BulkString,
 };
 use serde::{Serialize, de::DeserializeOwned};
-use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 use tokio::sync::{Mutex, RwLock};
 
 struct CacheEntry {
@@ -23,6 +31,9 @@ pub struct Cache {
     use_internal_cache: bool,
     mutex_map: RwLock<HashMap<String, Arc<Mutex<()>>>>,
     memory_cache: Arc<RwLock<HashMap<String, CacheEntry>>>,
+
+    hits: AtomicU64,
+    misses: AtomicU64,
 }
 
 impl Cache {
@@ -48,6 +59,8 @@ impl Cache {
             use_internal_cache: env.app_use_internal_cache,
             mutex_map: RwLock::new(HashMap::new()),
             memory_cache: Arc::new(RwLock::new(HashMap::new())),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         };
 
         let version = instance
@@ -94,6 +107,16 @@ impl Cache {
         Ok(version)
     }
 
+    #[inline]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
     pub async fn ratelimit(
         &self,
         limit_identifier: impl AsRef<str>,
@@ -162,7 +185,10 @@ impl Cache {
                     &entry.value,
                     bincode::config::standard(),
                 ) {
-                    Ok((value, _)) => return Ok(value),
+                    Ok((value, _)) => {
+                        self.hits.fetch_add(1, Ordering::Relaxed);
+                        return Ok(value);
+                    }
                     Err(err) => {
                         tracing::warn!("failed to deserialize from memory cache: {:?}", err);
                     }
@@ -189,6 +215,7 @@ impl Cache {
             }
 
             tracing::debug!("found in redis cache");
+            self.hits.fetch_add(1, Ordering::Relaxed);
             return Ok(val);
         }
 
@@ -217,7 +244,10 @@ impl Cache {
                 &entry.value,
                 bincode::config::standard(),
             ) {
-                Ok((value, _)) => return Ok(value),
+                Ok((value, _)) => {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(value);
+                }
                 Err(err) => {
                     tracing::warn!("failed to deserialize from memory cache: {:?}", err)
                 }
@@ -243,10 +273,12 @@ impl Cache {
             }
 
             tracing::debug!("found in redis cache after lock");
+            self.hits.fetch_add(1, Ordering::Relaxed);
             return Ok(val);
         }
 
         tracing::debug!("executing compute");
+        self.misses.fetch_add(1, Ordering::Relaxed);
         let result = match fn_compute().await {
             Ok(result) => result,
             Err(err) => return Err(err.into()),
@@ -105,6 +105,8 @@ impl Database {
         Ok(version.0)
     }
 
+    /// Applies pending migrations, the migrator holds an advisory lock while doing so,
+    /// so instances starting at the same time apply them one after another.
     pub async fn migrate(&self) -> Result<(), sqlx::Error> {
         let start = std::time::Instant::now();
 
@@ -126,6 +128,11 @@ impl Database {
         &self.write
     }
 
+    #[inline]
+    pub fn pools(&self) -> impl Iterator<Item = (&'static str, &sqlx::PgPool)> {
+        std::iter::once(("write", &self.write)).chain(self.read.as_ref().map(|read| ("read", read)))
+    }
+
     #[inline]
     pub fn read(&self) -> &sqlx::PgPool {
         self.read.as_ref().unwrap_or(&self.write)
@@ -0,0 +1,335 @@
+use crate::{
+    models::{
+        nest_egg::NestEgg,
+        node::{Node, NodeHealthStatus},
+        node_allocation::NodeAllocation,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use utoipa::ToSchema;
+use validator::Validate;
+
+fn default_allocations() -> i64 {
+    1
+}
+
+#[derive(ToSchema, Validate, Serialize, Deserialize, Clone)]
+pub struct DeploymentRequest {
+    #[validate(length(min = 1, max = 50))]
+    #[schema(min_items = 1, max_items = 50)]
+    pub location_uuids: Vec<uuid::Uuid>,
+
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub memory: i64,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub disk: i64,
+
+    /// The amount of allocations to assign, the first one becomes the primary allocation.
+    #[validate(range(min = 1, max = 100))]
+    #[schema(minimum = 1, maximum = 100)]
+    #[serde(default = "default_allocations")]
+    pub allocations: i64,
+    #[validate(range(min = 1, max = 65535))]
+    #[schema(minimum = 1, maximum = 65535)]
+    pub port_start: Option<i32>,
+    #[validate(range(min = 1, max = 65535))]
+    #[schema(minimum = 1, maximum = 65535)]
+    pub port_end: Option<i32>,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct DeploymentCandidate {
+    pub node_uuid: uuid::Uuid,
+    pub node_name: compact_str::CompactString,
+    pub location_uuid: uuid::Uuid,
+
+    pub free_memory: i64,
+    pub free_disk: i64,
+    pub free_allocations: i64,
+
+    pub score: Option<f64>,
+    pub selected: bool,
+    pub skipped_reason: Option<&'static str>,
+}
+
+pub struct DeploymentPlan {
+    pub node: Option<Node>,
+    pub allocations: Vec<NodeAllocation>,
+    pub port_start: i32,
+    pub port_end: i32,
+    pub additional_port_start: i32,
+    pub additional_port_end: i32,
+    pub candidates: Vec<DeploymentCandidate>,
+}
+
+impl DeploymentPlan {
+    #[inline]
+    pub fn primary_allocation_uuid(&self) -> Option<uuid::Uuid> {
+        self.allocations.first().map(|allocation| allocation.uuid)
+    }
+
+    #[inline]
+    pub fn additional_allocation_uuids(&self) -> Vec<uuid::Uuid> {
+        self.allocations
+            .iter()
+            .skip(1)
+            .map(|allocation| allocation.uuid)
+            .collect()
+    }
+
+    #[inline]
+    pub fn into_api_object(self) -> ApiDeploymentPlan {
+        ApiDeploymentPlan {
+            node_uuid: self.node.map(|node| node.uuid),
+            allocations: self
+                .allocations
+                .into_iter()
+                .map(|allocation| ApiDeploymentPlanAllocation {
+                    uuid: allocation.uuid,
+                    ip: compact_str::format_compact!("{}", allocation.ip.ip()),
+                    port: allocation.port,
+                })
+                .collect(),
+            port_start: self.port_start,
+            port_end: self.port_end,
+            additional_port_start: self.additional_port_start,
+            additional_port_end: self.additional_port_end,
+            candidates: self.candidates,
+        }
+    }
+}
+
+/// Plans where a new server should be deployed.
+///
+/// Every node in the requested locations is considered, nodes in maintenance, offline or without enough free
+/// memory, disk or allocations in the port range are skipped. The remaining nodes are scored by the share
+/// of memory and disk they would still have free after the deployment, the best one is selected.
+///
+/// Without a port range in the request, the primary allocation can use any port of the node while additional
+/// allocations are limited to the self assign range of the egg when it is enabled.
+#[inline]
+pub async fn plan(
+    database: &crate::database::Database,
+    egg: &NestEgg,
+    request: &DeploymentRequest,
+) -> Result<DeploymentPlan, crate::database::DatabaseError> {
+    plan_restricted(database, egg, request, &[], &[]).await
+}
+
+/// Same as [`plan`], but additionally considers the given nodes and never selects the excluded ones.
+///
+/// Servers that are being transferred count against both their current and their destination node.
+pub async fn plan_restricted(
+    database: &crate::database::Database,
+    egg: &NestEgg,
+    request: &DeploymentRequest,
+    node_uuids: &[uuid::Uuid],
+    excluded_node_uuids: &[uuid::Uuid],
+) -> Result<DeploymentPlan, crate::database::DatabaseError> {
+    let self_assign = &egg.config_allocations.user_self_assign;
+    let (port_start, port_end, additional_port_start, additional_port_end) =
+        if request.port_start.is_some() || request.port_end.is_some() {
+            let port_start = request.port_start.unwrap_or(1);
+            let port_end = request.port_end.unwrap_or(65535);
+
+            (port_start, port_end, port_start, port_end)
+        } else if self_assign.enabled && self_assign.is_valid() {
+            (
+                1,
+                65535,
+                self_assign.start_port as i32,
+                self_assign.end_port as i32,
+            )
+        } else {
+            (1, 65535, 1, 65535)
+        };
+
+    if port_start > port_end {
+        return Err(anyhow::Error::new(crate::response::DisplayError::new(
+            "deployment port_start must not be greater than port_end",
+        ))
+        .into());
+    }
+
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT
+            {},
+            COALESCE(node_usage.memory, 0)::bigint AS allocated_memory,
+            COALESCE(node_usage.disk, 0)::bigint AS allocated_disk,
+            node_free_allocations.free_allocations,
+            node_free_allocations.free_additional_allocations
+        FROM nodes
+        JOIN locations ON locations.uuid = nodes.location_uuid
+        LEFT JOIN (
+            SELECT server_usage.node_uuid, SUM(server_usage.memory) AS memory, SUM(server_usage.disk) AS disk
+            FROM (
+                SELECT servers.node_uuid, servers.memory, servers.disk
+                FROM servers
+                UNION ALL
+                SELECT servers.destination_node_uuid, servers.memory, servers.disk
+                FROM servers
+                WHERE servers.destination_node_uuid IS NOT NULL
+            ) AS server_usage
+            GROUP BY server_usage.node_uuid
+        ) AS node_usage ON node_usage.node_uuid = nodes.uuid
+        CROSS JOIN LATERAL (
+            SELECT
+                COUNT(*) AS free_allocations,
+                COUNT(*) FILTER (WHERE node_allocations.port BETWEEN $6 AND $7) AS free_additional_allocations
+            FROM node_allocations
+            LEFT JOIN server_allocations ON server_allocations.allocation_uuid = node_allocations.uuid
+            WHERE
+                node_allocations.node_uuid = nodes.uuid
+                AND server_allocations.uuid IS NULL
+                AND node_allocations.port BETWEEN $2 AND $3
+        ) AS node_free_allocations
+        WHERE
+            (nodes.location_uuid = ANY($1) OR nodes.uuid = ANY($4))
+            AND NOT nodes.uuid = ANY($5)
+        ORDER BY nodes.created
+        "#,
+        Node::columns_sql(None)
+    ))
+    .bind(&request.location_uuids)
+    .bind(port_start)
+    .bind(port_end)
+    .bind(node_uuids)
+    .bind(excluded_node_uuids)
+    .bind(additional_port_start)
+    .bind(additional_port_end)
+    .fetch_all(database.read())
+    .await?;
+
+    let mut nodes = Vec::with_capacity(rows.len());
+    let mut candidates = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let node = Node::map(None, &row)?;
+        let free_memory = node.memory - row.try_get::<i64, _>("allocated_memory")?;
+        let free_disk = node.disk - row.try_get::<i64, _>("allocated_disk")?;
+        let free_allocations: i64 = row.try_get("free_allocations")?;
+        let free_additional_allocations: i64 = row.try_get("free_additional_allocations")?;
+
+        let skipped_reason = if node.maintenance_message.is_some() {
+            Some("node is in maintenance")
+        } else if node.health_status == NodeHealthStatus::Offline {
+            Some("node is offline")
+        } else if free_memory < request.memory {
+            Some("not enough free memory")
+        } else if free_disk < request.disk {
+            Some("not enough free disk")
+        } else if free_allocations < request.allocations {
+            Some("not enough free allocations in port range")
+        } else if free_additional_allocations < request.allocations - 1 {
+            Some("not enough free allocations in self assign range")
+        } else {
+            None
+        };
+
+        let score = if skipped_reason.is_none() {
+            let memory_share = (free_memory - request.memory) as f64 / node.memory.max(1) as f64;
+            let disk_share = (free_disk - request.disk) as f64 / node.disk.max(1) as f64;
+
+            Some((memory_share + disk_share) / 2.0)
+        } else {
+            None
+        };
+
+        candidates.push(DeploymentCandidate {
+            node_uuid: node.uuid,
+            node_name: node.name.clone(),
+            location_uuid: node.location.uuid,
+            free_memory,
+            free_disk,
+            free_allocations,
+            score,
+            selected: false,
+            skipped_reason,
+        });
+        nodes.push(node);
+    }
+
+    let best = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| candidate.score.map(|score| (i, score)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i);
+
+    let (node, allocations) = match best {
+        Some(i) => {
+            candidates[i].selected = true;
+            let node = nodes.swap_remove(i);
+
+            let mut allocations = if request.allocations > 1 {
+                NodeAllocation::available_by_node_uuid_in_port_range(
+                    database,
+                    node.uuid,
+                    additional_port_start,
+                    additional_port_end,
+                    request.allocations - 1,
+                )
+                .await?
+            } else {
+                Vec::new()
+            };
+
+            // the additional range is part of the primary one, so one of these is never taken already
+            let primary_allocation = NodeAllocation::available_by_node_uuid_in_port_range(
+                database,
+                node.uuid,
+                port_start,
+                port_end,
+                request.allocations,
+            )
+            .await?
+            .into_iter()
+            .find(|allocation| !allocations.iter().any(|a| a.uuid == allocation.uuid));
+
+            match primary_allocation {
+                Some(primary_allocation) => allocations.insert(0, primary_allocation),
+                None => allocations.clear(),
+            }
+
+            (Some(node), allocations)
+        }
+        None => (None, Vec::new()),
+    };
+
+    Ok(DeploymentPlan {
+        node,
+        allocations,
+        port_start,
+        port_end,
+        additional_port_start,
+        additional_port_end,
+        candidates,
+    })
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct ApiDeploymentPlanAllocation {
+    pub uuid: uuid::Uuid,
+    pub ip: compact_str::CompactString,
+    pub port: i32,
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "DeploymentPlan")]
+pub struct ApiDeploymentPlan {
+    pub node_uuid: Option<uuid::Uuid>,
+    pub allocations: Vec<ApiDeploymentPlanAllocation>,
+    /// The port range of the primary allocation.
+    pub port_start: i32,
+    pub port_end: i32,
+    /// The port range of the additional allocations, part of the primary one.
+    pub additional_port_start: i32,
+    pub additional_port_end: i32,
+
+    pub candidates: Vec<DeploymentCandidate>,
+}
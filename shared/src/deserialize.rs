@@ -36,6 +36,16 @@ where
     Ok(value.filter(|s| !s.is_empty()))
 }
 
+/// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`), use together with `#[serde(default)]`.
+pub fn deserialize_explicit_option<'de, D, T: Deserialize<'de>>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 pub fn deserialize_array_or_not<'de, D, T: DeserializeOwned>(
     deserializer: D,
 ) -> Result<Vec<T>, D::Error>
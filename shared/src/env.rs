@@ -37,6 +37,7 @@ pub struct Env {
     pub port: u16,
 
     pub app_primary: bool,
+    pub app_leader_election: bool,
     pub app_debug: bool,
     pub app_use_decryption_cache: bool,
     pub app_use_internal_cache: bool,
@@ -114,7 +115,12 @@ impl Env {
                 .unwrap_or("true".to_string())
                 .trim_matches('"')
                 .parse()
-                .context("Invalid APP_DEBUG value")?,
+                .context("Invalid APP_PRIMARY value")?,
+            app_leader_election: std::env::var("APP_LEADER_ELECTION")
+                .unwrap_or("true".to_string())
+                .trim_matches('"')
+                .parse()
+                .context("Invalid APP_LEADER_ELECTION value")?,
             app_debug: std::env::var("APP_DEBUG")
                 .unwrap_or("false".to_string())
                 .trim_matches('"')
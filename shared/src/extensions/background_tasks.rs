@@ -1,19 +1,120 @@
 use crate::State;
 use futures_util::FutureExt;
-use std::{borrow::Cow, collections::HashMap, panic::AssertUnwindSafe, pin::Pin, sync::Arc};
-use tokio::sync::{OwnedRwLockReadGuard, RwLock};
+use rand::Rng;
+use rustis::commands::{
+    CallBuilder, ScriptingCommands, SetCondition, SetExpiration, StringCommands,
+};
+use serde::Serialize;
+use std::{
+    borrow::Cow, collections::HashMap, panic::AssertUnwindSafe, pin::Pin, sync::Arc, time::Duration,
+};
+use tokio::sync::{Notify, OwnedRwLockReadGuard, RwLock};
+use utoipa::ToSchema;
 
 pub type LoopFunc =
     dyn Fn(State) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send>> + Send;
 
+/// How long the lock of a running scheduled task is held before it has to be renewed.
+const RUNNING_LOCK_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Clone)]
+pub enum BackgroundTaskSchedule {
+    Cron(Box<cron::Schedule>),
+    Interval(Duration),
+}
+
+impl BackgroundTaskSchedule {
+    /// The first occurrence after `after`. Intervals are aligned to the unix epoch,
+    /// so every panel instance computes the same occurrences for the same schedule.
+    pub fn next_occurrence(
+        &self,
+        after: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self {
+            Self::Cron(schedule) => schedule.after(&after).next(),
+            Self::Interval(interval) => {
+                let interval = interval.as_millis().max(1) as i64;
+
+                chrono::DateTime::from_timestamp_millis(
+                    (after.timestamp_millis() / interval + 1) * interval,
+                )
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for BackgroundTaskSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cron(schedule) => write!(f, "{schedule}"),
+            Self::Interval(interval) => write!(f, "every {}s", interval.as_secs()),
+        }
+    }
+}
+
 pub struct BackgroundTask {
     pub name: &'static str,
     pub last_execution: std::time::Instant,
     pub last_error: Option<anyhow::Error>,
+    pub runs: u64,
+    pub failures: u64,
+
+    /// `None` for tasks added with [`BackgroundTaskBuilder::add_task`], which do their own sleeping.
+    pub schedule: Option<BackgroundTaskSchedule>,
+    pub jitter: Duration,
+    pub running: bool,
+    pub last_run: Option<chrono::DateTime<chrono::Utc>>,
+    pub next_run: Option<chrono::DateTime<chrono::Utc>>,
 
+    trigger: Arc<Notify>,
     pub task: tokio::task::JoinHandle<()>,
 }
 
+impl BackgroundTask {
+    /// Runs a scheduled task on this instance as soon as possible, returns `false` for unscheduled tasks.
+    pub fn trigger(&self) -> bool {
+        if self.schedule.is_none() {
+            return false;
+        }
+
+        self.trigger.notify_one();
+
+        true
+    }
+
+    pub fn to_admin_api_object(&self) -> AdminApiBackgroundTask {
+        AdminApiBackgroundTask {
+            name: self.name,
+            schedule: self.schedule.as_ref().map(|schedule| schedule.to_string()),
+            jitter: self.jitter.as_secs(),
+            running: self.running,
+            runs: self.runs,
+            failures: self.failures,
+            last_error: self.last_error.as_ref().map(|err| format!("{err:#}")),
+            last_run: self.last_run,
+            next_run: self.next_run,
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "BackgroundTask")]
+pub struct AdminApiBackgroundTask {
+    pub name: &'static str,
+    /// The cron expression or interval of scheduled tasks.
+    pub schedule: Option<String>,
+    /// The maximum random delay in seconds added to every scheduled run.
+    pub jitter: u64,
+
+    pub running: bool,
+    pub runs: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
+
+    pub last_run: Option<chrono::DateTime<chrono::Utc>>,
+    pub next_run: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 pub struct BackgroundTaskBuilder {
     state: State,
     tasks: Arc<RwLock<HashMap<&'static str, BackgroundTask>>>,
@@ -27,6 +128,10 @@ impl BackgroundTaskBuilder {
         }
     }
 
+    /// Adds a task that runs `loop_fn` forever while this instance is the leader,
+    /// `loop_fn` has to implement its own sleeping.
+    ///
+    /// Leadership is checked between runs, a run that already started is never cancelled.
     pub async fn add_task(&self, name: &'static str, loop_fn: Box<LoopFunc>) {
         if !self.state.env.app_primary {
             return;
@@ -41,50 +146,169 @@ impl BackgroundTaskBuilder {
                 name,
                 last_execution: std::time::Instant::now(),
                 last_error: None,
+                runs: 0,
+                failures: 0,
+                schedule: None,
+                jitter: Duration::ZERO,
+                running: true,
+                last_run: None,
+                next_run: None,
+                trigger: Arc::new(Notify::new()),
+                task: tokio::spawn(async move {
+                    loop {
+                        if !state.leader.is_leader() {
+                            tracing::info!(
+                                name,
+                                "pausing background task until this instance is the leader"
+                            );
+                            state.leader.leadership_acquired().await;
+                        }
+
+                        if !execute(&tasks, name, loop_fn(state.clone())).await {
+                            return;
+                        }
+                    }
+                }),
+            },
+        );
+    }
+
+    /// Adds a task that runs `run_fn` on every occurrence of a cron schedule, delayed by up to `jitter`.
+    ///
+    /// Occurrences run on the leader instance, only once even if several instances consider themselves
+    /// the leader, and never while a previous run is still going.
+    pub async fn add_cron_task(
+        &self,
+        name: &'static str,
+        schedule: cron::Schedule,
+        jitter: Duration,
+        run_fn: Box<LoopFunc>,
+    ) {
+        self.add_scheduled_task(
+            name,
+            BackgroundTaskSchedule::Cron(Box::new(schedule)),
+            jitter,
+            run_fn,
+        )
+        .await;
+    }
+
+    /// Adds a task that runs `run_fn` every `interval`, delayed by up to `jitter`.
+    ///
+    /// Runs happen on the leader instance, only once even if several instances consider themselves
+    /// the leader, and never while a previous run is still going.
+    pub async fn add_interval_task(
+        &self,
+        name: &'static str,
+        interval: Duration,
+        jitter: Duration,
+        run_fn: Box<LoopFunc>,
+    ) {
+        self.add_scheduled_task(
+            name,
+            BackgroundTaskSchedule::Interval(interval),
+            jitter,
+            run_fn,
+        )
+        .await;
+    }
+
+    async fn add_scheduled_task(
+        &self,
+        name: &'static str,
+        schedule: BackgroundTaskSchedule,
+        jitter: Duration,
+        run_fn: Box<LoopFunc>,
+    ) {
+        if !self.state.env.app_primary {
+            return;
+        }
+
+        let state = self.state.clone();
+        let tasks = Arc::clone(&self.tasks);
+        let trigger = Arc::new(Notify::new());
+
+        self.tasks.write().await.insert(
+            name,
+            BackgroundTask {
+                name,
+                last_execution: std::time::Instant::now(),
+                last_error: None,
+                runs: 0,
+                failures: 0,
+                schedule: Some(schedule.clone()),
+                jitter,
+                running: false,
+                last_run: None,
+                next_run: None,
+                trigger: Arc::clone(&trigger),
                 task: tokio::spawn(async move {
                     loop {
+                        let occurrence = schedule.next_occurrence(chrono::Utc::now());
+                        let next_run = occurrence.map(|occurrence| {
+                            occurrence + rand::rng().random_range(Duration::ZERO..=jitter)
+                        });
+
                         if let Some(task) = tasks.write().await.get_mut(name) {
-                            task.last_execution = std::time::Instant::now();
+                            task.next_run = next_run;
                         }
 
-                        tracing::debug!(name, "running background task loop function");
-                        let result = AssertUnwindSafe(loop_fn(state.clone()))
-                            .catch_unwind()
-                            .await;
-
-                        let result = match result {
-                            Ok(result) => result,
-                            Err(err) => {
-                                let err_msg: Cow<'_, str> =
-                                    if let Some(s) = err.downcast_ref::<&str>() {
-                                        (*s).into()
-                                    } else if let Some(s) = err.downcast_ref::<String>() {
-                                        s.clone().into()
-                                    } else {
-                                        "Unknown panic".into()
-                                    };
-
-                                tracing::error!(name, "background task panicked: {}", err_msg);
-                                sentry::capture_message(
-                                    &format!("Background task '{}' panicked: {}", name, err_msg),
-                                    sentry::Level::Error,
-                                );
-
-                                if let Some(task) = tasks.write().await.get_mut(name) {
-                                    task.last_error = Some(anyhow::anyhow!(err_msg));
+                        let delay = next_run.map(|next_run| {
+                            (next_run - chrono::Utc::now()).to_std().unwrap_or_default()
+                        });
+                        let triggered = tokio::select! {
+                            _ = async {
+                                match delay {
+                                    Some(delay) => tokio::time::sleep(delay).await,
+                                    None => std::future::pending().await,
                                 }
+                            } => false,
+                            _ = trigger.notified() => true,
+                        };
+
+                        if !triggered && !state.leader.is_leader() {
+                            continue;
+                        }
+
+                        if !triggered
+                            && let Some(occurrence) = occurrence
+                            && !claim_occurrence(&state, name, occurrence, jitter).await
+                        {
+                            tracing::debug!(
+                                name,
+                                "scheduled background task ran on another instance"
+                            );
+                            continue;
+                        }
+
+                        if !acquire_running_lock(&state, name).await {
+                            tracing::warn!(
+                                name,
+                                "skipping scheduled background task, a previous run is still going"
+                            );
+                            continue;
+                        }
 
-                                return;
+                        if let Some(task) = tasks.write().await.get_mut(name) {
+                            task.running = true;
+                        }
+
+                        let keep_running_lock = async {
+                            loop {
+                                tokio::time::sleep(RUNNING_LOCK_TTL / 3).await;
+                                renew_running_lock(&state, name).await;
                             }
                         };
 
-                        if let Err(err) = &result {
-                            tracing::error!(name, "a background task error occurred: {:?}", err);
-                            sentry_anyhow::capture_anyhow(err);
+                        tokio::select! {
+                            _ = execute(&tasks, name, run_fn(state.clone())) => {}
+                            _ = keep_running_lock => {}
                         }
 
+                        release_running_lock(&state, name).await;
+
                         if let Some(task) = tasks.write().await.get_mut(name) {
-                            task.last_error = result.err();
+                            task.running = false;
                         }
                     }
                 }),
@@ -93,6 +317,178 @@ impl BackgroundTaskBuilder {
     }
 }
 
+/// Runs a single iteration of a task and records its outcome, returns `false` if it panicked.
+async fn execute(
+    tasks: &RwLock<HashMap<&'static str, BackgroundTask>>,
+    name: &'static str,
+    run: Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send>>,
+) -> bool {
+    if let Some(task) = tasks.write().await.get_mut(name) {
+        task.last_execution = std::time::Instant::now();
+        task.last_run = Some(chrono::Utc::now());
+    }
+
+    tracing::debug!(name, "running background task loop function");
+    let result = AssertUnwindSafe(run).catch_unwind().await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => {
+            let err_msg: Cow<'_, str> = if let Some(s) = err.downcast_ref::<&str>() {
+                (*s).into()
+            } else if let Some(s) = err.downcast_ref::<String>() {
+                s.clone().into()
+            } else {
+                "Unknown panic".into()
+            };
+
+            tracing::error!(name, "background task panicked: {}", err_msg);
+            sentry::capture_message(
+                &format!("Background task '{}' panicked: {}", name, err_msg),
+                sentry::Level::Error,
+            );
+
+            if let Some(task) = tasks.write().await.get_mut(name) {
+                task.runs += 1;
+                task.failures += 1;
+                task.last_error = Some(anyhow::anyhow!(err_msg));
+            }
+
+            return false;
+        }
+    };
+
+    if let Err(err) = &result {
+        tracing::error!(name, "a background task error occurred: {:?}", err);
+        sentry_anyhow::capture_anyhow(err);
+    }
+
+    if let Some(task) = tasks.write().await.get_mut(name) {
+        task.runs += 1;
+        if result.is_err() {
+            task.failures += 1;
+        }
+        task.last_error = result.err();
+    }
+
+    true
+}
+
+/// Claims an occurrence of a scheduled task for this instance, only the first instance to claim it runs it.
+/// If the cache is unavailable the occurrence is run anyway, running a task twice beats not running it.
+async fn claim_occurrence(
+    state: &State,
+    name: &str,
+    occurrence: chrono::DateTime<chrono::Utc>,
+    jitter: Duration,
+) -> bool {
+    let key = format!(
+        "background_tasks::{name}::occurrence::{}",
+        occurrence.timestamp_millis()
+    );
+
+    match state
+        .cache
+        .client
+        .set_with_options(
+            key,
+            state.instance.to_string(),
+            SetCondition::NX,
+            SetExpiration::Ex(jitter.as_secs() + 60),
+            false,
+        )
+        .await
+    {
+        Ok(claimed) => claimed,
+        Err(err) => {
+            tracing::warn!(name, "failed to claim scheduled background task: {:?}", err);
+            true
+        }
+    }
+}
+
+async fn acquire_running_lock(state: &State, name: &str) -> bool {
+    match state
+        .cache
+        .client
+        .set_with_options(
+            format!("background_tasks::{name}::running"),
+            state.instance.to_string(),
+            SetCondition::NX,
+            SetExpiration::Px(RUNNING_LOCK_TTL.as_millis() as u64),
+            false,
+        )
+        .await
+    {
+        Ok(acquired) => acquired,
+        Err(err) => {
+            tracing::warn!(name, "failed to lock scheduled background task: {:?}", err);
+            true
+        }
+    }
+}
+
+/// Extends the running lock, but only while it is still held by this instance.
+const RENEW_RUNNING_LOCK_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+/// Deletes the running lock, but only while it is still held by this instance.
+const RELEASE_RUNNING_LOCK_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+async fn renew_running_lock(state: &State, name: &str) {
+    match state
+        .cache
+        .client
+        .eval::<i64>(
+            CallBuilder::script(RENEW_RUNNING_LOCK_SCRIPT)
+                .keys(vec![format!("background_tasks::{name}::running")])
+                .args(vec![
+                    state.instance.to_string(),
+                    RUNNING_LOCK_TTL.as_millis().to_string(),
+                ]),
+        )
+        .await
+    {
+        Ok(0) => tracing::warn!(
+            name,
+            "scheduled background task lock was taken over by another instance"
+        ),
+        Ok(_) => {}
+        Err(err) => tracing::warn!(
+            name,
+            "failed to renew scheduled background task lock: {:?}",
+            err
+        ),
+    }
+}
+
+async fn release_running_lock(state: &State, name: &str) {
+    if let Err(err) = state
+        .cache
+        .client
+        .eval::<i64>(
+            CallBuilder::script(RELEASE_RUNNING_LOCK_SCRIPT)
+                .keys(vec![format!("background_tasks::{name}::running")])
+                .args(vec![state.instance.to_string()]),
+        )
+        .await
+    {
+        tracing::warn!(
+            name,
+            "failed to unlock scheduled background task: {:?}",
+            err
+        );
+    }
+}
+
 #[derive(Default)]
 pub struct BackgroundTaskManager {
     builder: RwLock<Option<BackgroundTaskBuilder>>,
@@ -0,0 +1,210 @@
+use crate::{State, models::job::Job};
+use futures_util::FutureExt;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    borrow::Cow, collections::HashMap, panic::AssertUnwindSafe, pin::Pin, sync::Arc, time::Duration,
+};
+use tokio::sync::{OwnedSemaphorePermit, RwLock, Semaphore};
+
+/// How long a claimed job belongs to an instance before other instances may claim it again.
+const JOB_LEASE: Duration = Duration::from_secs(60);
+/// How often a running job renews its lease, this is also how fast cancellations are picked up.
+const JOB_LEASE_RENEWAL: Duration = Duration::from_secs(10);
+const JOB_CONCURRENCY: usize = 4;
+
+pub type JobFunc = dyn Fn(State, Job) -> Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send>>
+    + Send
+    + Sync;
+
+#[async_trait::async_trait]
+pub trait JobType: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// The name of the job type, this has to be globally unique, for example by prepending your package name.
+    const NAME: &'static str;
+    /// How often a job is attempted before it is marked as failed.
+    const MAX_ATTEMPTS: i32 = 3;
+    /// Jobs with a higher priority are claimed first.
+    const PRIORITY: i16 = 0;
+
+    /// Runs a single attempt of the job, returning an error schedules a retry if attempts are left.
+    async fn run(self, state: State, job: Job) -> Result<(), anyhow::Error>;
+
+    /// Queues the job to run as soon as a worker is available.
+    async fn enqueue(&self, database: &crate::database::Database) -> Result<Job, anyhow::Error> {
+        self.enqueue_at(database, None).await
+    }
+
+    /// Queues the job to run at `run_at`, or as soon as a worker is available if `None`.
+    async fn enqueue_at(
+        &self,
+        database: &crate::database::Database,
+        run_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<Job, anyhow::Error> {
+        Ok(Job::create(
+            database,
+            Self::NAME,
+            serde_json::to_value(self)?,
+            Self::PRIORITY,
+            Self::MAX_ATTEMPTS,
+            run_at,
+        )
+        .await?)
+    }
+
+    /// Whether an equal job is already waiting to run or running, used to avoid queueing duplicates.
+    async fn is_queued(&self, database: &crate::database::Database) -> Result<bool, anyhow::Error> {
+        Ok(Job::exists_unfinished(database, Self::NAME, &serde_json::to_value(self)?).await?)
+    }
+}
+
+#[derive(Default)]
+pub struct JobBuilder {
+    jobs: HashMap<&'static str, Arc<JobFunc>>,
+}
+
+impl JobBuilder {
+    /// Adds a job type, queued jobs of it are run by the leader instance.
+    pub fn add_job_type<T: JobType>(mut self) -> Self {
+        self.jobs.insert(
+            T::NAME,
+            Arc::new(|state, job| {
+                Box::pin(async move {
+                    let payload: T = serde_json::from_value(job.payload.clone())?;
+
+                    payload.run(state, job).await
+                })
+            }),
+        );
+
+        self
+    }
+}
+
+pub struct JobManager {
+    jobs: RwLock<HashMap<&'static str, Arc<JobFunc>>>,
+    workers: Arc<Semaphore>,
+}
+
+impl Default for JobManager {
+    fn default() -> Self {
+        Self {
+            jobs: RwLock::new(HashMap::new()),
+            workers: Arc::new(Semaphore::new(JOB_CONCURRENCY)),
+        }
+    }
+}
+
+impl JobManager {
+    pub async fn merge_builder(&self, builder: JobBuilder) {
+        self.jobs.write().await.extend(builder.jobs);
+    }
+
+    pub async fn job_types(&self) -> Vec<&'static str> {
+        self.jobs.read().await.keys().copied().collect()
+    }
+
+    /// Claims due jobs for the idle workers of this instance and runs them in the background.
+    pub async fn process(&self, state: &State) -> Result<(), anyhow::Error> {
+        let idle_workers = self.workers.available_permits();
+        if idle_workers == 0 {
+            return Ok(());
+        }
+
+        let jobs = self.jobs.read().await;
+        if jobs.is_empty() {
+            return Ok(());
+        }
+
+        let failed_jobs = Job::fail_expired(&state.database).await?;
+        if failed_jobs > 0 {
+            tracing::warn!("failed {} jobs whose last attempt expired", failed_jobs);
+        }
+
+        let names = jobs.keys().copied().collect::<Vec<_>>();
+        let claimed_jobs = Job::claim(
+            &state.database,
+            state.instance,
+            &names,
+            idle_workers as i64,
+            JOB_LEASE,
+        )
+        .await?;
+
+        for job in claimed_jobs {
+            let Some(job_fn) = jobs.get(job.name.as_str()).cloned() else {
+                continue;
+            };
+            let permit = Arc::clone(&self.workers).acquire_owned().await?;
+
+            tokio::spawn(run_job(state.clone(), state.instance, job_fn, job, permit));
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_job(
+    state: State,
+    instance: uuid::Uuid,
+    job_fn: Arc<JobFunc>,
+    job: Job,
+    _permit: OwnedSemaphorePermit,
+) {
+    tracing::debug!(job = %job.uuid, name = %job.name, attempt = job.attempts, "running job");
+
+    let run = AssertUnwindSafe(job_fn(state.clone(), job.clone())).catch_unwind();
+    let renew_lease = async {
+        loop {
+            tokio::time::sleep(JOB_LEASE_RENEWAL).await;
+
+            match job.renew_lease(&state.database, instance, JOB_LEASE).await {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(err) => {
+                    tracing::warn!(job = %job.uuid, "failed to renew job lease: {:?}", err);
+                }
+            }
+        }
+    };
+
+    let result = tokio::select! {
+        result = run => result,
+        _ = renew_lease => {
+            tracing::info!(job = %job.uuid, name = %job.name, "job was cancelled or lost its lease, stopping");
+            return;
+        }
+    };
+
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => {
+            let err_msg: Cow<'_, str> = if let Some(s) = err.downcast_ref::<&str>() {
+                (*s).into()
+            } else if let Some(s) = err.downcast_ref::<String>() {
+                s.clone().into()
+            } else {
+                "Unknown panic".into()
+            };
+
+            Err(anyhow::anyhow!("job panicked: {}", err_msg))
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            if let Err(err) = job.complete(&state.database, instance).await {
+                tracing::error!(job = %job.uuid, "failed to mark job as completed: {:?}", err);
+            }
+        }
+        Err(err) => {
+            tracing::error!(job = %job.uuid, name = %job.name, "a job error occurred: {:?}", err);
+            sentry_anyhow::capture_anyhow(&err);
+
+            if let Err(err) = job
+                .fail(&state.database, instance, &format!("{err:#}"))
+                .await
+            {
+                tracing::error!(job = %job.uuid, "failed to mark job as failed: {:?}", err);
+            }
+        }
+    }
+}
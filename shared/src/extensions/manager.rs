@@ -1,8 +1,8 @@
 use crate::{
     State,
     extensions::{
-        ConstructedExtension, ExtensionPermissionsBuilder, ExtensionRouteBuilder,
-        commands::CliCommandGroupBuilder,
+        ConstructedExtension, ExtensionMailTemplatesBuilder, ExtensionPermissionsBuilder,
+        ExtensionRouteBuilder, commands::CliCommandGroupBuilder,
     },
 };
 use std::sync::Arc;
@@ -25,15 +25,21 @@ impl ExtensionManager {
     ) -> (
         ExtensionRouteBuilder,
         super::background_tasks::BackgroundTaskBuilder,
+        super::jobs::JobBuilder,
     ) {
         let mut route_builder = ExtensionRouteBuilder::new(state.clone());
         let mut background_tasks_builder =
             super::background_tasks::BackgroundTaskBuilder::new(state.clone());
+        let mut jobs_builder = super::jobs::JobBuilder::default()
+            .add_job_type::<crate::models::egg_repository::EggRepositorySyncJob>(
+        );
         let mut permissions_builder = ExtensionPermissionsBuilder::new(
             crate::permissions::BASE_USER_PERMISSIONS.clone(),
             crate::permissions::BASE_ADMIN_PERMISSIONS.clone(),
             crate::permissions::BASE_SERVER_PERMISSIONS.clone(),
         );
+        let mut mail_templates_builder =
+            ExtensionMailTemplatesBuilder::new(crate::mail::BASE_MAIL_TEMPLATES.clone());
 
         for ext in self.vec.write().await.iter_mut() {
             ext.initialize(state.clone()).await;
@@ -42,9 +48,13 @@ impl ExtensionManager {
             background_tasks_builder = ext
                 .initialize_background_tasks(state.clone(), background_tasks_builder)
                 .await;
+            jobs_builder = ext.initialize_jobs(state.clone(), jobs_builder).await;
             permissions_builder = ext
                 .initialize_permissions(state.clone(), permissions_builder)
                 .await;
+            mail_templates_builder = ext
+                .initialize_mail_templates(state.clone(), mail_templates_builder)
+                .await;
         }
 
         crate::permissions::USER_PERMISSIONS
@@ -59,8 +69,9 @@ impl ExtensionManager {
             .write()
             .unwrap()
             .replace(permissions_builder.server_permissions);
+        *crate::mail::MAIL_TEMPLATES.write().unwrap() = mail_templates_builder.mail_templates;
 
-        (route_builder, background_tasks_builder)
+        (route_builder, background_tasks_builder, jobs_builder)
     }
 
     pub async fn init_cli(
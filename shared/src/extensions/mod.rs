@@ -1,6 +1,6 @@
 #![allow(unused_variables)]
 
-use crate::{State, permissions::PermissionGroup};
+use crate::{State, mail::BundledMailTemplate, permissions::PermissionGroup};
 use indexmap::IndexMap;
 use serde::Serialize;
 use std::{
@@ -13,6 +13,7 @@ use utoipa_axum::router::OpenApiRouter;
 pub mod background_tasks;
 pub mod commands;
 pub mod distr;
+pub mod jobs;
 pub mod manager;
 
 pub struct ExtensionRouteBuilder {
@@ -195,6 +196,28 @@ impl ExtensionPermissionsBuilder {
     }
 }
 
+pub struct ExtensionMailTemplatesBuilder {
+    pub mail_templates: IndexMap<&'static str, BundledMailTemplate>,
+}
+
+impl ExtensionMailTemplatesBuilder {
+    pub fn new(mail_templates: IndexMap<&'static str, BundledMailTemplate>) -> Self {
+        Self { mail_templates }
+    }
+
+    /// Adds a mail template, admins can override it per language like the bundled templates.
+    /// Render and send it with `state.mail.send_template`.
+    pub fn add_mail_template(
+        mut self,
+        template_name: &'static str,
+        template: BundledMailTemplate,
+    ) -> Self {
+        self.mail_templates.insert(template_name, template);
+
+        self
+    }
+}
+
 pub type ExtensionCallValue = Box<dyn std::any::Any + Send + Sync>;
 
 #[async_trait::async_trait]
@@ -229,6 +252,15 @@ pub trait Extension: Send + Sync {
         builder
     }
 
+    /// Your extension job types entrypoint, this runs as soon as the database is migrated and before the webserver starts
+    async fn initialize_jobs(
+        &mut self,
+        state: State,
+        builder: jobs::JobBuilder,
+    ) -> jobs::JobBuilder {
+        builder
+    }
+
     /// Your extension permissions entrypoint, this runs as soon as the database is migrated and before the webserver starts
     async fn initialize_permissions(
         &mut self,
@@ -238,6 +270,15 @@ pub trait Extension: Send + Sync {
         builder
     }
 
+    /// Your extension mail templates entrypoint, this runs as soon as the database is migrated and before the webserver starts
+    async fn initialize_mail_templates(
+        &mut self,
+        state: State,
+        builder: ExtensionMailTemplatesBuilder,
+    ) -> ExtensionMailTemplatesBuilder {
+        builder
+    }
+
     /// Your extension call processor, this can be called by other extensions to interact with yours,
     /// if the call does not apply to your extension, simply return `None` to continue the matching process.
     ///
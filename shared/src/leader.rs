@@ -0,0 +1,137 @@
+use std::{sync::Arc, time::Duration};
+use tokio::sync::watch;
+
+/// The advisory lock held by the leader, `("calagopus" as hex, 1)`.
+const LEADER_LOCK: (i32, i32) = (0x63616c61, 1);
+const LEADER_APPLICATION_NAME: &str = "calagopus-leader:";
+/// How often followers try to become the leader and the leader checks its connection,
+/// a dead leader is replaced within about twice this time.
+const LEADER_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Elects a single leader among all primary panel instances sharing the database.
+///
+/// The leader holds a postgres advisory lock on a dedicated connection, the lock is released by postgres
+/// as soon as that connection goes away, after which another instance takes over.
+pub struct LeaderElection {
+    instance: uuid::Uuid,
+    enabled: bool,
+    leading: watch::Sender<bool>,
+}
+
+impl LeaderElection {
+    /// With leader election disabled every primary instance considers itself the leader.
+    pub fn new(instance: uuid::Uuid, env: &crate::env::Env) -> Self {
+        let enabled = env.app_primary && env.app_leader_election;
+
+        Self {
+            instance,
+            enabled,
+            leading: watch::Sender::new(env.app_primary && !env.app_leader_election),
+        }
+    }
+
+    /// Starts competing for leadership in the background.
+    pub fn start(self: &Arc<Self>, database: Arc<crate::database::Database>) {
+        if !self.enabled {
+            return;
+        }
+
+        let election = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = election.lead(&database).await {
+                    tracing::error!("leader election connection failed: {:?}", err);
+                }
+
+                if election.leading.send_replace(false) {
+                    tracing::warn!(instance = %election.instance, "this instance is no longer the leader");
+                }
+
+                tokio::time::sleep(LEADER_CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    /// Waits for the leader lock on a dedicated connection and holds it until the connection fails.
+    async fn lead(&self, database: &crate::database::Database) -> Result<(), anyhow::Error> {
+        let mut connection = database.write().acquire().await?.detach();
+
+        sqlx::query("SELECT set_config('application_name', $1, false)")
+            .bind(format!("{LEADER_APPLICATION_NAME}{}", self.instance))
+            .execute(&mut connection)
+            .await?;
+
+        loop {
+            let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1, $2)")
+                .bind(LEADER_LOCK.0)
+                .bind(LEADER_LOCK.1)
+                .fetch_one(&mut connection)
+                .await?;
+
+            if acquired {
+                break;
+            }
+
+            tokio::time::sleep(LEADER_CHECK_INTERVAL).await;
+        }
+
+        tracing::info!(instance = %self.instance, "this instance is now the leader");
+        self.leading.send_replace(true);
+
+        loop {
+            tokio::time::sleep(LEADER_CHECK_INTERVAL).await;
+
+            tokio::time::timeout(
+                LEADER_CHECK_INTERVAL,
+                sqlx::query("SELECT 1").execute(&mut connection),
+            )
+            .await??;
+        }
+    }
+
+    #[inline]
+    pub fn is_leader(&self) -> bool {
+        *self.leading.borrow()
+    }
+
+    /// Resolves once this instance is the leader.
+    pub async fn leadership_acquired(&self) {
+        let _ = self.leading.subscribe().wait_for(|leader| *leader).await;
+    }
+
+    /// Resolves once this instance is no longer the leader.
+    pub async fn leadership_lost(&self) {
+        let _ = self.leading.subscribe().wait_for(|leader| !*leader).await;
+    }
+
+    /// Looks up the instance currently holding the leader lock, `None` if there is no elected leader
+    /// or, with leader election disabled, this instance is not a primary.
+    pub async fn leader(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<Option<uuid::Uuid>, sqlx::Error> {
+        if !self.enabled {
+            return Ok(self.is_leader().then_some(self.instance));
+        }
+
+        let application_name: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT pg_stat_activity.application_name
+            FROM pg_locks
+            JOIN pg_stat_activity ON pg_stat_activity.pid = pg_locks.pid
+            WHERE pg_locks.locktype = 'advisory' AND pg_locks.granted
+                AND pg_locks.classid::bigint = $1 AND pg_locks.objid::bigint = $2 AND pg_locks.objsubid = 2
+                AND pg_locks.database = (SELECT oid FROM pg_database WHERE datname = current_database())
+            "#,
+        )
+        .bind(LEADER_LOCK.0 as i64)
+        .bind(LEADER_LOCK.1 as i64)
+        .fetch_optional(database.write())
+        .await?;
+
+        Ok(application_name
+            .as_deref()
+            .and_then(|name| name.strip_prefix(LEADER_APPLICATION_NAME))
+            .and_then(|instance| instance.parse().ok()))
+    }
+}
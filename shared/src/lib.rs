@@ -11,12 +11,15 @@ pub mod cache;
 pub mod cap;
 pub mod captcha;
 pub mod database;
+pub mod deployment;
 pub mod deserialize;
 pub mod env;
 pub mod extensions;
 pub mod extract;
 pub mod jwt;
+pub mod leader;
 pub mod mail;
+pub mod metrics;
 pub mod models;
 pub mod permissions;
 pub mod prelude;
@@ -52,6 +55,14 @@ impl ApiError {
         })
     }
 
+    #[inline]
+    pub fn new_fields_value(errors: &[rule_validator::FieldError]) -> serde_json::Value {
+        serde_json::json!({
+            "errors": errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+            "field_errors": errors,
+        })
+    }
+
     #[inline]
     pub fn new_wings_value(error: wings_api::ApiError) -> serde_json::Value {
         serde_json::json!({
@@ -70,6 +81,8 @@ pub enum AppContainerType {
 }
 
 pub struct AppState {
+    /// Identifies this panel process among all instances sharing the database.
+    pub instance: uuid::Uuid,
     pub start_time: Instant,
     pub container_type: AppContainerType,
     pub version: String,
@@ -78,11 +91,14 @@ pub struct AppState {
 
     pub extensions: Arc<extensions::manager::ExtensionManager>,
     pub background_tasks: Arc<extensions::background_tasks::BackgroundTaskManager>,
+    pub jobs: Arc<extensions::jobs::JobManager>,
+    pub leader: Arc<leader::LeaderElection>,
     pub settings: Arc<settings::Settings>,
     pub jwt: Arc<jwt::Jwt>,
     pub storage: Arc<storage::Storage>,
     pub captcha: Arc<captcha::Captcha>,
     pub mail: Arc<mail::Mail>,
+    pub metrics: Arc<metrics::Metrics>,
     pub database: Arc<database::Database>,
     pub cache: Arc<cache::Cache>,
     pub env: Arc<env::Env>,
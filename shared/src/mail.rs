@@ -1,8 +1,211 @@
+use indexmap::IndexMap;
 use lettre::AsyncTransport;
-use std::sync::Arc;
+use serde::Serialize;
+use std::sync::{Arc, LazyLock, RwLock, RwLockReadGuard};
+use utoipa::ToSchema;
 
-pub const MAIL_PASSWORD_RESET: &str = include_str!("../mails/password_reset.html");
-pub const MAIL_ACCOUNT_CREATED: &str = include_str!("../mails/account_created.html");
+/// A mail template bundled with the panel or registered by an extension. Admins can override
+/// every template per language, the bundled version is used when no override matches.
+#[derive(Clone, Copy)]
+pub struct BundledMailTemplate {
+    pub description: &'static str,
+    pub subject: &'static str,
+    pub body: &'static str,
+    pub body_plain: &'static str,
+    /// Template specific variables and an example value for each, used when sending test mails.
+    pub variables: &'static [(&'static str, &'static str)],
+}
+
+impl BundledMailTemplate {
+    /// The example context used for test mails.
+    pub fn example_context(&self) -> serde_json::Value {
+        serde_json::Value::Object(
+            self.variables
+                .iter()
+                .map(|(name, example)| (name.to_string(), (*example).into()))
+                .collect(),
+        )
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(self, name: &'static str) -> AdminApiBundledMailTemplate {
+        AdminApiBundledMailTemplate {
+            name,
+            description: self.description,
+            subject: self.subject,
+            body: self.body,
+            body_plain: self.body_plain,
+            variables: self.variables.iter().map(|(name, _)| *name).collect(),
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminBundledMailTemplate")]
+pub struct AdminApiBundledMailTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+
+    pub subject: &'static str,
+    pub body: &'static str,
+    pub body_plain: &'static str,
+    pub variables: Vec<&'static str>,
+}
+
+pub(crate) static BASE_MAIL_TEMPLATES: LazyLock<IndexMap<&'static str, BundledMailTemplate>> =
+    LazyLock::new(|| {
+        IndexMap::from([
+            (
+                "password_reset",
+                BundledMailTemplate {
+                    description: "Sent when a user requests a password reset.",
+                    subject: "{{app_name}} - Password Reset",
+                    body: include_str!("../mails/password_reset.html"),
+                    body_plain: include_str!("../mails/password_reset.txt"),
+                    variables: &[(
+                        "reset_link",
+                        "https://panel.example.com/auth/reset-password?token=example",
+                    )],
+                },
+            ),
+            (
+                "magic_link",
+                BundledMailTemplate {
+                    description: "Sent when a user requests a passwordless login link.",
+                    subject: "{{app_name}} - Login Link",
+                    body: include_str!("../mails/magic_link.html"),
+                    body_plain: include_str!("../mails/magic_link.txt"),
+                    variables: &[
+                        (
+                            "login_link",
+                            "https://panel.example.com/auth/magic-link?token=example",
+                        ),
+                        ("expires_minutes", "15"),
+                    ],
+                },
+            ),
+            (
+                "account_created",
+                BundledMailTemplate {
+                    description: "Sent when an account is created for a user that was added as a subuser.",
+                    subject: "{{app_name}} - Account Created",
+                    body: include_str!("../mails/account_created.html"),
+                    body_plain: include_str!("../mails/account_created.txt"),
+                    variables: &[(
+                        "reset_link",
+                        "https://panel.example.com/auth/reset-password?token=example",
+                    )],
+                },
+            ),
+            (
+                "node_status",
+                BundledMailTemplate {
+                    description: "Sent to admins when a node goes offline or comes back online.",
+                    subject: "{{app_name}} - Node {{node_name}} is {{node_status}}",
+                    body: include_str!("../mails/node_status.html"),
+                    body_plain: include_str!("../mails/node_status.txt"),
+                    variables: &[
+                        ("node_name", "Node 1"),
+                        ("node_status", "offline"),
+                        ("node_details", "error sending request"),
+                        ("node_link", "https://panel.example.com/admin/nodes/example"),
+                    ],
+                },
+            ),
+            (
+                "server_notification",
+                BundledMailTemplate {
+                    description: "Sent through email notification channels when a subscribed server event happens.",
+                    subject: "{{app_name}} - {{notification_message}}",
+                    body: include_str!("../mails/server_notification.html"),
+                    body_plain: include_str!("../mails/server_notification.txt"),
+                    variables: &[
+                        ("notification_message", "Server \"Example\" has crashed."),
+                        ("server_link", "https://panel.example.com/server/example"),
+                    ],
+                },
+            ),
+        ])
+    });
+
+pub(crate) static MAIL_TEMPLATES: LazyLock<RwLock<IndexMap<&'static str, BundledMailTemplate>>> =
+    LazyLock::new(|| RwLock::new(BASE_MAIL_TEMPLATES.clone()));
+
+#[inline]
+pub fn get_mail_templates() -> RwLockReadGuard<'static, IndexMap<&'static str, BundledMailTemplate>>
+{
+    MAIL_TEMPLATES.read().unwrap()
+}
+
+pub struct RenderedMail {
+    pub subject: compact_str::CompactString,
+    pub body: String,
+    pub body_plain: String,
+}
+
+static HTML_BREAK: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"(?i)<br\s*/?>|</(p|h[1-6]|div|li|tr)>").unwrap());
+static HTML_TAG: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"<[^>]*>").unwrap());
+static BLANK_LINES: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"\n[ \t]*(\n[ \t]*)+").unwrap());
+
+/// Builds a plaintext alternative out of rendered html, used for overrides without a plaintext body.
+fn html_to_plain(html: &str) -> String {
+    let plain = HTML_BREAK.replace_all(html, "\n");
+    let plain = HTML_TAG.replace_all(&plain, "");
+    let plain = BLANK_LINES.replace_all(&plain, "\n\n");
+
+    plain
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#x2f;", "/")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Checks that a template source compiles, `name` decides the auto escaping like in [`render_template`].
+pub fn validate_template(name: &str, source: &str) -> Result<(), minijinja::Error> {
+    minijinja::Environment::new()
+        .template_from_named_str(name, source)
+        .map(|_| ())
+}
+
+/// Renders a mail, the html body is auto escaped while the subject and plaintext body are not.
+pub fn render_template(
+    subject: &str,
+    body: &str,
+    body_plain: Option<&str>,
+    context: &serde_json::Value,
+) -> Result<RenderedMail, minijinja::Error> {
+    let mut environment = minijinja::Environment::new();
+    environment.set_auto_escape_callback(|name| {
+        if name.ends_with(".html") {
+            minijinja::AutoEscape::Html
+        } else {
+            minijinja::AutoEscape::None
+        }
+    });
+
+    let subject = environment.render_named_str("subject.txt", subject, context)?;
+    let body = environment.render_named_str("body.html", body, context)?;
+    let body_plain = match body_plain {
+        Some(body_plain) => environment.render_named_str("body.txt", body_plain, context)?,
+        None => html_to_plain(&body),
+    };
+
+    Ok(RenderedMail {
+        subject: subject
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .into(),
+        body,
+        body_plain,
+    })
+}
 
 #[derive(Debug)]
 enum Transport {
@@ -26,11 +229,102 @@ enum Transport {
 
 pub struct Mail {
     settings: Arc<super::settings::Settings>,
+    database: Arc<super::database::Database>,
 }
 
 impl Mail {
-    pub fn new(settings: Arc<super::settings::Settings>) -> Self {
-        Self { settings }
+    pub fn new(
+        settings: Arc<super::settings::Settings>,
+        database: Arc<super::database::Database>,
+    ) -> Self {
+        Self { settings, database }
+    }
+
+    /// Renders a registered template for a user, using the override for the user's language, then
+    /// the override for the panel language and lastly the bundled template.
+    ///
+    /// `app_name`, `app_url` and `user` are always available to templates, `context` adds the
+    /// template specific variables.
+    pub async fn render(
+        &self,
+        name: &str,
+        user: Option<&super::models::user::User>,
+        mut context: serde_json::Value,
+    ) -> Result<RenderedMail, anyhow::Error> {
+        let template = get_mail_templates()
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("mail template {name} is not registered"))?;
+
+        let settings = self.settings.get().await;
+        let app_name = settings.app.name.clone();
+        let app_url = settings.app.url.clone();
+        let default_language = settings.app.language.clone();
+        drop(settings);
+
+        let language = user.map_or(default_language.as_str(), |user| user.language.as_str());
+        let template_override =
+            super::models::mail_template::MailTemplate::by_name_language_fallback(
+                &self.database,
+                name,
+                language,
+                &default_language,
+            )
+            .await?;
+
+        if let serde_json::Value::Object(context) = &mut context {
+            context.insert("app_name".into(), app_name.as_str().into());
+            context.insert("app_url".into(), app_url.as_str().into());
+
+            if let Some(user) = user {
+                context.insert(
+                    "user".into(),
+                    serde_json::json!({
+                        "uuid": user.uuid,
+                        "username": user.username,
+                        "email": user.email,
+                        "name_first": user.name_first,
+                        "name_last": user.name_last,
+                        "language": user.language,
+                    }),
+                );
+            }
+        }
+
+        Ok(match &template_override {
+            Some(template_override) => render_template(
+                &template_override.subject,
+                &template_override.body,
+                template_override.body_plain.as_deref(),
+                &context,
+            )?,
+            None => render_template(
+                template.subject,
+                template.body,
+                Some(template.body_plain),
+                &context,
+            )?,
+        })
+    }
+
+    /// Renders a registered template and sends it, failures are logged.
+    pub async fn send_template(
+        &self,
+        destination: compact_str::CompactString,
+        name: &str,
+        user: Option<&super::models::user::User>,
+        context: serde_json::Value,
+    ) {
+        match self.render(name, user, context).await {
+            Ok(mail) => self.send(destination, mail).await,
+            Err(err) => {
+                tracing::error!(
+                    template = name,
+                    "failed to render mail template: {:#?}",
+                    err
+                );
+            }
+        }
     }
 
     async fn get_transport(&self) -> Result<Transport, anyhow::Error> {
@@ -111,12 +405,7 @@ impl Mail {
         }
     }
 
-    pub async fn send(
-        &self,
-        destination: compact_str::CompactString,
-        subject: compact_str::CompactString,
-        body: String,
-    ) {
+    pub async fn send(&self, destination: compact_str::CompactString, mail: RenderedMail) {
         let transport = match self.get_transport().await {
             Ok(transport) => transport,
             Err(err) => {
@@ -128,7 +417,7 @@ impl Mail {
         tracing::debug!(
             transport = ?transport,
             destination = ?destination,
-            subject = ?subject,
+            subject = ?mail.subject,
             "sending email"
         );
 
@@ -144,14 +433,18 @@ impl Mail {
                         transport
                             .send(
                                 lettre::message::Message::builder()
-                                    .subject(subject)
+                                    .subject(mail.subject)
                                     .to(lettre::message::Mailbox::new(None, destination.parse()?))
                                     .from(lettre::message::Mailbox::new(
                                         from_name.map(String::from),
                                         from_address.parse()?,
                                     ))
-                                    .header(lettre::message::header::ContentType::TEXT_HTML)
-                                    .body(body)?,
+                                    .multipart(
+                                        lettre::message::MultiPart::alternative_plain_html(
+                                            mail.body_plain,
+                                            mail.body,
+                                        ),
+                                    )?,
                             )
                             .await?;
                     }
@@ -163,14 +456,18 @@ impl Mail {
                         transport
                             .send(
                                 lettre::message::Message::builder()
-                                    .subject(subject)
+                                    .subject(mail.subject)
                                     .to(lettre::message::Mailbox::new(None, destination.parse()?))
                                     .from(lettre::message::Mailbox::new(
                                         from_name.map(String::from),
                                         from_address.parse()?,
                                     ))
-                                    .header(lettre::message::header::ContentType::TEXT_HTML)
-                                    .body(body)?,
+                                    .multipart(
+                                        lettre::message::MultiPart::alternative_plain_html(
+                                            mail.body_plain,
+                                            mail.body,
+                                        ),
+                                    )?,
                             )
                             .await?;
                     }
@@ -182,14 +479,18 @@ impl Mail {
                         transport
                             .send(
                                 lettre::message::Message::builder()
-                                    .subject(subject)
+                                    .subject(mail.subject)
                                     .to(lettre::message::Mailbox::new(None, destination.parse()?))
                                     .from(lettre::message::Mailbox::new(
                                         from_name.map(String::from),
                                         from_address.parse()?,
                                     ))
-                                    .header(lettre::message::header::ContentType::TEXT_HTML)
-                                    .body(body)?,
+                                    .multipart(
+                                        lettre::message::MultiPart::alternative_plain_html(
+                                            mail.body_plain,
+                                            mail.body,
+                                        ),
+                                    )?,
                             )
                             .await?;
                     }
@@ -0,0 +1,188 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+const HTTP_DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Default)]
+struct HttpRouteMetrics {
+    requests: BTreeMap<u16, u64>,
+
+    duration_buckets: [u64; HTTP_DURATION_BUCKETS.len()],
+    duration_sum: f64,
+    duration_count: u64,
+}
+
+type HttpRouteKey = (compact_str::CompactString, compact_str::CompactString);
+
+/// Collects panel metrics and renders them in the Prometheus text exposition format.
+#[derive(Default)]
+pub struct Metrics {
+    http: Mutex<BTreeMap<HttpRouteKey, HttpRouteMetrics>>,
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl Metrics {
+    pub fn record_http_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+
+        let mut http = self.http.lock().unwrap();
+        let route_metrics = http.entry((method.into(), route.into())).or_default();
+
+        *route_metrics.requests.entry(status).or_default() += 1;
+        for (i, bucket) in HTTP_DURATION_BUCKETS.iter().enumerate() {
+            if seconds <= *bucket {
+                route_metrics.duration_buckets[i] += 1;
+            }
+        }
+        route_metrics.duration_sum += seconds;
+        route_metrics.duration_count += 1;
+    }
+
+    fn render_http(&self, output: &mut String) {
+        let http = self.http.lock().unwrap();
+
+        output.push_str("# HELP panel_http_requests_total Total HTTP requests by matched route.\n");
+        output.push_str("# TYPE panel_http_requests_total counter\n");
+        for ((method, route), route_metrics) in http.iter() {
+            for (status, count) in route_metrics.requests.iter() {
+                writeln!(
+                    output,
+                    "panel_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                    method,
+                    escape_label(route),
+                    status,
+                    count
+                )
+                .ok();
+            }
+        }
+
+        output.push_str(
+            "# HELP panel_http_request_duration_seconds HTTP request latency by matched route.\n",
+        );
+        output.push_str("# TYPE panel_http_request_duration_seconds histogram\n");
+        for ((method, route), route_metrics) in http.iter() {
+            let route = escape_label(route);
+
+            for (bucket, count) in HTTP_DURATION_BUCKETS
+                .iter()
+                .zip(route_metrics.duration_buckets.iter())
+            {
+                writeln!(
+                    output,
+                    "panel_http_request_duration_seconds_bucket{{method=\"{method}\",route=\"{route}\",le=\"{bucket}\"}} {count}",
+                )
+                .ok();
+            }
+            writeln!(
+                output,
+                "panel_http_request_duration_seconds_bucket{{method=\"{method}\",route=\"{route}\",le=\"+Inf\"}} {}",
+                route_metrics.duration_count
+            )
+            .ok();
+            writeln!(
+                output,
+                "panel_http_request_duration_seconds_sum{{method=\"{method}\",route=\"{route}\"}} {}",
+                route_metrics.duration_sum
+            )
+            .ok();
+            writeln!(
+                output,
+                "panel_http_request_duration_seconds_count{{method=\"{method}\",route=\"{route}\"}} {}",
+                route_metrics.duration_count
+            )
+            .ok();
+        }
+    }
+
+    pub async fn render(&self, state: &crate::State) -> Result<String, anyhow::Error> {
+        let mut output = String::new();
+
+        self.render_http(&mut output);
+
+        output.push_str("# HELP panel_database_connections Database pool connections.\n");
+        output.push_str("# TYPE panel_database_connections gauge\n");
+        for (pool_name, pool) in state.database.pools() {
+            let size = pool.size() as usize;
+            let idle = pool.num_idle();
+
+            writeln!(
+                output,
+                "panel_database_connections{{pool=\"{pool_name}\",state=\"idle\"}} {idle}"
+            )?;
+            writeln!(
+                output,
+                "panel_database_connections{{pool=\"{pool_name}\",state=\"active\"}} {}",
+                size.saturating_sub(idle)
+            )?;
+        }
+
+        output.push_str("# HELP panel_cache_requests_total Cache lookups by result.\n");
+        output.push_str("# TYPE panel_cache_requests_total counter\n");
+        writeln!(
+            output,
+            "panel_cache_requests_total{{result=\"hit\"}} {}",
+            state.cache.hits()
+        )?;
+        writeln!(
+            output,
+            "panel_cache_requests_total{{result=\"miss\"}} {}",
+            state.cache.misses()
+        )?;
+
+        let tasks = state.background_tasks.get_tasks().await;
+        output.push_str("# HELP panel_background_task_runs_total Background task loop runs.\n");
+        output.push_str("# TYPE panel_background_task_runs_total counter\n");
+        for task in tasks.values() {
+            writeln!(
+                output,
+                "panel_background_task_runs_total{{task=\"{}\"}} {}",
+                task.name, task.runs
+            )?;
+        }
+        output.push_str(
+            "# HELP panel_background_task_failures_total Background task loop runs that failed.\n",
+        );
+        output.push_str("# TYPE panel_background_task_failures_total counter\n");
+        for task in tasks.values() {
+            writeln!(
+                output,
+                "panel_background_task_failures_total{{task=\"{}\"}} {}",
+                task.name, task.failures
+            )?;
+        }
+        drop(tasks);
+
+        let (users, servers, nodes, backups): (i64, i64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM users),
+                (SELECT COUNT(*) FROM servers),
+                (SELECT COUNT(*) FROM nodes),
+                (SELECT COUNT(*) FROM server_backups WHERE server_backups.completed IS NOT NULL AND server_backups.deleted IS NULL)
+            "#,
+        )
+        .fetch_one(state.database.read())
+        .await?;
+
+        for (name, help, value) in [
+            ("panel_users", "Total users.", users),
+            ("panel_servers", "Total servers.", servers),
+            ("panel_nodes", "Total nodes.", nodes),
+            ("panel_backups", "Total completed backups.", backups),
+        ] {
+            writeln!(output, "# HELP {name} {help}")?;
+            writeln!(output, "# TYPE {name} gauge")?;
+            writeln!(output, "{name} {value}")?;
+        }
+
+        Ok(output)
+    }
+}
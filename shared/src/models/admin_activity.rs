@@ -114,6 +114,14 @@ impl AdminActivity {
         ip: Option<sqlx::types::ipnetwork::IpNetwork>,
         data: serde_json::Value,
     ) -> Result<(), crate::database::DatabaseError> {
+        let payload = serde_json::json!({
+            "user_uuid": user_uuid,
+            "api_key_uuid": api_key_uuid,
+            "ip": ip.map(|ip| ip.ip()),
+            "data": &data,
+            "created": chrono::Utc::now(),
+        });
+
         sqlx::query(
             r#"
             INSERT INTO admin_activities (user_uuid, api_key_uuid, event, ip, data)
@@ -128,6 +136,17 @@ impl AdminActivity {
         .execute(database.write())
         .await?;
 
+        if let Err(err) = super::webhook_delivery::WebhookDelivery::enqueue(
+            database,
+            super::webhook_delivery::WebhookEventScope::Admin,
+            event,
+            payload,
+        )
+        .await
+        {
+            tracing::warn!(event, "failed to queue webhook deliveries: {:#?}", err);
+        }
+
         Ok(())
     }
 
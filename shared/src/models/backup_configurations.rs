@@ -1,12 +1,14 @@
 use crate::prelude::*;
+use chrono::Datelike;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use sqlx::{Row, postgres::PgRow};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashSet},
     sync::{Arc, LazyLock},
 };
 use utoipa::ToSchema;
+use validator::Validate;
 
 #[derive(ToSchema, Serialize, Deserialize, Clone)]
 pub struct BackupConfigsS3 {
@@ -165,6 +167,95 @@ impl BackupConfigs {
     }
 }
 
+#[derive(ToSchema, Validate, Serialize, Deserialize, Default, Clone, Copy)]
+pub struct BackupRetentionPolicy {
+    /// Always keep the newest N backups of a server.
+    #[validate(range(min = 1, max = 1000))]
+    #[schema(minimum = 1, maximum = 1000)]
+    #[serde(default)]
+    pub keep_last: Option<u32>,
+    /// Keep the newest backup of every day for the past D days.
+    #[validate(range(min = 1, max = 3650))]
+    #[schema(minimum = 1, maximum = 3650)]
+    #[serde(default)]
+    pub keep_daily_days: Option<u32>,
+    /// Keep the newest backup of every week for the past W weeks.
+    #[validate(range(min = 1, max = 520))]
+    #[schema(minimum = 1, maximum = 520)]
+    #[serde(default)]
+    pub keep_weekly_weeks: Option<u32>,
+}
+
+impl BackupRetentionPolicy {
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.keep_last.is_some()
+            || self.keep_daily_days.is_some()
+            || self.keep_weekly_weeks.is_some()
+    }
+
+    /// Returns the backups that are no longer covered by this policy.
+    ///
+    /// `backups` must belong to a single server and be sorted newest first. Locked and
+    /// unfinished backups are never returned, but still count towards their day and week.
+    /// Failed backups never count towards the policy and are always returned unless locked.
+    pub fn expired<'a>(
+        &self,
+        backups: &'a [super::server_backup::ServerBackup],
+        now: chrono::NaiveDateTime,
+    ) -> Vec<&'a super::server_backup::ServerBackup> {
+        if !self.is_enabled() {
+            return Vec::new();
+        }
+
+        let daily_cutoff = self
+            .keep_daily_days
+            .map(|days| (now - chrono::Duration::days(days as i64)).date());
+        let weekly_cutoff = self
+            .keep_weekly_weeks
+            .map(|weeks| now - chrono::Duration::weeks(weeks as i64));
+
+        let mut kept_days = HashSet::new();
+        let mut kept_weeks = HashSet::new();
+        let mut expired = Vec::new();
+
+        let mut counted = 0;
+        for backup in backups {
+            if backup.completed.is_some() && !backup.successful {
+                if !backup.locked {
+                    expired.push(backup);
+                }
+
+                continue;
+            }
+
+            let i = counted;
+            counted += 1;
+
+            let day = backup.created.date();
+            let week = backup.created.iso_week();
+
+            let keep_last = self
+                .keep_last
+                .is_some_and(|keep_last| i < keep_last as usize);
+            let keep_daily =
+                daily_cutoff.is_some_and(|cutoff| day > cutoff) && kept_days.insert(day);
+            let keep_weekly = weekly_cutoff.is_some_and(|cutoff| backup.created > cutoff)
+                && kept_weeks.insert((week.year(), week.week()));
+
+            if backup.locked || backup.completed.is_none() {
+                continue;
+            }
+
+            if !keep_last && !keep_daily && !keep_weekly {
+                expired.push(backup);
+            }
+        }
+
+        expired
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct BackupConfiguration {
     pub uuid: uuid::Uuid,
@@ -174,6 +265,7 @@ pub struct BackupConfiguration {
 
     pub backup_disk: super::server_backup::BackupDisk,
     pub backup_configs: BackupConfigs,
+    pub retention_policy: BackupRetentionPolicy,
 
     pub created: chrono::NaiveDateTime,
 }
@@ -206,6 +298,10 @@ impl BaseModel for BackupConfiguration {
                 "backup_configurations.backup_configs",
                 compact_str::format_compact!("{prefix}backup_configs"),
             ),
+            (
+                "backup_configurations.retention_policy",
+                compact_str::format_compact!("{prefix}retention_policy"),
+            ),
             (
                 "backup_configurations.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -228,6 +324,10 @@ impl BaseModel for BackupConfiguration {
                 row.get(compact_str::format_compact!("{prefix}backup_configs").as_str()),
             )
             .unwrap_or_default(),
+            retention_policy: serde_json::from_value(
+                row.get(compact_str::format_compact!("{prefix}retention_policy").as_str()),
+            )
+            .unwrap_or_default(),
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
         })
     }
@@ -240,13 +340,14 @@ impl BackupConfiguration {
         description: Option<&str>,
         backup_disk: super::server_backup::BackupDisk,
         mut backup_configs: BackupConfigs,
+        retention_policy: BackupRetentionPolicy,
     ) -> Result<Self, crate::database::DatabaseError> {
         backup_configs.encrypt(database).await?;
 
         let row = sqlx::query(&format!(
             r#"
-            INSERT INTO backup_configurations (name, description, backup_disk, backup_configs)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO backup_configurations (name, description, backup_disk, backup_configs, retention_policy)
+            VALUES ($1, $2, $3, $4, $5)
             RETURNING {}
             "#,
             Self::columns_sql(None)
@@ -255,6 +356,7 @@ impl BackupConfiguration {
         .bind(description)
         .bind(backup_disk)
         .bind(serde_json::to_value(backup_configs)?)
+        .bind(serde_json::to_value(retention_policy)?)
         .fetch_one(database.write())
         .await?;
 
@@ -298,6 +400,26 @@ impl BackupConfiguration {
         })
     }
 
+    /// All configurations with at least one retention rule, unset rules are stored as `null`.
+    pub async fn all_with_retention_policy(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM backup_configurations
+            WHERE jsonb_strip_nulls(backup_configurations.retention_policy) != '{{}}'::jsonb
+            "#,
+            Self::columns_sql(None)
+        ))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
     #[inline]
     pub async fn into_admin_api_object(
         mut self,
@@ -311,6 +433,7 @@ impl BackupConfiguration {
             description: self.description,
             backup_disk: self.backup_disk,
             backup_configs: self.backup_configs,
+            retention_policy: self.retention_policy,
             created: self.created.and_utc(),
         })
     }
@@ -385,6 +508,124 @@ pub struct AdminApiBackupConfiguration {
 
     pub backup_disk: super::server_backup::BackupDisk,
     pub backup_configs: BackupConfigs,
+    pub retention_policy: BackupRetentionPolicy,
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        node::Node,
+        server_backup::{BackupDisk, ServerBackup},
+    };
+
+    fn now() -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2024, 6, 15)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    fn backup(name: &str, hours_ago: i64, successful: bool) -> ServerBackup {
+        let created = now() - chrono::Duration::hours(hours_ago);
+
+        ServerBackup {
+            uuid: uuid::Uuid::new_v4(),
+            server: None,
+            node: Node::get_fetchable(uuid::Uuid::nil()),
+            backup_configuration: None,
+            name: name.into(),
+            successful,
+            browsable: false,
+            streaming: false,
+            locked: false,
+            ignored_files: Vec::new(),
+            checksum: None,
+            bytes: 0,
+            files: 0,
+            disk: BackupDisk::Local,
+            upload_id: None,
+            upload_path: None,
+            completed: Some(created),
+            deleted: None,
+            created,
+        }
+    }
+
+    fn expired_names(policy: BackupRetentionPolicy, backups: &[ServerBackup]) -> Vec<&str> {
+        policy
+            .expired(backups, now())
+            .into_iter()
+            .map(|backup| backup.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn failed_backups_do_not_take_keep_last_slots() {
+        let backups = [
+            backup("failed-1", 1, false),
+            backup("good-1", 2, true),
+            backup("failed-2", 3, false),
+            backup("good-2", 4, true),
+            backup("good-3", 5, true),
+        ];
+
+        let policy = BackupRetentionPolicy {
+            keep_last: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            expired_names(policy, &backups),
+            ["failed-1", "failed-2", "good-3"]
+        );
+    }
+
+    #[test]
+    fn failed_backups_do_not_take_daily_and_weekly_slots() {
+        let backups = [
+            backup("failed-today", 1, false),
+            backup("good-today", 2, true),
+            backup("failed-yesterday", 25, false),
+            backup("good-yesterday", 26, true),
+            backup("good-last-week", 24 * 8, true),
+            backup("failed-last-week", 24 * 8 + 1, false),
+            backup("good-last-week-older", 24 * 8 + 2, true),
+        ];
+
+        let policy = BackupRetentionPolicy {
+            keep_daily_days: Some(3),
+            keep_weekly_weeks: Some(4),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            expired_names(policy, &backups),
+            [
+                "failed-today",
+                "failed-yesterday",
+                "failed-last-week",
+                "good-last-week-older",
+            ]
+        );
+    }
+
+    #[test]
+    fn locked_and_unfinished_backups_are_kept() {
+        let mut locked = backup("failed-locked", 1, false);
+        locked.locked = true;
+        let mut running = backup("running", 2, false);
+        running.completed = None;
+
+        let backups = [locked, running, backup("good", 3, true)];
+
+        let policy = BackupRetentionPolicy {
+            keep_last: Some(2),
+            ..Default::default()
+        };
+
+        assert!(expired_names(policy, &backups).is_empty());
+    }
+}
@@ -1,13 +1,91 @@
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, postgres::PgRow};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
 use std::{
-    collections::BTreeMap,
-    path::PathBuf,
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
     sync::{Arc, LazyLock},
 };
 use utoipa::ToSchema;
 
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "egg_repository_credentials_type",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum EggRepositoryCredentialsType {
+    Token,
+    SshKey,
+}
+
+/// The credentials used to clone a private repository, stored encrypted.
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+pub enum EggRepositoryCredentials {
+    /// An HTTPS username and access token, the username defaults to the one in the url or `git`.
+    Token {
+        username: Option<compact_str::CompactString>,
+        token: compact_str::CompactString,
+    },
+    /// An SSH deploy key in OpenSSH or PEM format.
+    SshKey {
+        private_key: String,
+        passphrase: Option<compact_str::CompactString>,
+    },
+}
+
+impl EggRepositoryCredentials {
+    #[inline]
+    pub fn credentials_type(&self) -> EggRepositoryCredentialsType {
+        match self {
+            Self::Token { .. } => EggRepositoryCredentialsType::Token,
+            Self::SshKey { .. } => EggRepositoryCredentialsType::SshKey,
+        }
+    }
+
+    fn to_cred(&self, username_from_url: Option<&str>) -> Result<git2::Cred, git2::Error> {
+        match self {
+            Self::Token { username, token } => git2::Cred::userpass_plaintext(
+                username.as_deref().or(username_from_url).unwrap_or("git"),
+                token,
+            ),
+            Self::SshKey {
+                private_key,
+                passphrase,
+            } => git2::Cred::ssh_key_from_memory(
+                username_from_url.unwrap_or("git"),
+                None,
+                private_key,
+                passphrase.as_deref(),
+            ),
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct EggRepositorySyncFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// The outcome of the last sync, paths are relative to the repository root.
+#[derive(ToSchema, Serialize, Deserialize, Clone, Default)]
+pub struct EggRepositorySyncReport {
+    pub found: usize,
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+    #[schema(inline)]
+    pub failed: Vec<EggRepositorySyncFailure>,
+    /// Installed eggs that were updated automatically following their update policy.
+    #[serde(default)]
+    pub updated_eggs: Vec<uuid::Uuid>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct EggRepository {
     pub uuid: uuid::Uuid,
@@ -15,7 +93,16 @@ pub struct EggRepository {
     pub name: compact_str::CompactString,
     pub description: Option<compact_str::CompactString>,
     pub git_repository: compact_str::CompactString,
+    /// The branch, tag or commit to sync, the default branch if `None`.
+    pub git_reference: Option<compact_str::CompactString>,
+    /// The directory eggs are searched in, the repository root if `None`.
+    pub git_subdirectory: Option<compact_str::CompactString>,
+
+    pub credentials_type: Option<EggRepositoryCredentialsType>,
+    pub credentials: Option<Vec<u8>>,
 
+    pub sync_schedule: Option<cron::Schedule>,
+    pub last_sync_report: Option<EggRepositorySyncReport>,
     pub last_synced: Option<chrono::NaiveDateTime>,
     pub created: chrono::NaiveDateTime,
 }
@@ -44,6 +131,30 @@ impl BaseModel for EggRepository {
                 "egg_repositories.git_repository",
                 compact_str::format_compact!("{prefix}git_repository"),
             ),
+            (
+                "egg_repositories.git_reference",
+                compact_str::format_compact!("{prefix}git_reference"),
+            ),
+            (
+                "egg_repositories.git_subdirectory",
+                compact_str::format_compact!("{prefix}git_subdirectory"),
+            ),
+            (
+                "egg_repositories.credentials_type",
+                compact_str::format_compact!("{prefix}credentials_type"),
+            ),
+            (
+                "egg_repositories.credentials",
+                compact_str::format_compact!("{prefix}credentials"),
+            ),
+            (
+                "egg_repositories.sync_schedule",
+                compact_str::format_compact!("{prefix}sync_schedule"),
+            ),
+            (
+                "egg_repositories.last_sync_report",
+                compact_str::format_compact!("{prefix}last_sync_report"),
+            ),
             (
                 "egg_repositories.last_synced",
                 compact_str::format_compact!("{prefix}last_synced"),
@@ -66,6 +177,25 @@ impl BaseModel for EggRepository {
                 .try_get(compact_str::format_compact!("{prefix}description").as_str())?,
             git_repository: row
                 .try_get(compact_str::format_compact!("{prefix}git_repository").as_str())?,
+            git_reference: row
+                .try_get(compact_str::format_compact!("{prefix}git_reference").as_str())?,
+            git_subdirectory: row
+                .try_get(compact_str::format_compact!("{prefix}git_subdirectory").as_str())?,
+            credentials_type: row
+                .try_get(compact_str::format_compact!("{prefix}credentials_type").as_str())?,
+            credentials: row
+                .try_get(compact_str::format_compact!("{prefix}credentials").as_str())?,
+            sync_schedule: row
+                .try_get::<Option<String>, _>(
+                    compact_str::format_compact!("{prefix}sync_schedule").as_str(),
+                )?
+                .and_then(|s| cron::Schedule::from_str(&s).ok()),
+            last_sync_report: row
+                .try_get::<Option<serde_json::Value>, _>(
+                    compact_str::format_compact!("{prefix}last_sync_report").as_str(),
+                )?
+                .map(serde_json::from_value)
+                .transpose()?,
             last_synced: row
                 .try_get(compact_str::format_compact!("{prefix}last_synced").as_str())?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
@@ -79,11 +209,27 @@ impl EggRepository {
         name: &str,
         description: Option<&str>,
         git_repository: &str,
+        git_reference: Option<&str>,
+        git_subdirectory: Option<&str>,
+        credentials: Option<&EggRepositoryCredentials>,
+        sync_schedule: Option<&cron::Schedule>,
     ) -> Result<Self, crate::database::DatabaseError> {
+        let encrypted_credentials = match credentials {
+            Some(credentials) => Some(
+                database
+                    .encrypt(serde_json::to_string(credentials)?)
+                    .await?,
+            ),
+            None => None,
+        };
+
         let row = sqlx::query(&format!(
             r#"
-            INSERT INTO egg_repositories (name, description, git_repository)
-            VALUES ($1, $2, $3)
+            INSERT INTO egg_repositories (
+                name, description, git_repository, git_reference, git_subdirectory,
+                credentials_type, credentials, sync_schedule
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING {}
             "#,
             Self::columns_sql(None)
@@ -91,12 +237,36 @@ impl EggRepository {
         .bind(name)
         .bind(description)
         .bind(git_repository)
+        .bind(git_reference)
+        .bind(git_subdirectory)
+        .bind(credentials.map(|credentials| credentials.credentials_type()))
+        .bind(encrypted_credentials)
+        .bind(sync_schedule.map(|schedule| schedule.to_string()))
         .fetch_one(database.write())
         .await?;
 
         Self::map(None, &row)
     }
 
+    pub async fn all_with_sync_schedule(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM egg_repositories
+            WHERE egg_repositories.sync_schedule IS NOT NULL
+            "#,
+            Self::columns_sql(None)
+        ))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
     pub async fn all_with_pagination(
         database: &crate::database::Database,
         page: i64,
@@ -134,55 +304,128 @@ impl EggRepository {
         })
     }
 
-    pub async fn sync(&self, database: &crate::database::Database) -> Result<usize, anyhow::Error> {
-        let git_repository = self.git_repository.clone();
+    pub async fn decrypt_credentials(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<Option<EggRepositoryCredentials>, anyhow::Error> {
+        match &self.credentials {
+            Some(credentials) => Ok(Some(serde_json::from_str(
+                &database.decrypt(credentials.clone()).await?,
+            )?)),
+            None => Ok(None),
+        }
+    }
 
-        let exported_eggs = tokio::task::spawn_blocking(
-            move || -> Result<Vec<(PathBuf, super::nest_egg::ExportedNestEgg)>, anyhow::Error> {
+    /// Clones the repository at the configured reference and replaces its known eggs with the ones found in it.
+    ///
+    /// Files that can not be parsed are listed in the returned report instead of failing the sync,
+    /// the report is also stored as [`Self::last_sync_report`].
+    pub async fn sync(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<EggRepositorySyncReport, anyhow::Error> {
+        let git_repository = self.git_repository.clone();
+        let git_reference = self.git_reference.clone();
+        let git_subdirectory = self.git_subdirectory.clone();
+        let credentials = self.decrypt_credentials(database).await?;
+
+        let (exported_eggs, failed) = tokio::task::spawn_blocking(
+            move || -> Result<
+                (
+                    Vec<(PathBuf, super::nest_egg::ExportedNestEgg)>,
+                    Vec<EggRepositorySyncFailure>,
+                ),
+                anyhow::Error,
+            > {
                 let mut exported_eggs = Vec::new();
+                let mut failed = Vec::new();
                 let temp_dir = tempfile::tempdir()?;
-                let filesystem = crate::cap::CapFilesystem::new(temp_dir.path().to_path_buf())?;
 
-                git2::Repository::clone(&git_repository, temp_dir.path())?;
+                let mut callbacks = git2::RemoteCallbacks::new();
+                if let Some(credentials) = credentials {
+                    let mut attempted = false;
+
+                    callbacks.credentials(move |_url, username_from_url, _allowed_types| {
+                        // libgit2 keeps asking for credentials as long as authentication fails
+                        if attempted {
+                            return Err(git2::Error::from_str(
+                                "authentication with the configured credentials failed",
+                            ));
+                        }
+                        attempted = true;
+
+                        credentials.to_cred(username_from_url)
+                    });
+                }
+
+                let mut fetch_options = git2::FetchOptions::new();
+                fetch_options.remote_callbacks(callbacks);
+
+                let repository = git2::build::RepoBuilder::new()
+                    .fetch_options(fetch_options)
+                    .clone(&git_repository, temp_dir.path())?;
+
+                if let Some(git_reference) = git_reference {
+                    let object = repository
+                        .revparse_single(&format!("origin/{git_reference}"))
+                        .or_else(|_| repository.revparse_single(&git_reference))?;
+                    let commit = object.peel_to_commit()?;
 
-                let mut walker = filesystem.walk_dir(".")?;
+                    repository.checkout_tree(
+                        commit.as_object(),
+                        Some(git2::build::CheckoutBuilder::new().force()),
+                    )?;
+                    repository.set_head_detached(commit.id())?;
+                }
+
+                let filesystem = crate::cap::CapFilesystem::new(temp_dir.path().to_path_buf())?;
+
+                let mut walker =
+                    filesystem.walk_dir(git_subdirectory.as_deref().map_or(Path::new("."), Path::new))?;
                 while let Some(Ok((is_dir, entry))) = walker.next_entry() {
                     if is_dir {
                         continue;
                     }
 
-                    if !matches!(
-                        entry.extension().and_then(|s| s.to_str()),
-                        Some("json") | Some("yaml")
-                    ) {
+                    let extension = entry.extension().and_then(|s| s.to_str());
+                    if !matches!(extension, Some("json") | Some("yaml")) {
                         continue;
                     }
 
-                    let file_content = match filesystem.read_to_string(&entry) {
-                        Ok(content) => content,
-                        Err(_) => continue,
-                    };
-                    let exported_egg: super::nest_egg::ExportedNestEgg =
-                        if entry.extension().and_then(|s| s.to_str()) == Some("json") {
-                            match serde_json::from_str(&file_content) {
-                                Ok(egg) => egg,
-                                Err(_) => continue,
+                    let exported_egg = match filesystem.read_to_string(&entry) {
+                        Ok(content) => {
+                            if extension == Some("json") {
+                                serde_json::from_str(&content).map_err(anyhow::Error::from)
+                            } else {
+                                serde_yml::from_str(&content).map_err(anyhow::Error::from)
                             }
-                        } else {
-                            match serde_yml::from_str(&file_content) {
-                                Ok(egg) => egg,
-                                Err(_) => continue,
-                            }
-                        };
+                        }
+                        Err(err) => Err(err),
+                    };
 
-                    exported_eggs.push((entry, exported_egg));
+                    match exported_egg {
+                        Ok(exported_egg) => exported_eggs.push((entry, exported_egg)),
+                        Err(err) => failed.push(EggRepositorySyncFailure {
+                            path: entry.to_string_lossy().to_string(),
+                            error: err.to_string(),
+                        }),
+                    }
                 }
 
-                Ok(exported_eggs)
+                Ok((exported_eggs, failed))
             },
         )
         .await??;
 
+        let mut existing_eggs =
+            super::egg_repository_egg::EggRepositoryEgg::all_by_egg_repository_uuid(
+                database, self.uuid,
+            )
+            .await?
+            .into_iter()
+            .map(|egg| (egg.path.clone(), egg))
+            .collect::<HashMap<_, _>>();
+
         super::egg_repository_egg::EggRepositoryEgg::delete_unused(
             database,
             self.uuid,
@@ -193,13 +436,33 @@ impl EggRepository {
         )
         .await?;
 
-        let exported_eggs_len = exported_eggs.len();
+        let mut report = EggRepositorySyncReport {
+            found: exported_eggs.len(),
+            failed,
+            ..Default::default()
+        };
+
+        for (path, mut exported_egg) in exported_eggs {
+            let path = path.to_string_lossy().to_string();
+
+            match existing_eggs.remove(&path) {
+                Some(existing_egg) => {
+                    // eggs without an uuid get a random one on every parse, keep the known one
+                    exported_egg.uuid = existing_egg.exported_egg.uuid;
+
+                    if serde_json::to_value(&existing_egg.exported_egg)?
+                        != serde_json::to_value(&exported_egg)?
+                    {
+                        report.changed.push(path.clone());
+                    }
+                }
+                None => report.added.push(path.clone()),
+            }
 
-        for (path, exported_egg) in exported_eggs {
             super::egg_repository_egg::EggRepositoryEgg::create(
                 database,
                 self.uuid,
-                &path.to_string_lossy(),
+                &path,
                 &exported_egg.name,
                 exported_egg.description.as_deref(),
                 &exported_egg.author,
@@ -208,18 +471,25 @@ impl EggRepository {
             .await?;
         }
 
+        report.removed = existing_eggs.into_keys().collect();
+        report.removed.sort();
+
+        report.updated_eggs =
+            super::nest_egg::NestEgg::apply_repository_updates(database, self.uuid).await?;
+
         sqlx::query(
             r#"
             UPDATE egg_repositories
-            SET last_synced = NOW()
+            SET last_synced = NOW(), last_sync_report = $2
             WHERE egg_repositories.uuid = $1
             "#,
         )
         .bind(self.uuid)
+        .bind(serde_json::to_value(&report)?)
         .execute(database.write())
         .await?;
 
-        Ok(exported_eggs_len)
+        Ok(report)
     }
 
     #[inline]
@@ -229,6 +499,13 @@ impl EggRepository {
             name: self.name,
             description: self.description,
             git_repository: self.git_repository,
+            git_reference: self.git_reference,
+            git_subdirectory: self.git_subdirectory,
+            credentials_type: self.credentials_type,
+            sync_schedule: self
+                .sync_schedule
+                .map(|schedule| schedule.to_string().into()),
+            last_sync_report: self.last_sync_report,
             last_synced: self.last_synced.map(|dt| dt.and_utc()),
             created: self.created.and_utc(),
         }
@@ -294,6 +571,39 @@ impl DeletableModel for EggRepository {
     }
 }
 
+/// Syncs an egg repository in the background, queued when a repository is created and on its sync schedule.
+#[derive(Serialize, Deserialize)]
+pub struct EggRepositorySyncJob {
+    pub egg_repository_uuid: uuid::Uuid,
+}
+
+#[async_trait::async_trait]
+impl crate::extensions::jobs::JobType for EggRepositorySyncJob {
+    const NAME: &'static str = "egg_repository.sync";
+
+    async fn run(self, state: crate::State, _job: super::job::Job) -> Result<(), anyhow::Error> {
+        let Some(egg_repository) =
+            EggRepository::by_uuid_optional(&state.database, self.egg_repository_uuid).await?
+        else {
+            return Ok(());
+        };
+
+        let report = egg_repository.sync(&state.database).await?;
+        tracing::info!(
+            egg_repository = %egg_repository.uuid,
+            "synced {} eggs from egg repository ({} added, {} changed, {} removed, {} failed, {} installed eggs updated)",
+            report.found,
+            report.added.len(),
+            report.changed.len(),
+            report.removed.len(),
+            report.failed.len(),
+            report.updated_eggs.len(),
+        );
+
+        Ok(())
+    }
+}
+
 #[derive(ToSchema, Serialize)]
 #[schema(title = "EggRepository")]
 pub struct AdminApiEggRepository {
@@ -302,7 +612,14 @@ pub struct AdminApiEggRepository {
     pub name: compact_str::CompactString,
     pub description: Option<compact_str::CompactString>,
     pub git_repository: compact_str::CompactString,
+    pub git_reference: Option<compact_str::CompactString>,
+    pub git_subdirectory: Option<compact_str::CompactString>,
+
+    pub credentials_type: Option<EggRepositoryCredentialsType>,
 
+    pub sync_schedule: Option<compact_str::CompactString>,
+    #[schema(inline)]
+    pub last_sync_report: Option<EggRepositorySyncReport>,
     pub last_synced: Option<chrono::DateTime<chrono::Utc>>,
     pub created: chrono::DateTime<chrono::Utc>,
 }
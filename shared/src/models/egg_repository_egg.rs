@@ -18,6 +18,8 @@ pub struct EggRepositoryEgg {
     pub author: compact_str::CompactString,
 
     pub exported_egg: super::nest_egg::ExportedNestEgg,
+    /// When the exported egg last changed upstream, compared against [`super::nest_egg::NestEgg::egg_repository_egg_applied`].
+    pub updated: chrono::NaiveDateTime,
 }
 
 impl BaseModel for EggRepositoryEgg {
@@ -56,6 +58,10 @@ impl BaseModel for EggRepositoryEgg {
                 "egg_repository_eggs.exported_egg",
                 compact_str::format_compact!("{prefix}exported_egg"),
             ),
+            (
+                "egg_repository_eggs.updated",
+                compact_str::format_compact!("{prefix}updated"),
+            ),
         ])
     }
 
@@ -76,6 +82,7 @@ impl BaseModel for EggRepositoryEgg {
             exported_egg: serde_json::from_value(
                 row.try_get(compact_str::format_compact!("{prefix}exported_egg").as_str())?,
             )?,
+            updated: row.try_get(compact_str::format_compact!("{prefix}updated").as_str())?,
         })
     }
 }
@@ -98,7 +105,11 @@ impl EggRepositoryEgg {
                 name = EXCLUDED.name,
                 description = EXCLUDED.description,
                 author = EXCLUDED.author,
-                exported_egg = EXCLUDED.exported_egg
+                exported_egg = EXCLUDED.exported_egg,
+                updated = CASE
+                    WHEN egg_repository_eggs.exported_egg IS DISTINCT FROM EXCLUDED.exported_egg THEN NOW()
+                    ELSE egg_repository_eggs.updated
+                END
             RETURNING {}
             "#,
             Self::columns_sql(None)
@@ -115,6 +126,27 @@ impl EggRepositoryEgg {
         Self::map(None, &row)
     }
 
+    pub async fn all_by_egg_repository_uuid(
+        database: &crate::database::Database,
+        egg_repository_uuid: uuid::Uuid,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM egg_repository_eggs
+            WHERE egg_repository_eggs.egg_repository_uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(egg_repository_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
     pub async fn by_egg_repository_uuid_uuid(
         database: &crate::database::Database,
         egg_repository_uuid: uuid::Uuid,
@@ -206,6 +238,7 @@ impl EggRepositoryEgg {
             description: self.description,
             author: self.author,
             exported_egg: self.exported_egg,
+            updated: self.updated.and_utc(),
         }
     }
 
@@ -226,6 +259,7 @@ impl EggRepositoryEgg {
             description: self.description,
             author: self.author,
             exported_egg: self.exported_egg,
+            updated: self.updated.and_utc(),
         })
     }
 }
@@ -300,6 +334,7 @@ pub struct AdminApiEggRepositoryEgg {
     pub author: compact_str::CompactString,
 
     pub exported_egg: super::nest_egg::ExportedNestEgg,
+    pub updated: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(ToSchema, Serialize)]
@@ -314,4 +349,5 @@ pub struct AdminApiEggEggRepositoryEgg {
     pub author: compact_str::CompactString,
 
     pub exported_egg: super::nest_egg::ExportedNestEgg,
+    pub updated: chrono::DateTime<chrono::Utc>,
 }
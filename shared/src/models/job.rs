@@ -0,0 +1,457 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(type_name = "job_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Job {
+    pub uuid: uuid::Uuid,
+    pub name: compact_str::CompactString,
+    pub payload: serde_json::Value,
+
+    pub status: JobStatus,
+    pub priority: i16,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub error: Option<String>,
+
+    pub locked_by: Option<uuid::Uuid>,
+    pub locked_until: Option<chrono::NaiveDateTime>,
+
+    pub run_at: chrono::NaiveDateTime,
+    pub started: Option<chrono::NaiveDateTime>,
+    pub finished: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for Job {
+    const NAME: &'static str = "job";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            ("jobs.uuid", compact_str::format_compact!("{prefix}uuid")),
+            ("jobs.name", compact_str::format_compact!("{prefix}name")),
+            (
+                "jobs.payload",
+                compact_str::format_compact!("{prefix}payload"),
+            ),
+            (
+                "jobs.status",
+                compact_str::format_compact!("{prefix}status"),
+            ),
+            (
+                "jobs.priority",
+                compact_str::format_compact!("{prefix}priority"),
+            ),
+            (
+                "jobs.attempts",
+                compact_str::format_compact!("{prefix}attempts"),
+            ),
+            (
+                "jobs.max_attempts",
+                compact_str::format_compact!("{prefix}max_attempts"),
+            ),
+            ("jobs.error", compact_str::format_compact!("{prefix}error")),
+            (
+                "jobs.locked_by",
+                compact_str::format_compact!("{prefix}locked_by"),
+            ),
+            (
+                "jobs.locked_until",
+                compact_str::format_compact!("{prefix}locked_until"),
+            ),
+            (
+                "jobs.run_at",
+                compact_str::format_compact!("{prefix}run_at"),
+            ),
+            (
+                "jobs.started",
+                compact_str::format_compact!("{prefix}started"),
+            ),
+            (
+                "jobs.finished",
+                compact_str::format_compact!("{prefix}finished"),
+            ),
+            (
+                "jobs.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            payload: row.try_get(compact_str::format_compact!("{prefix}payload").as_str())?,
+            status: row.try_get(compact_str::format_compact!("{prefix}status").as_str())?,
+            priority: row.try_get(compact_str::format_compact!("{prefix}priority").as_str())?,
+            attempts: row.try_get(compact_str::format_compact!("{prefix}attempts").as_str())?,
+            max_attempts: row
+                .try_get(compact_str::format_compact!("{prefix}max_attempts").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            locked_by: row.try_get(compact_str::format_compact!("{prefix}locked_by").as_str())?,
+            locked_until: row
+                .try_get(compact_str::format_compact!("{prefix}locked_until").as_str())?,
+            run_at: row.try_get(compact_str::format_compact!("{prefix}run_at").as_str())?,
+            started: row.try_get(compact_str::format_compact!("{prefix}started").as_str())?,
+            finished: row.try_get(compact_str::format_compact!("{prefix}finished").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl Job {
+    pub async fn create(
+        database: &crate::database::Database,
+        name: &str,
+        payload: serde_json::Value,
+        priority: i16,
+        max_attempts: i32,
+        run_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO jobs (name, payload, priority, max_attempts, run_at)
+            VALUES ($1, $2, $3, $4, COALESCE($5, NOW()))
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(name)
+        .bind(payload)
+        .bind(priority)
+        .bind(max_attempts)
+        .bind(run_at)
+        .fetch_one(database.write())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    pub async fn all_with_pagination(
+        database: &crate::database::Database,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+        status: Option<JobStatus>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM jobs
+            WHERE ($1 IS NULL OR jobs.name ILIKE '%' || $1 || '%')
+                AND ($2 IS NULL OR jobs.status = $2)
+            ORDER BY jobs.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(search)
+        .bind(status)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    /// Leases up to `limit` due jobs of the given names to `instance`, highest priority first.
+    ///
+    /// Jobs whose lease expired without being finished, for example because the panel instance running
+    /// them went away, are claimed again. Rows locked by concurrent claims are skipped,
+    /// so several panel instances can safely claim from the same table.
+    pub async fn claim(
+        database: &crate::database::Database,
+        instance: uuid::Uuid,
+        names: &[&str],
+        limit: i64,
+        lease: std::time::Duration,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            UPDATE jobs
+            SET
+                status = 'RUNNING',
+                attempts = jobs.attempts + 1,
+                locked_by = $1,
+                locked_until = NOW() + make_interval(secs => $2),
+                started = NOW()
+            WHERE jobs.uuid IN (
+                SELECT jobs.uuid
+                FROM jobs
+                WHERE jobs.name = ANY($3) AND (
+                    (jobs.status = 'PENDING' AND jobs.run_at <= NOW())
+                    OR (jobs.status = 'RUNNING' AND jobs.locked_until < NOW() AND jobs.attempts < jobs.max_attempts)
+                )
+                ORDER BY jobs.priority DESC, jobs.run_at
+                LIMIT $4
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(instance)
+        .bind(lease.as_secs_f64())
+        .bind(names)
+        .bind(limit)
+        .fetch_all(database.write())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Fails jobs whose lease expired on their last attempt, these would never be claimed again otherwise.
+    pub async fn fail_expired(database: &crate::database::Database) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'FAILED', error = 'job lease expired', locked_by = NULL, locked_until = NULL, finished = NOW()
+            WHERE jobs.status = 'RUNNING' AND jobs.locked_until < NOW() AND jobs.attempts >= jobs.max_attempts
+            "#,
+        )
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    /// Extends the lease of a running job, returns `false` if the job was cancelled or claimed by someone else.
+    pub async fn renew_lease(
+        &self,
+        database: &crate::database::Database,
+        instance: uuid::Uuid,
+        lease: std::time::Duration,
+    ) -> Result<bool, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            UPDATE jobs
+            SET locked_until = NOW() + make_interval(secs => $3)
+            WHERE jobs.uuid = $1 AND jobs.locked_by = $2 AND jobs.status = 'RUNNING'
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(instance)
+        .bind(lease.as_secs_f64())
+        .execute(database.write())
+        .await?
+        .rows_affected()
+            > 0)
+    }
+
+    pub async fn complete(
+        &self,
+        database: &crate::database::Database,
+        instance: uuid::Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'COMPLETED', error = NULL, locked_by = NULL, locked_until = NULL, finished = NOW()
+            WHERE jobs.uuid = $1 AND jobs.locked_by = $2 AND jobs.status = 'RUNNING'
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(instance)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a failed attempt, the job is retried with an exponential backoff until it runs out of attempts.
+    pub async fn fail(
+        &self,
+        database: &crate::database::Database,
+        instance: uuid::Uuid,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET
+                status = CASE
+                    WHEN jobs.attempts >= jobs.max_attempts THEN 'FAILED'::job_status
+                    ELSE 'PENDING'::job_status
+                END,
+                error = $3,
+                locked_by = NULL,
+                locked_until = NULL,
+                run_at = NOW() + make_interval(secs => LEAST(POWER(2, jobs.attempts) * 15, 3600)),
+                finished = CASE WHEN jobs.attempts >= jobs.max_attempts THEN NOW() END
+            WHERE jobs.uuid = $1 AND jobs.locked_by = $2 AND jobs.status = 'RUNNING'
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(instance)
+        .bind(error)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Cancels a pending or running job, running jobs are stopped once their lease is renewed.
+    pub async fn cancel(&self, database: &crate::database::Database) -> Result<bool, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'CANCELLED', locked_by = NULL, locked_until = NULL, finished = NOW()
+            WHERE jobs.uuid = $1 AND jobs.status IN ('PENDING', 'RUNNING')
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?
+        .rows_affected()
+            > 0)
+    }
+
+    /// Queues a failed or cancelled job again with a fresh set of attempts.
+    pub async fn retry(&self, database: &crate::database::Database) -> Result<bool, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'PENDING', attempts = 0, error = NULL, run_at = NOW(), started = NULL, finished = NULL
+            WHERE jobs.uuid = $1 AND jobs.status IN ('FAILED', 'CANCELLED')
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?
+        .rows_affected()
+            > 0)
+    }
+
+    /// Whether a job with the given name and payload is waiting to run or running.
+    pub async fn exists_unfinished(
+        database: &crate::database::Database,
+        name: &str,
+        payload: &serde_json::Value,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM jobs
+                WHERE jobs.name = $1 AND jobs.payload = $2 AND jobs.status IN ('PENDING', 'RUNNING')
+            )
+            "#,
+        )
+        .bind(name)
+        .bind(payload)
+        .fetch_one(database.read())
+        .await?
+        .try_get(0)
+    }
+
+    pub async fn delete_finished(database: &crate::database::Database) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            DELETE FROM jobs
+            WHERE jobs.status IN ('COMPLETED', 'CANCELLED') AND jobs.finished < NOW() - INTERVAL '7 days'
+            "#,
+        )
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(self) -> AdminApiJob {
+        AdminApiJob {
+            uuid: self.uuid,
+            name: self.name,
+            payload: self.payload,
+            status: self.status,
+            priority: self.priority,
+            attempts: self.attempts,
+            max_attempts: self.max_attempts,
+            error: self.error,
+            locked_by: self.locked_by,
+            locked_until: self.locked_until.map(|dt| dt.and_utc()),
+            run_at: self.run_at.and_utc(),
+            started: self.started.map(|dt| dt.and_utc()),
+            finished: self.finished.map(|dt| dt.and_utc()),
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for Job {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM jobs
+            WHERE jobs.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "Job")]
+pub struct AdminApiJob {
+    pub uuid: uuid::Uuid,
+    pub name: compact_str::CompactString,
+    pub payload: serde_json::Value,
+
+    pub status: JobStatus,
+    pub priority: i16,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub error: Option<String>,
+
+    /// The panel instance currently running the job.
+    pub locked_by: Option<uuid::Uuid>,
+    pub locked_until: Option<chrono::DateTime<chrono::Utc>>,
+
+    pub run_at: chrono::DateTime<chrono::Utc>,
+    pub started: Option<chrono::DateTime<chrono::Utc>>,
+    pub finished: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
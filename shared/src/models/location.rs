@@ -193,6 +193,15 @@ impl Location {
             created: self.created.and_utc(),
         }
     }
+
+    #[inline]
+    pub fn into_api_object(self) -> ApiLocation {
+        ApiLocation {
+            uuid: self.uuid,
+            name: self.name,
+            description: self.description,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -265,3 +274,12 @@ pub struct AdminApiLocation {
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ClientLocation")]
+pub struct ApiLocation {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub description: Option<compact_str::CompactString>,
+}
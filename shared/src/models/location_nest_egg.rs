@@ -0,0 +1,206 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LocationNestEgg {
+    pub location: super::location::Location,
+    pub egg: super::nest_egg::NestEgg,
+
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for LocationNestEgg {
+    const NAME: &'static str = "location_nest_egg";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        let mut columns = BTreeMap::from([(
+            "location_nest_eggs.created",
+            compact_str::format_compact!("{prefix}created"),
+        )]);
+
+        columns.extend(super::location::Location::columns(Some("location_")));
+        columns.extend(super::nest_egg::NestEgg::columns(Some("egg_")));
+
+        columns
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            location: super::location::Location::map(Some("location_"), row)?,
+            egg: super::nest_egg::NestEgg::map(Some("egg_"), row)?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl LocationNestEgg {
+    pub async fn create(
+        database: &crate::database::Database,
+        location_uuid: uuid::Uuid,
+        egg_uuid: uuid::Uuid,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            INSERT INTO location_nest_eggs (location_uuid, egg_uuid)
+            VALUES ($1, $2)
+            "#,
+        )
+        .bind(location_uuid)
+        .bind(egg_uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn by_location_uuid_egg_uuid(
+        database: &crate::database::Database,
+        location_uuid: uuid::Uuid,
+        egg_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM location_nest_eggs
+            JOIN locations ON location_nest_eggs.location_uuid = locations.uuid
+            JOIN nest_eggs ON location_nest_eggs.egg_uuid = nest_eggs.uuid
+            WHERE location_nest_eggs.location_uuid = $1 AND location_nest_eggs.egg_uuid = $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(location_uuid)
+        .bind(egg_uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_location_uuid_with_pagination(
+        database: &crate::database::Database,
+        location_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM location_nest_eggs
+            JOIN locations ON location_nest_eggs.location_uuid = locations.uuid
+            JOIN nest_eggs ON location_nest_eggs.egg_uuid = nest_eggs.uuid
+            WHERE location_nest_eggs.location_uuid = $1 AND ($2 IS NULL OR nest_eggs.name ILIKE '%' || $2 || '%')
+            ORDER BY location_nest_eggs.created
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(location_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn all(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM location_nest_eggs
+            JOIN locations ON location_nest_eggs.location_uuid = locations.uuid
+            JOIN nest_eggs ON location_nest_eggs.egg_uuid = nest_eggs.uuid
+            ORDER BY locations.name, nest_eggs.name
+            "#,
+            Self::columns_sql(None)
+        ))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(self) -> AdminApiLocationNestEgg {
+        AdminApiLocationNestEgg {
+            egg: self.egg.into_api_object(),
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for LocationNestEgg {
+    type DeleteOptions = ();
+
+    fn get_delete_listeners() -> &'static LazyLock<DeleteListenerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteListenerList<LocationNestEgg>> =
+            LazyLock::new(|| Arc::new(ListenerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete(
+        &self,
+        database: &Arc<crate::database::Database>,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        self.run_delete_listeners(&options, database, &mut transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM location_nest_eggs
+            WHERE location_nest_eggs.location_uuid = $1 AND location_nest_eggs.egg_uuid = $2
+            "#,
+        )
+        .bind(self.location.uuid)
+        .bind(self.egg.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "LocationNestEgg")]
+pub struct AdminApiLocationNestEgg {
+    pub egg: super::nest_egg::ApiNestEgg,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
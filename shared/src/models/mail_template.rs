@@ -0,0 +1,272 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+/// A database override of a registered mail template for a single language.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MailTemplate {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub language: compact_str::CompactString,
+
+    pub subject: compact_str::CompactString,
+    pub body: String,
+    pub body_plain: Option<String>,
+
+    pub updated: chrono::NaiveDateTime,
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for MailTemplate {
+    const NAME: &'static str = "mail_template";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "mail_templates.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "mail_templates.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "mail_templates.language",
+                compact_str::format_compact!("{prefix}language"),
+            ),
+            (
+                "mail_templates.subject",
+                compact_str::format_compact!("{prefix}subject"),
+            ),
+            (
+                "mail_templates.body",
+                compact_str::format_compact!("{prefix}body"),
+            ),
+            (
+                "mail_templates.body_plain",
+                compact_str::format_compact!("{prefix}body_plain"),
+            ),
+            (
+                "mail_templates.updated",
+                compact_str::format_compact!("{prefix}updated"),
+            ),
+            (
+                "mail_templates.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            language: row.try_get(compact_str::format_compact!("{prefix}language").as_str())?,
+            subject: row.try_get(compact_str::format_compact!("{prefix}subject").as_str())?,
+            body: row.try_get(compact_str::format_compact!("{prefix}body").as_str())?,
+            body_plain: row.try_get(compact_str::format_compact!("{prefix}body_plain").as_str())?,
+            updated: row.try_get(compact_str::format_compact!("{prefix}updated").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl MailTemplate {
+    /// Creates or replaces the override of a template for a language.
+    pub async fn upsert(
+        database: &crate::database::Database,
+        name: &str,
+        language: &str,
+        subject: &str,
+        body: &str,
+        body_plain: Option<&str>,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO mail_templates (name, language, subject, body, body_plain)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (name, language) DO UPDATE SET
+                subject = EXCLUDED.subject,
+                body = EXCLUDED.body,
+                body_plain = EXCLUDED.body_plain,
+                updated = NOW()
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(name)
+        .bind(language)
+        .bind(subject)
+        .bind(body)
+        .bind(body_plain)
+        .fetch_one(database.write())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    pub async fn by_name_language(
+        database: &crate::database::Database,
+        name: &str,
+        language: &str,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM mail_templates
+            WHERE mail_templates.name = $1 AND mail_templates.language = $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(name)
+        .bind(language)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    /// Finds the override to use for a language, falling back to the panel default language.
+    pub async fn by_name_language_fallback(
+        database: &crate::database::Database,
+        name: &str,
+        language: &str,
+        fallback_language: &str,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM mail_templates
+            WHERE mail_templates.name = $1 AND mail_templates.language IN ($2, $3)
+            ORDER BY mail_templates.language = $2 DESC
+            LIMIT 1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(name)
+        .bind(language)
+        .bind(fallback_language)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn all_by_name(
+        database: &crate::database::Database,
+        name: &str,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM mail_templates
+            WHERE mail_templates.name = $1
+            ORDER BY mail_templates.language
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(name)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn all(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM mail_templates
+            ORDER BY mail_templates.name, mail_templates.language
+            "#,
+            Self::columns_sql(None)
+        ))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(self) -> AdminApiMailTemplate {
+        AdminApiMailTemplate {
+            uuid: self.uuid,
+            language: self.language,
+            subject: self.subject,
+            body: self.body,
+            body_plain: self.body_plain,
+            updated: self.updated.and_utc(),
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for MailTemplate {
+    type DeleteOptions = ();
+
+    fn get_delete_listeners() -> &'static LazyLock<DeleteListenerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteListenerList<MailTemplate>> =
+            LazyLock::new(|| Arc::new(ListenerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete(
+        &self,
+        database: &Arc<crate::database::Database>,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        self.run_delete_listeners(&options, database, &mut transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM mail_templates
+            WHERE mail_templates.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminMailTemplate")]
+pub struct AdminApiMailTemplate {
+    pub uuid: uuid::Uuid,
+
+    pub language: compact_str::CompactString,
+
+    pub subject: compact_str::CompactString,
+    pub body: String,
+    pub body_plain: Option<String>,
+
+    pub updated: chrono::DateTime<chrono::Utc>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
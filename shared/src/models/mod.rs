@@ -18,8 +18,11 @@ pub mod backup_configurations;
 pub mod database_host;
 pub mod egg_repository;
 pub mod egg_repository_egg;
+pub mod job;
 pub mod location;
 pub mod location_database_host;
+pub mod location_nest_egg;
+pub mod mail_template;
 pub mod mount;
 pub mod nest;
 pub mod nest_egg;
@@ -27,22 +30,31 @@ pub mod nest_egg_mount;
 pub mod nest_egg_variable;
 pub mod node;
 pub mod node_allocation;
+pub mod node_evacuation;
+pub mod node_evacuation_server;
 pub mod node_mount;
 pub mod oauth_provider;
+pub mod resource_quota;
 pub mod role;
 pub mod server;
 pub mod server_activity;
 pub mod server_allocation;
 pub mod server_backup;
+pub mod server_clone;
 pub mod server_database;
 pub mod server_mount;
 pub mod server_schedule;
 pub mod server_schedule_step;
 pub mod server_subuser;
+pub mod server_suspension;
+pub mod server_template;
 pub mod server_variable;
 pub mod user;
 pub mod user_activity;
 pub mod user_api_key;
+pub mod user_magic_link;
+pub mod user_notification;
+pub mod user_notification_channel;
 pub mod user_oauth_link;
 pub mod user_password_reset;
 pub mod user_recovery_code;
@@ -50,6 +62,8 @@ pub mod user_security_key;
 pub mod user_server_group;
 pub mod user_session;
 pub mod user_ssh_key;
+pub mod webhook;
+pub mod webhook_delivery;
 
 #[derive(ToSchema, Validate, Deserialize)]
 pub struct PaginationParams {
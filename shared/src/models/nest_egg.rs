@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, postgres::PgRow};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
 use std::{
     collections::{BTreeMap, HashSet},
     sync::{Arc, LazyLock},
@@ -216,6 +216,309 @@ pub struct ExportedNestEgg {
     pub variables: Vec<super::nest_egg_variable::ExportedNestEggVariable>,
 }
 
+impl ExportedNestEgg {
+    /// Lists the variable rules that are not supported, these are left out when importing the egg.
+    pub fn compatibility_report(&self) -> NestEggCompatibilityReport {
+        let mut unsupported_rules = Vec::new();
+
+        for variable in &self.variables {
+            let (_, unsupported) = rule_validator::partition_rules(&variable.rules);
+
+            unsupported_rules.extend(unsupported.into_iter().map(|unsupported| {
+                NestEggUnsupportedRule {
+                    env_variable: variable.env_variable.clone(),
+                    rule: unsupported.rule,
+                    error: unsupported.error,
+                }
+            }));
+        }
+
+        NestEggCompatibilityReport { unsupported_rules }
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct NestEggUnsupportedRule {
+    pub env_variable: compact_str::CompactString,
+    pub rule: compact_str::CompactString,
+    pub error: compact_str::CompactString,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct NestEggCompatibilityReport {
+    pub unsupported_rules: Vec<NestEggUnsupportedRule>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "nest_egg_update_policy",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum NestEggUpdatePolicy {
+    /// Updates from the linked repository egg are only applied by an admin.
+    Manual,
+    /// Updates without breaking changes are applied when the repository is synced.
+    NonBreaking,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct NestEggValueChange {
+    pub old: compact_str::CompactString,
+    pub new: compact_str::CompactString,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct NestEggScriptChange {
+    #[schema(inline)]
+    pub old: NestEggConfigScript,
+    #[schema(inline)]
+    pub new: NestEggConfigScript,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct NestEggVariableChange {
+    pub env_variable: compact_str::CompactString,
+    /// The changed fields of the variable, like `default_value` or `rules`.
+    pub fields: Vec<&'static str>,
+}
+
+/// The changes applying an exported egg to an installed egg would make.
+#[derive(ToSchema, Serialize, Default)]
+pub struct NestEggDiff {
+    /// Other changed fields of the egg, like `features` or `config_stop`.
+    pub fields: Vec<&'static str>,
+    #[schema(inline)]
+    pub startup: Option<NestEggValueChange>,
+    #[schema(inline)]
+    pub install_script: Option<NestEggScriptChange>,
+
+    pub docker_images_added: IndexMap<compact_str::CompactString, compact_str::CompactString>,
+    pub docker_images_removed: IndexMap<compact_str::CompactString, compact_str::CompactString>,
+    #[schema(inline)]
+    pub docker_images_changed: IndexMap<compact_str::CompactString, NestEggValueChange>,
+
+    pub variables_added: Vec<compact_str::CompactString>,
+    pub variables_removed: Vec<compact_str::CompactString>,
+    #[schema(inline)]
+    pub variables_changed: Vec<NestEggVariableChange>,
+
+    pub config_files_added: Vec<compact_str::CompactString>,
+    pub config_files_removed: Vec<compact_str::CompactString>,
+    pub config_files_changed: Vec<compact_str::CompactString>,
+
+    /// Why the changes could break existing servers of the egg, empty if they are non-breaking.
+    pub breaking_changes: Vec<String>,
+}
+
+impl NestEggDiff {
+    pub fn new(current: &ExportedNestEgg, upstream: &ExportedNestEgg) -> Self {
+        fn differs(a: &impl Serialize, b: &impl Serialize) -> bool {
+            serde_json::to_value(a).ok() != serde_json::to_value(b).ok()
+        }
+
+        let mut diff = Self::default();
+
+        for (field, changed) in [
+            ("name", current.name != upstream.name),
+            ("description", current.description != upstream.description),
+            ("author", current.author != upstream.author),
+            (
+                "config_startup",
+                differs(&current.config.startup, &upstream.config.startup),
+            ),
+            (
+                "config_stop",
+                differs(&current.config.stop, &upstream.config.stop),
+            ),
+            (
+                "config_allocations",
+                differs(&current.config.allocations, &upstream.config.allocations),
+            ),
+            (
+                "force_outgoing_ip",
+                current.force_outgoing_ip != upstream.force_outgoing_ip,
+            ),
+            (
+                "separate_port",
+                current.separate_port != upstream.separate_port,
+            ),
+            ("features", current.features != upstream.features),
+            (
+                "file_denylist",
+                current.file_denylist != upstream.file_denylist,
+            ),
+        ] {
+            if changed {
+                diff.fields.push(field);
+            }
+        }
+
+        if current.startup != upstream.startup {
+            diff.startup = Some(NestEggValueChange {
+                old: current.startup.clone(),
+                new: upstream.startup.clone(),
+            });
+        }
+        if differs(
+            &current.scripts.installation,
+            &upstream.scripts.installation,
+        ) {
+            diff.install_script = Some(NestEggScriptChange {
+                old: current.scripts.installation.clone(),
+                new: upstream.scripts.installation.clone(),
+            });
+        }
+
+        for (name, image) in &upstream.docker_images {
+            match current.docker_images.get(name) {
+                Some(current_image) if current_image != image => {
+                    diff.docker_images_changed.insert(
+                        name.clone(),
+                        NestEggValueChange {
+                            old: current_image.clone(),
+                            new: image.clone(),
+                        },
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    diff.docker_images_added.insert(name.clone(), image.clone());
+                }
+            }
+        }
+        for (name, image) in &current.docker_images {
+            if !upstream.docker_images.contains_key(name) {
+                diff.breaking_changes
+                    .push(format!("docker image {name} is removed"));
+                diff.docker_images_removed
+                    .insert(name.clone(), image.clone());
+            }
+        }
+
+        for (i, variable) in upstream.variables.iter().enumerate() {
+            // unsupported rules are left out and unset orders are filled in when importing
+            let (rules, _) = rule_validator::partition_rules(&variable.rules);
+            let order = if variable.order == 0 {
+                i as i16 + 1
+            } else {
+                variable.order
+            };
+
+            let Some(current_variable) = current
+                .variables
+                .iter()
+                .find(|v| v.env_variable == variable.env_variable)
+            else {
+                if rules.iter().any(|rule| rule == "required")
+                    && variable.default_value.as_deref().is_none_or(str::is_empty)
+                {
+                    diff.breaking_changes.push(format!(
+                        "variable {} is required and has no default value",
+                        variable.env_variable
+                    ));
+                }
+
+                diff.variables_added.push(variable.env_variable.clone());
+                continue;
+            };
+
+            let mut fields = Vec::new();
+            for (field, changed) in [
+                ("name", current_variable.name != variable.name),
+                (
+                    "description",
+                    current_variable.description != variable.description,
+                ),
+                ("order", current_variable.order != order),
+                (
+                    "default_value",
+                    current_variable.default_value != variable.default_value,
+                ),
+                (
+                    "user_viewable",
+                    current_variable.user_viewable != variable.user_viewable,
+                ),
+                (
+                    "user_editable",
+                    current_variable.user_editable != variable.user_editable,
+                ),
+                ("rules", current_variable.rules != rules),
+            ] {
+                if changed {
+                    fields.push(field);
+                }
+            }
+
+            if fields.is_empty() {
+                continue;
+            }
+
+            if fields.contains(&"rules") {
+                diff.breaking_changes.push(format!(
+                    "rules of variable {} changed, existing values may no longer be valid",
+                    variable.env_variable
+                ));
+            }
+
+            diff.variables_changed.push(NestEggVariableChange {
+                env_variable: variable.env_variable.clone(),
+                fields,
+            });
+        }
+        for variable in &current.variables {
+            if !upstream
+                .variables
+                .iter()
+                .any(|v| v.env_variable == variable.env_variable)
+            {
+                diff.breaking_changes
+                    .push(format!("variable {} is removed", variable.env_variable));
+                diff.variables_removed.push(variable.env_variable.clone());
+            }
+        }
+
+        for (file, config) in &upstream.config.files {
+            match current.config.files.get(file) {
+                Some(current_config) if differs(current_config, config) => {
+                    diff.config_files_changed.push(file.clone());
+                }
+                Some(_) => {}
+                None => diff.config_files_added.push(file.clone()),
+            }
+        }
+        for file in current.config.files.keys() {
+            if !upstream.config.files.contains_key(file) {
+                diff.config_files_removed.push(file.clone());
+            }
+        }
+
+        diff
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+            && self.startup.is_none()
+            && self.install_script.is_none()
+            && self.docker_images_added.is_empty()
+            && self.docker_images_removed.is_empty()
+            && self.docker_images_changed.is_empty()
+            && self.variables_added.is_empty()
+            && self.variables_removed.is_empty()
+            && self.variables_changed.is_empty()
+            && self.config_files_added.is_empty()
+            && self.config_files_removed.is_empty()
+            && self.config_files_changed.is_empty()
+    }
+
+    #[inline]
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking_changes.is_empty()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct NestEgg {
     pub uuid: uuid::Uuid,
@@ -239,6 +542,12 @@ pub struct NestEgg {
     pub features: Vec<compact_str::CompactString>,
     pub docker_images: IndexMap<compact_str::CompactString, compact_str::CompactString>,
     pub file_denylist: Vec<compact_str::CompactString>,
+    /// Whether users may switch their servers from other eggs of the same nest to this egg.
+    pub user_selectable: bool,
+
+    pub update_policy: NestEggUpdatePolicy,
+    /// When the linked repository egg was last applied, updates made upstream after this are available.
+    pub egg_repository_egg_applied: Option<chrono::NaiveDateTime>,
 
     pub created: chrono::NaiveDateTime,
 }
@@ -319,6 +628,18 @@ impl BaseModel for NestEgg {
                 "nest_eggs.file_denylist",
                 compact_str::format_compact!("{prefix}file_denylist"),
             ),
+            (
+                "nest_eggs.user_selectable",
+                compact_str::format_compact!("{prefix}user_selectable"),
+            ),
+            (
+                "nest_eggs.update_policy",
+                compact_str::format_compact!("{prefix}update_policy"),
+            ),
+            (
+                "nest_eggs.egg_repository_egg_applied",
+                compact_str::format_compact!("{prefix}egg_repository_egg_applied"),
+            ),
             (
                 "nest_eggs.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -375,6 +696,13 @@ impl BaseModel for NestEgg {
             .unwrap_or_default(),
             file_denylist: row
                 .try_get(compact_str::format_compact!("{prefix}file_denylist").as_str())?,
+            user_selectable: row
+                .try_get(compact_str::format_compact!("{prefix}user_selectable").as_str())?,
+            update_policy: row
+                .try_get(compact_str::format_compact!("{prefix}update_policy").as_str())?,
+            egg_repository_egg_applied: row.try_get(
+                compact_str::format_compact!("{prefix}egg_repository_egg_applied").as_str(),
+            )?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
         })
     }
@@ -441,7 +769,7 @@ impl NestEgg {
         egg_repository_egg_uuid: Option<uuid::Uuid>,
         exported_egg: ExportedNestEgg,
     ) -> Result<Self, crate::database::DatabaseError> {
-        let egg = Self::create(
+        let mut egg = Self::create(
             database,
             nest_uuid,
             egg_repository_egg_uuid,
@@ -473,9 +801,7 @@ impl NestEgg {
         .await?;
 
         for variable in exported_egg.variables {
-            if rule_validator::validate_rules(&variable.rules).is_err() {
-                continue;
-            }
+            let (rules, _) = rule_validator::partition_rules(&variable.rules);
 
             if let Err(err) = super::nest_egg_variable::NestEggVariable::create(
                 database,
@@ -488,7 +814,7 @@ impl NestEgg {
                 variable.user_viewable,
                 variable.user_editable,
                 variable.secret,
-                &variable.rules,
+                &rules,
             )
             .await
             {
@@ -496,9 +822,140 @@ impl NestEgg {
             }
         }
 
+        if egg.egg_repository_egg.is_some() {
+            egg.egg_repository_egg_applied =
+                Some(egg.mark_egg_repository_egg_applied(database).await?);
+        }
+
         Ok(egg)
     }
 
+    /// Updates the egg to the exported egg of its linked repository egg, marking the upstream version as applied.
+    pub async fn import_repository_update(
+        &self,
+        database: &crate::database::Database,
+        egg_repository_egg: super::egg_repository_egg::EggRepositoryEgg,
+    ) -> Result<(), crate::database::DatabaseError> {
+        self.import_update(database, egg_repository_egg.exported_egg)
+            .await?;
+        self.mark_egg_repository_egg_applied(database).await?;
+
+        Ok(())
+    }
+
+    async fn mark_egg_repository_egg_applied(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<chrono::NaiveDateTime, crate::database::DatabaseError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE nest_eggs
+            SET egg_repository_egg_applied = NOW()
+            WHERE nest_eggs.uuid = $1
+            RETURNING nest_eggs.egg_repository_egg_applied
+            "#,
+        )
+        .bind(self.uuid)
+        .fetch_one(database.write())
+        .await?;
+
+        Ok(row.try_get("egg_repository_egg_applied")?)
+    }
+
+    /// Whether the linked repository egg changed since it was last applied to this egg.
+    #[inline]
+    pub fn update_available(
+        &self,
+        egg_repository_egg: &super::egg_repository_egg::EggRepositoryEgg,
+    ) -> bool {
+        self.egg_repository_egg_applied
+            .is_none_or(|applied| egg_repository_egg.updated > applied)
+    }
+
+    /// The changes updating the egg to its linked repository egg would make.
+    pub async fn repository_diff(
+        &self,
+        database: &crate::database::Database,
+        egg_repository_egg: &super::egg_repository_egg::EggRepositoryEgg,
+    ) -> Result<NestEggDiff, crate::database::DatabaseError> {
+        let current = self.clone().into_exported(database).await?;
+
+        Ok(NestEggDiff::new(&current, &egg_repository_egg.exported_egg))
+    }
+
+    /// All eggs whose linked repository egg changed since it was last applied,
+    /// optionally only the ones linked to eggs of a single repository.
+    pub async fn all_with_repository_updates(
+        database: &crate::database::Database,
+        egg_repository_uuid: Option<uuid::Uuid>,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM nest_eggs
+            JOIN egg_repository_eggs ON egg_repository_eggs.uuid = nest_eggs.egg_repository_egg_uuid
+            WHERE ($1 IS NULL OR egg_repository_eggs.egg_repository_uuid = $1)
+                AND (
+                    nest_eggs.egg_repository_egg_applied IS NULL
+                    OR egg_repository_eggs.updated > nest_eggs.egg_repository_egg_applied
+                )
+            ORDER BY nest_eggs.name
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(egg_repository_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Applies available updates of eggs linked to the repository that follow the [`NestEggUpdatePolicy::NonBreaking`] policy,
+    /// returns the eggs that were changed. Updates with breaking changes are left for an admin to review.
+    pub async fn apply_repository_updates(
+        database: &crate::database::Database,
+        egg_repository_uuid: uuid::Uuid,
+    ) -> Result<Vec<uuid::Uuid>, crate::database::DatabaseError> {
+        let mut updated_eggs = Vec::new();
+
+        for egg in Self::all_with_repository_updates(database, Some(egg_repository_uuid)).await? {
+            if egg.update_policy != NestEggUpdatePolicy::NonBreaking {
+                continue;
+            }
+
+            let Some(egg_repository_egg) = &egg.egg_repository_egg else {
+                continue;
+            };
+            let egg_repository_egg = egg_repository_egg.fetch(database).await?;
+
+            let diff = egg.repository_diff(database, &egg_repository_egg).await?;
+            if diff.is_breaking() {
+                tracing::info!(
+                    egg = %egg.uuid,
+                    "skipping automatic egg update with breaking changes: {}",
+                    diff.breaking_changes.join(", ")
+                );
+                continue;
+            }
+
+            if let Err(err) = egg
+                .import_repository_update(database, egg_repository_egg)
+                .await
+            {
+                tracing::warn!(egg = %egg.uuid, "failed to apply automatic egg update: {:?}", err);
+                continue;
+            }
+
+            if !diff.is_empty() {
+                updated_eggs.push(egg.uuid);
+            }
+        }
+
+        Ok(updated_eggs)
+    }
+
     pub async fn import_update(
         &self,
         database: &crate::database::Database,
@@ -567,9 +1024,7 @@ impl NestEgg {
         .await?;
 
         for (i, variable) in exported_egg.variables.iter().enumerate() {
-            if rule_validator::validate_rules(&variable.rules).is_err() {
-                continue;
-            }
+            let (rules, _) = rule_validator::partition_rules(&variable.rules);
 
             if let Err(err) = sqlx::query!(
                 "INSERT INTO nest_egg_variables (  
@@ -597,11 +1052,7 @@ impl NestEgg {
                 variable.default_value.as_deref(),
                 variable.user_viewable,
                 variable.user_editable,
-                &variable
-                    .rules
-                    .iter()
-                    .map(|r| r.as_str())
-                    .collect::<Vec<_>>() as &[&str]
+                &rules.iter().map(|r| r.as_str()).collect::<Vec<_>>() as &[&str]
             )
             .execute(database.read())
             .await
@@ -695,6 +1146,28 @@ impl NestEgg {
         row.try_map(|row| Self::map(None, &row))
     }
 
+    pub async fn all_user_selectable_by_nest_uuid(
+        database: &crate::database::Database,
+        nest_uuid: uuid::Uuid,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM nest_eggs
+            WHERE nest_eggs.nest_uuid = $1 AND nest_eggs.user_selectable
+            ORDER BY nest_eggs.name
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(nest_uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
     pub async fn count_by_nest_uuid(
         database: &crate::database::Database,
         nest_uuid: uuid::Uuid,
@@ -765,18 +1238,26 @@ impl NestEgg {
         self,
         database: &crate::database::Database,
     ) -> Result<AdminApiNestEgg, crate::database::DatabaseError> {
+        let (egg_repository_egg, update_available) = match &self.egg_repository_egg {
+            Some(egg_repository_egg) => {
+                let egg_repository_egg = egg_repository_egg.fetch_cached(database).await?;
+                let update_available = self.update_available(&egg_repository_egg);
+
+                (
+                    Some(
+                        egg_repository_egg
+                            .into_admin_egg_api_object(database)
+                            .await?,
+                    ),
+                    update_available,
+                )
+            }
+            None => (None, false),
+        };
+
         Ok(AdminApiNestEgg {
             uuid: self.uuid,
-            egg_repository_egg: match self.egg_repository_egg {
-                Some(egg_repository_egg) => Some(
-                    egg_repository_egg
-                        .fetch_cached(database)
-                        .await?
-                        .into_admin_egg_api_object(database)
-                        .await?,
-                ),
-                None => None,
-            },
+            egg_repository_egg,
             name: self.name,
             description: self.description,
             author: self.author,
@@ -791,6 +1272,9 @@ impl NestEgg {
             features: self.features,
             docker_images: self.docker_images,
             file_denylist: self.file_denylist,
+            user_selectable: self.user_selectable,
+            update_policy: self.update_policy,
+            update_available,
             created: self.created.and_utc(),
         })
     }
@@ -897,6 +1381,11 @@ pub struct AdminApiNestEgg {
     pub features: Vec<compact_str::CompactString>,
     pub docker_images: IndexMap<compact_str::CompactString, compact_str::CompactString>,
     pub file_denylist: Vec<compact_str::CompactString>,
+    pub user_selectable: bool,
+
+    pub update_policy: NestEggUpdatePolicy,
+    /// Whether the linked repository egg changed since it was last applied.
+    pub update_available: bool,
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
@@ -251,6 +251,7 @@ impl NestEggVariable {
             user_viewable: self.user_viewable,
             user_editable: self.user_editable,
             is_secret: self.secret,
+            rules_schema: rule_validator::rules_json_schema(&self.rules),
             rules: self.rules,
             created: self.created.and_utc(),
         }
@@ -309,6 +310,8 @@ pub struct AdminApiNestEggVariable {
     pub user_editable: bool,
     pub is_secret: bool,
     pub rules: Vec<compact_str::CompactString>,
+    /// The rules compiled to a JSON Schema, for validating values before submitting them.
+    pub rules_schema: serde_json::Value,
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
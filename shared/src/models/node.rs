@@ -1,15 +1,27 @@
 use crate::prelude::*;
 use rand::distr::SampleString;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, postgres::PgRow};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
 use std::{
     collections::BTreeMap,
     sync::{Arc, LazyLock},
 };
 use utoipa::ToSchema;
 
+const NODE_HEALTH_FAILURE_THRESHOLD: i16 = 3;
+
 pub type GetNode = crate::extract::ConsumingExtension<Node>;
 
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(type_name = "node_health_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NodeHealthStatus {
+    Unknown,
+    Online,
+    Offline,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Node {
     pub uuid: uuid::Uuid,
@@ -33,6 +45,12 @@ pub struct Node {
     pub token_id: compact_str::CompactString,
     pub token: Vec<u8>,
 
+    pub health_status: NodeHealthStatus,
+    pub health_latency: Option<i32>,
+    pub wings_version: Option<compact_str::CompactString>,
+    pub last_seen: Option<chrono::NaiveDateTime>,
+    pub health_checked: Option<chrono::NaiveDateTime>,
+
     pub created: chrono::NaiveDateTime,
 }
 
@@ -85,6 +103,26 @@ impl BaseModel for Node {
                 compact_str::format_compact!("{prefix}token_id"),
             ),
             ("nodes.token", compact_str::format_compact!("{prefix}token")),
+            (
+                "nodes.health_status",
+                compact_str::format_compact!("{prefix}health_status"),
+            ),
+            (
+                "nodes.health_latency",
+                compact_str::format_compact!("{prefix}health_latency"),
+            ),
+            (
+                "nodes.wings_version",
+                compact_str::format_compact!("{prefix}wings_version"),
+            ),
+            (
+                "nodes.last_seen",
+                compact_str::format_compact!("{prefix}last_seen"),
+            ),
+            (
+                "nodes.health_checked",
+                compact_str::format_compact!("{prefix}health_checked"),
+            ),
             (
                 "nodes.created",
                 compact_str::format_compact!("{prefix}created"),
@@ -130,6 +168,15 @@ impl BaseModel for Node {
             disk: row.try_get(compact_str::format_compact!("{prefix}disk").as_str())?,
             token_id: row.try_get(compact_str::format_compact!("{prefix}token_id").as_str())?,
             token: row.try_get(compact_str::format_compact!("{prefix}token").as_str())?,
+            health_status: row
+                .try_get(compact_str::format_compact!("{prefix}health_status").as_str())?,
+            health_latency: row
+                .try_get(compact_str::format_compact!("{prefix}health_latency").as_str())?,
+            wings_version: row
+                .try_get(compact_str::format_compact!("{prefix}wings_version").as_str())?,
+            last_seen: row.try_get(compact_str::format_compact!("{prefix}last_seen").as_str())?,
+            health_checked: row
+                .try_get(compact_str::format_compact!("{prefix}health_checked").as_str())?,
             created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
         })
     }
@@ -356,6 +403,161 @@ impl Node {
         .unwrap_or(0)
     }
 
+    pub async fn all(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM nodes
+            JOIN locations ON locations.uuid = nodes.location_uuid
+            ORDER BY nodes.created
+            "#,
+            Self::columns_sql(None)
+        ))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Checks whether wings on this node is reachable and persists the result.
+    ///
+    /// The node only goes offline after `NODE_HEALTH_FAILURE_THRESHOLD` failed checks in a row, so a single
+    /// slow response does not flap its status. Returns the new health status together with the error message
+    /// if the node could not be reached.
+    pub async fn check_health(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(NodeHealthStatus, Option<String>), crate::database::DatabaseError> {
+        let start = std::time::Instant::now();
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            self.api_client(database).get_system(),
+        )
+        .await;
+        let latency = start.elapsed().as_millis().min(i32::MAX as u128) as i32;
+
+        let (status, error) = match result {
+            Ok(Ok(system)) => {
+                sqlx::query(
+                    r#"
+                    UPDATE nodes
+                    SET health_status = $2, health_latency = $3, health_failures = 0, wings_version = $4, last_seen = NOW(), health_checked = NOW()
+                    WHERE nodes.uuid = $1
+                    "#,
+                )
+                .bind(self.uuid)
+                .bind(NodeHealthStatus::Online)
+                .bind(latency)
+                .bind(&system.version)
+                .execute(database.write())
+                .await?;
+
+                (NodeHealthStatus::Online, None)
+            }
+            Ok(Err(err)) => (NodeHealthStatus::Offline, Some(format!("{err:?}"))),
+            Err(_) => (
+                NodeHealthStatus::Offline,
+                Some("timed out after 10 seconds".to_string()),
+            ),
+        };
+
+        if status == NodeHealthStatus::Offline {
+            let row = sqlx::query(
+                r#"
+                UPDATE nodes
+                SET
+                    health_failures = LEAST(nodes.health_failures + 1, $3),
+                    health_status = CASE WHEN nodes.health_failures + 1 >= $3 THEN $2 ELSE nodes.health_status END,
+                    health_latency = NULL,
+                    health_checked = NOW()
+                WHERE nodes.uuid = $1
+                RETURNING nodes.health_status
+                "#,
+            )
+            .bind(self.uuid)
+            .bind(NodeHealthStatus::Offline)
+            .bind(NODE_HEALTH_FAILURE_THRESHOLD)
+            .fetch_one(database.write())
+            .await?;
+
+            return Ok((row.try_get("health_status")?, error));
+        }
+
+        Ok((status, error))
+    }
+
+    /// Records a health status change of this node as admin activity and mails all admins if enabled.
+    pub async fn report_health_change(
+        &self,
+        state: &crate::State,
+        status: NodeHealthStatus,
+        error: Option<&str>,
+    ) {
+        let event = match status {
+            NodeHealthStatus::Online => "node:health.online",
+            _ => "node:health.offline",
+        };
+
+        if let Err(err) = super::admin_activity::AdminActivity::log(
+            &state.database,
+            None,
+            None,
+            event,
+            None,
+            serde_json::json!({
+                "uuid": self.uuid,
+                "name": self.name,
+                "error": error,
+            }),
+        )
+        .await
+        {
+            tracing::warn!(node = %self.uuid, "failed to log node health change: {:#?}", err);
+        }
+
+        let settings = state.settings.get().await;
+        if !settings.app.node_status_mails_enabled {
+            return;
+        }
+
+        // the name and error are passed as is, the html body escapes them when rendering
+        let context = serde_json::json!({
+            "node_name": self.name,
+            "node_status": match status {
+                NodeHealthStatus::Online => "back online",
+                _ => "offline",
+            },
+            "node_details": error,
+            "node_link": format!("{}/admin/nodes/{}", settings.app.url, self.uuid),
+        });
+
+        drop(settings);
+
+        let admins = match super::user::User::all_admins(&state.database).await {
+            Ok(admins) => admins,
+            Err(err) => {
+                tracing::warn!(node = %self.uuid, "failed to fetch admins for node health mail: {:#?}", err);
+                return;
+            }
+        };
+
+        for admin in admins {
+            state
+                .mail
+                .send_template(
+                    admin.email.clone(),
+                    "node_status",
+                    Some(&admin),
+                    context.clone(),
+                )
+                .await;
+        }
+    }
+
     pub async fn cached_configuration(
         &self,
         database: &crate::database::Database,
@@ -461,6 +663,11 @@ impl Node {
             disk: self.disk,
             token_id: self.token_id,
             token: database.decrypt(self.token).await?,
+            health_status: self.health_status,
+            health_latency: self.health_latency,
+            wings_version: self.wings_version,
+            last_seen: self.last_seen.map(|dt| dt.and_utc()),
+            health_checked: self.health_checked.map(|dt| dt.and_utc()),
             created: self.created.and_utc(),
         })
     }
@@ -553,5 +760,11 @@ pub struct AdminApiNode {
     pub token_id: compact_str::CompactString,
     pub token: compact_str::CompactString,
 
+    pub health_status: NodeHealthStatus,
+    pub health_latency: Option<i32>,
+    pub wings_version: Option<compact_str::CompactString>,
+    pub last_seen: Option<chrono::DateTime<chrono::Utc>>,
+    pub health_checked: Option<chrono::DateTime<chrono::Utc>>,
+
     pub created: chrono::DateTime<chrono::Utc>,
 }
@@ -111,6 +111,68 @@ impl NodeAllocation {
         row.try_map(|row| Self::map(None, &row))
     }
 
+    pub async fn available_by_node_uuid_in_port_range(
+        database: &crate::database::Database,
+        node_uuid: uuid::Uuid,
+        port_start: i32,
+        port_end: i32,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM node_allocations
+            LEFT JOIN server_allocations ON server_allocations.allocation_uuid = node_allocations.uuid
+            WHERE
+                node_allocations.node_uuid = $1
+                AND server_allocations.uuid IS NULL
+                AND node_allocations.port BETWEEN $2 AND $3
+            ORDER BY node_allocations.ip, node_allocations.port
+            LIMIT $4
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(node_uuid)
+        .bind(port_start)
+        .bind(port_end)
+        .bind(limit)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Returns at most one free allocation for each of the given ports.
+    pub async fn available_by_node_uuid_ports(
+        database: &crate::database::Database,
+        node_uuid: uuid::Uuid,
+        ports: &[i32],
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT DISTINCT ON (node_allocations.port) {}
+            FROM node_allocations
+            LEFT JOIN server_allocations ON server_allocations.allocation_uuid = node_allocations.uuid
+            WHERE
+                node_allocations.node_uuid = $1
+                AND server_allocations.uuid IS NULL
+                AND node_allocations.port = ANY($2)
+            ORDER BY node_allocations.port, node_allocations.ip
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(node_uuid)
+        .bind(ports)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
     pub async fn available_by_node_uuid_with_pagination(
         database: &crate::database::Database,
         node_uuid: uuid::Uuid,
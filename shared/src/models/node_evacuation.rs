@@ -0,0 +1,629 @@
+use super::node_evacuation_server::{NodeEvacuationServer, NodeEvacuationServerStatus};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "node_evacuation_status",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum NodeEvacuationStatus {
+    Running,
+    Paused,
+    Cancelled,
+    Completed,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct NodeEvacuationOptions {
+    /// Whether backups stored on the evacuated node are transferred along with their server.
+    pub transfer_backups: bool,
+    pub delete_source_backups: bool,
+    pub archive_format: wings_api::TransferArchiveFormat,
+    pub compression_level: Option<wings_api::CompressionLevel>,
+    pub multiplex_channels: u64,
+}
+
+#[derive(ToSchema, Serialize, Default)]
+pub struct NodeEvacuationProgress {
+    pub pending: i64,
+    pub transferring: i64,
+    pub completed: i64,
+    pub failed: i64,
+    pub cancelled: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NodeEvacuation {
+    pub uuid: uuid::Uuid,
+    pub node: Fetchable<super::node::Node>,
+    pub destination_location: Option<Fetchable<super::location::Location>>,
+    pub destination_node_uuids: Vec<uuid::Uuid>,
+
+    pub status: NodeEvacuationStatus,
+    pub concurrency: i16,
+    pub options: NodeEvacuationOptions,
+
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for NodeEvacuation {
+    const NAME: &'static str = "node_evacuation";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "node_evacuations.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "node_evacuations.node_uuid",
+                compact_str::format_compact!("{prefix}node_uuid"),
+            ),
+            (
+                "node_evacuations.destination_location_uuid",
+                compact_str::format_compact!("{prefix}destination_location_uuid"),
+            ),
+            (
+                "node_evacuations.destination_node_uuids",
+                compact_str::format_compact!("{prefix}destination_node_uuids"),
+            ),
+            (
+                "node_evacuations.status",
+                compact_str::format_compact!("{prefix}status"),
+            ),
+            (
+                "node_evacuations.concurrency",
+                compact_str::format_compact!("{prefix}concurrency"),
+            ),
+            (
+                "node_evacuations.options",
+                compact_str::format_compact!("{prefix}options"),
+            ),
+            (
+                "node_evacuations.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "node_evacuations.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            node: super::node::Node::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}node_uuid").as_str())?,
+            ),
+            destination_location: super::location::Location::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}destination_location_uuid"),
+            ),
+            destination_node_uuids: row
+                .try_get(compact_str::format_compact!("{prefix}destination_node_uuids").as_str())?,
+            status: row.try_get(compact_str::format_compact!("{prefix}status").as_str())?,
+            concurrency: row
+                .try_get(compact_str::format_compact!("{prefix}concurrency").as_str())?,
+            options: serde_json::from_value(
+                row.try_get(compact_str::format_compact!("{prefix}options").as_str())?,
+            )?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl NodeEvacuation {
+    /// Creates the evacuation and queues every server on the node that is not already being transferred.
+    pub async fn create(
+        database: &crate::database::Database,
+        node_uuid: uuid::Uuid,
+        destination_location_uuid: Option<uuid::Uuid>,
+        destination_node_uuids: &[uuid::Uuid],
+        concurrency: i16,
+        options: &NodeEvacuationOptions,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let mut transaction = database.write().begin().await?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO node_evacuations (node_uuid, destination_location_uuid, destination_node_uuids, concurrency, options)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(node_uuid)
+        .bind(destination_location_uuid)
+        .bind(destination_node_uuids)
+        .bind(concurrency)
+        .bind(serde_json::to_value(options)?)
+        .fetch_one(&mut *transaction)
+        .await?;
+        let evacuation = Self::map(None, &row)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO node_evacuation_servers (evacuation_uuid, server_uuid)
+            SELECT $1, servers.uuid
+            FROM servers
+            WHERE servers.node_uuid = $2 AND servers.destination_node_uuid IS NULL
+            ORDER BY servers.created
+            "#,
+        )
+        .bind(evacuation.uuid)
+        .bind(node_uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(evacuation)
+    }
+
+    pub async fn by_node_uuid_uuid(
+        database: &crate::database::Database,
+        node_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM node_evacuations
+            WHERE node_evacuations.node_uuid = $1 AND node_evacuations.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(node_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn active_by_node_uuid(
+        database: &crate::database::Database,
+        node_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM node_evacuations
+            WHERE node_evacuations.node_uuid = $1 AND node_evacuations.status IN ('RUNNING', 'PAUSED')
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(node_uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_node_uuid_with_pagination(
+        database: &crate::database::Database,
+        node_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM node_evacuations
+            WHERE node_evacuations.node_uuid = $1
+            ORDER BY node_evacuations.created DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(node_uuid)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn all_active(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM node_evacuations
+            WHERE node_evacuations.status IN ('RUNNING', 'PAUSED')
+            ORDER BY node_evacuations.created
+            "#,
+            Self::columns_sql(None)
+        ))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn progress(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<NodeEvacuationProgress, crate::database::DatabaseError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT node_evacuation_servers.status, COUNT(*) AS count
+            FROM node_evacuation_servers
+            WHERE node_evacuation_servers.evacuation_uuid = $1
+            GROUP BY node_evacuation_servers.status
+            "#,
+        )
+        .bind(self.uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        let mut progress = NodeEvacuationProgress::default();
+        for row in rows {
+            let count: i64 = row.try_get("count")?;
+
+            match row.try_get("status")? {
+                NodeEvacuationServerStatus::Pending => progress.pending = count,
+                NodeEvacuationServerStatus::Transferring => progress.transferring = count,
+                NodeEvacuationServerStatus::Completed => progress.completed = count,
+                NodeEvacuationServerStatus::Failed => progress.failed = count,
+                NodeEvacuationServerStatus::Cancelled => progress.cancelled = count,
+            }
+        }
+
+        Ok(progress)
+    }
+
+    pub async fn update(
+        &mut self,
+        database: &crate::database::Database,
+        status: NodeEvacuationStatus,
+        concurrency: i16,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE node_evacuations
+            SET status = $2, concurrency = $3
+            WHERE node_evacuations.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(status)
+        .bind(concurrency)
+        .execute(database.write())
+        .await?;
+
+        self.status = status;
+        self.concurrency = concurrency;
+
+        Ok(())
+    }
+
+    /// Cancels the evacuation, servers that are already being transferred finish their transfer.
+    pub async fn cancel(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE node_evacuations
+            SET status = 'CANCELLED', completed = NOW()
+            WHERE node_evacuations.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE node_evacuation_servers
+            SET status = 'CANCELLED', finished = NOW()
+            WHERE node_evacuation_servers.evacuation_uuid = $1 AND node_evacuation_servers.status = 'PENDING'
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        self.status = NodeEvacuationStatus::Cancelled;
+
+        Ok(())
+    }
+
+    /// Advances the evacuation: finished transfers are recorded, and while running, new transfers are
+    /// started until `concurrency` transfers are in flight. Once no server is left the evacuation completes.
+    pub async fn process(&self, state: &crate::State) -> Result<(), anyhow::Error> {
+        NodeEvacuationServer::sync_transferring(&state.database, self.uuid).await?;
+
+        let mut progress = self.progress(&state.database).await?;
+
+        if self.status == NodeEvacuationStatus::Running {
+            let slots = self.concurrency as i64 - progress.transferring;
+
+            if slots > 0 {
+                let pending = NodeEvacuationServer::pending_by_evacuation_uuid(
+                    &state.database,
+                    self.uuid,
+                    slots,
+                )
+                .await?;
+
+                for entry in pending {
+                    progress.pending -= 1;
+
+                    match self.start_transfer(state, &entry).await {
+                        Ok(Some(destination_node_uuid)) => {
+                            entry
+                                .set_transferring(&state.database, destination_node_uuid)
+                                .await?;
+                            progress.transferring += 1;
+                        }
+                        Ok(None) => {
+                            entry
+                                .set_finished(
+                                    &state.database,
+                                    NodeEvacuationServerStatus::Completed,
+                                    None,
+                                )
+                                .await?;
+                        }
+                        Err(err) => {
+                            tracing::warn!(
+                                evacuation = %self.uuid,
+                                server = %entry.server.uuid,
+                                "failed to start evacuation transfer: {:?}",
+                                err
+                            );
+
+                            entry
+                                .set_finished(
+                                    &state.database,
+                                    NodeEvacuationServerStatus::Failed,
+                                    Some(&err.to_string()),
+                                )
+                                .await?;
+                        }
+                    }
+                }
+            }
+        }
+
+        if progress.pending <= 0 && progress.transferring <= 0 {
+            sqlx::query(
+                r#"
+                UPDATE node_evacuations
+                SET status = 'COMPLETED', completed = NOW()
+                WHERE node_evacuations.uuid = $1
+                "#,
+            )
+            .bind(self.uuid)
+            .execute(state.database.write())
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Picks a destination node and matching free allocations for the server and starts its transfer,
+    /// returns the destination node or `None` when the server has already left the evacuated node.
+    async fn start_transfer(
+        &self,
+        state: &crate::State,
+        entry: &NodeEvacuationServer,
+    ) -> Result<Option<uuid::Uuid>, anyhow::Error> {
+        let server =
+            match super::server::Server::by_uuid_optional(&state.database, entry.server.uuid)
+                .await?
+            {
+                Some(server) => server,
+                None => return Err(anyhow::anyhow!("server no longer exists")),
+            };
+
+        if server.node.uuid != self.node.uuid {
+            return Ok(None);
+        }
+        if let Some(destination_node) = &server.destination_node {
+            return Ok(Some(destination_node.uuid));
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT node_allocations.port, server_allocations.uuid = servers.allocation_uuid AS "primary"
+            FROM server_allocations
+            JOIN node_allocations ON node_allocations.uuid = server_allocations.allocation_uuid
+            JOIN servers ON servers.uuid = server_allocations.server_uuid
+            WHERE server_allocations.server_uuid = $1
+            ORDER BY "primary" DESC, server_allocations.created
+            "#,
+        )
+        .bind(server.uuid)
+        .fetch_all(state.database.read())
+        .await?;
+        let has_primary = rows.first().is_some_and(|row| {
+            row.try_get::<Option<bool>, _>("primary").ok().flatten() == Some(true)
+        });
+        let ports = rows
+            .iter()
+            .map(|row| row.try_get::<i32, _>("port"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let plan = crate::deployment::plan_restricted(
+            &state.database,
+            &server.egg,
+            &crate::deployment::DeploymentRequest {
+                location_uuids: self
+                    .destination_location
+                    .as_ref()
+                    .map(|location| vec![location.uuid])
+                    .unwrap_or_default(),
+                memory: server.memory,
+                disk: server.disk,
+                allocations: ports.len() as i64,
+                port_start: Some(1),
+                port_end: Some(65535),
+            },
+            &self.destination_node_uuids,
+            &[self.node.uuid],
+        )
+        .await?;
+        let destination_node = match plan.node {
+            Some(node) => node,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "no destination node has enough free resources"
+                ));
+            }
+        };
+
+        // prefer allocations with the same port as before, fill the rest with whatever the planner picked
+        let mut same_port = super::node_allocation::NodeAllocation::available_by_node_uuid_ports(
+            &state.database,
+            destination_node.uuid,
+            &ports,
+        )
+        .await?;
+        let mut fallback = plan.allocations.into_iter();
+        let mut allocation_uuids = Vec::with_capacity(ports.len());
+
+        for port in &ports {
+            let allocation = match same_port.iter().position(|a| a.port == *port) {
+                Some(i) => Some(same_port.swap_remove(i)),
+                None => fallback.find(|a| {
+                    !same_port.iter().any(|s| s.uuid == a.uuid)
+                        && !allocation_uuids.contains(&a.uuid)
+                }),
+            };
+
+            match allocation {
+                Some(allocation) => allocation_uuids.push(allocation.uuid),
+                None => {
+                    return Err(anyhow::anyhow!(
+                        "not enough free allocations on the destination node"
+                    ));
+                }
+            }
+        }
+
+        let allocation_uuid = if has_primary && !allocation_uuids.is_empty() {
+            Some(allocation_uuids.remove(0))
+        } else {
+            None
+        };
+
+        let backups = if self.options.transfer_backups {
+            sqlx::query(
+                r#"
+                SELECT server_backups.uuid
+                FROM server_backups
+                WHERE
+                    server_backups.server_uuid = $1
+                    AND server_backups.node_uuid = $2
+                    AND server_backups.disk != 'S3'
+                    AND server_backups.completed IS NOT NULL
+                    AND server_backups.deleted IS NULL
+                "#,
+            )
+            .bind(server.uuid)
+            .bind(self.node.uuid)
+            .fetch_all(state.database.read())
+            .await?
+            .into_iter()
+            .map(|row| row.try_get("uuid"))
+            .collect::<Result<Vec<_>, _>>()?
+        } else {
+            Vec::new()
+        };
+
+        server
+            .transfer(
+                &state.database,
+                &state.jwt,
+                &destination_node,
+                allocation_uuid,
+                &allocation_uuids,
+                super::server::ServerTransferOptions {
+                    backups,
+                    delete_source_backups: self.options.delete_source_backups,
+                    archive_format: self.options.archive_format,
+                    compression_level: self.options.compression_level,
+                    multiplex_channels: self.options.multiplex_channels,
+                },
+            )
+            .await
+            .map_err(|_| anyhow::anyhow!("failed to start the transfer on the evacuated node"))?;
+
+        Ok(Some(destination_node.uuid))
+    }
+
+    #[inline]
+    pub async fn into_admin_api_object(
+        self,
+        database: &crate::database::Database,
+    ) -> Result<AdminApiNodeEvacuation, crate::database::DatabaseError> {
+        let progress = self.progress(database).await?;
+
+        Ok(AdminApiNodeEvacuation {
+            uuid: self.uuid,
+            node_uuid: self.node.uuid,
+            destination_location_uuid: self.destination_location.map(|location| location.uuid),
+            destination_node_uuids: self.destination_node_uuids,
+            status: self.status,
+            concurrency: self.concurrency,
+            options: self.options,
+            progress,
+            completed: self.completed.map(|dt| dt.and_utc()),
+            created: self.created.and_utc(),
+        })
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminNodeEvacuation")]
+pub struct AdminApiNodeEvacuation {
+    pub uuid: uuid::Uuid,
+    pub node_uuid: uuid::Uuid,
+    pub destination_location_uuid: Option<uuid::Uuid>,
+    pub destination_node_uuids: Vec<uuid::Uuid>,
+
+    pub status: NodeEvacuationStatus,
+    pub concurrency: i16,
+    pub options: NodeEvacuationOptions,
+    pub progress: NodeEvacuationProgress,
+
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,271 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "node_evacuation_server_status",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum NodeEvacuationServerStatus {
+    Pending,
+    Transferring,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NodeEvacuationServer {
+    pub uuid: uuid::Uuid,
+    pub server: Fetchable<super::server::Server>,
+    pub destination_node: Option<Fetchable<super::node::Node>>,
+
+    pub status: NodeEvacuationServerStatus,
+    pub error: Option<String>,
+
+    pub started: Option<chrono::NaiveDateTime>,
+    pub finished: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for NodeEvacuationServer {
+    const NAME: &'static str = "node_evacuation_server";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "node_evacuation_servers.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "node_evacuation_servers.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "node_evacuation_servers.destination_node_uuid",
+                compact_str::format_compact!("{prefix}destination_node_uuid"),
+            ),
+            (
+                "node_evacuation_servers.status",
+                compact_str::format_compact!("{prefix}status"),
+            ),
+            (
+                "node_evacuation_servers.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "node_evacuation_servers.started",
+                compact_str::format_compact!("{prefix}started"),
+            ),
+            (
+                "node_evacuation_servers.finished",
+                compact_str::format_compact!("{prefix}finished"),
+            ),
+            (
+                "node_evacuation_servers.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server: super::server::Server::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            ),
+            destination_node: super::node::Node::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}destination_node_uuid"),
+            ),
+            status: row.try_get(compact_str::format_compact!("{prefix}status").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            started: row.try_get(compact_str::format_compact!("{prefix}started").as_str())?,
+            finished: row.try_get(compact_str::format_compact!("{prefix}finished").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl NodeEvacuationServer {
+    pub async fn by_evacuation_uuid_with_pagination(
+        database: &crate::database::Database,
+        evacuation_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        status: Option<NodeEvacuationServerStatus>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM node_evacuation_servers
+            WHERE node_evacuation_servers.evacuation_uuid = $1 AND ($2 IS NULL OR node_evacuation_servers.status = $2)
+            ORDER BY node_evacuation_servers.created, node_evacuation_servers.uuid
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(evacuation_uuid)
+        .bind(status)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn pending_by_evacuation_uuid(
+        database: &crate::database::Database,
+        evacuation_uuid: uuid::Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM node_evacuation_servers
+            WHERE node_evacuation_servers.evacuation_uuid = $1 AND node_evacuation_servers.status = 'PENDING'
+            ORDER BY node_evacuation_servers.created, node_evacuation_servers.uuid
+            LIMIT $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(evacuation_uuid)
+        .bind(limit)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Finishes every transferring entry whose transfer is over, the server either ended up off the
+    /// evacuated node or its transfer was reset by the failure callback of the node.
+    pub async fn sync_transferring(
+        database: &crate::database::Database,
+        evacuation_uuid: uuid::Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            UPDATE node_evacuation_servers
+            SET
+                status = CASE
+                    WHEN servers.node_uuid != node_evacuations.node_uuid
+                    THEN 'COMPLETED'::node_evacuation_server_status
+                    ELSE 'FAILED'::node_evacuation_server_status
+                END,
+                error = CASE
+                    WHEN servers.node_uuid != node_evacuations.node_uuid THEN NULL
+                    ELSE 'transfer failed on the destination node'
+                END,
+                finished = NOW()
+            FROM servers, node_evacuations
+            WHERE
+                node_evacuation_servers.evacuation_uuid = $1
+                AND node_evacuation_servers.status = 'TRANSFERRING'
+                AND servers.uuid = node_evacuation_servers.server_uuid
+                AND node_evacuations.uuid = node_evacuation_servers.evacuation_uuid
+                AND (servers.node_uuid != node_evacuations.node_uuid OR servers.destination_node_uuid IS NULL)
+            "#,
+        )
+        .bind(evacuation_uuid)
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    pub async fn set_transferring(
+        &self,
+        database: &crate::database::Database,
+        destination_node_uuid: uuid::Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE node_evacuation_servers
+            SET status = 'TRANSFERRING', destination_node_uuid = $2, error = NULL, started = NOW()
+            WHERE node_evacuation_servers.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(destination_node_uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_finished(
+        &self,
+        database: &crate::database::Database,
+        status: NodeEvacuationServerStatus,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE node_evacuation_servers
+            SET status = $2, error = $3, finished = NOW()
+            WHERE node_evacuation_servers.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(status)
+        .bind(error)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(self) -> AdminApiNodeEvacuationServer {
+        AdminApiNodeEvacuationServer {
+            uuid: self.uuid,
+            server_uuid: self.server.uuid,
+            destination_node_uuid: self.destination_node.map(|node| node.uuid),
+            status: self.status,
+            error: self.error,
+            started: self.started.map(|dt| dt.and_utc()),
+            finished: self.finished.map(|dt| dt.and_utc()),
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminNodeEvacuationServer")]
+pub struct AdminApiNodeEvacuationServer {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: uuid::Uuid,
+    pub destination_node_uuid: Option<uuid::Uuid>,
+
+    pub status: NodeEvacuationServerStatus,
+    pub error: Option<String>,
+
+    pub started: Option<chrono::DateTime<chrono::Utc>>,
+    pub finished: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
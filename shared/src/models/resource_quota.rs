@@ -0,0 +1,481 @@
+use crate::prelude::*;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ResourceQuota {
+    pub uuid: uuid::Uuid,
+    pub user_uuid: Option<uuid::Uuid>,
+    pub role_uuid: Option<uuid::Uuid>,
+
+    pub servers: Option<i32>,
+    pub memory: Option<i64>,
+    pub disk: Option<i64>,
+    pub cpu: Option<i32>,
+    pub databases: Option<i32>,
+    pub backups: Option<i32>,
+    pub allocations: Option<i32>,
+    pub schedules: Option<i32>,
+
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for ResourceQuota {
+    const NAME: &'static str = "resource_quota";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "resource_quotas.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "resource_quotas.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "resource_quotas.role_uuid",
+                compact_str::format_compact!("{prefix}role_uuid"),
+            ),
+            (
+                "resource_quotas.servers",
+                compact_str::format_compact!("{prefix}servers"),
+            ),
+            (
+                "resource_quotas.memory",
+                compact_str::format_compact!("{prefix}memory"),
+            ),
+            (
+                "resource_quotas.disk",
+                compact_str::format_compact!("{prefix}disk"),
+            ),
+            (
+                "resource_quotas.cpu",
+                compact_str::format_compact!("{prefix}cpu"),
+            ),
+            (
+                "resource_quotas.databases",
+                compact_str::format_compact!("{prefix}databases"),
+            ),
+            (
+                "resource_quotas.backups",
+                compact_str::format_compact!("{prefix}backups"),
+            ),
+            (
+                "resource_quotas.allocations",
+                compact_str::format_compact!("{prefix}allocations"),
+            ),
+            (
+                "resource_quotas.schedules",
+                compact_str::format_compact!("{prefix}schedules"),
+            ),
+            (
+                "resource_quotas.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            user_uuid: row.try_get(compact_str::format_compact!("{prefix}user_uuid").as_str())?,
+            role_uuid: row.try_get(compact_str::format_compact!("{prefix}role_uuid").as_str())?,
+            servers: row.try_get(compact_str::format_compact!("{prefix}servers").as_str())?,
+            memory: row.try_get(compact_str::format_compact!("{prefix}memory").as_str())?,
+            disk: row.try_get(compact_str::format_compact!("{prefix}disk").as_str())?,
+            cpu: row.try_get(compact_str::format_compact!("{prefix}cpu").as_str())?,
+            databases: row.try_get(compact_str::format_compact!("{prefix}databases").as_str())?,
+            backups: row.try_get(compact_str::format_compact!("{prefix}backups").as_str())?,
+            allocations: row
+                .try_get(compact_str::format_compact!("{prefix}allocations").as_str())?,
+            schedules: row.try_get(compact_str::format_compact!("{prefix}schedules").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl ResourceQuota {
+    async fn upsert(
+        database: &crate::database::Database,
+        target: &str,
+        target_uuid: uuid::Uuid,
+        quota: &ApiResourceQuota,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO resource_quotas (
+                {target}, servers, memory, disk, cpu, databases, backups, allocations, schedules
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT ({target}) DO UPDATE SET
+                servers = EXCLUDED.servers,
+                memory = EXCLUDED.memory,
+                disk = EXCLUDED.disk,
+                cpu = EXCLUDED.cpu,
+                databases = EXCLUDED.databases,
+                backups = EXCLUDED.backups,
+                allocations = EXCLUDED.allocations,
+                schedules = EXCLUDED.schedules
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(target_uuid)
+        .bind(quota.servers)
+        .bind(quota.memory)
+        .bind(quota.disk)
+        .bind(quota.cpu)
+        .bind(quota.databases)
+        .bind(quota.backups)
+        .bind(quota.allocations)
+        .bind(quota.schedules)
+        .fetch_one(database.write())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    #[inline]
+    pub async fn upsert_for_user(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+        quota: &ApiResourceQuota,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        Self::upsert(database, "user_uuid", user_uuid, quota).await
+    }
+
+    #[inline]
+    pub async fn upsert_for_role(
+        database: &crate::database::Database,
+        role_uuid: uuid::Uuid,
+        quota: &ApiResourceQuota,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        Self::upsert(database, "role_uuid", role_uuid, quota).await
+    }
+
+    pub async fn by_user_uuid(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM resource_quotas
+            WHERE resource_quotas.user_uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(user_uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_role_uuid(
+        database: &crate::database::Database,
+        role_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM resource_quotas
+            WHERE resource_quotas.role_uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(role_uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    /// Returns the quota that applies to a user, a quota set on the user itself takes precedence over the one of its role.
+    #[inline]
+    pub async fn effective_by_user_uuid(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        Self::effective_by_user_uuid_with(database.read(), user_uuid).await
+    }
+
+    async fn effective_by_user_uuid_with(
+        executor: impl sqlx::PgExecutor<'_>,
+        user_uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM resource_quotas
+            WHERE resource_quotas.user_uuid = $1
+                OR resource_quotas.role_uuid = (SELECT users.role_uuid FROM users WHERE users.uuid = $1)
+            ORDER BY resource_quotas.user_uuid IS NULL
+            LIMIT 1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(user_uuid)
+        .fetch_optional(executor)
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    #[inline]
+    pub async fn usage_by_user_uuid(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+        exclude_server_uuid: Option<uuid::Uuid>,
+    ) -> Result<ApiResourceUsage, crate::database::DatabaseError> {
+        Self::usage_by_user_uuid_with(database.read(), user_uuid, exclude_server_uuid).await
+    }
+
+    async fn usage_by_user_uuid_with(
+        executor: impl sqlx::PgExecutor<'_>,
+        user_uuid: uuid::Uuid,
+        exclude_server_uuid: Option<uuid::Uuid>,
+    ) -> Result<ApiResourceUsage, crate::database::DatabaseError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) AS servers,
+                COALESCE(SUM(servers.memory), 0)::bigint AS memory,
+                COALESCE(SUM(servers.disk), 0)::bigint AS disk,
+                COALESCE(SUM(servers.cpu), 0)::bigint AS cpu,
+                COALESCE(SUM(servers.database_limit), 0)::bigint AS databases,
+                COALESCE(SUM(servers.backup_limit), 0)::bigint AS backups,
+                COALESCE(SUM(servers.allocation_limit), 0)::bigint AS allocations,
+                COALESCE(SUM(servers.schedule_limit), 0)::bigint AS schedules
+            FROM servers
+            WHERE servers.owner_uuid = $1 AND ($2 IS NULL OR servers.uuid != $2)
+            "#,
+        )
+        .bind(user_uuid)
+        .bind(exclude_server_uuid)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(ApiResourceUsage {
+            servers: row.try_get("servers")?,
+            memory: row.try_get("memory")?,
+            disk: row.try_get("disk")?,
+            cpu: row.try_get("cpu")?,
+            databases: row.try_get("databases")?,
+            backups: row.try_get("backups")?,
+            allocations: row.try_get("allocations")?,
+            schedules: row.try_get("schedules")?,
+        })
+    }
+
+    /// Checks whether a server with the given limits fits into the quota of its owner.
+    ///
+    /// The owner row is locked for the rest of `transaction`, so the server has to be written in the same transaction
+    /// to keep concurrent requests from exceeding the quota together.
+    ///
+    /// `exclude_server_uuid` should be set when the server already exists, so its current limits are not counted twice.
+    pub async fn enforce(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        owner_uuid: uuid::Uuid,
+        exclude_server_uuid: Option<uuid::Uuid>,
+        limits: &super::server::ApiServerLimits,
+        feature_limits: &super::server::ApiServerFeatureLimits,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query("SELECT 1 FROM users WHERE users.uuid = $1 FOR UPDATE")
+            .bind(owner_uuid)
+            .execute(&mut **transaction)
+            .await?;
+
+        let quota = match Self::effective_by_user_uuid_with(&mut **transaction, owner_uuid).await? {
+            Some(quota) => quota,
+            None => return Ok(()),
+        };
+
+        let usage =
+            Self::usage_by_user_uuid_with(&mut **transaction, owner_uuid, exclude_server_uuid)
+                .await?;
+
+        let checks = [
+            (
+                "servers",
+                quota.servers.map(i64::from),
+                usage.servers,
+                1,
+                false,
+            ),
+            ("memory", quota.memory, usage.memory, limits.memory, true),
+            ("disk", quota.disk, usage.disk, limits.disk, true),
+            (
+                "cpu",
+                quota.cpu.map(i64::from),
+                usage.cpu,
+                limits.cpu as i64,
+                true,
+            ),
+            (
+                "databases",
+                quota.databases.map(i64::from),
+                usage.databases,
+                feature_limits.databases as i64,
+                false,
+            ),
+            (
+                "backups",
+                quota.backups.map(i64::from),
+                usage.backups,
+                feature_limits.backups as i64,
+                false,
+            ),
+            (
+                "allocations",
+                quota.allocations.map(i64::from),
+                usage.allocations,
+                feature_limits.allocations as i64,
+                false,
+            ),
+            (
+                "schedules",
+                quota.schedules.map(i64::from),
+                usage.schedules,
+                feature_limits.schedules as i64,
+                false,
+            ),
+        ];
+
+        for (resource, max, used, requested, zero_is_unlimited) in checks {
+            let Some(max) = max else {
+                continue;
+            };
+
+            if zero_is_unlimited && requested == 0 {
+                return Err(anyhow::Error::new(
+                    crate::response::DisplayError::new(format!(
+                        "unlimited {resource} is not allowed by the resource quota of the server owner"
+                    ))
+                    .with_status(StatusCode::FORBIDDEN),
+                )
+                .into());
+            }
+
+            if used + requested > max {
+                return Err(anyhow::Error::new(
+                    crate::response::DisplayError::new(format!(
+                        "resource quota of the server owner exceeded for {resource} ({} / {max})",
+                        used + requested
+                    ))
+                    .with_status(StatusCode::FORBIDDEN),
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn into_api_object(self) -> ApiResourceQuota {
+        ApiResourceQuota {
+            servers: self.servers,
+            memory: self.memory,
+            disk: self.disk,
+            cpu: self.cpu,
+            databases: self.databases,
+            backups: self.backups,
+            allocations: self.allocations,
+            schedules: self.schedules,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ResourceQuota {
+    type DeleteOptions = ();
+
+    fn get_delete_listeners() -> &'static LazyLock<DeleteListenerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteListenerList<ResourceQuota>> =
+            LazyLock::new(|| Arc::new(ListenerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete(
+        &self,
+        database: &Arc<crate::database::Database>,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        self.run_delete_listeners(&options, database, &mut transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM resource_quotas
+            WHERE resource_quotas.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(ToSchema, Validate, Serialize, Deserialize)]
+#[schema(title = "ResourceQuota")]
+pub struct ApiResourceQuota {
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub servers: Option<i32>,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub memory: Option<i64>,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub disk: Option<i64>,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub cpu: Option<i32>,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub databases: Option<i32>,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub backups: Option<i32>,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub allocations: Option<i32>,
+    #[validate(range(min = 0))]
+    #[schema(minimum = 0)]
+    pub schedules: Option<i32>,
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ResourceUsage")]
+pub struct ApiResourceUsage {
+    pub servers: i64,
+    pub memory: i64,
+    pub disk: i64,
+    pub cpu: i64,
+    pub databases: i64,
+    pub backups: i64,
+    pub allocations: i64,
+    pub schedules: i64,
+}
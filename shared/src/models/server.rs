@@ -1,4 +1,5 @@
 use crate::{State, prelude::*, storage::StorageUrlRetriever};
+use compact_str::ToCompactString;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use sqlx::{Row, postgres::PgRow, prelude::Type};
@@ -9,6 +10,8 @@ use std::{
 use utoipa::ToSchema;
 use validator::Validate;
 
+const EGG_CHANGE_BACKUP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(12 * 60 * 60);
+
 pub type GetServer = crate::extract::ConsumingExtension<Server>;
 pub type GetServerActivityLogger = crate::extract::ConsumingExtension<ServerActivityLogger>;
 
@@ -92,6 +95,7 @@ pub struct Server {
 
     pub status: Option<ServerStatus>,
     pub suspended: bool,
+    pub suspension: Option<Fetchable<super::server_suspension::ServerSuspension>>,
 
     pub name: compact_str::CompactString,
     pub description: Option<compact_str::CompactString>,
@@ -163,6 +167,10 @@ impl BaseModel for Server {
                 "servers.suspended",
                 compact_str::format_compact!("{prefix}suspended"),
             ),
+            (
+                "servers.suspension_uuid",
+                compact_str::format_compact!("{prefix}suspension_uuid"),
+            ),
             ("servers.name", compact_str::format_compact!("{prefix}name")),
             (
                 "servers.description",
@@ -279,6 +287,10 @@ impl BaseModel for Server {
                 ),
             status: row.try_get(compact_str::format_compact!("{prefix}status").as_str())?,
             suspended: row.try_get(compact_str::format_compact!("{prefix}suspended").as_str())?,
+            suspension: super::server_suspension::ServerSuspension::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}suspension_uuid"),
+            ),
             name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
             description: row
                 .try_get(compact_str::format_compact!("{prefix}description").as_str())?,
@@ -342,6 +354,16 @@ impl Server {
         variables: &HashMap<uuid::Uuid, &'_ str>,
     ) -> Result<uuid::Uuid, crate::database::DatabaseError> {
         let mut transaction = database.write().begin().await?;
+
+        super::resource_quota::ResourceQuota::enforce(
+            &mut transaction,
+            owner_uuid,
+            None,
+            limits,
+            feature_limits,
+        )
+        .await?;
+
         let mut attempts = 0;
 
         loop {
@@ -519,6 +541,74 @@ impl Server {
         }
     }
 
+    /// Checks the current limits of the server against the resource quota of its owner, not counting the server twice.
+    ///
+    /// Nothing is checked unless the owner changed or a limit was raised compared to `previous`,
+    /// so servers that are already over quota can still be edited and scaled down. Without `previous`
+    /// the limits are always checked.
+    pub async fn enforce_resource_quota(
+        &self,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        previous: Option<&Self>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        fn raised(current: i64, previous: i64, zero_is_unlimited: bool) -> bool {
+            if zero_is_unlimited && (current == 0 || previous == 0) {
+                return current == 0 && previous != 0;
+            }
+
+            current > previous
+        }
+
+        if let Some(previous) = previous
+            && self.owner.uuid == previous.owner.uuid
+            && !raised(self.memory, previous.memory, true)
+            && !raised(self.disk, previous.disk, true)
+            && !raised(self.cpu as i64, previous.cpu as i64, true)
+            && !raised(
+                self.allocation_limit as i64,
+                previous.allocation_limit as i64,
+                false,
+            )
+            && !raised(
+                self.database_limit as i64,
+                previous.database_limit as i64,
+                false,
+            )
+            && !raised(
+                self.backup_limit as i64,
+                previous.backup_limit as i64,
+                false,
+            )
+            && !raised(
+                self.schedule_limit as i64,
+                previous.schedule_limit as i64,
+                false,
+            )
+        {
+            return Ok(());
+        }
+
+        super::resource_quota::ResourceQuota::enforce(
+            transaction,
+            self.owner.uuid,
+            Some(self.uuid),
+            &ApiServerLimits {
+                cpu: self.cpu,
+                memory: self.memory,
+                swap: self.swap,
+                disk: self.disk,
+                io_weight: self.io_weight,
+            },
+            &ApiServerFeatureLimits {
+                allocations: self.allocation_limit,
+                databases: self.database_limit,
+                backups: self.backup_limit,
+                schedules: self.schedule_limit,
+            },
+        )
+        .await
+    }
+
     pub async fn by_node_uuid_uuid(
         database: &crate::database::Database,
         node_uuid: uuid::Uuid,
@@ -1084,6 +1174,413 @@ impl Server {
         Ok(())
     }
 
+    /// Starts transferring the server to another node, returns the uuid of the new primary server allocation.
+    ///
+    /// If the source node refuses the transfer, the destination and the reserved allocations are released again.
+    pub async fn transfer(
+        &self,
+        database: &crate::database::Database,
+        jwt: &crate::jwt::Jwt,
+        destination_node: &super::node::Node,
+        allocation_uuid: Option<uuid::Uuid>,
+        allocation_uuids: &[uuid::Uuid],
+        options: ServerTransferOptions,
+    ) -> Result<Option<uuid::Uuid>, anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        let destination_allocation_uuid = if let Some(allocation_uuid) = allocation_uuid {
+            Some(
+                sqlx::query(
+                    r#"
+                    INSERT INTO server_allocations (server_uuid, allocation_uuid)
+                    VALUES ($1, $2)
+                    ON CONFLICT DO NOTHING
+                    RETURNING uuid
+                    "#,
+                )
+                .bind(self.uuid)
+                .bind(allocation_uuid)
+                .fetch_one(&mut *transaction)
+                .await?
+                .get::<uuid::Uuid, _>("uuid"),
+            )
+        } else {
+            None
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE servers
+            SET destination_node_uuid = $2, destination_allocation_uuid = $3
+            WHERE servers.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(destination_node.uuid)
+        .bind(destination_allocation_uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        if !allocation_uuids.is_empty() {
+            sqlx::query(
+                r#"
+                INSERT INTO server_allocations (server_uuid, allocation_uuid)
+                SELECT $1, UNNEST($2::uuid[])
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(self.uuid)
+            .bind(allocation_uuids)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let token = destination_node.create_jwt(
+            database,
+            jwt,
+            &crate::jwt::BasePayload {
+                issuer: "panel".into(),
+                subject: Some(self.uuid.to_string()),
+                audience: Vec::new(),
+                expiration_time: Some(chrono::Utc::now().timestamp() + 600),
+                not_before: None,
+                issued_at: Some(chrono::Utc::now().timestamp()),
+                jwt_id: self.node.uuid.to_string(),
+            },
+        )?;
+
+        transaction.commit().await?;
+
+        let mut url = destination_node.url.clone();
+        url.set_path("/api/transfers");
+
+        if let Err(err) = self
+            .node
+            .fetch_cached(database)
+            .await?
+            .api_client(database)
+            .post_servers_server_transfer(
+                self.uuid,
+                &wings_api::servers_server_transfer::post::RequestBody {
+                    url: url.to_compact_string(),
+                    token: format!("Bearer {token}").into(),
+                    backups: options.backups,
+                    delete_backups: options.delete_source_backups,
+                    archive_format: options.archive_format,
+                    compression_level: options.compression_level,
+                    multiplex_streams: options.multiplex_channels,
+                },
+            )
+            .await
+        {
+            tracing::error!(server = %self.uuid, "failed to transfer server to node: {:?}", err);
+
+            let mut transaction = database.write().begin().await?;
+
+            sqlx::query(
+                r#"
+                UPDATE servers
+                SET destination_node_uuid = NULL, destination_allocation_uuid = NULL
+                WHERE servers.uuid = $1
+                "#,
+            )
+            .bind(self.uuid)
+            .execute(&mut *transaction)
+            .await?;
+            sqlx::query(
+                r#"
+                DELETE FROM server_allocations
+                USING node_allocations
+                WHERE
+                    node_allocations.uuid = server_allocations.allocation_uuid
+                    AND server_allocations.server_uuid = $1
+                    AND node_allocations.node_uuid = $2
+                "#,
+            )
+            .bind(self.uuid)
+            .bind(destination_node.uuid)
+            .execute(&mut *transaction)
+            .await?;
+
+            transaction.commit().await?;
+
+            return Err(anyhow::Error::new(
+                crate::response::DisplayError::new("failed to transfer server to node")
+                    .with_status(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+            ));
+        }
+
+        Ok(destination_allocation_uuid)
+    }
+
+    /// Changes the egg of the server and reinstalls it, returns the backup that is taken beforehand.
+    ///
+    /// Variables of the new egg take their value from the overrides, then from the variable of the current
+    /// egg with the same env name, then from their default. The reinstall is started right away, or once
+    /// the backup has completed, a failed or timed out backup leaves the server on its current egg.
+    pub async fn change_egg(
+        self,
+        database: &Arc<crate::database::Database>,
+        egg: super::nest_egg::NestEgg,
+        options: ServerChangeEggOptions,
+    ) -> Result<Option<uuid::Uuid>, anyhow::Error> {
+        let egg_variables =
+            super::nest_egg_variable::NestEggVariable::all_by_egg_uuid(database, egg.uuid).await?;
+
+        for env_variable in options.variables.keys() {
+            match egg_variables
+                .iter()
+                .find(|variable| variable.env_variable == *env_variable)
+            {
+                Some(variable) if !options.restricted || variable.user_editable => {}
+                Some(_) => {
+                    return Err(crate::response::DisplayError::new(format!(
+                        "variables: {env_variable} is not editable"
+                    ))
+                    .with_status(axum::http::StatusCode::BAD_REQUEST)
+                    .into());
+                }
+                None => {
+                    return Err(crate::response::DisplayError::new(format!(
+                        "variables: egg has no variable named {env_variable}"
+                    ))
+                    .with_status(axum::http::StatusCode::BAD_REQUEST)
+                    .into());
+                }
+            }
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT nest_egg_variables.env_variable, server_variables.value
+            FROM server_variables
+            JOIN nest_egg_variables ON nest_egg_variables.uuid = server_variables.variable_uuid
+            WHERE server_variables.server_uuid = $1 AND nest_egg_variables.egg_uuid = $2
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(self.egg.uuid)
+        .fetch_all(database.read())
+        .await?;
+        let mut current_values = HashMap::new();
+        for row in rows {
+            current_values.insert(
+                row.try_get::<compact_str::CompactString, _>("env_variable")?,
+                row.try_get::<String, _>("value")?,
+            );
+        }
+
+        let values = egg_variables
+            .iter()
+            .map(|variable| {
+                let value = options
+                    .variables
+                    .get(&variable.env_variable)
+                    .or_else(|| current_values.get(&variable.env_variable))
+                    .or(variable.default_value.as_ref())
+                    .map_or("", |value| value.as_str());
+
+                (variable, value)
+            })
+            .collect::<Vec<_>>();
+
+        let validator = rule_validator::Validator::new(
+            values
+                .iter()
+                .map(|(variable, value)| {
+                    (
+                        variable.env_variable.as_str(),
+                        (variable.rules.as_slice(), *value),
+                    )
+                })
+                .collect(),
+        )
+        .and_then(|validator| {
+            validator.validate().map_err(|errors| {
+                errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+                    .into()
+            })
+        });
+        if let Err(error) = validator {
+            return Err(crate::response::DisplayError::new(error.to_string())
+                .with_status(axum::http::StatusCode::BAD_REQUEST)
+                .into());
+        }
+
+        let image = match options.image {
+            Some(image) => {
+                if options.restricted && !egg.docker_images.values().any(|i| *i == image) {
+                    return Err(crate::response::DisplayError::new(
+                        "image: not one of the images of the egg",
+                    )
+                    .with_status(axum::http::StatusCode::BAD_REQUEST)
+                    .into());
+                }
+
+                image
+            }
+            None if egg.docker_images.values().any(|i| *i == self.image) => self.image.clone(),
+            None => match egg.docker_images.values().next() {
+                Some(image) => image.clone(),
+                None => {
+                    return Err(crate::response::DisplayError::new(
+                        "egg does not have any docker images",
+                    )
+                    .with_status(axum::http::StatusCode::EXPECTATION_FAILED)
+                    .into());
+                }
+            },
+        };
+        let startup = options.startup.unwrap_or_else(|| egg.startup.clone());
+        let variables = values
+            .into_iter()
+            .map(|(variable, value)| (variable.uuid, value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        let rows_affected = sqlx::query(
+            r#"
+            UPDATE servers
+            SET status = 'INSTALLING'
+            WHERE servers.uuid = $1 AND servers.status IS NULL
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(crate::response::DisplayError::new(
+                "server is not in a valid state to change its egg.",
+            )
+            .with_status(axum::http::StatusCode::EXPECTATION_FAILED)
+            .into());
+        }
+
+        let change = ServerEggChange {
+            server_uuid: self.uuid,
+            node: self.node.clone(),
+            egg,
+            startup,
+            image,
+            variables,
+            truncate_directory: options.truncate_directory,
+        };
+
+        if !options.backup {
+            if let Err(err) = change.apply(database).await {
+                Self::reset_installing(database, self.uuid).await?;
+
+                return Err(err);
+            }
+
+            return Ok(None);
+        }
+
+        let backup = match super::server_backup::ServerBackup::create(
+            database,
+            self,
+            &format!("before changing egg to {}", change.egg.name),
+            Vec::new(),
+        )
+        .await
+        {
+            Ok(backup) => backup,
+            Err(err) => {
+                Self::reset_installing(database, change.server_uuid).await?;
+
+                return Err(err);
+            }
+        };
+
+        tokio::spawn({
+            let database = Arc::clone(database);
+            let backup_uuid = backup.uuid;
+
+            async move {
+                let started = std::time::Instant::now();
+
+                let backup = loop {
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+
+                    if started.elapsed() >= EGG_CHANGE_BACKUP_TIMEOUT {
+                        break Err(anyhow::anyhow!(
+                            "timed out waiting for the backup before the egg change"
+                        ));
+                    }
+
+                    match super::server_backup::ServerBackup::by_uuid_optional(
+                        &database,
+                        backup_uuid,
+                    )
+                    .await
+                    {
+                        Ok(Some(backup)) if backup.completed.is_none() => continue,
+                        Ok(backup) => break Ok(backup),
+                        Err(err) => {
+                            tracing::warn!(server = %change.server_uuid, backup = %backup_uuid, "failed to check egg change backup: {:?}", err);
+                        }
+                    }
+                };
+
+                let result = match backup {
+                    Ok(Some(backup)) if backup.successful => change.apply(&database).await,
+                    Ok(_) => Err(anyhow::anyhow!("the backup before the egg change failed")),
+                    Err(err) => Err(err),
+                };
+
+                if let Err(err) = result {
+                    tracing::error!(server = %change.server_uuid, "failed to change server egg: {:?}", err);
+
+                    if let Err(err) = Self::reset_installing(&database, change.server_uuid).await {
+                        tracing::error!(server = %change.server_uuid, "failed to reset server status: {:?}", err);
+                    }
+
+                    if let Err(err) = super::server_activity::ServerActivity::log(
+                        &database,
+                        change.server_uuid,
+                        None,
+                        None,
+                        "server:settings.egg-failed",
+                        None,
+                        serde_json::json!({
+                            "egg_uuid": change.egg.uuid,
+                            "error": err.to_string(),
+                        }),
+                    )
+                    .await
+                    {
+                        tracing::warn!(server = %change.server_uuid, "failed to log server activity: {:#?}", err);
+                    }
+                }
+            }
+        });
+
+        Ok(Some(backup.uuid))
+    }
+
+    async fn reset_installing(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE servers
+            SET status = NULL
+            WHERE servers.uuid = $1 AND servers.status = 'INSTALLING'
+            "#,
+        )
+        .bind(uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
     pub fn wings_permissions(&self, user: &super::user::User) -> Vec<&str> {
         let mut permissions = Vec::new();
         if user.admin {
@@ -1263,6 +1760,23 @@ impl Server {
             schedule_steps.insert(schedules[i].uuid, steps);
         }
 
+        // wings has no dedicated field for it, so the user-facing suspension message is passed along as labels
+        let mut labels = IndexMap::new();
+        if self.suspended
+            && let Some(suspension) = &self.suspension
+            && let Ok(suspension) = suspension.fetch_cached(database).await
+        {
+            if let Some(message) = suspension.message {
+                labels.insert("suspension.message".into(), message.into());
+            }
+            if let Some(expires) = suspension.expires {
+                labels.insert(
+                    "suspension.expires".into(),
+                    expires.and_utc().to_rfc3339().into(),
+                );
+            }
+        }
+
         Ok(RemoteApiServer {
             settings: wings_api::ServerConfiguration {
                 uuid: self.uuid,
@@ -1283,7 +1797,7 @@ impl Server {
                         )
                     })
                     .collect(),
-                labels: IndexMap::new(),
+                labels,
                 backups: backups.into_iter().map(|b| b.uuid).collect(),
                 schedules: schedules
                     .into_iter()
@@ -1388,7 +1902,7 @@ impl Server {
     ) -> Result<AdminApiServer, anyhow::Error> {
         let allocation_uuid = self.allocation.as_ref().map(|a| a.uuid);
 
-        let (node, backup_configuration, egg) = tokio::join!(
+        let (node, backup_configuration, egg, suspension) = tokio::join!(
             async {
                 match self.node.fetch_cached(database).await {
                     Ok(node) => Ok(node.into_admin_api_object(database).await?),
@@ -1411,7 +1925,13 @@ impl Server {
                     None
                 }
             },
-            self.egg.into_admin_api_object(database)
+            self.egg.into_admin_api_object(database),
+            async {
+                match &self.suspension {
+                    Some(suspension) => suspension.fetch_cached(database).await.ok(),
+                    None => None,
+                }
+            }
         );
 
         Ok(AdminApiServer {
@@ -1426,6 +1946,8 @@ impl Server {
             backup_configuration,
             status: self.status,
             suspended: self.suspended,
+            suspension: suspension
+                .map(|suspension| suspension.into_admin_api_object(storage_url_retriever)),
             name: self.name,
             description: self.description,
             limits: ApiServerLimits {
@@ -1459,6 +1981,10 @@ impl Server {
     ) -> Result<ApiServer, anyhow::Error> {
         let allocation_uuid = self.allocation.as_ref().map(|a| a.uuid);
         let node = self.node.fetch_cached(database).await?;
+        let suspension = match &self.suspension {
+            Some(suspension) => suspension.fetch_cached(database).await.ok(),
+            None => None,
+        };
 
         Ok(ApiServer {
             uuid: self.uuid,
@@ -1485,6 +2011,7 @@ impl Server {
             sftp_port: node.sftp_port,
             status: self.status,
             suspended: self.suspended,
+            suspension: suspension.map(|suspension| suspension.into_api_object()),
             name: self.name,
             description: self.description,
             limits: ApiServerLimits {
@@ -1538,6 +2065,198 @@ impl ByUuid for Server {
     }
 }
 
+pub struct ServerTransferOptions {
+    pub backups: Vec<uuid::Uuid>,
+    pub delete_source_backups: bool,
+    pub archive_format: wings_api::TransferArchiveFormat,
+    pub compression_level: Option<wings_api::CompressionLevel>,
+    pub multiplex_channels: u64,
+}
+
+#[derive(Default)]
+pub struct ServerChangeEggOptions {
+    pub startup: Option<compact_str::CompactString>,
+    pub image: Option<compact_str::CompactString>,
+    pub variables: IndexMap<compact_str::CompactString, String>,
+    /// Only allow overriding user editable variables and images of the egg.
+    pub restricted: bool,
+    pub truncate_directory: bool,
+    pub backup: bool,
+}
+
+struct ServerEggChange {
+    server_uuid: uuid::Uuid,
+    node: Fetchable<super::node::Node>,
+    egg: super::nest_egg::NestEgg,
+    startup: compact_str::CompactString,
+    image: compact_str::CompactString,
+    variables: HashMap<uuid::Uuid, String>,
+    truncate_directory: bool,
+}
+
+impl ServerEggChange {
+    /// Swaps the egg of the server and reinstalls it, if the server cannot be reinstalled
+    /// the previous egg, startup, image and variables are put back.
+    async fn apply(&self, database: &Arc<crate::database::Database>) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT servers.egg_uuid, servers.startup, servers.image
+            FROM servers
+            WHERE servers.uuid = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(self.server_uuid)
+        .fetch_one(&mut *transaction)
+        .await?;
+        let previous_egg_uuid: uuid::Uuid = row.try_get("egg_uuid")?;
+        let previous_startup: compact_str::CompactString = row.try_get("startup")?;
+        let previous_image: compact_str::CompactString = row.try_get("image")?;
+
+        let mut previous_variables = HashMap::new();
+        for row in sqlx::query(
+            r#"
+            SELECT server_variables.variable_uuid, server_variables.value
+            FROM server_variables
+            WHERE server_variables.server_uuid = $1
+            "#,
+        )
+        .bind(self.server_uuid)
+        .fetch_all(&mut *transaction)
+        .await?
+        {
+            previous_variables.insert(
+                row.try_get::<uuid::Uuid, _>("variable_uuid")?,
+                row.try_get::<String, _>("value")?,
+            );
+        }
+
+        Self::store(
+            &mut transaction,
+            self.server_uuid,
+            self.egg.uuid,
+            &self.startup,
+            &self.image,
+            &self.variables,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        if let Err(err) = self.reinstall(database).await {
+            tracing::error!(server = %self.server_uuid, "failed to reinstall server after egg change, restoring previous egg: {:?}", err);
+
+            let restored = async {
+                let mut transaction = database.write().begin().await?;
+
+                Self::store(
+                    &mut transaction,
+                    self.server_uuid,
+                    previous_egg_uuid,
+                    &previous_startup,
+                    &previous_image,
+                    &previous_variables,
+                )
+                .await?;
+
+                transaction.commit().await?;
+
+                Server::by_uuid(database, self.server_uuid)
+                    .await?
+                    .sync(database)
+                    .await
+            }
+            .await;
+
+            if let Err(restore_err) = restored {
+                tracing::error!(server = %self.server_uuid, "failed to restore previous egg: {:?}", restore_err);
+
+                return Err(err.context(
+                    "the egg was changed but the server could not be reinstalled, restoring the previous egg failed as well",
+                ));
+            }
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    async fn store(
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        server_uuid: uuid::Uuid,
+        egg_uuid: uuid::Uuid,
+        startup: &str,
+        image: &str,
+        variables: &HashMap<uuid::Uuid, String>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE servers
+            SET egg_uuid = $2, startup = $3, image = $4
+            WHERE servers.uuid = $1
+            "#,
+        )
+        .bind(server_uuid)
+        .bind(egg_uuid)
+        .bind(startup)
+        .bind(image)
+        .execute(&mut **transaction)
+        .await?;
+
+        sqlx::query("DELETE FROM server_variables WHERE server_variables.server_uuid = $1")
+            .bind(server_uuid)
+            .execute(&mut **transaction)
+            .await?;
+
+        for (variable_uuid, value) in variables {
+            sqlx::query(
+                r#"
+                INSERT INTO server_variables (server_uuid, variable_uuid, value)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(server_uuid)
+            .bind(variable_uuid)
+            .bind(value)
+            .execute(&mut **transaction)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn reinstall(
+        &self,
+        database: &Arc<crate::database::Database>,
+    ) -> Result<(), anyhow::Error> {
+        let server = Server::by_uuid(database, self.server_uuid).await?;
+        server.sync(database).await?;
+
+        self.node
+            .fetch_cached(database)
+            .await?
+            .api_client(database)
+            .post_servers_server_reinstall(
+                self.server_uuid,
+                &wings_api::servers_server_reinstall::post::RequestBody {
+                    truncate_directory: self.truncate_directory,
+                    installation_script: Some(wings_api::InstallationScript {
+                        container_image: self.egg.config_script.container.clone(),
+                        entrypoint: self.egg.config_script.entrypoint.clone(),
+                        script: self.egg.config_script.content.clone().into(),
+                        environment: Default::default(),
+                    }),
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
 #[derive(Default)]
 pub struct DeleteServerOptions {
     pub force: bool,
@@ -1671,6 +2390,7 @@ pub struct AdminApiServer {
 
     pub status: Option<ServerStatus>,
     pub suspended: bool,
+    pub suspension: Option<super::server_suspension::AdminApiServerSuspension>,
 
     pub name: compact_str::CompactString,
     pub description: Option<compact_str::CompactString>,
@@ -1701,6 +2421,7 @@ pub struct ApiServer {
 
     pub status: Option<ServerStatus>,
     pub suspended: bool,
+    pub suspension: Option<super::server_suspension::ApiServerSuspension>,
 
     pub is_owner: bool,
     pub permissions: Vec<compact_str::CompactString>,
@@ -84,6 +84,15 @@ impl ServerActivity {
         ip: Option<sqlx::types::ipnetwork::IpNetwork>,
         data: serde_json::Value,
     ) -> Result<(), crate::database::DatabaseError> {
+        let payload = serde_json::json!({
+            "server_uuid": server_uuid,
+            "user_uuid": user_uuid,
+            "api_key_uuid": api_key_uuid,
+            "ip": ip.map(|ip| ip.ip()),
+            "data": &data,
+            "created": chrono::Utc::now(),
+        });
+
         sqlx::query(
             r#"
             INSERT INTO server_activities (server_uuid, user_uuid, api_key_uuid, event, ip, data)
@@ -95,10 +104,34 @@ impl ServerActivity {
         .bind(api_key_uuid)
         .bind(event)
         .bind(ip)
-        .bind(data)
+        .bind(&data)
         .execute(database.write())
         .await?;
 
+        if let Err(err) = super::webhook_delivery::WebhookDelivery::enqueue(
+            database,
+            super::webhook_delivery::WebhookEventScope::Server,
+            event,
+            payload,
+        )
+        .await
+        {
+            tracing::warn!(event, "failed to queue webhook deliveries: {:#?}", err);
+        }
+
+        if let Some(notification_event) =
+            super::user_notification_channel::UserNotificationEvent::from_activity_event(event)
+            && let Err(err) = super::user_notification::UserNotification::enqueue(
+                database,
+                server_uuid,
+                notification_event,
+                &data,
+            )
+            .await
+        {
+            tracing::warn!(event, "failed to queue user notifications: {:#?}", err);
+        }
+
         Ok(())
     }
 
@@ -112,6 +145,15 @@ impl ServerActivity {
         data: serde_json::Value,
         timestamp: chrono::DateTime<chrono::Utc>,
     ) -> Result<(), crate::database::DatabaseError> {
+        let payload = serde_json::json!({
+            "server_uuid": server_uuid,
+            "user_uuid": user_uuid,
+            "api_key_uuid": null,
+            "ip": ip.map(|ip| ip.ip()),
+            "data": &data,
+            "created": timestamp,
+        });
+
         sqlx::query(
             r#"
             INSERT INTO server_activities (server_uuid, user_uuid, event, ip, data, created)
@@ -122,11 +164,35 @@ impl ServerActivity {
         .bind(user_uuid)
         .bind(event)
         .bind(ip)
-        .bind(data)
+        .bind(&data)
         .bind(timestamp.naive_utc())
         .execute(database.write())
         .await?;
 
+        if let Err(err) = super::webhook_delivery::WebhookDelivery::enqueue(
+            database,
+            super::webhook_delivery::WebhookEventScope::Server,
+            event,
+            payload,
+        )
+        .await
+        {
+            tracing::warn!(event, "failed to queue webhook deliveries: {:#?}", err);
+        }
+
+        if let Some(notification_event) =
+            super::user_notification_channel::UserNotificationEvent::from_activity_event(event)
+            && let Err(err) = super::user_notification::UserNotification::enqueue(
+                database,
+                server_uuid,
+                notification_event,
+                &data,
+            )
+            .await
+        {
+            tracing::warn!(event, "failed to queue user notifications: {:#?}", err);
+        }
+
         Ok(())
     }
 
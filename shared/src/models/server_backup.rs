@@ -655,6 +655,59 @@ impl ServerBackup {
         }
     }
 
+    /// Deletes every backup that is no longer covered by the retention policy of its backup configuration.
+    ///
+    /// Returns the amount of deleted backups.
+    pub async fn delete_expired(
+        database: &Arc<crate::database::Database>,
+    ) -> Result<usize, anyhow::Error> {
+        let backup_configurations =
+            super::backup_configurations::BackupConfiguration::all_with_retention_policy(database)
+                .await?;
+        let now = chrono::Utc::now().naive_utc();
+
+        let mut deleted = 0;
+        for backup_configuration in backup_configurations {
+            let rows = sqlx::query(&format!(
+                r#"
+                SELECT {}
+                FROM server_backups
+                WHERE server_backups.backup_configuration_uuid = $1
+                    AND server_backups.server_uuid IS NOT NULL
+                    AND server_backups.deleted IS NULL
+                ORDER BY server_backups.server_uuid, server_backups.created DESC
+                "#,
+                Self::columns_sql(None)
+            ))
+            .bind(backup_configuration.uuid)
+            .fetch_all(database.read())
+            .await?;
+
+            let backups = rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?;
+
+            for server_backups in backups.chunk_by(|a, b| {
+                a.server.as_ref().map(|s| s.uuid) == b.server.as_ref().map(|s| s.uuid)
+            }) {
+                for backup in backup_configuration
+                    .retention_policy
+                    .expired(server_backups, now)
+                {
+                    if let Err(err) = backup.delete(database, ()).await {
+                        tracing::error!(backup = %backup.uuid, "failed to delete expired backup: {:?}", err);
+                        continue;
+                    }
+
+                    deleted += 1;
+                }
+            }
+        }
+
+        Ok(deleted)
+    }
+
     #[inline]
     pub fn s3_path(server_uuid: uuid::Uuid, backup_uuid: uuid::Uuid) -> String {
         format!("{server_uuid}/{backup_uuid}.tar.gz")
@@ -0,0 +1,602 @@
+use crate::prelude::*;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(type_name = "server_clone_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ServerCloneStatus {
+    BackingUp,
+    RestoringFiles,
+    CopyingDatabases,
+    Completed,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerClone {
+    pub uuid: uuid::Uuid,
+    pub server: Option<Fetchable<super::server::Server>>,
+    pub clone_server: Fetchable<super::server::Server>,
+    pub backup: Option<Fetchable<super::server_backup::ServerBackup>>,
+
+    pub copy_files: bool,
+    pub copy_databases: bool,
+
+    pub status: ServerCloneStatus,
+    pub error: Option<String>,
+
+    pub completed: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for ServerClone {
+    const NAME: &'static str = "server_clone";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_clones.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_clones.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_clones.clone_server_uuid",
+                compact_str::format_compact!("{prefix}clone_server_uuid"),
+            ),
+            (
+                "server_clones.backup_uuid",
+                compact_str::format_compact!("{prefix}backup_uuid"),
+            ),
+            (
+                "server_clones.copy_files",
+                compact_str::format_compact!("{prefix}copy_files"),
+            ),
+            (
+                "server_clones.copy_databases",
+                compact_str::format_compact!("{prefix}copy_databases"),
+            ),
+            (
+                "server_clones.status",
+                compact_str::format_compact!("{prefix}status"),
+            ),
+            (
+                "server_clones.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "server_clones.completed",
+                compact_str::format_compact!("{prefix}completed"),
+            ),
+            (
+                "server_clones.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server: super::server::Server::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            clone_server: super::server::Server::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}clone_server_uuid").as_str())?,
+            ),
+            backup: super::server_backup::ServerBackup::get_fetchable_from_row(
+                row,
+                compact_str::format_compact!("{prefix}backup_uuid"),
+            ),
+            copy_files: row.try_get(compact_str::format_compact!("{prefix}copy_files").as_str())?,
+            copy_databases: row
+                .try_get(compact_str::format_compact!("{prefix}copy_databases").as_str())?,
+            status: row.try_get(compact_str::format_compact!("{prefix}status").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            completed: row.try_get(compact_str::format_compact!("{prefix}completed").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl ServerClone {
+    /// Creates a new server with the egg, variables, startup, limits and mounts of `server` on `node`.
+    ///
+    /// When files are copied the clone skips its installer and waits for a backup of the source server,
+    /// which is restored onto it by [`ServerClone::process`]. Copying files to a different node requires
+    /// the backup to be downloadable from there, which is only the case for S3 backups.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        database: &Arc<crate::database::Database>,
+        server: &super::server::Server,
+        node: &super::node::Node,
+        allocation_uuid: Option<uuid::Uuid>,
+        allocation_uuids: &[uuid::Uuid],
+        name: &str,
+        copy_files: bool,
+        copy_databases: bool,
+    ) -> Result<Self, anyhow::Error> {
+        if copy_files {
+            if server.status.is_some() {
+                return Err(crate::response::DisplayError::new(
+                    "server is not in a valid state to copy its files",
+                )
+                .with_status(StatusCode::CONFLICT)
+                .into());
+            }
+
+            let backup_configuration =
+                server.backup_configuration(database).await.ok_or_else(|| {
+                    crate::response::DisplayError::new(
+                        "no backup configuration available, unable to copy files",
+                    )
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                })?;
+
+            match backup_configuration.backup_disk {
+                super::server_backup::BackupDisk::S3 => {}
+                super::server_backup::BackupDisk::Local
+                | super::server_backup::BackupDisk::DdupBak
+                | super::server_backup::BackupDisk::Restic
+                    if node.uuid == server.node.uuid => {}
+                _ => {
+                    return Err(crate::response::DisplayError::new(
+                        "the backup configuration of this server does not allow copying files to this node",
+                    )
+                    .with_status(StatusCode::EXPECTATION_FAILED)
+                    .into());
+                }
+            }
+        }
+
+        let variables = super::server_variable::ServerVariable::all_by_server_uuid_egg_uuid(
+            database,
+            server.uuid,
+            server.egg.uuid,
+        )
+        .await?;
+
+        let clone_server_uuid = super::server::Server::create(
+            database,
+            node,
+            server.owner.uuid,
+            server.egg.uuid,
+            server.backup_configuration.as_ref().map(|b| b.uuid),
+            allocation_uuid,
+            allocation_uuids,
+            None,
+            false,
+            copy_files,
+            name,
+            server.description.as_deref(),
+            &super::server::ApiServerLimits {
+                cpu: server.cpu,
+                memory: server.memory,
+                swap: server.swap,
+                disk: server.disk,
+                io_weight: server.io_weight,
+            },
+            &server.pinned_cpus,
+            &server.startup,
+            &server.image,
+            server.timezone.as_deref(),
+            &super::server::ApiServerFeatureLimits {
+                allocations: server.allocation_limit,
+                databases: server.database_limit,
+                backups: server.backup_limit,
+                schedules: server.schedule_limit,
+            },
+            &variables
+                .iter()
+                .map(|variable| (variable.variable.uuid, variable.value.as_str()))
+                .collect::<HashMap<_, _>>(),
+        )
+        .await?;
+
+        let mount_uuids: Vec<uuid::Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT server_mounts.mount_uuid
+            FROM server_mounts
+            WHERE server_mounts.server_uuid = $1
+            "#,
+        )
+        .bind(server.uuid)
+        .fetch_all(database.read())
+        .await?;
+
+        for mount_uuid in mount_uuids {
+            if super::mount::Mount::by_node_uuid_egg_uuid_uuid(
+                database,
+                node.uuid,
+                server.egg.uuid,
+                mount_uuid,
+            )
+            .await?
+            .is_some()
+            {
+                super::server_mount::ServerMount::create(database, clone_server_uuid, mount_uuid)
+                    .await?;
+            }
+        }
+
+        let backup_uuid = if copy_files {
+            sqlx::query(
+                r#"
+                UPDATE servers
+                SET status = 'RESTORING_BACKUP'
+                WHERE servers.uuid = $1
+                "#,
+            )
+            .bind(clone_server_uuid)
+            .execute(database.write())
+            .await?;
+
+            let backup = super::server_backup::ServerBackup::create(
+                database,
+                server.clone(),
+                &format!("clone of {} into {name}", server.name),
+                Vec::new(),
+            )
+            .await?;
+
+            Some(backup.uuid)
+        } else {
+            None
+        };
+
+        let status = if copy_files {
+            ServerCloneStatus::BackingUp
+        } else if copy_databases {
+            ServerCloneStatus::CopyingDatabases
+        } else {
+            ServerCloneStatus::Completed
+        };
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO server_clones (server_uuid, clone_server_uuid, backup_uuid, copy_files, copy_databases, status, completed)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(server.uuid)
+        .bind(clone_server_uuid)
+        .bind(backup_uuid)
+        .bind(copy_files)
+        .bind(copy_databases)
+        .bind(status)
+        .bind(if status == ServerCloneStatus::Completed {
+            Some(chrono::Utc::now().naive_utc())
+        } else {
+            None
+        })
+        .fetch_one(database.write())
+        .await?;
+
+        let clone_server = super::server::Server::by_uuid(database, clone_server_uuid).await?;
+        tokio::spawn({
+            let database = Arc::clone(database);
+
+            async move {
+                if let Err(err) = clone_server.sync(&database).await {
+                    tracing::error!(server = %clone_server_uuid, "failed to sync cloned server: {:?}", err);
+                }
+            }
+        });
+
+        Ok(Self::map(None, &row)?)
+    }
+
+    pub async fn by_server_uuid_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM server_clones
+            WHERE (server_clones.server_uuid = $1 OR server_clones.clone_server_uuid = $1) AND server_clones.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(server_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    /// Lists the clones made of the server and the clone the server itself was created from.
+    pub async fn by_server_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_clones
+            WHERE server_clones.server_uuid = $1 OR server_clones.clone_server_uuid = $1
+            ORDER BY server_clones.created DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(server_uuid)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn all_active(
+        database: &crate::database::Database,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM server_clones
+            WHERE server_clones.status IN ('BACKING_UP', 'RESTORING_FILES', 'COPYING_DATABASES')
+            ORDER BY server_clones.created
+            "#,
+            Self::columns_sql(None)
+        ))
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    /// Records the result of a backup restore reported by the node of a clone that is restoring its files.
+    pub async fn finish_restore(
+        database: &crate::database::Database,
+        clone_server_uuid: uuid::Uuid,
+        successful: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE server_clones
+            SET
+                status = CASE
+                    WHEN $2 THEN 'COPYING_DATABASES'::server_clone_status
+                    ELSE 'FAILED'::server_clone_status
+                END,
+                error = CASE WHEN $2 THEN NULL ELSE 'failed to restore the files on the clone' END,
+                completed = CASE WHEN $2 THEN NULL ELSE NOW() END
+            WHERE server_clones.clone_server_uuid = $1 AND server_clones.status = 'RESTORING_FILES'
+            "#,
+        )
+        .bind(clone_server_uuid)
+        .bind(successful)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Advances the clone: once the backup of the source server is done it is restored onto the clone,
+    /// after the restore the databases are copied. Any failure ends the clone, the cloned server is kept.
+    pub async fn process(
+        &self,
+        database: &Arc<crate::database::Database>,
+    ) -> Result<(), anyhow::Error> {
+        let result = match self.status {
+            ServerCloneStatus::BackingUp => self.restore_files(database).await,
+            ServerCloneStatus::RestoringFiles => {
+                let clone_server =
+                    super::server::Server::by_uuid(database, self.clone_server.uuid).await?;
+
+                if clone_server.status == Some(super::server::ServerStatus::RestoringBackup) {
+                    return Ok(());
+                }
+
+                self.copy_databases(database).await
+            }
+            ServerCloneStatus::CopyingDatabases => self.copy_databases(database).await,
+            ServerCloneStatus::Completed | ServerCloneStatus::Failed => return Ok(()),
+        };
+
+        if let Err(err) = result {
+            tracing::warn!(clone = %self.uuid, "failed to clone server: {:?}", err);
+
+            self.finish(database, Some(&err.to_string())).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn restore_files(
+        &self,
+        database: &Arc<crate::database::Database>,
+    ) -> Result<(), anyhow::Error> {
+        let backup = match &self.backup {
+            Some(backup) => backup.fetch(database).await?,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "the backup of the source server was deleted"
+                ));
+            }
+        };
+
+        if backup.completed.is_none() {
+            return Ok(());
+        }
+        if !backup.successful {
+            return Err(anyhow::anyhow!("failed to back up the source server"));
+        }
+
+        let clone_server = super::server::Server::by_uuid(database, self.clone_server.uuid).await?;
+        backup.restore(database, clone_server, true).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE server_clones
+            SET status = 'RESTORING_FILES'
+            WHERE server_clones.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn copy_databases(
+        &self,
+        database: &Arc<crate::database::Database>,
+    ) -> Result<(), anyhow::Error> {
+        if self.copy_databases {
+            let server = self
+                .server
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("the source server was deleted"))?;
+            let clone_server =
+                super::server::Server::by_uuid(database, self.clone_server.uuid).await?;
+
+            for server_database in
+                super::server_database::ServerDatabase::all_by_server_uuid(database, server.uuid)
+                    .await?
+            {
+                let name = server_database
+                    .name
+                    .split_once('_')
+                    .map_or(server_database.name.as_str(), |(_, name)| name);
+
+                let clone_database_uuid = super::server_database::ServerDatabase::create(
+                    database,
+                    &clone_server,
+                    &server_database.database_host,
+                    name,
+                )
+                .await?;
+                let clone_database =
+                    super::server_database::ServerDatabase::by_uuid(database, clone_database_uuid)
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("cloned database not found"))?;
+
+                server_database.copy_into(database, &clone_database).await?;
+            }
+        }
+
+        self.finish(database, None).await?;
+
+        Ok(())
+    }
+
+    /// Ends the clone with the given error and removes the backup that was only made for it.
+    async fn finish(
+        &self,
+        database: &Arc<crate::database::Database>,
+        error: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE server_clones
+            SET status = $2, error = $3, completed = NOW()
+            WHERE server_clones.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(if error.is_some() {
+            ServerCloneStatus::Failed
+        } else {
+            ServerCloneStatus::Completed
+        })
+        .bind(error)
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE servers
+            SET status = NULL
+            WHERE servers.uuid = $1 AND servers.status = 'RESTORING_BACKUP'
+            "#,
+        )
+        .bind(self.clone_server.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        if let Some(backup) = &self.backup
+            && let Ok(backup) = backup.fetch(database).await
+            && let Err(err) = backup.delete(database, ()).await
+        {
+            tracing::warn!(clone = %self.uuid, backup = %backup.uuid, "failed to delete clone backup: {:?}", err);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn into_api_object(self) -> ApiServerClone {
+        ApiServerClone {
+            uuid: self.uuid,
+            server_uuid: self.server.map(|server| server.uuid),
+            clone_server_uuid: self.clone_server.uuid,
+            copy_files: self.copy_files,
+            copy_databases: self.copy_databases,
+            status: self.status,
+            error: self.error,
+            completed: self.completed.map(|dt| dt.and_utc()),
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerClone")]
+pub struct ApiServerClone {
+    pub uuid: uuid::Uuid,
+    pub server_uuid: Option<uuid::Uuid>,
+    pub clone_server_uuid: uuid::Uuid,
+
+    pub copy_files: bool,
+    pub copy_databases: bool,
+
+    pub status: ServerCloneStatus,
+    pub error: Option<String>,
+
+    pub completed: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
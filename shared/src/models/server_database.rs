@@ -6,7 +6,7 @@ use crate::{
 use rand::distr::SampleString;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, postgres::PgRow};
+use sqlx::{ConnectOptions, Connection, Row, postgres::PgRow};
 use std::{
     collections::BTreeMap,
     sync::{Arc, LazyLock},
@@ -455,6 +455,142 @@ impl ServerDatabase {
         }
     }
 
+    /// Copies the contents of this database into `target`, which has to live on the same database host.
+    ///
+    /// On MySQL every base table is recreated from its definition and filled with the source rows, views,
+    /// triggers and routines are not copied. On Postgres the target is recreated with this database as
+    /// its template, which fails while anything else is connected to the source database.
+    pub async fn copy_into(
+        &self,
+        database: &crate::database::Database,
+        target: &ServerDatabase,
+    ) -> Result<(), anyhow::Error> {
+        if self.database_host.uuid != target.database_host.uuid {
+            return Err(anyhow::anyhow!(
+                "unable to copy databases between different database hosts"
+            ));
+        }
+
+        if [&self.name, &self.username, &target.name, &target.username]
+            .iter()
+            .any(|value| value.contains(|c| ['"', '\'', '`'].contains(&c)))
+        {
+            return Err(anyhow::anyhow!(
+                "unable to copy database with escape characters"
+            ));
+        }
+
+        match self.database_host.get_connection(database).await? {
+            crate::models::database_host::DatabasePool::Mysql(pool) => {
+                let mut connection = pool.acquire().await?.detach();
+
+                let tables: Vec<String> = sqlx::query_scalar(
+                    "SELECT table_name FROM information_schema.tables WHERE table_schema = ? AND table_type = 'BASE TABLE'",
+                )
+                .bind(self.name.as_str())
+                .fetch_all(&mut connection)
+                .await?;
+
+                sqlx::raw_sql(&format!("USE `{}`", target.name))
+                    .execute(&mut connection)
+                    .await?;
+                sqlx::raw_sql("SET FOREIGN_KEY_CHECKS = 0")
+                    .execute(&mut connection)
+                    .await?;
+
+                for table in tables {
+                    if table.contains('`') {
+                        return Err(anyhow::anyhow!(
+                            "unable to copy table with escape characters"
+                        ));
+                    }
+
+                    let row = sqlx::query(&format!("SHOW CREATE TABLE `{}`.`{table}`", self.name))
+                        .fetch_one(&mut connection)
+                        .await?;
+                    let definition: String = row.try_get(1)?;
+
+                    sqlx::raw_sql(&definition).execute(&mut connection).await?;
+                    sqlx::query(&format!(
+                        "INSERT INTO `{}`.`{table}` SELECT * FROM `{}`.`{table}`",
+                        target.name, self.name
+                    ))
+                    .execute(&mut connection)
+                    .await?;
+                }
+            }
+            crate::models::database_host::DatabasePool::Postgres(pool) => {
+                sqlx::query(&format!("DROP DATABASE IF EXISTS \"{}\"", target.name))
+                    .execute(pool.as_ref())
+                    .await?;
+                sqlx::query(&format!(
+                    "CREATE DATABASE \"{}\" WITH TEMPLATE \"{}\" OWNER \"{}\"",
+                    target.name, self.name, target.username
+                ))
+                .execute(pool.as_ref())
+                .await?;
+
+                let password = database
+                    .decrypt(self.database_host.password.clone())
+                    .await?;
+                let mut connection = sqlx::postgres::PgConnectOptions::new()
+                    .host(&self.database_host.host)
+                    .port(self.database_host.port as u16)
+                    .username(&self.database_host.username)
+                    .password(&password)
+                    .database(&target.name)
+                    .connect()
+                    .await?;
+
+                // objects inside the copy still belong to the source user, REASSIGN OWNED would also
+                // hand over the source database itself, so only the relations and schemas are moved
+                sqlx::raw_sql(&format!(
+                    r#"
+                    DO $$
+                    DECLARE
+                        object record;
+                    BEGIN
+                        FOR object IN
+                            SELECT pg_class.oid::regclass AS name
+                            FROM pg_class
+                            JOIN pg_roles ON pg_roles.oid = pg_class.relowner
+                            WHERE pg_roles.rolname = '{source}'
+                                AND pg_class.relkind IN ('r', 'p', 'v', 'm', 'f', 'S')
+                                AND NOT EXISTS (
+                                    SELECT 1
+                                    FROM pg_depend
+                                    WHERE pg_depend.objid = pg_class.oid
+                                        AND pg_depend.classid = 'pg_class'::regclass
+                                        AND pg_depend.refclassid = 'pg_class'::regclass
+                                        AND pg_depend.deptype IN ('a', 'i')
+                                )
+                        LOOP
+                            EXECUTE format('ALTER TABLE %s OWNER TO %I', object.name, '{target}');
+                        END LOOP;
+
+                        FOR object IN
+                            SELECT pg_namespace.nspname AS name
+                            FROM pg_namespace
+                            JOIN pg_roles ON pg_roles.oid = pg_namespace.nspowner
+                            WHERE pg_roles.rolname = '{source}'
+                        LOOP
+                            EXECUTE format('ALTER SCHEMA %I OWNER TO %I', object.name, '{target}');
+                        END LOOP;
+                    END $$;
+                    "#,
+                    source = self.username,
+                    target = target.username,
+                ))
+                .execute(&mut connection)
+                .await?;
+
+                connection.close().await?;
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline]
     pub async fn into_admin_api_object(
         self,
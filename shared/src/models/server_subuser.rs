@@ -125,18 +125,12 @@ impl ServerSubuser {
                 {
                     Ok(token) => {
                         let settings = settings.get().await;
-
-                        let mail_content = crate::mail::MAIL_ACCOUNT_CREATED
-                            .replace("{{app_name}}", &settings.app.name)
-                            .replace("{{user_username}}", &user.username)
-                            .replace(
-                                "{{reset_link}}",
-                                &format!(
-                                    "{}/auth/reset-password?token={}",
-                                    settings.app.url,
-                                    urlencoding::encode(&token),
-                                ),
-                            );
+                        let reset_link = format!(
+                            "{}/auth/reset-password?token={}",
+                            settings.app.url,
+                            urlencoding::encode(&token),
+                        );
+                        drop(settings);
 
                         super::user_activity::UserActivity::log(
                             database,
@@ -148,10 +142,13 @@ impl ServerSubuser {
                         )
                         .await?;
 
-                        mail.send(
+                        mail.send_template(
                             user.email.clone(),
-                            format!("{} - Account Created", settings.app.name).into(),
-                            mail_content,
+                            "account_created",
+                            Some(&user),
+                            serde_json::json!({
+                                "reset_link": reset_link,
+                            }),
                         )
                         .await;
 
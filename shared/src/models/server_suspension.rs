@@ -0,0 +1,378 @@
+use crate::{prelude::*, storage::StorageUrlRetriever};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerSuspension {
+    pub uuid: uuid::Uuid,
+    pub server: Fetchable<super::server::Server>,
+    pub user: Option<super::user::User>,
+
+    pub reason: String,
+    pub message: Option<String>,
+
+    pub starts: chrono::NaiveDateTime,
+    pub expires: Option<chrono::NaiveDateTime>,
+    pub lifted: Option<chrono::NaiveDateTime>,
+
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for ServerSuspension {
+    const NAME: &'static str = "server_suspension";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        let mut columns = BTreeMap::from([
+            (
+                "server_suspensions.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_suspensions.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "server_suspensions.reason",
+                compact_str::format_compact!("{prefix}reason"),
+            ),
+            (
+                "server_suspensions.message",
+                compact_str::format_compact!("{prefix}message"),
+            ),
+            (
+                "server_suspensions.starts",
+                compact_str::format_compact!("{prefix}starts"),
+            ),
+            (
+                "server_suspensions.expires",
+                compact_str::format_compact!("{prefix}expires"),
+            ),
+            (
+                "server_suspensions.lifted",
+                compact_str::format_compact!("{prefix}lifted"),
+            ),
+            (
+                "server_suspensions.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ]);
+
+        columns.extend(super::user::User::columns(Some("user_")));
+
+        columns
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            server: super::server::Server::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            ),
+            user: if row.try_get::<uuid::Uuid, _>("user_uuid").is_ok() {
+                Some(super::user::User::map(Some("user_"), row)?)
+            } else {
+                None
+            },
+            reason: row.try_get(compact_str::format_compact!("{prefix}reason").as_str())?,
+            message: row.try_get(compact_str::format_compact!("{prefix}message").as_str())?,
+            starts: row.try_get(compact_str::format_compact!("{prefix}starts").as_str())?,
+            expires: row.try_get(compact_str::format_compact!("{prefix}expires").as_str())?,
+            lifted: row.try_get(compact_str::format_compact!("{prefix}lifted").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl ServerSuspension {
+    /// Creates a suspension record, the server itself is only suspended once [`Self::reconcile`] picks it up.
+    pub async fn create(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        user_uuid: Option<uuid::Uuid>,
+        reason: &str,
+        message: Option<&str>,
+        starts: Option<chrono::NaiveDateTime>,
+        expires: Option<chrono::NaiveDateTime>,
+    ) -> Result<uuid::Uuid, crate::database::DatabaseError> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO server_suspensions (server_uuid, user_uuid, reason, message, starts, expires)
+            VALUES ($1, $2, $3, $4, COALESCE($5, NOW()), $6)
+            RETURNING server_suspensions.uuid
+            "#,
+        )
+        .bind(server_uuid)
+        .bind(user_uuid)
+        .bind(reason)
+        .bind(message)
+        .bind(starts)
+        .bind(expires)
+        .fetch_one(database.write())
+        .await?;
+
+        Ok(row.try_get("uuid")?)
+    }
+
+    pub async fn by_server_uuid_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM server_suspensions
+            LEFT JOIN users ON users.uuid = server_suspensions.user_uuid
+            LEFT JOIN roles ON roles.uuid = users.role_uuid
+            WHERE server_suspensions.server_uuid = $1 AND server_suspensions.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(server_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_server_uuid_with_pagination(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_suspensions
+            LEFT JOIN users ON users.uuid = server_suspensions.user_uuid
+            LEFT JOIN roles ON roles.uuid = users.role_uuid
+            WHERE server_suspensions.server_uuid = $1
+            ORDER BY server_suspensions.starts DESC, server_suspensions.created DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(server_uuid)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    /// Lifts every suspension of the server that is currently in effect.
+    pub async fn lift_active_by_server_uuid(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE server_suspensions
+            SET lifted = NOW()
+            WHERE server_suspensions.server_uuid = $1
+                AND server_suspensions.lifted IS NULL
+                AND server_suspensions.starts <= NOW()
+            "#,
+        )
+        .bind(server_uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Brings `servers.suspended` in line with the suspension records, starting due suspensions
+    /// and ending expired or lifted ones. Returns the uuids of the servers whose state changed.
+    ///
+    /// Servers without any suspension records are left untouched.
+    pub async fn reconcile(
+        database: &crate::database::Database,
+        server_uuid: Option<uuid::Uuid>,
+    ) -> Result<Vec<uuid::Uuid>, crate::database::DatabaseError> {
+        let rows = sqlx::query(
+            r#"
+            WITH desired AS (
+                SELECT servers.uuid AS server_uuid, (
+                    SELECT server_suspensions.uuid
+                    FROM server_suspensions
+                    WHERE server_suspensions.server_uuid = servers.uuid
+                        AND server_suspensions.lifted IS NULL
+                        AND server_suspensions.starts <= NOW()
+                        AND (server_suspensions.expires IS NULL OR server_suspensions.expires > NOW())
+                    ORDER BY server_suspensions.expires DESC NULLS FIRST
+                    LIMIT 1
+                ) AS suspension_uuid
+                FROM servers
+                WHERE ($1::uuid IS NULL OR servers.uuid = $1)
+                    AND EXISTS (SELECT 1 FROM server_suspensions WHERE server_suspensions.server_uuid = servers.uuid)
+            )
+            UPDATE servers
+            SET suspended = desired.suspension_uuid IS NOT NULL, suspension_uuid = desired.suspension_uuid
+            FROM desired
+            WHERE servers.uuid = desired.server_uuid
+                AND (
+                    servers.suspended != (desired.suspension_uuid IS NOT NULL)
+                    OR servers.suspension_uuid IS DISTINCT FROM desired.suspension_uuid
+                )
+            RETURNING servers.uuid
+            "#,
+        )
+        .bind(server_uuid)
+        .fetch_all(database.write())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| row.try_get("uuid"))
+            .try_collect_vec()?)
+    }
+
+    /// Pushes the current configuration of the given servers to their nodes, used after [`Self::reconcile`].
+    pub async fn sync_servers(database: &crate::database::Database, server_uuids: Vec<uuid::Uuid>) {
+        for server_uuid in server_uuids {
+            let server = match super::server::Server::by_uuid_optional(database, server_uuid).await
+            {
+                Ok(Some(server)) => server,
+                Ok(None) => continue,
+                Err(err) => {
+                    tracing::warn!(server = %server_uuid, "failed to fetch suspended server: {:#?}", err);
+                    continue;
+                }
+            };
+
+            if let Err(err) = server.sync(database).await {
+                tracing::warn!(server = %server_uuid, "failed to sync server suspension on node: {:#?}", err);
+            }
+        }
+    }
+
+    pub async fn lift(
+        &mut self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE server_suspensions
+            SET lifted = NOW()
+            WHERE server_suspensions.uuid = $1 AND server_suspensions.lifted IS NULL
+            RETURNING server_suspensions.lifted
+            "#,
+        )
+        .bind(self.uuid)
+        .fetch_optional(database.write())
+        .await?;
+
+        if let Some(row) = row {
+            self.lifted = Some(row.try_get("lifted")?);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        let now = chrono::Utc::now().naive_utc();
+
+        self.lifted.is_none()
+            && self.starts <= now
+            && self.expires.is_none_or(|expires| expires > now)
+    }
+
+    #[inline]
+    pub fn into_api_object(self) -> ApiServerSuspension {
+        ApiServerSuspension {
+            message: self.message,
+            expires: self.expires.map(|dt| dt.and_utc()),
+        }
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(
+        self,
+        storage_url_retriever: &StorageUrlRetriever<'_>,
+    ) -> AdminApiServerSuspension {
+        AdminApiServerSuspension {
+            active: self.is_active(),
+            uuid: self.uuid,
+            user: self
+                .user
+                .map(|user| user.into_api_object(storage_url_retriever)),
+            reason: self.reason,
+            message: self.message,
+            starts: self.starts.and_utc(),
+            expires: self.expires.map(|dt| dt.and_utc()),
+            lifted: self.lifted.map(|dt| dt.and_utc()),
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for ServerSuspension {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM server_suspensions
+            LEFT JOIN users ON users.uuid = server_suspensions.user_uuid
+            LEFT JOIN roles ON roles.uuid = users.role_uuid
+            WHERE server_suspensions.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "ServerSuspension")]
+pub struct ApiServerSuspension {
+    pub message: Option<String>,
+    pub expires: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminServerSuspension")]
+pub struct AdminApiServerSuspension {
+    pub uuid: uuid::Uuid,
+    pub user: Option<super::user::ApiUser>,
+
+    pub reason: String,
+    pub message: Option<String>,
+    pub active: bool,
+
+    pub starts: chrono::DateTime<chrono::Utc>,
+    pub expires: Option<chrono::DateTime<chrono::Utc>>,
+    pub lifted: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,448 @@
+use crate::prelude::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerTemplate {
+    pub uuid: uuid::Uuid,
+    pub egg: Fetchable<super::nest_egg::NestEgg>,
+    pub backup_configuration: Option<Fetchable<super::backup_configurations::BackupConfiguration>>,
+
+    pub name: compact_str::CompactString,
+    pub description: Option<compact_str::CompactString>,
+
+    pub memory: i64,
+    pub swap: i64,
+    pub disk: i64,
+    pub io_weight: Option<i16>,
+    pub cpu: i32,
+    pub pinned_cpus: Vec<i16>,
+
+    pub startup: Option<compact_str::CompactString>,
+    pub image: Option<compact_str::CompactString>,
+    pub timezone: Option<compact_str::CompactString>,
+
+    pub allocation_limit: i32,
+    pub database_limit: i32,
+    pub backup_limit: i32,
+    pub schedule_limit: i32,
+
+    pub variables: IndexMap<compact_str::CompactString, String>,
+    pub mount_uuids: Vec<uuid::Uuid>,
+
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for ServerTemplate {
+    const NAME: &'static str = "server_template";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "server_templates.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "server_templates.egg_uuid",
+                compact_str::format_compact!("{prefix}egg_uuid"),
+            ),
+            (
+                "server_templates.backup_configuration_uuid",
+                compact_str::format_compact!("{prefix}backup_configuration_uuid"),
+            ),
+            (
+                "server_templates.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "server_templates.description",
+                compact_str::format_compact!("{prefix}description"),
+            ),
+            (
+                "server_templates.memory",
+                compact_str::format_compact!("{prefix}memory"),
+            ),
+            (
+                "server_templates.swap",
+                compact_str::format_compact!("{prefix}swap"),
+            ),
+            (
+                "server_templates.disk",
+                compact_str::format_compact!("{prefix}disk"),
+            ),
+            (
+                "server_templates.io_weight",
+                compact_str::format_compact!("{prefix}io_weight"),
+            ),
+            (
+                "server_templates.cpu",
+                compact_str::format_compact!("{prefix}cpu"),
+            ),
+            (
+                "server_templates.pinned_cpus",
+                compact_str::format_compact!("{prefix}pinned_cpus"),
+            ),
+            (
+                "server_templates.startup",
+                compact_str::format_compact!("{prefix}startup"),
+            ),
+            (
+                "server_templates.image",
+                compact_str::format_compact!("{prefix}image"),
+            ),
+            (
+                "server_templates.timezone",
+                compact_str::format_compact!("{prefix}timezone"),
+            ),
+            (
+                "server_templates.allocation_limit",
+                compact_str::format_compact!("{prefix}allocation_limit"),
+            ),
+            (
+                "server_templates.database_limit",
+                compact_str::format_compact!("{prefix}database_limit"),
+            ),
+            (
+                "server_templates.backup_limit",
+                compact_str::format_compact!("{prefix}backup_limit"),
+            ),
+            (
+                "server_templates.schedule_limit",
+                compact_str::format_compact!("{prefix}schedule_limit"),
+            ),
+            (
+                "server_templates.variables",
+                compact_str::format_compact!("{prefix}variables"),
+            ),
+            (
+                "server_templates.mount_uuids",
+                compact_str::format_compact!("{prefix}mount_uuids"),
+            ),
+            (
+                "server_templates.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            egg: super::nest_egg::NestEgg::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}egg_uuid").as_str())?,
+            ),
+            backup_configuration:
+                super::backup_configurations::BackupConfiguration::get_fetchable_from_row(
+                    row,
+                    compact_str::format_compact!("{prefix}backup_configuration_uuid"),
+                ),
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            description: row
+                .try_get(compact_str::format_compact!("{prefix}description").as_str())?,
+            memory: row.try_get(compact_str::format_compact!("{prefix}memory").as_str())?,
+            swap: row.try_get(compact_str::format_compact!("{prefix}swap").as_str())?,
+            disk: row.try_get(compact_str::format_compact!("{prefix}disk").as_str())?,
+            io_weight: row.try_get(compact_str::format_compact!("{prefix}io_weight").as_str())?,
+            cpu: row.try_get(compact_str::format_compact!("{prefix}cpu").as_str())?,
+            pinned_cpus: row
+                .try_get(compact_str::format_compact!("{prefix}pinned_cpus").as_str())?,
+            startup: row.try_get(compact_str::format_compact!("{prefix}startup").as_str())?,
+            image: row.try_get(compact_str::format_compact!("{prefix}image").as_str())?,
+            timezone: row.try_get(compact_str::format_compact!("{prefix}timezone").as_str())?,
+            allocation_limit: row
+                .try_get(compact_str::format_compact!("{prefix}allocation_limit").as_str())?,
+            database_limit: row
+                .try_get(compact_str::format_compact!("{prefix}database_limit").as_str())?,
+            backup_limit: row
+                .try_get(compact_str::format_compact!("{prefix}backup_limit").as_str())?,
+            schedule_limit: row
+                .try_get(compact_str::format_compact!("{prefix}schedule_limit").as_str())?,
+            variables: serde_json::from_value(row.try_get::<serde_json::Value, _>(
+                compact_str::format_compact!("{prefix}variables").as_str(),
+            )?)?,
+            mount_uuids: row
+                .try_get(compact_str::format_compact!("{prefix}mount_uuids").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl ServerTemplate {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        database: &crate::database::Database,
+        egg_uuid: uuid::Uuid,
+        backup_configuration_uuid: Option<uuid::Uuid>,
+        name: &str,
+        description: Option<&str>,
+        limits: &super::server::ApiServerLimits,
+        pinned_cpus: &[i16],
+        startup: Option<&str>,
+        image: Option<&str>,
+        timezone: Option<&str>,
+        feature_limits: &super::server::ApiServerFeatureLimits,
+        variables: &IndexMap<compact_str::CompactString, String>,
+        mount_uuids: &[uuid::Uuid],
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO server_templates (
+                egg_uuid, backup_configuration_uuid, name, description,
+                memory, swap, disk, io_weight, cpu, pinned_cpus,
+                startup, image, timezone,
+                allocation_limit, database_limit, backup_limit, schedule_limit,
+                variables, mount_uuids
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(egg_uuid)
+        .bind(backup_configuration_uuid)
+        .bind(name)
+        .bind(description)
+        .bind(limits.memory)
+        .bind(limits.swap)
+        .bind(limits.disk)
+        .bind(limits.io_weight)
+        .bind(limits.cpu)
+        .bind(pinned_cpus)
+        .bind(startup)
+        .bind(image)
+        .bind(timezone)
+        .bind(feature_limits.allocations)
+        .bind(feature_limits.databases)
+        .bind(feature_limits.backups)
+        .bind(feature_limits.schedules)
+        .bind(serde_json::to_value(variables)?)
+        .bind(mount_uuids)
+        .fetch_one(database.write())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    pub async fn all_with_pagination(
+        database: &crate::database::Database,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM server_templates
+            WHERE ($1 IS NULL OR server_templates.name ILIKE '%' || $1 || '%')
+            ORDER BY server_templates.created
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    pub async fn save(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE server_templates
+            SET
+                egg_uuid = $1, backup_configuration_uuid = $2, name = $3, description = $4,
+                memory = $5, swap = $6, disk = $7, io_weight = $8, cpu = $9, pinned_cpus = $10,
+                startup = $11, image = $12, timezone = $13,
+                allocation_limit = $14, database_limit = $15, backup_limit = $16, schedule_limit = $17,
+                variables = $18, mount_uuids = $19
+            WHERE server_templates.uuid = $20
+            "#,
+        )
+        .bind(self.egg.uuid)
+        .bind(
+            self.backup_configuration
+                .as_ref()
+                .map(|backup_configuration| backup_configuration.uuid),
+        )
+        .bind(&self.name)
+        .bind(self.description.as_deref())
+        .bind(self.memory)
+        .bind(self.swap)
+        .bind(self.disk)
+        .bind(self.io_weight)
+        .bind(self.cpu)
+        .bind(&self.pinned_cpus)
+        .bind(self.startup.as_deref())
+        .bind(self.image.as_deref())
+        .bind(self.timezone.as_deref())
+        .bind(self.allocation_limit)
+        .bind(self.database_limit)
+        .bind(self.backup_limit)
+        .bind(self.schedule_limit)
+        .bind(serde_json::to_value(&self.variables)?)
+        .bind(&self.mount_uuids)
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn limits(&self) -> super::server::ApiServerLimits {
+        super::server::ApiServerLimits {
+            cpu: self.cpu,
+            memory: self.memory,
+            swap: self.swap,
+            disk: self.disk,
+            io_weight: self.io_weight,
+        }
+    }
+
+    #[inline]
+    pub fn feature_limits(&self) -> super::server::ApiServerFeatureLimits {
+        super::server::ApiServerFeatureLimits {
+            allocations: self.allocation_limit,
+            databases: self.database_limit,
+            backups: self.backup_limit,
+            schedules: self.schedule_limit,
+        }
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(self) -> AdminApiServerTemplate {
+        AdminApiServerTemplate {
+            limits: self.limits(),
+            feature_limits: self.feature_limits(),
+            uuid: self.uuid,
+            egg_uuid: self.egg.uuid,
+            backup_configuration_uuid: self
+                .backup_configuration
+                .map(|backup_configuration| backup_configuration.uuid),
+            name: self.name,
+            description: self.description,
+            pinned_cpus: self.pinned_cpus,
+            startup: self.startup,
+            image: self.image,
+            timezone: self.timezone,
+            variables: self.variables,
+            mount_uuids: self.mount_uuids,
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for ServerTemplate {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM server_templates
+            WHERE server_templates.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for ServerTemplate {
+    type DeleteOptions = ();
+
+    fn get_delete_listeners() -> &'static LazyLock<DeleteListenerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteListenerList<ServerTemplate>> =
+            LazyLock::new(|| Arc::new(ListenerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete(
+        &self,
+        database: &Arc<crate::database::Database>,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        self.run_delete_listeners(&options, database, &mut transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM server_templates
+            WHERE server_templates.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminServerTemplate")]
+pub struct AdminApiServerTemplate {
+    pub uuid: uuid::Uuid,
+    pub egg_uuid: uuid::Uuid,
+    pub backup_configuration_uuid: Option<uuid::Uuid>,
+
+    pub name: compact_str::CompactString,
+    pub description: Option<compact_str::CompactString>,
+
+    #[schema(inline)]
+    pub limits: super::server::ApiServerLimits,
+    pub pinned_cpus: Vec<i16>,
+    #[schema(inline)]
+    pub feature_limits: super::server::ApiServerFeatureLimits,
+
+    pub startup: Option<compact_str::CompactString>,
+    pub image: Option<compact_str::CompactString>,
+    pub timezone: Option<compact_str::CompactString>,
+
+    pub variables: IndexMap<compact_str::CompactString, String>,
+    pub mount_uuids: Vec<uuid::Uuid>,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -120,6 +120,7 @@ impl ServerVariable {
             value: self.value,
             is_editable: self.variable.user_editable,
             is_secret: self.variable.secret,
+            rules_schema: rule_validator::rules_json_schema(&self.variable.rules),
             rules: self.variable.rules,
             created: self.created.and_utc(),
         }
@@ -138,6 +139,8 @@ pub struct ApiServerVariable {
     pub is_editable: bool,
     pub is_secret: bool,
     pub rules: Vec<compact_str::CompactString>,
+    /// The rules compiled to a JSON Schema, for validating values before submitting them.
+    pub rules_schema: serde_json::Value,
 
     pub created: chrono::DateTime<chrono::Utc>,
 }
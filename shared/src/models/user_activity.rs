@@ -101,6 +101,14 @@ impl UserActivity {
         ip: Option<sqlx::types::ipnetwork::IpNetwork>,
         data: serde_json::Value,
     ) -> Result<(), crate::database::DatabaseError> {
+        let payload = serde_json::json!({
+            "user_uuid": user_uuid,
+            "api_key_uuid": api_key_uuid,
+            "ip": ip.map(|ip| ip.ip()),
+            "data": &data,
+            "created": chrono::Utc::now(),
+        });
+
         sqlx::query(
             r#"
             INSERT INTO user_activities (user_uuid, api_key_uuid, event, ip, data, created)
@@ -115,6 +123,17 @@ impl UserActivity {
         .execute(database.write())
         .await?;
 
+        if let Err(err) = super::webhook_delivery::WebhookDelivery::enqueue(
+            database,
+            super::webhook_delivery::WebhookEventScope::User,
+            event,
+            payload,
+        )
+        .await
+        {
+            tracing::warn!(event, "failed to queue webhook deliveries: {:#?}", err);
+        }
+
         Ok(())
     }
 
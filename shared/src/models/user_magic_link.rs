@@ -0,0 +1,159 @@
+use crate::prelude::*;
+use rand::distr::SampleString;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize)]
+pub struct UserMagicLink {
+    pub uuid: uuid::Uuid,
+    pub user: super::user::User,
+
+    pub token: String,
+
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for UserMagicLink {
+    const NAME: &'static str = "user_magic_link";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        let mut columns = BTreeMap::from([
+            (
+                "user_magic_links.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "user_magic_links.token",
+                compact_str::format_compact!("{prefix}token"),
+            ),
+            (
+                "user_magic_links.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ]);
+
+        columns.extend(super::user::User::columns(Some("user_")));
+
+        columns
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            user: super::user::User::map(Some("user_"), row)?,
+            token: row.try_get(compact_str::format_compact!("{prefix}token").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl UserMagicLink {
+    /// Minutes a magic link stays valid after it has been sent.
+    pub const VALIDITY_MINUTES: i32 = 15;
+
+    pub async fn create(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+    ) -> Result<String, anyhow::Error> {
+        let existing = sqlx::query(
+            r#"
+            SELECT COUNT(*)
+            FROM user_magic_links
+            WHERE user_magic_links.user_uuid = $1 AND user_magic_links.created > NOW() - INTERVAL '1 minute'
+            "#,
+        )
+        .bind(user_uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        if let Some(row) = existing
+            && row.get::<i64, _>(0) > 0
+        {
+            return Err(anyhow::anyhow!(
+                "a magic link was already requested recently"
+            ));
+        }
+
+        let token = rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 96);
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_magic_links (user_uuid, token, created)
+            VALUES ($1, crypt($2, gen_salt('bf')), NOW())
+            "#,
+        )
+        .bind(user_uuid)
+        .bind(&token)
+        .execute(database.write())
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Redeems a magic link, removing every outstanding link of the user so that only one can ever be used.
+    pub async fn delete_by_token(
+        database: &crate::database::Database,
+        token: &str,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}, {} FROM user_magic_links
+            JOIN users ON users.uuid = user_magic_links.user_uuid
+            LEFT JOIN roles ON roles.uuid = users.role_uuid
+            WHERE
+                user_magic_links.token = crypt($1, user_magic_links.token)
+                AND user_magic_links.created > NOW() - make_interval(mins => $2)
+            "#,
+            Self::columns_sql(None),
+            super::user::User::columns_sql(Some("user_"))
+        ))
+        .bind(token)
+        .bind(Self::VALIDITY_MINUTES)
+        .fetch_optional(database.read())
+        .await?;
+
+        let row = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let magic_link = Self::map(None, &row)?;
+
+        let deleted = sqlx::query(
+            r#"
+            DELETE FROM user_magic_links
+            WHERE user_magic_links.user_uuid = $1 AND user_magic_links.created <= $2
+            "#,
+        )
+        .bind(magic_link.user.uuid)
+        .bind(magic_link.created)
+        .execute(database.write())
+        .await?;
+
+        if deleted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(magic_link))
+    }
+
+    pub async fn delete_expired(database: &crate::database::Database) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            DELETE FROM user_magic_links
+            WHERE user_magic_links.created < NOW() - make_interval(mins => $1)
+            "#,
+        )
+        .bind(Self::VALIDITY_MINUTES)
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+}
@@ -0,0 +1,329 @@
+use super::user_notification_channel::{
+    UserNotificationChannel, UserNotificationChannelType, UserNotificationEvent,
+};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::collections::BTreeMap;
+
+const MAX_ATTEMPTS: i16 = 5;
+const BASE_BACKOFF_SECONDS: i64 = 60;
+
+#[derive(Serialize, Deserialize)]
+pub struct UserNotification {
+    pub uuid: uuid::Uuid,
+    pub channel: Fetchable<UserNotificationChannel>,
+    pub server: Fetchable<super::server::Server>,
+
+    pub event: compact_str::CompactString,
+    pub data: serde_json::Value,
+
+    pub attempts: i16,
+    pub error: Option<String>,
+
+    pub next_attempt: chrono::NaiveDateTime,
+    pub sent: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for UserNotification {
+    const NAME: &'static str = "user_notification";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "user_notifications.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "user_notifications.channel_uuid",
+                compact_str::format_compact!("{prefix}channel_uuid"),
+            ),
+            (
+                "user_notifications.server_uuid",
+                compact_str::format_compact!("{prefix}server_uuid"),
+            ),
+            (
+                "user_notifications.event",
+                compact_str::format_compact!("{prefix}event"),
+            ),
+            (
+                "user_notifications.data",
+                compact_str::format_compact!("{prefix}data"),
+            ),
+            (
+                "user_notifications.attempts",
+                compact_str::format_compact!("{prefix}attempts"),
+            ),
+            (
+                "user_notifications.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "user_notifications.next_attempt",
+                compact_str::format_compact!("{prefix}next_attempt"),
+            ),
+            (
+                "user_notifications.sent",
+                compact_str::format_compact!("{prefix}sent"),
+            ),
+            (
+                "user_notifications.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            channel: UserNotificationChannel::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}channel_uuid").as_str())?,
+            ),
+            server: super::server::Server::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}server_uuid").as_str())?,
+            ),
+            event: row.try_get(compact_str::format_compact!("{prefix}event").as_str())?,
+            data: row.try_get(compact_str::format_compact!("{prefix}data").as_str())?,
+            attempts: row.try_get(compact_str::format_compact!("{prefix}attempts").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            next_attempt: row
+                .try_get(compact_str::format_compact!("{prefix}next_attempt").as_str())?,
+            sent: row.try_get(compact_str::format_compact!("{prefix}sent").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl UserNotification {
+    /// Queues a notification for every enabled channel subscribed to the event whose user owns the
+    /// server or is a subuser on it.
+    pub async fn enqueue(
+        database: &crate::database::Database,
+        server_uuid: uuid::Uuid,
+        event: UserNotificationEvent,
+        data: &serde_json::Value,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        Ok(sqlx::query(
+            r#"
+            INSERT INTO user_notifications (channel_uuid, server_uuid, event, data)
+            SELECT user_notification_channels.uuid, servers.uuid, $2, $3
+            FROM user_notification_channels
+            JOIN servers ON servers.uuid = $1
+            WHERE user_notification_channels.enabled
+                AND $2 = ANY(user_notification_channels.events)
+                AND (
+                    user_notification_channels.user_uuid = servers.owner_uuid
+                    OR EXISTS (
+                        SELECT 1 FROM server_subusers
+                        WHERE server_subusers.server_uuid = servers.uuid
+                            AND server_subusers.user_uuid = user_notification_channels.user_uuid
+                    )
+                )
+            "#,
+        )
+        .bind(server_uuid)
+        .bind(event.as_str())
+        .bind(data)
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    pub async fn all_due(
+        database: &crate::database::Database,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM user_notifications
+            WHERE user_notifications.sent IS NULL
+                AND user_notifications.attempts < $1
+                AND user_notifications.next_attempt <= NOW()
+            ORDER BY user_notifications.next_attempt
+            LIMIT $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(MAX_ATTEMPTS)
+        .bind(limit)
+        .fetch_all(database.read())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn delete_old(database: &crate::database::Database) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            DELETE FROM user_notifications
+            WHERE (user_notifications.sent IS NOT NULL OR user_notifications.attempts >= $1)
+                AND user_notifications.created < NOW() - INTERVAL '7 days'
+            "#,
+        )
+        .bind(MAX_ATTEMPTS)
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    fn message(
+        event: UserNotificationEvent,
+        server_name: &str,
+        data: &serde_json::Value,
+    ) -> String {
+        let name = data
+            .get("name")
+            .and_then(|name| name.as_str())
+            .unwrap_or("unknown");
+
+        match event {
+            UserNotificationEvent::ServerCrashed => {
+                format!("Server \"{server_name}\" has crashed.")
+            }
+            UserNotificationEvent::BackupFailed => {
+                format!("Backup \"{name}\" of server \"{server_name}\" has failed.")
+            }
+            UserNotificationEvent::InstallFinished => {
+                if data
+                    .get("successful")
+                    .and_then(|successful| successful.as_bool())
+                    == Some(false)
+                {
+                    format!("Installation of server \"{server_name}\" has failed.")
+                } else {
+                    format!("Installation of server \"{server_name}\" has finished.")
+                }
+            }
+            UserNotificationEvent::ScheduleFailed => {
+                format!("Schedule \"{name}\" of server \"{server_name}\" has failed.")
+            }
+            UserNotificationEvent::SubuserAdded => format!(
+                "{} has been added as a subuser to server \"{server_name}\".",
+                data.get("email")
+                    .and_then(|email| email.as_str())
+                    .unwrap_or("someone")
+            ),
+        }
+    }
+
+    /// Sends the notification through its channel and records the outcome. Failed attempts are
+    /// rescheduled with exponential backoff until `MAX_ATTEMPTS` is reached.
+    pub async fn deliver(&self, state: &crate::State) -> Result<bool, anyhow::Error> {
+        let channel = self.channel.fetch_cached(&state.database).await?;
+        let server = self.server.fetch_cached(&state.database).await?;
+        let event = UserNotificationEvent::parse(&self.event)
+            .ok_or_else(|| anyhow::anyhow!("unknown notification event {}", self.event))?;
+
+        let settings = state.settings.get().await;
+        let app_name = settings.app.name.clone();
+        let server_link = format!(
+            "{}/server/{:08x}",
+            settings.app.url.trim_end_matches('/'),
+            server.uuid_short
+        );
+        drop(settings);
+
+        let message = Self::message(event, &server.name, &self.data);
+
+        let error = match (channel.r#type, channel.url.as_deref()) {
+            (UserNotificationChannelType::Email, _) => {
+                let user = channel.user.fetch_cached(&state.database).await?;
+
+                match state
+                    .mail
+                    .render(
+                        "server_notification",
+                        Some(&user),
+                        serde_json::json!({
+                            "event": event,
+                            "server": {
+                                "uuid": server.uuid,
+                                "name": server.name,
+                            },
+                            "notification_message": message,
+                            "server_link": server_link,
+                        }),
+                    )
+                    .await
+                {
+                    Ok(mail) => {
+                        state.mail.send(user.email, mail).await;
+
+                        None
+                    }
+                    Err(err) => Some(err.to_string()),
+                }
+            }
+            (_, None) => Some("channel has no url".to_string()),
+            (channel_type, Some(url)) => {
+                let body = if channel_type == UserNotificationChannelType::Discord {
+                    serde_json::json!({
+                        "username": app_name,
+                        "embeds": [{
+                            "title": server.name,
+                            "description": message,
+                            "url": server_link,
+                            "timestamp": self.created.and_utc(),
+                        }],
+                    })
+                } else {
+                    serde_json::json!({
+                        "uuid": self.uuid,
+                        "event": event,
+                        "server": {
+                            "uuid": server.uuid,
+                            "name": server.name,
+                        },
+                        "message": message,
+                        "data": self.data,
+                        "created": self.created.and_utc(),
+                    })
+                };
+
+                match state
+                    .client
+                    .post(url)
+                    .timeout(std::time::Duration::from_secs(15))
+                    .json(&body)
+                    .send()
+                    .await
+                {
+                    Ok(response) if response.status().is_success() => None,
+                    Ok(response) => Some(format!("endpoint responded with {}", response.status())),
+                    Err(err) => Some(err.to_string()),
+                }
+            }
+        };
+
+        let sent = error.is_none();
+        let backoff = BASE_BACKOFF_SECONDS * 2i64.pow(self.attempts.max(0) as u32);
+
+        sqlx::query(
+            r#"
+            UPDATE user_notifications
+            SET attempts = attempts + 1, error = $2,
+                sent = CASE WHEN $3 THEN NOW() ELSE NULL END,
+                next_attempt = NOW() + make_interval(secs => $4)
+            WHERE user_notifications.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(error)
+        .bind(sent)
+        .bind(backoff as f64)
+        .execute(state.database.write())
+        .await?;
+
+        Ok(sent)
+    }
+}
@@ -0,0 +1,388 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "user_notification_channel_type",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum UserNotificationChannelType {
+    Email,
+    Discord,
+    Webhook,
+}
+
+impl UserNotificationChannelType {
+    /// Checks that the url fits the channel type. Email channels deliver to the account email and
+    /// take no url, discord channels require a discord webhook url.
+    pub fn validate_url(self, url: Option<&str>) -> Result<(), &'static str> {
+        match (self, url) {
+            (Self::Email, None) => Ok(()),
+            (Self::Email, Some(_)) => Err("email channels do not take a url"),
+            (_, None) => Err("url is required for this channel type"),
+            (Self::Discord, Some(url)) => {
+                if url.starts_with("https://discord.com/api/webhooks/")
+                    || url.starts_with("https://discordapp.com/api/webhooks/")
+                {
+                    Ok(())
+                } else {
+                    Err("url is not a discord webhook url")
+                }
+            }
+            (Self::Webhook, Some(url)) => {
+                if url.starts_with("https://") || url.starts_with("http://") {
+                    Ok(())
+                } else {
+                    Err("url must be a http or https url")
+                }
+            }
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+pub enum UserNotificationEvent {
+    ServerCrashed,
+    BackupFailed,
+    InstallFinished,
+    ScheduleFailed,
+    SubuserAdded,
+}
+
+impl UserNotificationEvent {
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ServerCrashed => "server_crashed",
+            Self::BackupFailed => "backup_failed",
+            Self::InstallFinished => "install_finished",
+            Self::ScheduleFailed => "schedule_failed",
+            Self::SubuserAdded => "subuser_added",
+        }
+    }
+
+    #[inline]
+    pub fn parse(event: &str) -> Option<Self> {
+        match event {
+            "server_crashed" => Some(Self::ServerCrashed),
+            "backup_failed" => Some(Self::BackupFailed),
+            "install_finished" => Some(Self::InstallFinished),
+            "schedule_failed" => Some(Self::ScheduleFailed),
+            "subuser_added" => Some(Self::SubuserAdded),
+            _ => None,
+        }
+    }
+
+    /// Maps a server activity event, either logged by the panel or reported by wings, to the
+    /// notification event users can subscribe to.
+    #[inline]
+    pub fn from_activity_event(event: &str) -> Option<Self> {
+        match event {
+            "server:crash" => Some(Self::ServerCrashed),
+            "server:backup.fail" => Some(Self::BackupFailed),
+            "server:install.complete" | "server:install.fail" => Some(Self::InstallFinished),
+            "server:schedule.fail" => Some(Self::ScheduleFailed),
+            "server:subuser.create" => Some(Self::SubuserAdded),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UserNotificationChannel {
+    pub uuid: uuid::Uuid,
+    pub user: Fetchable<super::user::User>,
+
+    pub name: compact_str::CompactString,
+    pub r#type: UserNotificationChannelType,
+    pub url: Option<compact_str::CompactString>,
+    pub events: Vec<UserNotificationEvent>,
+    pub enabled: bool,
+
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for UserNotificationChannel {
+    const NAME: &'static str = "user_notification_channel";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "user_notification_channels.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "user_notification_channels.user_uuid",
+                compact_str::format_compact!("{prefix}user_uuid"),
+            ),
+            (
+                "user_notification_channels.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "user_notification_channels.type",
+                compact_str::format_compact!("{prefix}type"),
+            ),
+            (
+                "user_notification_channels.url",
+                compact_str::format_compact!("{prefix}url"),
+            ),
+            (
+                "user_notification_channels.events",
+                compact_str::format_compact!("{prefix}events"),
+            ),
+            (
+                "user_notification_channels.enabled",
+                compact_str::format_compact!("{prefix}enabled"),
+            ),
+            (
+                "user_notification_channels.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            user: super::user::User::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}user_uuid").as_str())?,
+            ),
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            r#type: row.try_get(compact_str::format_compact!("{prefix}type").as_str())?,
+            url: row.try_get(compact_str::format_compact!("{prefix}url").as_str())?,
+            events: row
+                .try_get::<Vec<String>, _>(compact_str::format_compact!("{prefix}events").as_str())?
+                .into_iter()
+                .filter_map(|event| UserNotificationEvent::parse(&event))
+                .collect(),
+            enabled: row.try_get(compact_str::format_compact!("{prefix}enabled").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl UserNotificationChannel {
+    pub async fn create(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+        name: &str,
+        r#type: UserNotificationChannelType,
+        url: Option<&str>,
+        events: &[UserNotificationEvent],
+        enabled: bool,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO user_notification_channels (user_uuid, name, type, url, events, enabled)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(user_uuid)
+        .bind(name)
+        .bind(r#type)
+        .bind(url)
+        .bind(
+            events
+                .iter()
+                .map(|event| event.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(enabled)
+        .fetch_one(database.write())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    pub async fn update(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE user_notification_channels
+            SET name = $2, url = $3, events = $4, enabled = $5
+            WHERE user_notification_channels.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(&self.name)
+        .bind(self.url.as_deref())
+        .bind(
+            self.events
+                .iter()
+                .map(|event| event.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(self.enabled)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn by_user_uuid_uuid(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM user_notification_channels
+            WHERE user_notification_channels.user_uuid = $1 AND user_notification_channels.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(user_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_user_uuid_with_pagination(
+        database: &crate::database::Database,
+        user_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM user_notification_channels
+            WHERE user_notification_channels.user_uuid = $1 AND ($2 IS NULL OR user_notification_channels.name ILIKE '%' || $2 || '%')
+            ORDER BY user_notification_channels.created
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(user_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    #[inline]
+    pub fn into_api_object(self) -> ApiUserNotificationChannel {
+        ApiUserNotificationChannel {
+            uuid: self.uuid,
+            name: self.name,
+            r#type: self.r#type,
+            url: self.url,
+            events: self.events,
+            enabled: self.enabled,
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for UserNotificationChannel {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM user_notification_channels
+            WHERE user_notification_channels.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for UserNotificationChannel {
+    type DeleteOptions = ();
+
+    fn get_delete_listeners() -> &'static LazyLock<DeleteListenerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteListenerList<UserNotificationChannel>> =
+            LazyLock::new(|| Arc::new(ListenerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete(
+        &self,
+        database: &Arc<crate::database::Database>,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        self.run_delete_listeners(&options, database, &mut transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM user_notification_channels
+            WHERE user_notification_channels.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "UserNotificationChannel")]
+pub struct ApiUserNotificationChannel {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub r#type: UserNotificationChannelType,
+    pub url: Option<compact_str::CompactString>,
+    pub events: Vec<UserNotificationEvent>,
+    pub enabled: bool,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,269 @@
+use crate::prelude::*;
+use hmac::Mac;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock},
+};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Webhook {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub description: Option<compact_str::CompactString>,
+
+    pub url: String,
+    pub secret: Vec<u8>,
+    pub events: Vec<compact_str::CompactString>,
+    pub enabled: bool,
+
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for Webhook {
+    const NAME: &'static str = "webhook";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "webhooks.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "webhooks.name",
+                compact_str::format_compact!("{prefix}name"),
+            ),
+            (
+                "webhooks.description",
+                compact_str::format_compact!("{prefix}description"),
+            ),
+            ("webhooks.url", compact_str::format_compact!("{prefix}url")),
+            (
+                "webhooks.secret",
+                compact_str::format_compact!("{prefix}secret"),
+            ),
+            (
+                "webhooks.events",
+                compact_str::format_compact!("{prefix}events"),
+            ),
+            (
+                "webhooks.enabled",
+                compact_str::format_compact!("{prefix}enabled"),
+            ),
+            (
+                "webhooks.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            name: row.try_get(compact_str::format_compact!("{prefix}name").as_str())?,
+            description: row
+                .try_get(compact_str::format_compact!("{prefix}description").as_str())?,
+            url: row.try_get(compact_str::format_compact!("{prefix}url").as_str())?,
+            secret: row.try_get(compact_str::format_compact!("{prefix}secret").as_str())?,
+            events: row.try_get(compact_str::format_compact!("{prefix}events").as_str())?,
+            enabled: row.try_get(compact_str::format_compact!("{prefix}enabled").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl Webhook {
+    pub async fn create(
+        database: &crate::database::Database,
+        name: &str,
+        description: Option<&str>,
+        url: &str,
+        secret: &str,
+        events: &[compact_str::CompactString],
+        enabled: bool,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO webhooks (name, description, url, secret, events, enabled)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(name)
+        .bind(description)
+        .bind(url)
+        .bind(
+            database
+                .encrypt(secret.to_string())
+                .await
+                .map_err(|err| sqlx::Error::Encode(err.into()))?,
+        )
+        .bind(events)
+        .bind(enabled)
+        .fetch_one(database.write())
+        .await?;
+
+        Self::map(None, &row)
+    }
+
+    pub async fn all_with_pagination(
+        database: &crate::database::Database,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM webhooks
+            WHERE $1 IS NULL OR webhooks.name ILIKE '%' || $1 || '%' OR webhooks.url ILIKE '%' || $1 || '%'
+            ORDER BY webhooks.created
+            LIMIT $2 OFFSET $3
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    /// Signs `{timestamp}.{body}` with the webhook secret, returning the hex encoded HMAC-SHA256.
+    pub async fn sign(
+        &self,
+        database: &crate::database::Database,
+        timestamp: i64,
+        body: &[u8],
+    ) -> Result<String, anyhow::Error> {
+        let secret = database.decrypt(self.secret.clone()).await?;
+
+        let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())?;
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+
+        Ok(mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect())
+    }
+
+    #[inline]
+    pub async fn into_admin_api_object(
+        self,
+        database: &crate::database::Database,
+    ) -> Result<AdminApiWebhook, anyhow::Error> {
+        Ok(AdminApiWebhook {
+            uuid: self.uuid,
+            name: self.name,
+            description: self.description,
+            url: self.url,
+            secret: database.decrypt(self.secret).await?,
+            events: self.events,
+            enabled: self.enabled,
+            created: self.created.and_utc(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ByUuid for Webhook {
+    async fn by_uuid(
+        database: &crate::database::Database,
+        uuid: uuid::Uuid,
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM webhooks
+            WHERE webhooks.uuid = $1
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(uuid)
+        .fetch_one(database.read())
+        .await?;
+
+        Self::map(None, &row)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeletableModel for Webhook {
+    type DeleteOptions = ();
+
+    fn get_delete_listeners() -> &'static LazyLock<DeleteListenerList<Self>> {
+        static DELETE_LISTENERS: LazyLock<DeleteListenerList<Webhook>> =
+            LazyLock::new(|| Arc::new(ListenerList::default()));
+
+        &DELETE_LISTENERS
+    }
+
+    async fn delete(
+        &self,
+        database: &Arc<crate::database::Database>,
+        options: Self::DeleteOptions,
+    ) -> Result<(), anyhow::Error> {
+        let mut transaction = database.write().begin().await?;
+
+        self.run_delete_listeners(&options, database, &mut transaction)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM webhooks
+            WHERE webhooks.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminWebhook")]
+pub struct AdminApiWebhook {
+    pub uuid: uuid::Uuid,
+
+    pub name: compact_str::CompactString,
+    pub description: Option<compact_str::CompactString>,
+
+    pub url: String,
+    pub secret: compact_str::CompactString,
+    pub events: Vec<compact_str::CompactString>,
+    pub enabled: bool,
+
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,396 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, prelude::Type};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+const MAX_ATTEMPTS: i16 = 8;
+const BASE_BACKOFF_SECONDS: i64 = 30;
+const CLAIM_LEASE_SECONDS: i64 = 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventScope {
+    Admin,
+    User,
+    Server,
+}
+
+impl WebhookEventScope {
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::User => "user",
+            Self::Server => "server",
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+#[schema(rename_all = "snake_case")]
+#[sqlx(
+    type_name = "webhook_delivery_status",
+    rename_all = "SCREAMING_SNAKE_CASE"
+)]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub uuid: uuid::Uuid,
+    pub webhook: Fetchable<super::webhook::Webhook>,
+
+    pub event: compact_str::CompactString,
+    pub payload: serde_json::Value,
+
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i16,
+    pub response_status: Option<i16>,
+    pub error: Option<String>,
+
+    pub next_attempt: chrono::NaiveDateTime,
+    pub last_attempt: Option<chrono::NaiveDateTime>,
+    pub created: chrono::NaiveDateTime,
+}
+
+impl BaseModel for WebhookDelivery {
+    const NAME: &'static str = "webhook_delivery";
+
+    #[inline]
+    fn columns(prefix: Option<&str>) -> BTreeMap<&'static str, compact_str::CompactString> {
+        let prefix = prefix.unwrap_or_default();
+
+        BTreeMap::from([
+            (
+                "webhook_deliveries.uuid",
+                compact_str::format_compact!("{prefix}uuid"),
+            ),
+            (
+                "webhook_deliveries.webhook_uuid",
+                compact_str::format_compact!("{prefix}webhook_uuid"),
+            ),
+            (
+                "webhook_deliveries.event",
+                compact_str::format_compact!("{prefix}event"),
+            ),
+            (
+                "webhook_deliveries.payload",
+                compact_str::format_compact!("{prefix}payload"),
+            ),
+            (
+                "webhook_deliveries.status",
+                compact_str::format_compact!("{prefix}status"),
+            ),
+            (
+                "webhook_deliveries.attempts",
+                compact_str::format_compact!("{prefix}attempts"),
+            ),
+            (
+                "webhook_deliveries.response_status",
+                compact_str::format_compact!("{prefix}response_status"),
+            ),
+            (
+                "webhook_deliveries.error",
+                compact_str::format_compact!("{prefix}error"),
+            ),
+            (
+                "webhook_deliveries.next_attempt",
+                compact_str::format_compact!("{prefix}next_attempt"),
+            ),
+            (
+                "webhook_deliveries.last_attempt",
+                compact_str::format_compact!("{prefix}last_attempt"),
+            ),
+            (
+                "webhook_deliveries.created",
+                compact_str::format_compact!("{prefix}created"),
+            ),
+        ])
+    }
+
+    #[inline]
+    fn map(prefix: Option<&str>, row: &PgRow) -> Result<Self, crate::database::DatabaseError> {
+        let prefix = prefix.unwrap_or_default();
+
+        Ok(Self {
+            uuid: row.try_get(compact_str::format_compact!("{prefix}uuid").as_str())?,
+            webhook: super::webhook::Webhook::get_fetchable(
+                row.try_get(compact_str::format_compact!("{prefix}webhook_uuid").as_str())?,
+            ),
+            event: row.try_get(compact_str::format_compact!("{prefix}event").as_str())?,
+            payload: row.try_get(compact_str::format_compact!("{prefix}payload").as_str())?,
+            status: row.try_get(compact_str::format_compact!("{prefix}status").as_str())?,
+            attempts: row.try_get(compact_str::format_compact!("{prefix}attempts").as_str())?,
+            response_status: row
+                .try_get(compact_str::format_compact!("{prefix}response_status").as_str())?,
+            error: row.try_get(compact_str::format_compact!("{prefix}error").as_str())?,
+            next_attempt: row
+                .try_get(compact_str::format_compact!("{prefix}next_attempt").as_str())?,
+            last_attempt: row
+                .try_get(compact_str::format_compact!("{prefix}last_attempt").as_str())?,
+            created: row.try_get(compact_str::format_compact!("{prefix}created").as_str())?,
+        })
+    }
+}
+
+impl WebhookDelivery {
+    /// Queues a delivery of an activity event for every enabled webhook whose event filter matches
+    /// `{scope}:{event}`. An empty filter matches everything, a trailing `*` matches by prefix.
+    pub async fn enqueue(
+        database: &crate::database::Database,
+        scope: WebhookEventScope,
+        event: &str,
+        payload: serde_json::Value,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let event = format!("{}:{event}", scope.as_str());
+
+        Ok(sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries (webhook_uuid, event, payload)
+            SELECT webhooks.uuid, $1, $2
+            FROM webhooks
+            WHERE webhooks.enabled AND (
+                CARDINALITY(webhooks.events) = 0
+                OR EXISTS (
+                    SELECT 1 FROM UNNEST(webhooks.events) AS filter
+                    WHERE filter = $1 OR (RIGHT(filter, 1) = '*' AND STARTS_WITH($1, LEFT(filter, -1)))
+                )
+            )
+            "#,
+        )
+        .bind(&event)
+        .bind(payload)
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    pub async fn by_webhook_uuid_uuid(
+        database: &crate::database::Database,
+        webhook_uuid: uuid::Uuid,
+        uuid: uuid::Uuid,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM webhook_deliveries
+            WHERE webhook_deliveries.webhook_uuid = $1 AND webhook_deliveries.uuid = $2
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(webhook_uuid)
+        .bind(uuid)
+        .fetch_optional(database.read())
+        .await?;
+
+        row.try_map(|row| Self::map(None, &row))
+    }
+
+    pub async fn by_webhook_uuid_with_pagination(
+        database: &crate::database::Database,
+        webhook_uuid: uuid::Uuid,
+        page: i64,
+        per_page: i64,
+        search: Option<&str>,
+    ) -> Result<super::Pagination<Self>, crate::database::DatabaseError> {
+        let offset = (page - 1) * per_page;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COUNT(*) OVER() AS total_count
+            FROM webhook_deliveries
+            WHERE webhook_deliveries.webhook_uuid = $1 AND ($2 IS NULL OR webhook_deliveries.event ILIKE '%' || $2 || '%')
+            ORDER BY webhook_deliveries.created DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(webhook_uuid)
+        .bind(search)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(database.read())
+        .await?;
+
+        Ok(super::Pagination {
+            total: rows
+                .first()
+                .map_or(Ok(0), |row| row.try_get("total_count"))?,
+            per_page,
+            page,
+            data: rows
+                .into_iter()
+                .map(|row| Self::map(None, &row))
+                .try_collect_vec()?,
+        })
+    }
+
+    /// Claims up to `limit` due deliveries by pushing their next attempt back by a lease,
+    /// so other instances skip them while they are being delivered.
+    pub async fn claim_due(
+        database: &crate::database::Database,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt = NOW() + make_interval(secs => $2)
+            WHERE webhook_deliveries.uuid IN (
+                SELECT due_deliveries.uuid
+                FROM webhook_deliveries AS due_deliveries
+                WHERE due_deliveries.status = 'PENDING' AND due_deliveries.next_attempt <= NOW()
+                ORDER BY due_deliveries.next_attempt
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            Self::columns_sql(None)
+        ))
+        .bind(limit)
+        .bind(CLAIM_LEASE_SECONDS as f64)
+        .fetch_all(database.write())
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Self::map(None, &row))
+            .try_collect_vec()
+    }
+
+    pub async fn delete_old(database: &crate::database::Database) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+            DELETE FROM webhook_deliveries
+            WHERE webhook_deliveries.status != 'PENDING' AND webhook_deliveries.created < NOW() - INTERVAL '7 days'
+            "#,
+        )
+        .execute(database.write())
+        .await?
+        .rows_affected())
+    }
+
+    /// Sends the delivery to its webhook and records the outcome. Failed attempts are rescheduled
+    /// with exponential backoff until `MAX_ATTEMPTS` is reached.
+    pub async fn deliver(
+        &self,
+        database: &crate::database::Database,
+        client: &reqwest::Client,
+    ) -> Result<WebhookDeliveryStatus, anyhow::Error> {
+        let webhook = self.webhook.fetch(database).await?;
+
+        let body = serde_json::to_vec(&serde_json::json!({
+            "uuid": self.uuid,
+            "event": self.event,
+            "payload": self.payload,
+            "attempt": self.attempts + 1,
+        }))?;
+        let timestamp = chrono::Utc::now().timestamp();
+        let signature = webhook.sign(database, timestamp, &body).await?;
+
+        let (response_status, error) = match client
+            .post(&webhook.url)
+            .timeout(std::time::Duration::from_secs(15))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Id", webhook.uuid.to_string())
+            .header("X-Webhook-Delivery", self.uuid.to_string())
+            .header("X-Webhook-Event", self.event.as_str())
+            .header("X-Webhook-Timestamp", timestamp.to_string())
+            .header("X-Webhook-Signature", format!("sha256={signature}"))
+            .body(body)
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16() as i16), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16() as i16),
+                Some(format!("endpoint responded with {}", response.status())),
+            ),
+            Err(err) => (None, Some(err.to_string())),
+        };
+
+        let attempts = self.attempts + 1;
+        let status = if error.is_none() {
+            WebhookDeliveryStatus::Delivered
+        } else if attempts >= MAX_ATTEMPTS {
+            WebhookDeliveryStatus::Failed
+        } else {
+            WebhookDeliveryStatus::Pending
+        };
+        let backoff = BASE_BACKOFF_SECONDS * 2i64.pow(self.attempts.max(0) as u32);
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, attempts = $3, response_status = $4, error = $5,
+                next_attempt = NOW() + make_interval(secs => $6), last_attempt = NOW()
+            WHERE webhook_deliveries.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .bind(status)
+        .bind(attempts)
+        .bind(response_status)
+        .bind(error)
+        .bind(backoff as f64)
+        .execute(database.write())
+        .await?;
+
+        Ok(status)
+    }
+
+    pub async fn retry(&self, database: &crate::database::Database) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'PENDING', attempts = 0, next_attempt = NOW()
+            WHERE webhook_deliveries.uuid = $1
+            "#,
+        )
+        .bind(self.uuid)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn into_admin_api_object(self) -> AdminApiWebhookDelivery {
+        AdminApiWebhookDelivery {
+            uuid: self.uuid,
+            event: self.event,
+            payload: self.payload,
+            status: self.status,
+            attempts: self.attempts,
+            response_status: self.response_status,
+            error: self.error,
+            next_attempt: self.next_attempt.and_utc(),
+            last_attempt: self.last_attempt.map(|dt| dt.and_utc()),
+            created: self.created.and_utc(),
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize)]
+#[schema(title = "AdminWebhookDelivery")]
+pub struct AdminApiWebhookDelivery {
+    pub uuid: uuid::Uuid,
+
+    pub event: compact_str::CompactString,
+    pub payload: serde_json::Value,
+
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i16,
+    pub response_status: Option<i16>,
+    pub error: Option<String>,
+
+    pub next_attempt: chrono::DateTime<chrono::Utc>,
+    pub last_attempt: Option<chrono::DateTime<chrono::Utc>>,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
@@ -102,6 +102,10 @@ pub(crate) static BASE_USER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permiss
                         ),
                         ("update", "Allows to modify server groups."),
                         ("delete", "Allows to delete server groups from the account."),
+                        (
+                            "deploy",
+                            "Allows to deploy new servers within the account's resource quota.",
+                        ),
                     ]),
                 },
             ),
@@ -150,6 +154,27 @@ pub(crate) static BASE_USER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permiss
                     ]),
                 },
             ),
+            (
+                "notification-channels",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage notification channels on an account.",
+                    permissions: IndexMap::from([
+                        (
+                            "create",
+                            "Allows to create new notification channels for the account.",
+                        ),
+                        (
+                            "read",
+                            "Allows to view notification channels for the account.",
+                        ),
+                        ("update", "Allows to modify notification channels."),
+                        (
+                            "delete",
+                            "Allows to delete notification channels from the account.",
+                        ),
+                    ]),
+                },
+            ),
             (
                 "oauth-links",
                 PermissionGroup {
@@ -240,6 +265,10 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                             "oauth-links",
                             "Allows to view and manage a users' oauth links.",
                         ),
+                        (
+                            "quota",
+                            "Allows to view and manage a users' resource quota.",
+                        ),
                     ]),
                 },
             ),
@@ -252,6 +281,10 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                         ("read", "Allows to view roles for the panel."),
                         ("update", "Allows to modify roles."),
                         ("delete", "Allows to delete roles from the panel."),
+                        (
+                            "quota",
+                            "Allows to view and manage a roles' resource quota.",
+                        ),
                     ]),
                 },
             ),
@@ -268,6 +301,10 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                             "database-hosts",
                             "Allows to view and manage a locations' database hosts.",
                         ),
+                        (
+                            "eggs",
+                            "Allows to view and manage the eggs users can deploy in a location.",
+                        ),
                     ]),
                 },
             ),
@@ -318,6 +355,10 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                         ),
                         ("mounts", "Allows to view and manage a nodes' mounts."),
                         ("backups", "Allows to view and manage a nodes' backups."),
+                        (
+                            "evacuations",
+                            "Allows to view and manage evacuations that transfer every server off a node.",
+                        ),
                     ]),
                 },
             ),
@@ -331,6 +372,14 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                         ("update", "Allows to modify servers."),
                         ("delete", "Allows to delete servers from the panel."),
                         ("transfer", "Allows to transfer servers to other nodes."),
+                        (
+                            "change-egg",
+                            "Allows to change the egg of servers, which reinstalls them.",
+                        ),
+                        (
+                            "clone",
+                            "Allows to clone servers, including their files and databases.",
+                        ),
                         (
                             "allocations",
                             "Allows to view and manage a servers' allocations.",
@@ -340,6 +389,28 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                             "Allows to view and manage a servers' variables.",
                         ),
                         ("mounts", "Allows to view and manage a servers' mounts."),
+                        (
+                            "suspensions",
+                            "Allows to view and manage a servers' suspensions.",
+                        ),
+                    ]),
+                },
+            ),
+            (
+                "server-templates",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage server templates for the panel.",
+                    permissions: IndexMap::from([
+                        (
+                            "create",
+                            "Allows to create new server templates for the panel.",
+                        ),
+                        ("read", "Allows to view server templates for the panel."),
+                        ("update", "Allows to modify server templates."),
+                        (
+                            "delete",
+                            "Allows to delete server templates from the panel.",
+                        ),
                     ]),
                 },
             ),
@@ -436,6 +507,38 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                     ]),
                 },
             ),
+            (
+                "webhooks",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage outbound webhooks for the panel.",
+                    permissions: IndexMap::from([
+                        ("create", "Allows to create new webhooks for the panel."),
+                        (
+                            "read",
+                            "Allows to view webhooks and their deliveries for the panel.",
+                        ),
+                        ("update", "Allows to modify webhooks and retry deliveries."),
+                        ("delete", "Allows to delete webhooks from the panel."),
+                    ]),
+                },
+            ),
+            (
+                "mail-templates",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage mail templates for the panel.",
+                    permissions: IndexMap::from([
+                        (
+                            "read",
+                            "Allows to view mail templates and their overrides for the panel.",
+                        ),
+                        (
+                            "update",
+                            "Allows to create, modify and delete mail template overrides.",
+                        ),
+                        ("test", "Allows to send test mails for mail templates."),
+                    ]),
+                },
+            ),
             (
                 "extensions",
                 PermissionGroup {
@@ -446,6 +549,30 @@ pub(crate) static BASE_ADMIN_PERMISSIONS: LazyLock<IndexMap<&'static str, Permis
                     )]),
                 },
             ),
+            (
+                "background-tasks",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage background tasks for the panel.",
+                    permissions: IndexMap::from([
+                        (
+                            "read",
+                            "Allows to view background tasks and their last errors.",
+                        ),
+                        ("run", "Allows to run scheduled background tasks manually."),
+                    ]),
+                },
+            ),
+            (
+                "jobs",
+                PermissionGroup {
+                    description: "Permissions that control the ability to manage queued jobs for the panel.",
+                    permissions: IndexMap::from([
+                        ("read", "Allows to view queued jobs and their errors."),
+                        ("retry", "Allows to retry failed or cancelled jobs."),
+                        ("cancel", "Allows to cancel pending or running jobs."),
+                    ]),
+                },
+            ),
             (
                 "activity",
                 PermissionGroup {
@@ -671,6 +798,23 @@ pub(crate) static BASE_SERVER_PERMISSIONS: LazyLock<IndexMap<&'static str, Permi
                             "cancel-install",
                             "Allows cancelling the install process of this server.",
                         ),
+                        (
+                            "change-egg",
+                            "Allows to switch this server to another egg made selectable for users, which reinstalls it.",
+                        ),
+                    ]),
+                },
+            ),
+            (
+                "clones",
+                PermissionGroup {
+                    description: "Permissions that control the ability to clone this server.",
+                    permissions: IndexMap::from([
+                        (
+                            "create",
+                            "Allows to clone this server into a new server owned by the server owner.",
+                        ),
+                        ("read", "Allows to view existing clones of the server."),
                     ]),
                 },
             ),
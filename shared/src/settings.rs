@@ -123,6 +123,8 @@ pub struct AppSettingsApp {
 
     pub telemetry_enabled: bool,
     pub registration_enabled: bool,
+    pub magic_link_login_enabled: bool,
+    pub node_status_mails_enabled: bool,
 }
 
 impl AppSettingsApp {
@@ -146,6 +148,10 @@ impl AppSettingsApp {
         values.push(self.telemetry_enabled.to_compact_string());
         keys.push("app::registration_enabled");
         values.push(self.registration_enabled.to_compact_string());
+        keys.push("app::magic_link_login_enabled");
+        values.push(self.magic_link_login_enabled.to_compact_string());
+        keys.push("app::node_status_mails_enabled");
+        values.push(self.node_status_mails_enabled.to_compact_string());
 
         (keys, values)
     }
@@ -176,6 +182,14 @@ impl AppSettingsApp {
                 .remove("app::registration_enabled")
                 .map(|s| s == "true")
                 .unwrap_or(true),
+            magic_link_login_enabled: map
+                .remove("app::magic_link_login_enabled")
+                .map(|s| s == "true")
+                .unwrap_or(false),
+            node_status_mails_enabled: map
+                .remove("app::node_status_mails_enabled")
+                .map(|s| s == "true")
+                .unwrap_or(false),
         }
     }
 }
@@ -284,6 +298,44 @@ impl AppSettingsServer {
     }
 }
 
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct AppSettingsMetrics {
+    pub enabled: bool,
+    pub token: compact_str::CompactString,
+}
+
+impl AppSettingsMetrics {
+    /// Checks a bearer token against the configured token, an empty configured token never matches.
+    pub fn verify_token(&self, token: &str) -> bool {
+        !self.token.is_empty()
+            && constant_time_eq::constant_time_eq(token.as_bytes(), self.token.as_bytes())
+    }
+
+    pub fn serialize(&self) -> (Vec<&'static str>, Vec<compact_str::CompactString>) {
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+
+        keys.push("metrics::enabled");
+        values.push(self.enabled.to_compact_string());
+        keys.push("metrics::token");
+        values.push(self.token.clone());
+
+        (keys, values)
+    }
+
+    pub fn deserialize(
+        map: &mut HashMap<compact_str::CompactString, compact_str::CompactString>,
+    ) -> Self {
+        AppSettingsMetrics {
+            enabled: map
+                .remove("metrics::enabled")
+                .map(|s| s == "true")
+                .unwrap_or(false),
+            token: map.remove("metrics::token").unwrap_or_default(),
+        }
+    }
+}
+
 #[derive(ToSchema, Serialize, Deserialize)]
 pub struct AppSettings {
     pub telemetry_uuid: Option<uuid::Uuid>,
@@ -301,6 +353,8 @@ pub struct AppSettings {
     pub webauthn: AppSettingsWebauthn,
     #[schema(inline)]
     pub server: AppSettingsServer,
+    #[schema(inline)]
+    pub metrics: AppSettingsMetrics,
 }
 
 impl AppSettings {
@@ -503,6 +557,9 @@ impl AppSettings {
         let (keys_server, values_server) = self.server.serialize();
         keys.extend(keys_server);
         values.extend(values_server);
+        let (keys_metrics, values_metrics) = self.metrics.serialize();
+        keys.extend(keys_metrics);
+        values.extend(values_metrics);
 
         (keys, values)
     }
@@ -686,6 +743,7 @@ impl AppSettings {
             app: AppSettingsApp::deserialize(map),
             webauthn: AppSettingsWebauthn::deserialize(map),
             server: AppSettingsServer::deserialize(map),
+            metrics: AppSettingsMetrics::deserialize(map),
         }
     }
 }
@@ -734,6 +792,10 @@ impl<'a> SettingsGuard<'a> {
 
         censor_values("", &mut json);
 
+        if let Some(serde_json::Value::String(token)) = json.pointer_mut("/metrics/token") {
+            *token = "*".repeat(token.len());
+        }
+
         json
     }
 }
@@ -160,6 +160,10 @@ pub enum ScheduleActionInner {
     CreateBackup {
         ignore_failure: bool,
         foreground: bool,
+        /// Rotate out the oldest unlocked backup instead of failing when the backup limit is reached,
+        /// `None` rotates, matching the behavior from before this option existed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rotate_oldest: Option<bool>,
 
         name: Option<ScheduleDynamicParameter>,
         ignored_files: Vec<compact_str::CompactString>,